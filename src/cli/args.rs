@@ -6,8 +6,9 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 use serde::Deserialize;
 
 use crate::models::{
-    IndexBackendKind, IndexConfig, SearchConfig, SearchMode, SymbolAttributesRequest,
-    SymbolAttributesUpdate, SymbolKind, SymbolSelector,
+    CheckDepsConfig, DepsConfig, HierarchyConfig, HierarchyDirection, IndexBackendKind,
+    IndexConfig, QueryExpr, QueryField, QueryTerm, SearchConfig, SearchMode,
+    SymbolAttributesRequest, SymbolAttributesUpdate, SymbolKind, SymbolSelector,
 };
 /// Top-level CLI entrypoint for `symgrep`.
 #[derive(Parser, Debug)]
@@ -41,6 +42,18 @@ pub enum Commands {
     Serve(ServeArgs),
     /// Explore callers/callees for symbols.
     Follow(FollowArgs),
+    /// Export the call graph between selected symbols (DOT, Mermaid, JSON).
+    Graph(GraphArgs),
+    /// Run a raw tree-sitter query and report its captures.
+    Query(QueryArgs),
+    /// Find usages of a symbol name (calls, reads, writes, types, imports).
+    Refs(RefsArgs),
+    /// Show a file's imports, its importers and their transitive closure.
+    Deps(DepsArgs),
+    /// Check import cycles and layering rules; exits non-zero on findings.
+    CheckDeps(CheckDepsArgs),
+    /// Show the supertypes or subtypes of a class, interface or trait.
+    Hierarchy(HierarchyArgs),
     /// Update symbol attributes (keywords, description) in an index.
     Annotate(AnnotateArgs),
 }
@@ -49,6 +62,9 @@ pub enum Commands {
 #[derive(Args, Debug)]
 pub struct SearchArgs {
     /// Search pattern (syntax not implemented yet).
+    ///
+    /// May start with `-` for negated terms such as `-is:test`.
+    #[arg(allow_hyphen_values = true)]
     pub pattern: String,
 
     /// Paths to search (defaults to current directory if omitted).
@@ -67,6 +83,11 @@ pub struct SearchArgs {
     #[arg(long = "language")]
     pub language: Option<String>,
 
+    /// C++ compile database (`compile_commands.json`) used to resolve
+    /// `#include` paths.
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
     /// Interpret the pattern as a literal identifier/word.
     ///
     /// - In text mode, restricts matches to whole identifiers (e.g. `foo`
@@ -76,13 +97,38 @@ pub struct SearchArgs {
     #[arg(long = "literal")]
     pub literal: bool,
 
+    /// Let text-mode matches span multiple lines.
+    ///
+    /// The pattern is interpreted as a regular expression in which
+    /// `\n` and `\s` can match line breaks (similar to `rg -U`).
+    #[arg(short = 'U', long = "multiline")]
+    pub multiline: bool,
+
+    /// Text encoding used to read files in text mode (e.g. `latin1`,
+    /// `utf-16le`). Files with a byte-order mark are transcoded
+    /// automatically.
+    #[arg(long = "encoding")]
+    pub encoding: Option<String>,
+
+    /// Annotate text-mode matches with the innermost enclosing symbol
+    /// (function, method, class, ...) for supported languages.
+    #[arg(long = "enclosing")]
+    pub enclosing: bool,
+
+    /// Restrict text-mode matches to comments, string literals, or the
+    /// remaining code (comma-separated for several, e.g. `--in
+    /// comments,strings`). Uses the language backend's syntax tree.
+    #[arg(long = "in", value_enum, value_delimiter = ',')]
+    pub in_scopes: Vec<SyntaxScopeArg>,
+
     /// Search mode (text, symbol, or auto).
     #[arg(long = "mode", value_enum, default_value_t = SearchModeArg::Text)]
     pub mode: SearchModeArg,
 
     /// Symbol views to materialize in symbol mode.
     ///
-    /// Values: `meta`, `decl`, `def`, `parent`, `comment`, `matches`.
+    /// Values: `meta`, `decl`, `def`, `parent`, `comment`, `doc`,
+    /// `matches`. `doc` prints the summary line of the doc comment.
     /// Multiple views can be combined via commas or repeated flags,
     /// e.g. `--view decl,comment` or `--view decl --view comment`.
     #[arg(long = "view", value_delimiter = ',')]
@@ -145,6 +191,13 @@ pub struct SearchArgs {
     #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
     pub format: OutputFormat,
 
+    /// When to highlight match spans in text and table output.
+    ///
+    /// `auto` highlights only when stdout is a terminal and `NO_COLOR`
+    /// is not set.
+    #[arg(long = "color", value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
     /// Optional server URL for delegating search to a daemon.
     ///
     /// When set (either via this flag or the `SYMGREP_SERVER_URL`
@@ -182,7 +235,32 @@ impl FollowDirectionArg {
 #[derive(Args, Debug)]
 pub struct FollowArgs {
     /// Search pattern used to select target symbols.
-    pub pattern: String,
+    ///
+    /// Not used in path mode (`--from` / `--to`).
+    #[arg(required_unless_present = "from", conflicts_with = "from")]
+    pub pattern: Option<String>,
+
+    /// Path mode: pattern selecting the symbols call paths start from.
+    #[arg(long = "from", requires = "to")]
+    pub from: Option<String>,
+
+    /// Path mode: pattern selecting the symbols call paths must reach.
+    #[arg(long = "to", requires = "from")]
+    pub to: Option<String>,
+
+    /// Number of caller/callee levels to follow (default 1, i.e.
+    /// direct relationships only).
+    ///
+    /// In path mode this is the maximum number of calls in a path
+    /// (default 10).
+    #[arg(long = "depth", value_parser = clap::value_parser!(u32).range(1..))]
+    pub depth: Option<u32>,
+
+    /// Maximum number of caller/callee nodes (or, in path mode,
+    /// expanded call edges) before expansion stops and the result is
+    /// marked `truncated`.
+    #[arg(long = "max-nodes")]
+    pub max_nodes: Option<usize>,
 
     /// Paths to search (defaults to current directory if omitted).
     #[arg(short = 'p', long = "path")]
@@ -200,6 +278,10 @@ pub struct FollowArgs {
     #[arg(long = "language")]
     pub language: Option<String>,
 
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
     /// Interpret the pattern as a literal identifier when matching
     /// target symbol names.
     ///
@@ -240,23 +322,446 @@ pub struct FollowArgs {
     #[arg(long = "max-lines")]
     pub max_lines: Option<usize>,
 
+    /// Read symbols and call edges from an existing index instead of
+    /// parsing every file.
+    #[arg(long = "use-index")]
+    pub use_index: bool,
+
+    /// Index backend to use when `--use-index` is enabled.
+    #[arg(long = "index-backend", value_enum)]
+    pub index_backend: Option<IndexBackendArg>,
+
+    /// Location for on-disk index data used with `--use-index`.
+    #[arg(long = "index-path")]
+    pub index_path: Option<PathBuf>,
+
+    /// Output format (text or json).
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Optional server URL for delegating follow to a daemon.
+    ///
+    /// When set (either via this flag or the `SYMGREP_SERVER_URL`
+    /// environment variable), the CLI sends an internal symbol search
+    /// request to the HTTP server instead of running a local search.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local follow.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `query` subcommand.
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    /// Tree-sitter query in S-expression syntax, e.g.
+    /// `(call_expression function: (identifier) @callee)`.
+    pub query: String,
+
+    /// Paths to search (defaults to current directory if omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Language whose grammar the query is written for (e.g. "rust").
+    ///
+    /// When omitted, the query runs against every language whose
+    /// grammar accepts it; files in other languages are skipped.
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// Maximum number of captures to return.
+    #[arg(long = "limit")]
+    pub limit: Option<usize>,
+
+    /// Maximum number of lines per capture snippet.
+    #[arg(long = "max-lines")]
+    pub max_lines: Option<usize>,
+
+    /// Output format (text, table, or json).
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// When to highlight captured nodes in text and table output.
+    #[arg(long = "color", value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Optional server URL for delegating the query to a daemon.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local execution.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `refs` subcommand.
+#[derive(Args, Debug)]
+pub struct RefsArgs {
+    /// Identifier to find usages of (matched exactly).
+    pub name: String,
+
+    /// Paths to search (defaults to current directory if omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Optional language filter (e.g. "typescript").
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
+    /// Only report these usage kinds (comma-separated, e.g.
+    /// `--kind write,call`).
+    #[arg(long = "kind", value_enum, value_delimiter = ',')]
+    pub kinds: Vec<ReferenceKindArg>,
+
+    /// Maximum number of usages to return.
+    #[arg(long = "limit")]
+    pub limit: Option<usize>,
+
+    /// Set to 0 to omit the source line of each usage.
+    #[arg(long = "max-lines")]
+    pub max_lines: Option<usize>,
+
+    /// Output format (text, table, or json).
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// When to highlight usages in text and table output.
+    #[arg(long = "color", value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Optional server URL for delegating the search to a daemon.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local search.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `graph` subcommand.
+#[derive(Args, Debug)]
+pub struct GraphArgs {
+    /// Search pattern selecting the symbols that become graph nodes.
+    pub pattern: String,
+
+    /// Paths to search (defaults to current directory if omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Optional language hint or filter (e.g. "typescript").
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
+    /// Interpret the pattern as a literal identifier when matching
+    /// symbol names.
+    #[arg(long = "literal")]
+    pub literal: bool,
+
+    /// Maximum number of selected symbols.
+    #[arg(long = "limit")]
+    pub limit: Option<usize>,
+
+    /// Group nodes into clusters by defining file or by enclosing
+    /// namespace/module.
+    #[arg(long = "cluster", value_enum)]
+    pub cluster: Option<GraphClusterArg>,
+
+    /// Also include callees outside the selected symbols as external
+    /// nodes.
+    #[arg(long = "external")]
+    pub external: bool,
+
+    /// Read symbols and call edges from an existing index instead of
+    /// parsing every file.
+    #[arg(long = "use-index")]
+    pub use_index: bool,
+
+    /// Index backend to use when `--use-index` is enabled.
+    #[arg(long = "index-backend", value_enum)]
+    pub index_backend: Option<IndexBackendArg>,
+
+    /// Location for on-disk index data used with `--use-index`.
+    #[arg(long = "index-path")]
+    pub index_path: Option<PathBuf>,
+
+    /// Output format (dot, mermaid, or json).
+    #[arg(long = "format", value_enum, default_value_t = GraphFormatArg::Dot)]
+    pub format: GraphFormatArg,
+
+    /// Optional server URL for delegating the search to a daemon.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local search.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `deps` subcommand.
+#[derive(Args, Debug)]
+pub struct DepsArgs {
+    /// File whose dependencies are reported.
+    pub file: PathBuf,
+
+    /// Paths searched for importers (defaults to current directory if
+    /// omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Optional language filter (e.g. "typescript").
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
+    /// Also report every file reachable through imports and every
+    /// file that reaches this one.
+    #[arg(long = "transitive")]
+    pub transitive: bool,
+
+    /// Read dependencies from an existing index instead of parsing
+    /// every file.
+    #[arg(long = "use-index")]
+    pub use_index: bool,
+
+    /// Index backend to use when `--use-index` is enabled.
+    #[arg(long = "index-backend", value_enum)]
+    pub index_backend: Option<IndexBackendArg>,
+
+    /// Location for on-disk index data used with `--use-index`.
+    #[arg(long = "index-path")]
+    pub index_path: Option<PathBuf>,
+
+    /// Output format (text or json).
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Optional server URL for delegating the query to a daemon.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local execution.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `check-deps` subcommand.
+#[derive(Args, Debug)]
+pub struct CheckDepsArgs {
+    /// Paths whose files are checked (defaults to current directory if
+    /// omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Optional language filter (e.g. "typescript").
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
+    /// Layering rule of the form "<glob> must not import <glob>".
+    /// Repeatable; replaces the rules from the config file.
+    #[arg(long = "rule")]
+    pub rules: Vec<String>,
+
+    /// Only check layering rules, not import cycles.
+    #[arg(long = "no-cycles")]
+    pub no_cycles: bool,
+
+    /// Read dependencies from an existing index instead of parsing
+    /// every file.
+    #[arg(long = "use-index")]
+    pub use_index: bool,
+
+    /// Index backend to use when `--use-index` is enabled.
+    #[arg(long = "index-backend", value_enum)]
+    pub index_backend: Option<IndexBackendArg>,
+
+    /// Location for on-disk index data used with `--use-index`.
+    #[arg(long = "index-path")]
+    pub index_path: Option<PathBuf>,
+
+    /// Output format (text, json or sarif).
+    #[arg(long = "format", value_enum, default_value_t = CheckDepsFormatArg::Text)]
+    pub format: CheckDepsFormatArg,
+
+    /// Optional server URL for delegating the check to a daemon.
+    #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Disable use of any configured server and force local execution.
+    #[arg(long = "no-server")]
+    pub no_server: bool,
+}
+
+/// Arguments specific to the `hierarchy` subcommand.
+#[derive(Args, Debug)]
+pub struct HierarchyArgs {
+    /// Name of the class, interface or trait.
+    pub symbol: String,
+
+    /// Paths searched for type relations (defaults to current
+    /// directory if omitted).
+    #[arg(short = 'p', long = "path")]
+    pub paths: Vec<PathBuf>,
+
+    /// Inclusion globs applied to candidate files.
+    #[arg(long = "glob")]
+    pub globs: Vec<String>,
+
+    /// Exclusion globs applied to candidate files.
+    #[arg(long = "exclude")]
+    pub exclude_globs: Vec<String>,
+
+    /// Optional language filter (e.g. "typescript").
+    #[arg(long = "language")]
+    pub language: Option<String>,
+
+    /// C++ compile database (`compile_commands.json`).
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
+    /// Walk towards the types the symbol extends or implements
+    /// (supertypes) or the types extending or implementing it
+    /// (subtypes).
+    #[arg(long = "direction", value_enum, default_value_t = HierarchyDirectionArg::Subtypes)]
+    pub direction: HierarchyDirectionArg,
+
+    /// Maximum number of relation hops (unlimited by default).
+    #[arg(long = "depth")]
+    pub depth: Option<u32>,
+
+    /// Read type relations from an existing index instead of parsing
+    /// every file.
+    #[arg(long = "use-index")]
+    pub use_index: bool,
+
+    /// Index backend to use when `--use-index` is enabled.
+    #[arg(long = "index-backend", value_enum)]
+    pub index_backend: Option<IndexBackendArg>,
+
+    /// Location for on-disk index data used with `--use-index`.
+    #[arg(long = "index-path")]
+    pub index_path: Option<PathBuf>,
+
     /// Output format (text or json).
     #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
     pub format: OutputFormat,
 
-    /// Optional server URL for delegating follow to a daemon.
-    ///
-    /// When set (either via this flag or the `SYMGREP_SERVER_URL`
-    /// environment variable), the CLI sends an internal symbol search
-    /// request to the HTTP server instead of running a local search.
+    /// Optional server URL for delegating the query to a daemon.
     #[arg(long = "server", env = "SYMGREP_SERVER_URL")]
     pub server: Option<String>,
 
-    /// Disable use of any configured server and force local follow.
+    /// Disable use of any configured server and force local execution.
     #[arg(long = "no-server")]
     pub no_server: bool,
 }
 
+/// Direction for the `hierarchy` subcommand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HierarchyDirectionArg {
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirectionArg {
+    pub fn to_model(self) -> HierarchyDirection {
+        match self {
+            HierarchyDirectionArg::Supertypes => HierarchyDirection::Supertypes,
+            HierarchyDirectionArg::Subtypes => HierarchyDirection::Subtypes,
+        }
+    }
+}
+
+/// Output format for the `check-deps` subcommand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckDepsFormatArg {
+    Text,
+    Json,
+    Sarif,
+}
+
+/// Output format for the `graph` subcommand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormatArg {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+/// Clustering for the `graph` subcommand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphClusterArg {
+    File,
+    Namespace,
+}
+
+impl GraphClusterArg {
+    pub fn to_model(self) -> crate::models::GraphClusterBy {
+        match self {
+            GraphClusterArg::File => crate::models::GraphClusterBy::File,
+            GraphClusterArg::Namespace => crate::models::GraphClusterBy::Namespace,
+        }
+    }
+}
+
 /// CLI representation of search mode.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -266,6 +771,48 @@ pub enum SearchModeArg {
     Auto,
 }
 
+/// CLI representation of `--in` syntactic scopes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyntaxScopeArg {
+    Code,
+    Comments,
+    Strings,
+}
+
+impl SyntaxScopeArg {
+    pub fn to_model(self) -> crate::models::SyntaxScope {
+        match self {
+            SyntaxScopeArg::Code => crate::models::SyntaxScope::Code,
+            SyntaxScopeArg::Comments => crate::models::SyntaxScope::Comments,
+            SyntaxScopeArg::Strings => crate::models::SyntaxScope::Strings,
+        }
+    }
+}
+
+/// CLI representation of `refs --kind` usage kinds.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKindArg {
+    Read,
+    Write,
+    Type,
+    Import,
+    Call,
+}
+
+impl ReferenceKindArg {
+    pub fn to_model(self) -> crate::models::ReferenceKind {
+        match self {
+            ReferenceKindArg::Read => crate::models::ReferenceKind::Read,
+            ReferenceKindArg::Write => crate::models::ReferenceKind::Write,
+            ReferenceKindArg::Type => crate::models::ReferenceKind::Type,
+            ReferenceKindArg::Import => crate::models::ReferenceKind::Import,
+            ReferenceKindArg::Call => crate::models::ReferenceKind::Call,
+        }
+    }
+}
+
 /// CLI representation of symbol views for symbol mode.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -275,6 +822,7 @@ pub enum SymbolViewArg {
     Def,
     Parent,
     Comment,
+    Doc,
     Matches,
 }
 
@@ -286,6 +834,7 @@ impl SymbolViewArg {
             SymbolViewArg::Def => crate::models::SymbolView::Def,
             SymbolViewArg::Parent => crate::models::SymbolView::Parent,
             SymbolViewArg::Comment => crate::models::SymbolView::Comment,
+            SymbolViewArg::Doc => crate::models::SymbolView::Doc,
             SymbolViewArg::Matches => crate::models::SymbolView::Matches,
         }
     }
@@ -300,6 +849,15 @@ pub enum OutputFormat {
     Json,
 }
 
+/// CLI representation of `--color`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
 /// CLI representation of index backend kind.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -352,6 +910,11 @@ pub struct IndexArgs {
     #[arg(long = "language")]
     pub language: Option<String>,
 
+    /// C++ compile database (`compile_commands.json`); C/C++ sources
+    /// it does not list are skipped.
+    #[arg(long = "compile-commands")]
+    pub compile_commands: Option<PathBuf>,
+
     /// Index backend to use.
     ///
     /// When omitted, the file backend is used by default.
@@ -521,33 +1084,8 @@ pub fn search_config_from_args(args: &SearchArgs) -> Result<SearchConfig> {
     };
 
     let index = if args.use_index {
-        let backend_arg = match (&args.index_backend, &args.index_path) {
-            (Some(kind), _) => *kind,
-            (None, Some(path)) => {
-                if path
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .map(|ext| ext.eq_ignore_ascii_case("sqlite"))
-                    .unwrap_or(false)
-                {
-                    IndexBackendArg::Sqlite
-                } else {
-                    IndexBackendArg::File
-                }
-            }
-            (None, None) => IndexBackendArg::File,
-        };
-
-        let backend = match backend_arg {
-            IndexBackendArg::File => IndexBackendKind::File,
-            IndexBackendArg::Sqlite => IndexBackendKind::Sqlite,
-        };
-
-        let index_path = match (&args.index_path, backend_arg) {
-            (Some(path), _) => path.clone(),
-            (None, IndexBackendArg::File) => PathBuf::from(".symgrep"),
-            (None, IndexBackendArg::Sqlite) => PathBuf::from(".symgrep").join("index.sqlite"),
-        };
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
 
         Some(IndexConfig {
             paths: paths.clone(),
@@ -556,12 +1094,14 @@ pub fn search_config_from_args(args: &SearchArgs) -> Result<SearchConfig> {
             backend,
             index_path,
             language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
         })
     } else {
         None
     };
 
     let symbol_views = args.view.iter().map(|v| v.to_model()).collect();
+    let scopes = args.in_scopes.iter().map(|s| s.to_model()).collect();
 
     Ok(SearchConfig {
         pattern: args.pattern.clone(),
@@ -575,24 +1115,71 @@ pub fn search_config_from_args(args: &SearchArgs) -> Result<SearchConfig> {
         limit: args.limit,
         max_lines: args.max_lines,
         reindex_on_search: args.reindex_on_search,
+        multiline: args.multiline,
+        encoding: args.encoding.clone(),
+        enclosing: args.enclosing,
+        call_graph: false,
+        scopes,
+        reference_kinds: Vec::new(),
         query_expr: None,
         index,
+        compile_commands: args.compile_commands.clone(),
     })
 }
 
 /// Build a core `SearchConfig` from CLI `FollowArgs`.
 ///
-/// Follow always uses symbol-mode search and currently ignores
-/// index-backed search in order to ensure call metadata is available.
+/// Follow always uses symbol-mode search; with `--use-index` the call
+/// edges come from the index's `calls` table.
 pub fn follow_search_config_from_args(args: &FollowArgs) -> Result<SearchConfig> {
+    let pattern = args
+        .pattern
+        .clone()
+        .or_else(|| args.from.clone())
+        .unwrap_or_default();
+    follow_config_for_pattern(args, pattern)
+}
+
+/// Build the `SearchConfig` used to load the call graph for
+/// multi-level and path follows: every symbol under the follow paths.
+pub fn follow_graph_config_from_args(args: &FollowArgs) -> Result<SearchConfig> {
+    let mut config = follow_config_for_pattern(args, "name:".to_string())?;
+    config.literal = false;
+    config.limit = None;
+    Ok(config)
+}
+
+/// Build the `SearchConfig` selecting the `--to` symbols of a path
+/// follow.
+pub fn follow_to_config_from_args(args: &FollowArgs) -> Result<SearchConfig> {
+    let mut config = follow_config_for_pattern(args, args.to.clone().unwrap_or_default())?;
+    config.limit = None;
+    Ok(config)
+}
+
+fn follow_config_for_pattern(args: &FollowArgs, pattern: String) -> Result<SearchConfig> {
     let paths = if args.paths.is_empty() {
         vec![PathBuf::from(".")]
     } else {
         args.paths.clone()
     };
 
+    let index = args.use_index.then(|| {
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
+        IndexConfig {
+            paths: paths.clone(),
+            globs: args.globs.clone(),
+            exclude_globs: args.exclude_globs.clone(),
+            backend,
+            index_path,
+            language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
+        }
+    });
+
     Ok(SearchConfig {
-        pattern: args.pattern.clone(),
+        pattern,
         paths,
         globs: args.globs.clone(),
         exclude_globs: args.exclude_globs.clone(),
@@ -606,8 +1193,286 @@ pub fn follow_search_config_from_args(args: &FollowArgs) -> Result<SearchConfig>
         limit: args.limit,
         max_lines: None,
         reindex_on_search: false,
+        multiline: false,
+        encoding: None,
+        enclosing: false,
+        call_graph: true,
+        scopes: Vec::new(),
+        reference_kinds: Vec::new(),
+        index,
+        query_expr: None,
+        compile_commands: args.compile_commands.clone(),
+    })
+}
+
+/// Build a core `SearchConfig` for `engine::run_query` from CLI
+/// `QueryArgs`. The query source is carried in `pattern`.
+pub fn query_config_from_args(args: &QueryArgs) -> Result<SearchConfig> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    Ok(SearchConfig {
+        pattern: args.query.clone(),
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        mode: SearchMode::Text,
+        literal: false,
+        symbol_views: Vec::new(),
+        limit: args.limit,
+        max_lines: args.max_lines,
+        reindex_on_search: false,
+        multiline: false,
+        encoding: None,
+        enclosing: true,
+        call_graph: false,
+        scopes: Vec::new(),
+        reference_kinds: Vec::new(),
+        index: None,
+        query_expr: None,
+        compile_commands: None,
+    })
+}
+
+/// Build a core `SearchConfig` for a `refs:` search from CLI
+/// `RefsArgs`.
+pub fn refs_search_config_from_args(args: &RefsArgs) -> Result<SearchConfig> {
+    let name = args.name.trim();
+    if name.is_empty() {
+        bail!("refs name must not be empty");
+    }
+
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    Ok(SearchConfig {
+        pattern: format!("refs:{name}"),
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        mode: SearchMode::Text,
+        literal: false,
+        symbol_views: Vec::new(),
+        limit: args.limit,
+        max_lines: args.max_lines,
+        reindex_on_search: false,
+        multiline: false,
+        encoding: None,
+        enclosing: true,
+        call_graph: false,
+        scopes: Vec::new(),
+        reference_kinds: args.kinds.iter().map(|k| k.to_model()).collect(),
         index: None,
+        query_expr: Some(QueryExpr::Term(QueryTerm {
+            field: QueryField::References,
+            value: name.to_string(),
+        })),
+        compile_commands: args.compile_commands.clone(),
+    })
+}
+
+/// Build a core `SearchConfig` from CLI `GraphArgs`.
+///
+/// Namespace clustering needs each symbol's `parent_chain`, so the
+/// parent view is requested in that case.
+pub fn graph_search_config_from_args(args: &GraphArgs) -> Result<SearchConfig> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    let symbol_views = match args.cluster {
+        Some(GraphClusterArg::Namespace) => vec![crate::models::SymbolView::Parent],
+        _ => Vec::new(),
+    };
+
+    let index = args.use_index.then(|| {
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
+        IndexConfig {
+            paths: paths.clone(),
+            globs: args.globs.clone(),
+            exclude_globs: args.exclude_globs.clone(),
+            backend,
+            index_path,
+            language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
+        }
+    });
+
+    Ok(SearchConfig {
+        pattern: args.pattern.clone(),
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        mode: SearchMode::Symbol,
+        literal: args.literal,
+        symbol_views,
+        limit: args.limit,
+        max_lines: None,
+        reindex_on_search: false,
+        multiline: false,
+        encoding: None,
+        enclosing: false,
+        call_graph: true,
+        scopes: Vec::new(),
+        reference_kinds: Vec::new(),
+        index,
         query_expr: None,
+        compile_commands: args.compile_commands.clone(),
+    })
+}
+
+/// Backend and path of the index used by `--use-index`.
+///
+/// Without `--index-backend`, a `.sqlite` extension on `--index-path`
+/// selects the SQLite backend; the default file backend location
+/// lets the engine auto-select an existing index.
+fn search_index_location(
+    backend: Option<IndexBackendArg>,
+    index_path: Option<&PathBuf>,
+) -> (IndexBackendKind, PathBuf) {
+    let backend_arg = match (backend, index_path) {
+        (Some(kind), _) => kind,
+        (None, Some(path)) => {
+            if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|ext| ext.eq_ignore_ascii_case("sqlite"))
+                .unwrap_or(false)
+            {
+                IndexBackendArg::Sqlite
+            } else {
+                IndexBackendArg::File
+            }
+        }
+        (None, None) => IndexBackendArg::File,
+    };
+
+    let backend = match backend_arg {
+        IndexBackendArg::File => IndexBackendKind::File,
+        IndexBackendArg::Sqlite => IndexBackendKind::Sqlite,
+    };
+
+    let index_path = match (index_path, backend_arg) {
+        (Some(path), _) => path.clone(),
+        (None, IndexBackendArg::File) => PathBuf::from(".symgrep"),
+        (None, IndexBackendArg::Sqlite) => PathBuf::from(".symgrep").join("index.sqlite"),
+    };
+
+    (backend, index_path)
+}
+
+/// Build a core `DepsConfig` from CLI `DepsArgs`.
+pub fn deps_config_from_args(args: &DepsArgs) -> Result<DepsConfig> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    let index = args.use_index.then(|| {
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
+        IndexConfig {
+            paths: paths.clone(),
+            globs: args.globs.clone(),
+            exclude_globs: args.exclude_globs.clone(),
+            backend,
+            index_path,
+            language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
+        }
+    });
+
+    Ok(DepsConfig {
+        file: args.file.clone(),
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        transitive: args.transitive,
+        index,
+        compile_commands: args.compile_commands.clone(),
+    })
+}
+
+/// Build a core `CheckDepsConfig` from CLI `CheckDepsArgs`.
+pub fn check_deps_config_from_args(args: &CheckDepsArgs) -> Result<CheckDepsConfig> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    let index = args.use_index.then(|| {
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
+        IndexConfig {
+            paths: paths.clone(),
+            globs: args.globs.clone(),
+            exclude_globs: args.exclude_globs.clone(),
+            backend,
+            index_path,
+            language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
+        }
+    });
+
+    Ok(CheckDepsConfig {
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        rules: args.rules.clone(),
+        skip_cycles: args.no_cycles,
+        index,
+        compile_commands: args.compile_commands.clone(),
+    })
+}
+
+/// Build a core `HierarchyConfig` from CLI `HierarchyArgs`.
+pub fn hierarchy_config_from_args(args: &HierarchyArgs) -> Result<HierarchyConfig> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+
+    let index = args.use_index.then(|| {
+        let (backend, index_path) =
+            search_index_location(args.index_backend, args.index_path.as_ref());
+        IndexConfig {
+            paths: paths.clone(),
+            globs: args.globs.clone(),
+            exclude_globs: args.exclude_globs.clone(),
+            backend,
+            index_path,
+            language: args.language.clone(),
+            compile_commands: args.compile_commands.clone(),
+        }
+    });
+
+    Ok(HierarchyConfig {
+        symbol: args.symbol.clone(),
+        paths,
+        globs: args.globs.clone(),
+        exclude_globs: args.exclude_globs.clone(),
+        language: args.language.clone(),
+        direction: args.direction.to_model(),
+        depth: args.depth,
+        index,
+        compile_commands: args.compile_commands.clone(),
     })
 }
 
@@ -654,6 +1519,7 @@ pub fn index_config_from_args(args: &IndexArgs) -> Result<IndexConfig> {
         backend,
         index_path,
         language: args.language.clone(),
+        compile_commands: args.compile_commands.clone(),
     })
 }
 
@@ -700,6 +1566,7 @@ pub fn index_info_config_from_args(args: &IndexInfoArgs) -> Result<IndexConfig>
         backend,
         index_path,
         language: args.language.clone(),
+        compile_commands: None,
     })
 }
 
@@ -743,6 +1610,7 @@ pub fn symbol_attributes_request_from_args(args: &AnnotateArgs) -> Result<Symbol
         backend,
         index_path,
         language: None,
+        compile_commands: None,
     };
 
     let selector = SymbolSelector {
@@ -796,11 +1664,17 @@ mod tests {
             context: None,
             use_index: false,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            in_scopes: Vec::new(),
             index_backend: None,
             index_path: None,
             format: OutputFormat::Text,
+            color: ColorChoice::Auto,
             server: None,
             no_server: false,
+            compile_commands: None,
         };
 
         let config = search_config_from_args(&args).expect("config");
@@ -832,11 +1706,17 @@ mod tests {
             context: Some(2),
             use_index: true,
             reindex_on_search: true,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            in_scopes: Vec::new(),
             index_backend: Some(IndexBackendArg::File),
             index_path: Some(PathBuf::from(".symgrep")),
             format: OutputFormat::Json,
+            color: ColorChoice::Auto,
             server: Some("http://localhost:7878".to_string()),
             no_server: false,
+            compile_commands: None,
         };
 
         let config = search_config_from_args(&args).expect("config");
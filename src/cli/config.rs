@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -5,9 +6,14 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::cli::args::{
-    FollowDirectionArg, IndexBackendArg, OutputFormat, SearchModeArg, SymbolViewArg,
+    CheckDepsFormatArg, FollowDirectionArg, GraphClusterArg, GraphFormatArg,
+    HierarchyDirectionArg, IndexBackendArg, OutputFormat, ReferenceKindArg, SearchModeArg,
+    SymbolViewArg, SyntaxScopeArg,
+};
+use crate::cli::{
+    AnnotateArgs, CheckDepsArgs, DepsArgs, FollowArgs, GraphArgs, HierarchyArgs, IndexArgs,
+    IndexInfoArgs, QueryArgs, RefsArgs, SearchArgs, ServeArgs,
 };
-use crate::cli::{AnnotateArgs, FollowArgs, IndexArgs, IndexInfoArgs, SearchArgs, ServeArgs};
 
 /// Top-level representation of `.symgrep/config.toml`.
 #[derive(Debug, Default, Deserialize)]
@@ -27,8 +33,34 @@ pub struct CliConfig {
     #[serde(default)]
     pub follow: Option<FollowSection>,
 
+    #[serde(default)]
+    pub query: Option<QuerySection>,
+
+    #[serde(default)]
+    pub refs: Option<RefsSection>,
+
+    #[serde(default)]
+    pub graph: Option<GraphSection>,
+
+    #[serde(default)]
+    pub deps: Option<DepsSection>,
+
+    #[serde(default)]
+    pub check_deps: Option<CheckDepsSection>,
+
+    #[serde(default)]
+    pub hierarchy: Option<HierarchySection>,
+
     #[serde(default)]
     pub http: Option<HttpSection>,
+
+    #[serde(default)]
+    pub cpp: Option<CppSection>,
+
+    /// Saved queries (`[queries]`), referenced from patterns as
+    /// `@name` or `@name(arg, ...)`.
+    #[serde(default)]
+    pub queries: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -44,6 +76,14 @@ pub struct SearchSection {
     #[serde(default)]
     pub literal: Option<bool>,
     #[serde(default)]
+    pub multiline: Option<bool>,
+    #[serde(default)]
+    pub encoding: Option<String>,
+    #[serde(default)]
+    pub enclosing: Option<bool>,
+    #[serde(default, rename = "in")]
+    pub in_scopes: Option<Vec<SyntaxScopeArg>>,
+    #[serde(default)]
     pub mode: Option<SearchModeArg>,
     #[serde(default)]
     pub view: Option<Vec<SymbolViewArg>>,
@@ -117,6 +157,162 @@ pub struct ServeSection {
     pub addr: Option<String>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct QuerySection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub max_lines: Option<usize>,
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RefsSection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub kind: Option<Vec<ReferenceKindArg>>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GraphSection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cluster: Option<GraphClusterArg>,
+    #[serde(default)]
+    pub external: Option<bool>,
+    #[serde(default)]
+    pub use_index: Option<bool>,
+    #[serde(default)]
+    pub index_backend: Option<IndexBackendArg>,
+    #[serde(default)]
+    pub index_path: Option<PathBuf>,
+    #[serde(default)]
+    pub format: Option<GraphFormatArg>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DepsSection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub transitive: Option<bool>,
+    #[serde(default)]
+    pub use_index: Option<bool>,
+    #[serde(default)]
+    pub index_backend: Option<IndexBackendArg>,
+    #[serde(default)]
+    pub index_path: Option<PathBuf>,
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CheckDepsSection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<String>,
+    #[serde(default)]
+    pub no_cycles: Option<bool>,
+    #[serde(default)]
+    pub use_index: Option<bool>,
+    #[serde(default)]
+    pub index_backend: Option<IndexBackendArg>,
+    #[serde(default)]
+    pub index_path: Option<PathBuf>,
+    #[serde(default)]
+    pub format: Option<CheckDepsFormatArg>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct HierarchySection {
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub globs: Vec<String>,
+    #[serde(default, alias = "exclude")]
+    pub exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub direction: Option<HierarchyDirectionArg>,
+    #[serde(default)]
+    pub depth: Option<u32>,
+    #[serde(default)]
+    pub use_index: Option<bool>,
+    #[serde(default)]
+    pub index_backend: Option<IndexBackendArg>,
+    #[serde(default)]
+    pub index_path: Option<PathBuf>,
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub no_server: Option<bool>,
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct FollowSection {
     #[serde(default)]
@@ -134,10 +330,20 @@ pub struct FollowSection {
     #[serde(default)]
     pub limit: Option<usize>,
     #[serde(default)]
+    pub depth: Option<u32>,
+    #[serde(default)]
+    pub max_nodes: Option<usize>,
+    #[serde(default)]
     pub max_lines: Option<usize>,
     #[serde(default)]
     pub context: Option<usize>,
     #[serde(default)]
+    pub use_index: Option<bool>,
+    #[serde(default)]
+    pub index_backend: Option<IndexBackendArg>,
+    #[serde(default)]
+    pub index_path: Option<PathBuf>,
+    #[serde(default)]
     pub format: Option<OutputFormat>,
     #[serde(default)]
     pub server: Option<String>,
@@ -151,6 +357,22 @@ pub struct HttpSection {
     pub server_url: Option<String>,
 }
 
+/// C++ settings shared by every command that parses C++ files.
+#[derive(Debug, Default, Deserialize)]
+pub struct CppSection {
+    /// Path of the build's `compile_commands.json`.
+    #[serde(default)]
+    pub compile_commands: Option<PathBuf>,
+}
+
+/// Default `--compile-commands` from the `[cpp]` section.
+fn cpp_compile_commands(config: &CliConfig) -> Option<PathBuf> {
+    config
+        .cpp
+        .as_ref()
+        .and_then(|cpp| cpp.compile_commands.clone())
+}
+
 /// Discover and load a project-local `.symgrep/config.toml` (or
 /// `.symgrep/symgrep.toml`) starting from the current working
 /// directory and walking up parent directories.
@@ -217,6 +439,30 @@ pub fn apply_search_config_defaults(config: &CliConfig, args: &mut SearchArgs) {
             }
         }
 
+        if !args.multiline {
+            if let Some(true) = search.multiline {
+                args.multiline = true;
+            }
+        }
+
+        if args.encoding.is_none() {
+            if let Some(encoding) = &search.encoding {
+                args.encoding = Some(encoding.clone());
+            }
+        }
+
+        if !args.enclosing {
+            if let Some(true) = search.enclosing {
+                args.enclosing = true;
+            }
+        }
+
+        if args.in_scopes.is_empty() {
+            if let Some(scopes) = &search.in_scopes {
+                args.in_scopes = scopes.clone();
+            }
+        }
+
         // Apply config mode only when CLI mode is still at its default value (Text).
         // This allows config to set a project-wide mode while CLI --mode always overrides.
         if matches!(args.mode, SearchModeArg::Text) {
@@ -306,6 +552,10 @@ pub fn apply_search_config_defaults(config: &CliConfig, args: &mut SearchArgs) {
             }
         }
     }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
 }
 
 pub fn apply_follow_config_defaults(config: &CliConfig, args: &mut FollowArgs) {
@@ -346,12 +596,38 @@ pub fn apply_follow_config_defaults(config: &CliConfig, args: &mut FollowArgs) {
             }
         }
 
+        if args.depth.is_none() {
+            if let Some(depth) = follow.depth.filter(|d| *d > 0) {
+                args.depth = Some(depth);
+            }
+        }
+
+        if args.max_nodes.is_none() {
+            if let Some(max_nodes) = follow.max_nodes {
+                args.max_nodes = Some(max_nodes);
+            }
+        }
+
         if args.max_lines.is_none() {
             if let Some(max_lines) = follow.max_lines {
                 args.max_lines = Some(max_lines);
             }
         }
 
+        if !args.use_index {
+            if let Some(true) = follow.use_index {
+                args.use_index = true;
+            }
+        }
+
+        if args.index_backend.is_none() {
+            args.index_backend = follow.index_backend;
+        }
+
+        if args.index_path.is_none() {
+            args.index_path = follow.index_path.clone();
+        }
+
         if args.context.is_none() {
             if let Some(context) = follow.context {
                 args.context = Some(context);
@@ -386,106 +662,383 @@ pub fn apply_follow_config_defaults(config: &CliConfig, args: &mut FollowArgs) {
             }
         }
     }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cli::args::{OutputFormat, SearchArgs};
+pub fn apply_query_config_defaults(config: &CliConfig, args: &mut QueryArgs) {
+    let query = config.query.as_ref();
 
-    fn empty_search_args_with_mode(mode: SearchModeArg) -> SearchArgs {
-        SearchArgs {
-            pattern: "foo".to_string(),
-            paths: Vec::new(),
-            globs: Vec::new(),
-            exclude_globs: Vec::new(),
-            language: None,
-            literal: false,
-            mode,
-            view: Vec::new(),
-            limit: None,
-            max_lines: None,
-            context: None,
-            use_index: false,
-            reindex_on_search: false,
-            index_backend: None,
-            index_path: None,
-            format: OutputFormat::Text,
-            server: None,
-            no_server: false,
+    if let Some(query) = query {
+        if args.paths.is_empty() && !query.paths.is_empty() {
+            args.paths = query.paths.clone();
         }
-    }
 
-    #[test]
-    fn search_config_applies_view_for_symbol_mode() {
-        let config = CliConfig {
-            search: Some(SearchSection {
-                view: Some(vec![SymbolViewArg::Def, SymbolViewArg::Matches]),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+        if args.globs.is_empty() && !query.globs.is_empty() {
+            args.globs = query.globs.clone();
+        }
 
-        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
+        if args.exclude_globs.is_empty() && !query.exclude_globs.is_empty() {
+            args.exclude_globs = query.exclude_globs.clone();
+        }
 
-        apply_search_config_defaults(&config, &mut args);
+        if args.language.is_none() {
+            args.language = query.language.clone();
+        }
 
-        assert_eq!(
-            args.view,
-            vec![SymbolViewArg::Def, SymbolViewArg::Matches]
-        );
-    }
+        if args.limit.is_none() {
+            args.limit = query.limit;
+        }
 
-    #[test]
-    fn search_config_does_not_apply_view_in_text_mode() {
-        let config = CliConfig {
-            search: Some(SearchSection {
-                view: Some(vec![SymbolViewArg::Def]),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+        if args.max_lines.is_none() {
+            args.max_lines = query.max_lines;
+        }
 
-        let mut args = empty_search_args_with_mode(SearchModeArg::Text);
+        if matches!(args.format, OutputFormat::Text) {
+            if let Some(format) = query.format {
+                args.format = format;
+            }
+        }
 
-        apply_search_config_defaults(&config, &mut args);
+        if !args.no_server {
+            if let Some(true) = query.no_server {
+                args.no_server = true;
+            }
+        }
+    }
 
-        assert!(args.view.is_empty());
+    if args.server.is_none() {
+        args.server = query
+            .and_then(|query| query.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
     }
+}
 
-    #[test]
-    fn search_config_respects_cli_view_override() {
-        let config = CliConfig {
-            search: Some(SearchSection {
-                view: Some(vec![SymbolViewArg::Def]),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+pub fn apply_graph_config_defaults(config: &CliConfig, args: &mut GraphArgs) {
+    let graph = config.graph.as_ref();
 
-        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
-        args.view = vec![SymbolViewArg::Decl];
+    if let Some(graph) = graph {
+        if args.paths.is_empty() && !graph.paths.is_empty() {
+            args.paths = graph.paths.clone();
+        }
 
-        apply_search_config_defaults(&config, &mut args);
+        if args.globs.is_empty() && !graph.globs.is_empty() {
+            args.globs = graph.globs.clone();
+        }
 
-        assert_eq!(args.view, vec![SymbolViewArg::Decl]);
+        if args.exclude_globs.is_empty() && !graph.exclude_globs.is_empty() {
+            args.exclude_globs = graph.exclude_globs.clone();
+        }
+
+        if args.language.is_none() {
+            args.language = graph.language.clone();
+        }
+
+        if args.limit.is_none() {
+            args.limit = graph.limit;
+        }
+
+        if args.cluster.is_none() {
+            args.cluster = graph.cluster;
+        }
+
+        if !args.external {
+            if let Some(true) = graph.external {
+                args.external = true;
+            }
+        }
+
+        if !args.use_index {
+            if let Some(true) = graph.use_index {
+                args.use_index = true;
+            }
+        }
+
+        if args.index_backend.is_none() {
+            args.index_backend = graph.index_backend;
+        }
+
+        if args.index_path.is_none() {
+            args.index_path = graph.index_path.clone();
+        }
+
+        if matches!(args.format, GraphFormatArg::Dot) {
+            if let Some(format) = graph.format {
+                args.format = format;
+            }
+        }
+
+        if !args.no_server {
+            if let Some(true) = graph.no_server {
+                args.no_server = true;
+            }
+        }
     }
 
-    #[test]
-    fn search_config_applies_reindex_on_search_flag() {
-        let config = CliConfig {
-            search: Some(SearchSection {
-                reindex_on_search: Some(true),
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+    if args.server.is_none() {
+        args.server = graph
+            .and_then(|graph| graph.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
+    }
 
-        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
+}
 
-        apply_search_config_defaults(&config, &mut args);
+pub fn apply_deps_config_defaults(config: &CliConfig, args: &mut DepsArgs) {
+    let deps = config.deps.as_ref();
 
-        assert!(args.reindex_on_search);
+    if let Some(deps) = deps {
+        if args.paths.is_empty() && !deps.paths.is_empty() {
+            args.paths = deps.paths.clone();
+        }
+
+        if args.globs.is_empty() && !deps.globs.is_empty() {
+            args.globs = deps.globs.clone();
+        }
+
+        if args.exclude_globs.is_empty() && !deps.exclude_globs.is_empty() {
+            args.exclude_globs = deps.exclude_globs.clone();
+        }
+
+        if args.language.is_none() {
+            args.language = deps.language.clone();
+        }
+
+        if !args.transitive {
+            if let Some(true) = deps.transitive {
+                args.transitive = true;
+            }
+        }
+
+        if !args.use_index {
+            if let Some(true) = deps.use_index {
+                args.use_index = true;
+            }
+        }
+
+        if args.index_backend.is_none() {
+            args.index_backend = deps.index_backend;
+        }
+
+        if args.index_path.is_none() {
+            args.index_path = deps.index_path.clone();
+        }
+
+        if matches!(args.format, OutputFormat::Text) {
+            if let Some(format) = deps.format {
+                args.format = format;
+            }
+        }
+
+        if !args.no_server {
+            if let Some(true) = deps.no_server {
+                args.no_server = true;
+            }
+        }
+    }
+
+    if args.server.is_none() {
+        args.server = deps
+            .and_then(|deps| deps.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
+    }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
+}
+
+pub fn apply_check_deps_config_defaults(config: &CliConfig, args: &mut CheckDepsArgs) {
+    let check = config.check_deps.as_ref();
+
+    if let Some(check) = check {
+        if args.paths.is_empty() && !check.paths.is_empty() {
+            args.paths = check.paths.clone();
+        }
+
+        if args.globs.is_empty() && !check.globs.is_empty() {
+            args.globs = check.globs.clone();
+        }
+
+        if args.exclude_globs.is_empty() && !check.exclude_globs.is_empty() {
+            args.exclude_globs = check.exclude_globs.clone();
+        }
+
+        if args.language.is_none() {
+            args.language = check.language.clone();
+        }
+
+        if args.rules.is_empty() && !check.rules.is_empty() {
+            args.rules = check.rules.clone();
+        }
+
+        if !args.no_cycles {
+            if let Some(true) = check.no_cycles {
+                args.no_cycles = true;
+            }
+        }
+
+        if !args.use_index {
+            if let Some(true) = check.use_index {
+                args.use_index = true;
+            }
+        }
+
+        if args.index_backend.is_none() {
+            args.index_backend = check.index_backend;
+        }
+
+        if args.index_path.is_none() {
+            args.index_path = check.index_path.clone();
+        }
+
+        if matches!(args.format, CheckDepsFormatArg::Text) {
+            if let Some(format) = check.format {
+                args.format = format;
+            }
+        }
+
+        if !args.no_server {
+            if let Some(true) = check.no_server {
+                args.no_server = true;
+            }
+        }
+    }
+
+    if args.server.is_none() {
+        args.server = check
+            .and_then(|check| check.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
+    }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
+}
+
+pub fn apply_hierarchy_config_defaults(config: &CliConfig, args: &mut HierarchyArgs) {
+    let hierarchy = config.hierarchy.as_ref();
+
+    if let Some(hierarchy) = hierarchy {
+        if args.paths.is_empty() && !hierarchy.paths.is_empty() {
+            args.paths = hierarchy.paths.clone();
+        }
+
+        if args.globs.is_empty() && !hierarchy.globs.is_empty() {
+            args.globs = hierarchy.globs.clone();
+        }
+
+        if args.exclude_globs.is_empty() && !hierarchy.exclude_globs.is_empty() {
+            args.exclude_globs = hierarchy.exclude_globs.clone();
+        }
+
+        if args.language.is_none() {
+            args.language = hierarchy.language.clone();
+        }
+
+        if matches!(args.direction, HierarchyDirectionArg::Subtypes) {
+            if let Some(direction) = hierarchy.direction {
+                args.direction = direction;
+            }
+        }
+
+        if args.depth.is_none() {
+            args.depth = hierarchy.depth;
+        }
+
+        if !args.use_index {
+            if let Some(true) = hierarchy.use_index {
+                args.use_index = true;
+            }
+        }
+
+        if args.index_backend.is_none() {
+            args.index_backend = hierarchy.index_backend;
+        }
+
+        if args.index_path.is_none() {
+            args.index_path = hierarchy.index_path.clone();
+        }
+
+        if matches!(args.format, OutputFormat::Text) {
+            if let Some(format) = hierarchy.format {
+                args.format = format;
+            }
+        }
+
+        if !args.no_server {
+            if let Some(true) = hierarchy.no_server {
+                args.no_server = true;
+            }
+        }
+    }
+
+    if args.server.is_none() {
+        args.server = hierarchy
+            .and_then(|hierarchy| hierarchy.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
+    }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
+}
+
+pub fn apply_refs_config_defaults(config: &CliConfig, args: &mut RefsArgs) {
+    let refs = config.refs.as_ref();
+
+    if let Some(refs) = refs {
+        if args.paths.is_empty() && !refs.paths.is_empty() {
+            args.paths = refs.paths.clone();
+        }
+
+        if args.globs.is_empty() && !refs.globs.is_empty() {
+            args.globs = refs.globs.clone();
+        }
+
+        if args.exclude_globs.is_empty() && !refs.exclude_globs.is_empty() {
+            args.exclude_globs = refs.exclude_globs.clone();
+        }
+
+        if args.language.is_none() {
+            args.language = refs.language.clone();
+        }
+
+        if args.kinds.is_empty() {
+            if let Some(kinds) = &refs.kind {
+                args.kinds = kinds.clone();
+            }
+        }
+
+        if args.limit.is_none() {
+            args.limit = refs.limit;
+        }
+
+        if matches!(args.format, OutputFormat::Text) {
+            if let Some(format) = refs.format {
+                args.format = format;
+            }
+        }
+
+        if !args.no_server {
+            if let Some(true) = refs.no_server {
+                args.no_server = true;
+            }
+        }
+    }
+
+    if args.server.is_none() {
+        args.server = refs
+            .and_then(|refs| refs.server.clone())
+            .or_else(|| config.http.as_ref().and_then(|http| http.server_url.clone()));
+    }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
     }
 }
 
@@ -543,6 +1096,10 @@ pub fn apply_index_config_defaults(config: &CliConfig, args: &mut IndexArgs) {
             }
         }
     }
+
+    if args.compile_commands.is_none() {
+        args.compile_commands = cpp_compile_commands(config);
+    }
 }
 
 pub fn apply_index_info_config_defaults(config: &CliConfig, args: &mut IndexInfoArgs) {
@@ -626,3 +1183,110 @@ pub fn apply_annotate_config_defaults(config: &CliConfig, args: &mut AnnotateArg
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::{ColorChoice, OutputFormat, SearchArgs};
+
+    fn empty_search_args_with_mode(mode: SearchModeArg) -> SearchArgs {
+        SearchArgs {
+            pattern: "foo".to_string(),
+            paths: Vec::new(),
+            globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            language: None,
+            literal: false,
+            mode,
+            view: Vec::new(),
+            limit: None,
+            max_lines: None,
+            context: None,
+            use_index: false,
+            reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            in_scopes: Vec::new(),
+            index_backend: None,
+            index_path: None,
+            format: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            server: None,
+            no_server: false,
+            compile_commands: None,
+        }
+    }
+
+    #[test]
+    fn search_config_applies_view_for_symbol_mode() {
+        let config = CliConfig {
+            search: Some(SearchSection {
+                view: Some(vec![SymbolViewArg::Def, SymbolViewArg::Matches]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
+
+        apply_search_config_defaults(&config, &mut args);
+
+        assert_eq!(
+            args.view,
+            vec![SymbolViewArg::Def, SymbolViewArg::Matches]
+        );
+    }
+
+    #[test]
+    fn search_config_does_not_apply_view_in_text_mode() {
+        let config = CliConfig {
+            search: Some(SearchSection {
+                view: Some(vec![SymbolViewArg::Def]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut args = empty_search_args_with_mode(SearchModeArg::Text);
+
+        apply_search_config_defaults(&config, &mut args);
+
+        assert!(args.view.is_empty());
+    }
+
+    #[test]
+    fn search_config_respects_cli_view_override() {
+        let config = CliConfig {
+            search: Some(SearchSection {
+                view: Some(vec![SymbolViewArg::Def]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
+        args.view = vec![SymbolViewArg::Decl];
+
+        apply_search_config_defaults(&config, &mut args);
+
+        assert_eq!(args.view, vec![SymbolViewArg::Decl]);
+    }
+
+    #[test]
+    fn search_config_applies_reindex_on_search_flag() {
+        let config = CliConfig {
+            search: Some(SearchSection {
+                reindex_on_search: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut args = empty_search_args_with_mode(SearchModeArg::Symbol);
+
+        apply_search_config_defaults(&config, &mut args);
+
+        assert!(args.reindex_on_search);
+    }
+}
@@ -1,21 +1,47 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
+use std::path::PathBuf;
 
 use anyhow::Result;
 
 use crate::cli::args::FollowArgs;
 use crate::models::{
-    CallRef, FollowCallSite, FollowDirection, FollowEdge, FollowResult, FollowSymbolRef,
-    FollowTarget, SearchResult, SymbolKind, FOLLOW_RESULT_VERSION,
+    CallRef, FollowCallSite, FollowDirection, FollowEdge, FollowPath, FollowResult,
+    FollowSymbolRef, FollowTarget, SearchResult, Symbol, SymbolKind, FOLLOW_RESULT_VERSION,
 };
 
+/// Default `--depth` for path mode.
+pub const DEFAULT_PATH_DEPTH: usize = 10;
+
+/// Default `--max-nodes`.
+pub const DEFAULT_MAX_NODES: usize = 1000;
+
+/// Bounds for multi-level follows and path searches.
+#[derive(Debug, Clone, Copy)]
+pub struct FollowLimits {
+    /// Levels of callers/callees to expand, or the maximum number of
+    /// calls in a path.
+    pub depth: usize,
+    /// Maximum number of nodes produced (trees) or call edges
+    /// expanded (paths).
+    pub max_nodes: usize,
+}
+
 /// Build a `FollowResult` from a symbol-mode `SearchResult` and a
 /// requested follow direction.
+///
+/// Direct callers/callees come from the target symbols themselves.
+/// Deeper levels are looked up in `graph`, which is required when
+/// `limits.depth` is greater than 1.
 pub fn build_follow_result(
     result: &SearchResult,
     direction: FollowDirection,
+    graph: Option<&CallGraph>,
+    limits: FollowLimits,
 ) -> FollowResult {
     let mut targets = Vec::new();
+    let mut budget = limits.max_nodes;
+    let mut truncated = false;
 
     for symbol in &result.symbols {
         let mut target = FollowTarget {
@@ -24,12 +50,24 @@ pub fn build_follow_result(
             callees: Vec::new(),
         };
 
+        let mut expand = |edges: Vec<FollowEdge>, callers: bool| {
+            let mut expansion = Expansion {
+                graph,
+                callers,
+                depth: limits.depth,
+                budget: &mut budget,
+                truncated: &mut truncated,
+                ancestors: vec![node_key(&symbol.name, &symbol.file)],
+            };
+            expansion.expand(edges, 1)
+        };
+
         if matches!(direction, FollowDirection::Callers | FollowDirection::Both) {
-            target.callers = group_call_edges(&symbol.called_by);
+            target.callers = expand(group_call_edges(&symbol.called_by), true);
         }
 
         if matches!(direction, FollowDirection::Callees | FollowDirection::Both) {
-            target.callees = group_call_edges(&symbol.calls);
+            target.callees = expand(group_call_edges(&symbol.calls), false);
         }
 
         targets.push(target);
@@ -40,7 +78,250 @@ pub fn build_follow_result(
         direction,
         query: result.query.clone(),
         targets,
+        to: None,
+        paths: Vec::new(),
+        truncated,
+    }
+}
+
+/// Build a path-mode `FollowResult`: every call path of at most
+/// `limits.depth` calls from a symbol of `from` to a symbol of `to`.
+///
+/// Paths stop at the first `to` symbol they reach and never visit a
+/// symbol twice. They are ordered by length, then by symbol names.
+/// Shorter paths are searched first; once `limits.max_nodes` call
+/// edges have been expanded the result is marked `truncated`.
+pub fn build_follow_paths(
+    from: &SearchResult,
+    to: &SearchResult,
+    graph: &CallGraph,
+    limits: FollowLimits,
+) -> FollowResult {
+    let sinks: HashSet<NodeKey> = to
+        .symbols
+        .iter()
+        .map(|s| node_key(&s.name, &s.file))
+        .collect();
+
+    let mut search = PathSearch {
+        graph,
+        sinks: &sinks,
+        depth: limits.depth,
+        budget: limits.max_nodes,
+        truncated: false,
+        paths: Vec::new(),
+    };
+
+    let mut seen = HashSet::new();
+    let sources: Vec<(FollowSymbolRef, NodeKey)> = from
+        .symbols
+        .iter()
+        .filter(|symbol| seen.insert(node_key(&symbol.name, &symbol.file)))
+        .map(|symbol| {
+            let from = FollowSymbolRef {
+                name: symbol.name.clone(),
+                kind: Some(symbol.kind),
+                file: symbol.file.clone(),
+            };
+            (from, node_key(&symbol.name, &symbol.file))
+        })
+        .collect();
+    search.walk(&sources);
+
+    let truncated = search.truncated;
+    let mut paths = search.paths;
+    paths.sort_by(|a, b| {
+        a.steps.len().cmp(&b.steps.len()).then_with(|| {
+            let names = |p: &FollowPath| {
+                std::iter::once(&p.from)
+                    .chain(p.steps.iter().map(|s| &s.symbol))
+                    .map(|s| (s.name.clone(), s.file.clone()))
+                    .collect::<Vec<_>>()
+            };
+            names(a).cmp(&names(b))
+        })
+    });
+
+    FollowResult {
+        version: FOLLOW_RESULT_VERSION.to_string(),
+        direction: FollowDirection::Callees,
+        query: from.query.clone(),
+        targets: Vec::new(),
+        to: Some(to.query.clone()),
+        paths,
+        truncated,
+    }
+}
+
+/// Symbols are identified by name and defining file, like the groups
+/// of a `FollowEdge`.
+type NodeKey = (String, PathBuf);
+
+fn node_key(name: &str, file: &std::path::Path) -> NodeKey {
+    (name.to_string(), file.to_path_buf())
+}
+
+/// Call relationships of every symbol in a search result, used to
+/// follow calls beyond the target symbols.
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    nodes: HashMap<NodeKey, GraphNode>,
+}
+
+#[derive(Debug, Default)]
+struct GraphNode {
+    kind: Option<SymbolKind>,
+    calls: Vec<CallRef>,
+    called_by: Vec<CallRef>,
+}
+
+impl CallGraph {
+    /// Symbols sharing a name and file (e.g. overloads) are merged
+    /// into one node.
+    pub fn from_symbols(symbols: &[Symbol]) -> Self {
+        let mut nodes: HashMap<NodeKey, GraphNode> = HashMap::new();
+        for symbol in symbols {
+            let node = nodes
+                .entry(node_key(&symbol.name, &symbol.file))
+                .or_default();
+            node.kind.get_or_insert(symbol.kind);
+            node.calls.extend(symbol.calls.iter().cloned());
+            node.called_by.extend(symbol.called_by.iter().cloned());
+        }
+        Self { nodes }
+    }
+
+    /// Grouped callers or callees of `key`, or `None` for symbols
+    /// outside the graph (e.g. unresolved callees).
+    fn edges(&self, key: &NodeKey, callers: bool) -> Option<Vec<FollowEdge>> {
+        let node = self.nodes.get(key)?;
+        let refs = if callers {
+            &node.called_by
+        } else {
+            &node.calls
+        };
+        let mut edges = group_call_edges(refs);
+        for edge in &mut edges {
+            edge.call_sites
+                .dedup_by(|a, b| a.line == b.line && a.column == b.column);
+            if edge.symbol.kind.is_none() {
+                edge.symbol.kind = self.kind_of(&edge.symbol);
+            }
+        }
+        Some(edges)
     }
+
+    fn kind_of(&self, symbol: &FollowSymbolRef) -> Option<SymbolKind> {
+        self.nodes
+            .get(&node_key(&symbol.name, &symbol.file))
+            .and_then(|node| node.kind)
+    }
+}
+
+/// Depth-first expansion of caller/callee trees with cycle detection.
+struct Expansion<'a> {
+    graph: Option<&'a CallGraph>,
+    callers: bool,
+    depth: usize,
+    budget: &'a mut usize,
+    truncated: &'a mut bool,
+    /// Symbols on the branch being expanded, starting with the target.
+    ancestors: Vec<NodeKey>,
+}
+
+impl Expansion<'_> {
+    fn expand(&mut self, edges: Vec<FollowEdge>, level: usize) -> Vec<FollowEdge> {
+        let mut result = Vec::with_capacity(edges.len());
+
+        for mut edge in edges {
+            if *self.budget == 0 {
+                *self.truncated = true;
+                break;
+            }
+            *self.budget -= 1;
+
+            if edge.symbol.kind.is_none() {
+                edge.symbol.kind = self.graph.and_then(|g| g.kind_of(&edge.symbol));
+            }
+
+            let key = node_key(&edge.symbol.name, &edge.symbol.file);
+            if self.ancestors.contains(&key) {
+                edge.cycle = true;
+            } else if level < self.depth {
+                if let Some(children) = self.graph.and_then(|g| g.edges(&key, self.callers)) {
+                    self.ancestors.push(key);
+                    edge.children = self.expand(children, level + 1);
+                    self.ancestors.pop();
+                }
+            }
+
+            result.push(edge);
+        }
+
+        result
+    }
+}
+
+/// Breadth-first enumeration of simple call paths between two symbol
+/// sets, so that the shortest paths are found first when the budget
+/// runs out.
+struct PathSearch<'a> {
+    graph: &'a CallGraph,
+    sinks: &'a HashSet<NodeKey>,
+    depth: usize,
+    budget: usize,
+    truncated: bool,
+    paths: Vec<FollowPath>,
+}
+
+impl PathSearch<'_> {
+    fn walk(&mut self, sources: &[(FollowSymbolRef, NodeKey)]) {
+        // Paths still being extended: the index of their source and
+        // the calls taken so far.
+        let mut queue: VecDeque<(usize, Vec<FollowEdge>)> =
+            (0..sources.len()).map(|source| (source, Vec::new())).collect();
+
+        while let Some((source, steps)) = queue.pop_front() {
+            if steps.len() >= self.depth {
+                continue;
+            }
+            let (from, from_key) = &sources[source];
+            let key = steps
+                .last()
+                .map_or_else(|| from_key.clone(), edge_key);
+            let Some(edges) = self.graph.edges(&key, false) else {
+                continue;
+            };
+
+            for edge in edges {
+                if self.budget == 0 {
+                    self.truncated = true;
+                    return;
+                }
+                self.budget -= 1;
+
+                let next = edge_key(&edge);
+                if next == *from_key || steps.iter().any(|step| edge_key(step) == next) {
+                    continue;
+                }
+
+                let mut path = steps.clone();
+                path.push(edge);
+                if self.sinks.contains(&next) {
+                    self.paths.push(FollowPath {
+                        from: from.clone(),
+                        steps: path,
+                    });
+                } else {
+                    queue.push_back((source, path));
+                }
+            }
+        }
+    }
+}
+
+fn edge_key(edge: &FollowEdge) -> NodeKey {
+    node_key(&edge.symbol.name, &edge.symbol.file)
 }
 
 fn group_call_edges(edges: &[CallRef]) -> Vec<FollowEdge> {
@@ -57,7 +338,10 @@ fn group_call_edges(edges: &[CallRef]) -> Vec<FollowEdge> {
             continue;
         };
 
-        let key = (edge.name.clone(), edge.file.clone());
+        // Cross-file callees are keyed by their defining file; call
+        // sites always live in the file of the edge itself.
+        let symbol_file = edge.target_file.as_ref().unwrap_or(&edge.file);
+        let key = (edge.name.clone(), symbol_file.clone());
         let entry = grouped.entry(key).or_default();
 
         if entry.kind.is_none() {
@@ -67,7 +351,8 @@ fn group_call_edges(edges: &[CallRef]) -> Vec<FollowEdge> {
         entry.call_sites.push(FollowCallSite {
             file: edge.file.clone(),
             line,
-            column: None,
+            column: edge.column,
+            edge_kind: edge.edge_kind,
         });
     }
 
@@ -92,6 +377,8 @@ fn group_call_edges(edges: &[CallRef]) -> Vec<FollowEdge> {
         result.push(FollowEdge {
             symbol,
             call_sites: group.call_sites,
+            children: Vec::new(),
+            cycle: false,
         });
     }
 
@@ -107,6 +394,14 @@ pub fn print_follow_text(result: &FollowResult, args: &FollowArgs) -> Result<()>
     let context = args.context.unwrap_or(0) as u32;
     let max_lines_per_block = args.max_lines.unwrap_or(usize::MAX);
 
+    if result.to.is_some() {
+        print_follow_paths(result);
+        if result.truncated {
+            eprintln!("warning: path search stopped early; raise --max-nodes to explore further");
+        }
+        return Ok(());
+    }
+
     if result.targets.is_empty() {
         return Ok(());
     }
@@ -165,6 +460,10 @@ pub fn print_follow_text(result: &FollowResult, args: &FollowArgs) -> Result<()>
         }
     }
 
+    if result.truncated {
+        eprintln!("warning: follow output truncated; raise --max-nodes to expand further");
+    }
+
     Ok(())
 }
 
@@ -200,103 +499,189 @@ fn print_edge_groups(
     highlight_with_target_name: bool,
 ) {
     for group in groups {
-        let file = group.symbol.file.display().to_string();
-        let kind = group
-            .symbol
-            .kind
-            .map(|k| format!("{:?}", k).to_lowercase())
-            .unwrap_or_else(|| "symbol".to_string());
+        // Every call site of a group lives in the same file, which is
+        // not the symbol's own file for callees resolved across files.
+        let file = group
+            .call_sites
+            .first()
+            .map_or(&group.symbol.file, |site| &site.file)
+            .display()
+            .to_string();
+        let kind = kind_label(group.symbol.kind);
 
         let first_line = group.call_sites.first().map(|s| s.line).unwrap_or(0);
         println!(
-            "{label}: {} ({kind})  [{file}:{first_line}]",
-            group.symbol.name
+            "{label}: {} ({kind})  [{file}:{first_line}]{}",
+            group.symbol.name,
+            if group.cycle { "  (cycle)" } else { "" }
         );
-
-        let Some(lines) = load_file_lines(&file, file_cache) else {
-            continue;
-        };
-
-        if max_lines_per_block == 0 {
-            continue;
+        let printed = print_call_site_context(
+            group,
+            &file,
+            target_name,
+            context,
+            max_lines_per_block,
+            file_cache,
+            highlight_with_target_name,
+        );
+        print_edge_tree(&group.children, 1);
+        if printed || !group.children.is_empty() {
+            println!();
         }
+    }
+}
 
-        let mut windows: Vec<(u32, u32)> = Vec::new();
-        let mut call_columns: HashMap<u32, u32> = HashMap::new();
+/// Print merged context windows around the call sites of `group`.
+/// Returns whether anything was printed.
+fn print_call_site_context(
+    group: &FollowEdge,
+    file: &str,
+    target_name: &str,
+    context: u32,
+    max_lines_per_block: usize,
+    file_cache: &mut HashMap<String, Vec<String>>,
+    highlight_with_target_name: bool,
+) -> bool {
+    let Some(lines) = load_file_lines(file, file_cache) else {
+        return false;
+    };
 
-        for site in &group.call_sites {
-            if site.line == 0 {
-                continue;
-            }
+    if max_lines_per_block == 0 {
+        return false;
+    }
 
-            let total_lines = lines.len() as u32;
-            if total_lines == 0 || site.line > total_lines {
-                continue;
-            }
+    let mut windows: Vec<(u32, u32)> = Vec::new();
+    let mut call_columns: HashMap<u32, u32> = HashMap::new();
 
-            let line_no = site.line;
-            let start = line_no.saturating_sub(context).max(1);
-            let end = (line_no + context).min(total_lines);
-            windows.push((start, end));
-
-            if let Some(col) = compute_call_column(
-                lines,
-                line_no,
-                target_name,
-                &group.symbol.name,
-                highlight_with_target_name,
-            ) {
-                call_columns.entry(line_no).or_insert(col);
-            }
+    for site in &group.call_sites {
+        if site.line == 0 {
+            continue;
         }
 
-        if windows.is_empty() {
+        let total_lines = lines.len() as u32;
+        if total_lines == 0 || site.line > total_lines {
             continue;
         }
 
-        windows.sort_by_key(|(start, _)| *start);
-        let mut merged: Vec<(u32, u32)> = Vec::new();
-        for (start, end) in windows {
-            if let Some(last) = merged.last_mut() {
-                if start <= last.1 + 1 {
-                    last.1 = last.1.max(end);
-                } else {
-                    merged.push((start, end));
-                }
+        let line_no = site.line;
+        let start = line_no.saturating_sub(context).max(1);
+        let end = (line_no + context).min(total_lines);
+        windows.push((start, end));
+
+        if let Some(col) = compute_call_column(
+            lines,
+            line_no,
+            target_name,
+            &group.symbol.name,
+            highlight_with_target_name,
+        ) {
+            call_columns.entry(line_no).or_insert(col);
+        }
+    }
+
+    if windows.is_empty() {
+        return false;
+    }
+
+    windows.sort_by_key(|(start, _)| *start);
+    let mut merged: Vec<(u32, u32)> = Vec::new();
+    for (start, end) in windows {
+        if let Some(last) = merged.last_mut() {
+            if start <= last.1 + 1 {
+                last.1 = last.1.max(end);
             } else {
                 merged.push((start, end));
             }
+        } else {
+            merged.push((start, end));
         }
+    }
 
-        let mut printed = 0usize;
-
-        for (start, end) in merged {
-            for line_no in start..=end {
-                if printed >= max_lines_per_block {
-                    break;
-                }
-
-                let idx = (line_no - 1) as usize;
-                if idx >= lines.len() {
-                    break;
-                }
-
-                let text = &lines[idx];
-                if let Some(col) = call_columns.get(&line_no).copied() {
-                    println!("{line_no}:{col}:  {text}");
-                } else {
-                    println!("{line_no}:  {text}");
-                }
+    let mut printed = 0usize;
 
-                printed += 1;
+    for (start, end) in merged {
+        for line_no in start..=end {
+            if printed >= max_lines_per_block {
+                break;
             }
 
-            if printed >= max_lines_per_block {
+            let idx = (line_no - 1) as usize;
+            if idx >= lines.len() {
                 break;
             }
+
+            let text = &lines[idx];
+            if let Some(col) = call_columns.get(&line_no).copied() {
+                println!("{line_no}:{col}:  {text}");
+            } else {
+                println!("{line_no}:  {text}");
+            }
+
+            printed += 1;
+        }
+
+        if printed >= max_lines_per_block {
+            break;
         }
+    }
 
-        println!();
+    true
+}
+
+/// Print deeper follow levels as an indented outline of headers.
+fn print_edge_tree(groups: &[FollowEdge], level: usize) {
+    for group in groups {
+        let site = group
+            .call_sites
+            .first()
+            .map(|site| format!("{}:{}", site.file.display(), site.line))
+            .unwrap_or_default();
+        println!(
+            "{}- {} ({})  [{site}]{}",
+            "  ".repeat(level),
+            group.symbol.name,
+            kind_label(group.symbol.kind),
+            if group.cycle { "  (cycle)" } else { "" }
+        );
+        print_edge_tree(&group.children, level + 1);
+    }
+}
+
+fn kind_label(kind: Option<SymbolKind>) -> String {
+    kind.map(|k| format!("{:?}", k).to_lowercase())
+        .unwrap_or_else(|| "symbol".to_string())
+}
+
+/// Render path-mode results: one line per path, then one line per
+/// call with its first call site.
+fn print_follow_paths(result: &FollowResult) {
+    for (idx, path) in result.paths.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        let names: Vec<&str> = std::iter::once(&path.from)
+            .chain(path.steps.iter().map(|step| &step.symbol))
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        println!("Path: {}", names.join(" -> "));
+        println!(
+            "  {} ({})  [{}]",
+            path.from.name,
+            kind_label(path.from.kind),
+            path.from.file.display()
+        );
+        for step in &path.steps {
+            let site = step
+                .call_sites
+                .first()
+                .map(|site| format!("{}:{}", site.file.display(), site.line))
+                .unwrap_or_default();
+            println!(
+                "  -> {} ({})  [{site}]",
+                step.symbol.name,
+                kind_label(step.symbol.kind)
+            );
+        }
     }
 }
 
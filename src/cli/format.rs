@@ -1,10 +1,19 @@
 use std::cmp;
 use std::collections::BTreeMap;
+use std::io::IsTerminal;
 
 use anyhow::Result;
 
-use crate::cli::args::{SearchArgs, SymbolViewArg};
-use crate::models::{ContextInfo, ContextNode, IndexSummary, SearchResult};
+use crate::cli::args::{ColorChoice, SearchArgs, SymbolViewArg};
+use crate::models::{
+    CheckDepsResult, ContextInfo, ContextNode, DepsResult, FileDependency, HierarchyDirection,
+    HierarchyEdge, HierarchyResult, IndexSummary, SearchMatch, SearchResult,
+    TransitiveDependency,
+};
+
+/// ANSI style used to highlight match spans (bold red, as in ripgrep).
+const MATCH_STYLE: &str = "\x1b[1;31m";
+const RESET_STYLE: &str = "\x1b[0m";
 
 /// Internal representation of a row rendered by the CLI.
 ///
@@ -18,9 +27,25 @@ struct DisplayRow {
     name: String,
     context_name: Option<String>,
     snippet_lines: Vec<String>,
+    /// Byte ranges of text-match occurrences within the first
+    /// snippet line.
+    spans: Vec<(usize, usize)>,
+    /// `kind name` of the symbol enclosing a text match, if known.
+    enclosing: Option<String>,
     is_symbol: bool,
 }
 
+/// Resolve `--color` to whether match spans should be highlighted.
+pub fn use_color(choice: ColorChoice) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+        }
+    }
+}
+
 /// Render a `SearchResult` in human-readable text form.
 ///
 /// When symbol views are specified via `--view`, symbol-mode output
@@ -37,6 +62,7 @@ pub fn print_text(result: &SearchResult, args: &SearchArgs) -> Result<()> {
     } else {
         let rows = build_rows(result);
         let max_lines = args.max_lines.unwrap_or(usize::MAX);
+        let color = use_color(args.color);
 
         for row in rows {
             let col_suffix = row.column.map(|c| format!(":{c}")).unwrap_or_default();
@@ -53,7 +79,19 @@ pub fn print_text(result: &SearchResult, args: &SearchArgs) -> Result<()> {
                 }
             } else {
                 let snippet = row.snippet_lines.first().cloned().unwrap_or_default();
-                println!("{}:{}{}: {}", row.file, row.line, col_suffix, snippet);
+                let snippet = if color {
+                    highlight(&snippet, &row.spans, snippet.len())
+                } else {
+                    snippet
+                };
+                let enclosing = row
+                    .enclosing
+                    .map(|label| format!(" (in {label})"))
+                    .unwrap_or_default();
+                println!(
+                    "{}:{}{}{}: {}",
+                    row.file, row.line, col_suffix, enclosing, snippet
+                );
             }
         }
 
@@ -61,6 +99,204 @@ pub fn print_text(result: &SearchResult, args: &SearchArgs) -> Result<()> {
     }
 }
 
+/// Render `symgrep query` captures as
+/// `file:line:col (in kind name): @capture text`, using the first line
+/// of each captured node.
+pub fn print_query_text(result: &SearchResult, color: bool) -> Result<()> {
+    for m in &result.matches {
+        let Some(capture) = &m.capture else {
+            continue;
+        };
+        let col_suffix = m.column.map(|c| format!(":{c}")).unwrap_or_default();
+        let enclosing = m
+            .enclosing
+            .as_ref()
+            .map(|e| format!(" (in {} {})", format!("{:?}", e.kind).to_lowercase(), e.name))
+            .unwrap_or_default();
+        let text = capture.text.lines().next().unwrap_or_default();
+        let text = if color {
+            highlight(text, &[(0, text.len())], text.len())
+        } else {
+            text.to_string()
+        };
+        println!(
+            "{}:{}{}{}: @{} {}",
+            m.path.display(),
+            m.line,
+            col_suffix,
+            enclosing,
+            capture.name,
+            text
+        );
+    }
+
+    Ok(())
+}
+
+/// Render `symgrep refs` usages as
+/// `file:line:col (in kind name): [usage] line`.
+pub fn print_refs_text(result: &SearchResult, color: bool) -> Result<()> {
+    for m in &result.matches {
+        let col_suffix = m.column.map(|c| format!(":{c}")).unwrap_or_default();
+        let enclosing = m
+            .enclosing
+            .as_ref()
+            .map(|e| format!(" (in {} {})", format!("{:?}", e.kind).to_lowercase(), e.name))
+            .unwrap_or_default();
+        let usage = m
+            .reference_kind
+            .map(|kind| format!("[{}] ", format!("{kind:?}").to_lowercase()))
+            .unwrap_or_default();
+        let snippet = m.snippet.as_deref().unwrap_or_default();
+        let snippet = if color {
+            let ranges: Vec<_> = span_ranges(m).collect();
+            highlight(snippet, &ranges, snippet.len())
+        } else {
+            snippet.to_string()
+        };
+        println!(
+            "{}:{}{}{}: {}{}",
+            m.path.display(),
+            m.line,
+            col_suffix,
+            enclosing,
+            usage,
+            snippet
+        );
+    }
+
+    Ok(())
+}
+
+/// Print a `DepsResult` as one section per list. Unresolved
+/// dependencies are marked `(external)`.
+pub fn print_deps_text(result: &DepsResult) -> Result<()> {
+    print_dependency_section("imports", &result.imports);
+    print_dependency_section("importers", &result.importers);
+    if let Some(files) = &result.transitive_imports {
+        print_transitive_section("transitive imports", files);
+    }
+    if let Some(files) = &result.transitive_importers {
+        print_transitive_section("transitive importers", files);
+    }
+    Ok(())
+}
+
+fn print_dependency_section(title: &str, deps: &[FileDependency]) {
+    println!("{title} ({}):", deps.len());
+    for dep in deps {
+        println!("  {}", dependency_line(dep));
+    }
+}
+
+fn dependency_line(dep: &FileDependency) -> String {
+    let target = match &dep.target {
+        Some(target) => format!(" -> {}", target.display()),
+        None => " (external)".to_string(),
+    };
+    format!(
+        "{}:{}: {} {}{}",
+        dep.file.display(),
+        dep.line,
+        dep.kind.as_str(),
+        dep.module,
+        target
+    )
+}
+
+/// Print a `CheckDepsResult`: one block per cycle and violation,
+/// followed by a summary line.
+pub fn print_check_deps_text(result: &CheckDepsResult) -> Result<()> {
+    for cycle in &result.cycles {
+        println!("import cycle ({} files):", cycle.files.len());
+        for dep in &cycle.dependencies {
+            println!("  {}", dependency_line(dep));
+        }
+    }
+    for violation in &result.violations {
+        println!("layering violation: {}", violation.rule);
+        println!("  {}", dependency_line(&violation.dependency));
+    }
+
+    if result.cycles.is_empty() && result.violations.is_empty() {
+        println!(
+            "checked {} files: no import cycles or layering violations",
+            result.files_checked
+        );
+    } else {
+        println!(
+            "checked {} files: {}",
+            result.files_checked,
+            check_deps_findings(result)
+        );
+    }
+    Ok(())
+}
+
+/// Finding counts of a `CheckDepsResult`, e.g. "1 import cycle,
+/// 2 layering violations".
+pub fn check_deps_findings(result: &CheckDepsResult) -> String {
+    let plural = |count: usize, noun: &str| {
+        let suffix = if count == 1 { "" } else { "s" };
+        format!("{count} {noun}{suffix}")
+    };
+    format!(
+        "{}, {}",
+        plural(result.cycles.len(), "import cycle"),
+        plural(result.violations.len(), "layering violation")
+    )
+}
+
+/// Print a `HierarchyResult`: the definitions of the queried type,
+/// then one `Sub extends Super` line per relation, indented by depth.
+pub fn print_hierarchy_text(result: &HierarchyResult) -> Result<()> {
+    if result.definitions.is_empty() {
+        println!("{} (no definition found)", result.symbol);
+    }
+    for def in &result.definitions {
+        println!(
+            "{} ({}) {}:{}",
+            def.name,
+            format!("{:?}", def.kind).to_lowercase(),
+            def.file.display(),
+            def.line
+        );
+    }
+
+    let title = match result.direction {
+        HierarchyDirection::Supertypes => "supertypes",
+        HierarchyDirection::Subtypes => "subtypes",
+    };
+    println!("{title} ({}):", result.edges.len());
+    for edge in &result.edges {
+        println!(
+            "{}{}",
+            "  ".repeat(edge.depth as usize),
+            hierarchy_edge_line(edge)
+        );
+    }
+    Ok(())
+}
+
+/// `Sub extends Super  file:line` for a hierarchy edge.
+fn hierarchy_edge_line(edge: &HierarchyEdge) -> String {
+    format!(
+        "{} {} {}  {}:{}",
+        edge.subtype,
+        edge.kind.as_str(),
+        edge.supertype,
+        edge.file.display(),
+        edge.line
+    )
+}
+
+fn print_transitive_section(title: &str, files: &[TransitiveDependency]) {
+    println!("{title} ({}):", files.len());
+    for dep in files {
+        println!("  {} (depth {})", dep.file.display(), dep.depth);
+    }
+}
+
 fn print_text_mode_with_context(
     result: &SearchResult,
     args: &SearchArgs,
@@ -71,16 +307,17 @@ fn print_text_mode_with_context(
 
     let context = args.context.unwrap_or(0) as u32;
     let max_lines_per_file = args.max_lines.unwrap_or(usize::MAX);
+    let color = use_color(args.color);
 
-    let mut matches_by_file: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    let mut matches_by_file: BTreeMap<String, Vec<&SearchMatch>> = BTreeMap::new();
     for m in &result.matches {
         let path = m.path.display().to_string();
-        matches_by_file.entry(path).or_default().push(m.line);
+        matches_by_file.entry(path).or_default().push(m);
     }
 
     let mut first_file = true;
 
-    for (file, lines) in matches_by_file {
+    for (file, file_matches) in matches_by_file {
         let source = match std::fs::read_to_string(&file) {
             Ok(s) => s,
             Err(_) => continue,
@@ -91,7 +328,7 @@ fn print_text_mode_with_context(
         }
 
         let mut windows: Vec<(u32, u32)> = Vec::new();
-        for line in lines {
+        for line in file_matches.iter().map(|m| m.line) {
             let start = line.saturating_sub(context).max(1);
             let end = (line + context).min(all_lines.len() as u32);
             windows.push((start, end));
@@ -140,7 +377,16 @@ fn print_text_mode_with_context(
                 }
 
                 let text = all_lines[idx];
-                println!("{line_no}:  {text}");
+                let spans: Vec<(usize, usize)> = file_matches
+                    .iter()
+                    .filter(|m| color && m.line == line_no)
+                    .flat_map(|m| span_ranges(m))
+                    .collect();
+                if spans.is_empty() {
+                    println!("{line_no}:  {text}");
+                } else {
+                    println!("{line_no}:  {}", highlight(text, &spans, text.len()));
+                }
                 printed += 1;
             }
 
@@ -193,7 +439,10 @@ pub fn print_index_summary_text(summary: &IndexSummary) -> Result<()> {
 /// - KIND
 /// - NAME
 /// - CONTEXT (derived from parent_chain when available)
-pub fn print_table(result: &SearchResult) -> Result<()> {
+///
+/// When `color` is set, text-match spans are highlighted in the
+/// CONTEXT column.
+pub fn print_table(result: &SearchResult, color: bool) -> Result<()> {
     let rows = build_rows(result);
 
     if rows.is_empty() {
@@ -243,7 +492,19 @@ pub fn print_table(result: &SearchResult) -> Result<()> {
         let line_str = row.line.to_string();
         let kind = truncate(&row.kind, kind_width);
         let name = truncate(&row.name, name_width);
-        let context = truncate(row.context_name.as_deref().unwrap_or(""), context_width);
+        let context_full = row.context_name.as_deref().unwrap_or("");
+        let mut context = truncate(context_full, context_width);
+        if color && !row.spans.is_empty() {
+            // Highlight only the untruncated part, then pad by visible
+            // width since escape codes would throw off `{:<width$}`.
+            let visible = if context.len() == context_full.len() {
+                context.len()
+            } else {
+                context.len() - '…'.len_utf8()
+            };
+            let padding = context_width.saturating_sub(context.chars().count());
+            context = highlight(&context, &row.spans, visible) + &" ".repeat(padding);
+        }
 
         println!(
             "{:<file_width$} {:>line_width$} {:<kind_width$} {:<name_width$} {:<context_width$}",
@@ -279,6 +540,8 @@ fn build_symbol_rows(result: &SearchResult) -> Vec<DisplayRow> {
             name: symbol.name.clone(),
             context_name,
             snippet_lines,
+            spans: Vec::new(),
+            enclosing: None,
             is_symbol: true,
         });
     }
@@ -294,19 +557,66 @@ fn build_text_rows(result: &SearchResult) -> Vec<DisplayRow> {
             file: m.path.display().to_string(),
             line: m.line,
             column: m.column,
-            kind: "text".to_string(),
-            name: result.query.clone(),
+            kind: match (&m.capture, m.reference_kind) {
+                (Some(capture), _) => format!("@{}", capture.name),
+                (None, Some(kind)) => format!("{kind:?}").to_lowercase(),
+                (None, None) => "text".to_string(),
+            },
+            name: m.capture.as_ref().map_or_else(
+                || result.query.clone(),
+                |c| c.text.lines().next().unwrap_or_default().to_string(),
+            ),
             context_name: m.snippet.as_deref().map(|s| s.to_string()),
             snippet_lines: m
                 .snippet
                 .as_deref()
                 .map(|s| vec![s.to_string()])
                 .unwrap_or_default(),
+            spans: span_ranges(m).collect(),
+            enclosing: m
+                .enclosing
+                .as_ref()
+                .map(|e| format!("{} {}", format!("{:?}", e.kind).to_lowercase(), e.name)),
             is_symbol: false,
         })
         .collect()
 }
 
+fn span_ranges(m: &SearchMatch) -> impl Iterator<Item = (usize, usize)> + '_ {
+    m.spans
+        .iter()
+        .map(|span| (span.start as usize, span.end as usize))
+}
+
+/// Wrap the given byte ranges of `text` in highlight escape codes.
+///
+/// Ranges are clipped to the first `limit` bytes; ranges that overlap
+/// an earlier one or do not fall on character boundaries are skipped.
+fn highlight(text: &str, ranges: &[(usize, usize)], limit: usize) -> String {
+    let limit = limit.min(text.len());
+    let mut out = String::with_capacity(text.len() + ranges.len() * 12);
+    let mut pos = 0;
+
+    for &(start, end) in ranges {
+        let end = end.min(limit);
+        if start < pos
+            || start >= end
+            || !text.is_char_boundary(start)
+            || !text.is_char_boundary(end)
+        {
+            continue;
+        }
+        out.push_str(&text[pos..start]);
+        out.push_str(MATCH_STYLE);
+        out.push_str(&text[start..end]);
+        out.push_str(RESET_STYLE);
+        pos = end;
+    }
+
+    out.push_str(&text[pos..]);
+    out
+}
+
 fn context_snippet_lines(context: Option<&ContextInfo>) -> Vec<String> {
     context
         .map(|c| c.snippet.lines().map(|s| s.to_string()).collect())
@@ -346,6 +656,7 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
         .view
         .iter()
         .any(|v| matches!(v, SymbolViewArg::Comment));
+    let show_doc = args.view.iter().any(|v| matches!(v, SymbolViewArg::Doc));
     let show_matches = args
         .view
         .iter()
@@ -362,9 +673,10 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
         .any(|v| matches!(v, SymbolViewArg::Meta))
         && !has_region_view
         && !show_comment
+        && !show_doc
         && !show_matches;
     let show_context =
-        has_region_view || (!show_comment && !show_matches && !meta_only);
+        has_region_view || (!show_comment && !show_doc && !show_matches && !meta_only);
     let max_lines = args.max_lines.unwrap_or(usize::MAX);
     let context_lines = args.context.unwrap_or(0);
 
@@ -374,10 +686,17 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
         let col = Some(symbol.range.start_column);
         let col_suffix = col.map(|c| format!(":{c}")).unwrap_or_default();
         let kind = format!("{:?}", symbol.kind).to_lowercase();
-        let def_suffix = symbol
-            .def_line_count
-            .map(|n| format!(" (def: {n} lines)"))
-            .unwrap_or_default();
+        // Members defined out of line also name their definition.
+        let def_location = symbol
+            .definition
+            .as_ref()
+            .map(|def| format!("{}:{}", def.file.display(), def.range.start_line));
+        let def_suffix = match (def_location, symbol.def_line_count) {
+            (Some(location), Some(n)) => format!(" (def: {location}, {n} lines)"),
+            (Some(location), None) => format!(" (def: {location})"),
+            (None, Some(n)) => format!(" (def: {n} lines)"),
+            (None, None) => String::new(),
+        };
 
         println!("{file}:{line}{col_suffix}: {kind} {}{def_suffix}", symbol.name);
 
@@ -392,8 +711,8 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
                             let end_idx =
                                 range.end_line.saturating_sub(1) as usize;
                             if start_idx < lines.len() && end_idx < lines.len() {
-                                for idx in start_idx..=end_idx {
-                                    println!("{}", lines[idx]);
+                                for line in &lines[start_idx..=end_idx] {
+                                    println!("{line}");
                                 }
                             }
                         }
@@ -406,6 +725,17 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
             }
         }
 
+        if show_doc {
+            let summary = symbol
+                .attributes
+                .as_ref()
+                .and_then(|attrs| attrs.doc.as_ref())
+                .and_then(|doc| doc.summary.as_deref());
+            if let Some(summary) = summary {
+                println!("{summary}");
+            }
+        }
+
         let context = result
             .contexts
             .iter()
@@ -533,7 +863,7 @@ fn print_symbol_text_with_views(result: &SearchResult, args: &SearchArgs) -> Res
 
 #[cfg(test)]
 mod tests {
-    use super::truncate;
+    use super::{highlight, truncate};
 
     #[test]
     fn truncate_leaves_short_strings_unchanged() {
@@ -553,4 +883,19 @@ mod tests {
         assert_eq!(truncate(s, 3), "éé…");
         assert_eq!(truncate(s, 2), "é…");
     }
+
+    #[test]
+    fn highlight_wraps_each_span() {
+        let out = highlight("foo bar foo", &[(0, 3), (8, 11)], 11);
+        assert_eq!(
+            out,
+            "\x1b[1;31mfoo\x1b[0m bar \x1b[1;31mfoo\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn highlight_clips_to_limit_and_char_boundaries() {
+        assert_eq!(highlight("héllo", &[(1, 3)], 2), "héllo");
+        assert_eq!(highlight("abcdef", &[(2, 6)], 4), "ab\x1b[1;31mcd\x1b[0mef");
+    }
 }
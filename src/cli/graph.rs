@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::models::{
+    ContextKind, FollowCallSite, GraphClusterBy, GraphEdge, GraphNode, GraphResult, SearchResult,
+    SymbolKind, GRAPH_RESULT_VERSION,
+};
+
+/// Build a `GraphResult` from a symbol-mode `SearchResult`.
+///
+/// Every selected symbol becomes a node (symbols sharing a name and
+/// file are merged) and its `calls` become edges. Calls to symbols
+/// outside the selection are dropped unless `external` is set, in
+/// which case the callee is added as an external node.
+pub fn build_graph_result(
+    result: &SearchResult,
+    cluster_by: Option<GraphClusterBy>,
+    external: bool,
+) -> GraphResult {
+    let namespaces = symbol_namespaces(result);
+    let cluster_for = |file: &PathBuf, namespace: Option<&String>| match cluster_by {
+        Some(GraphClusterBy::File) => Some(file.display().to_string()),
+        Some(GraphClusterBy::Namespace) => namespace.cloned(),
+        None => None,
+    };
+
+    let mut nodes: Vec<GraphNode> = Vec::new();
+    let mut ids: HashMap<(String, PathBuf), String> = HashMap::new();
+
+    for (idx, symbol) in result.symbols.iter().enumerate() {
+        let key = (symbol.name.clone(), symbol.file.clone());
+        if ids.contains_key(&key) {
+            continue;
+        }
+        let id = format!("n{}", nodes.len());
+        ids.insert(key, id.clone());
+        nodes.push(GraphNode {
+            id,
+            name: symbol.name.clone(),
+            kind: Some(symbol.kind),
+            file: symbol.file.clone(),
+            line: Some(symbol.range.start_line),
+            cluster: cluster_for(&symbol.file, namespaces.get(&idx)),
+            external: false,
+        });
+    }
+
+    let mut edges: Vec<GraphEdge> = Vec::new();
+    let mut edge_index: HashMap<(String, String), usize> = HashMap::new();
+
+    for symbol in &result.symbols {
+        let from = ids[&(symbol.name.clone(), symbol.file.clone())].clone();
+
+        for call in &symbol.calls {
+            let Some(line) = call.line else {
+                continue;
+            };
+
+            let file = call.target_file.as_ref().unwrap_or(&call.file);
+            let key = (call.name.clone(), file.clone());
+            let to = match ids.get(&key) {
+                Some(id) => id.clone(),
+                None if external => {
+                    let id = format!("n{}", nodes.len());
+                    nodes.push(GraphNode {
+                        id: id.clone(),
+                        name: call.name.clone(),
+                        kind: call.kind,
+                        file: file.clone(),
+                        line: None,
+                        cluster: cluster_for(file, None),
+                        external: true,
+                    });
+                    ids.insert(key, id.clone());
+                    id
+                }
+                None => continue,
+            };
+
+            let slot = *edge_index
+                .entry((from.clone(), to.clone()))
+                .or_insert_with(|| {
+                    edges.push(GraphEdge {
+                        from: from.clone(),
+                        to,
+                        call_sites: Vec::new(),
+                    });
+                    edges.len() - 1
+                });
+            edges[slot].call_sites.push(FollowCallSite {
+                file: call.file.clone(),
+                line,
+                column: call.column,
+                edge_kind: call.edge_kind,
+            });
+        }
+    }
+
+    for edge in &mut edges {
+        edge.call_sites
+            .sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+        edge.call_sites
+            .dedup_by(|a, b| a.file == b.file && a.line == b.line && a.column == b.column);
+    }
+
+    GraphResult {
+        version: GRAPH_RESULT_VERSION.to_string(),
+        query: result.query.clone(),
+        cluster_by,
+        nodes,
+        edges,
+    }
+}
+
+/// Namespace path (e.g. `deep::level1`) of each symbol, keyed by its
+/// index in `result.symbols`, from the parent contexts' `parent_chain`.
+fn symbol_namespaces(result: &SearchResult) -> HashMap<usize, String> {
+    result
+        .contexts
+        .iter()
+        .filter(|context| context.kind == ContextKind::Parent)
+        .filter_map(|context| {
+            let idx = context.symbol_index?;
+            let names: Vec<&str> = context
+                .parent_chain
+                .iter()
+                .filter(|node| node.kind == Some(SymbolKind::Namespace))
+                .map(|node| node.name.as_str())
+                .collect();
+            (!names.is_empty()).then(|| (idx, names.join("::")))
+        })
+        .collect()
+}
+
+/// Clusters in order of first appearance, with the nodes of each.
+fn clusters(result: &GraphResult) -> (Vec<(&str, Vec<&GraphNode>)>, Vec<&GraphNode>) {
+    let mut clustered: Vec<(&str, Vec<&GraphNode>)> = Vec::new();
+    let mut loose = Vec::new();
+
+    for node in &result.nodes {
+        match node.cluster.as_deref() {
+            Some(label) => match clustered.iter_mut().find(|(l, _)| *l == label) {
+                Some((_, nodes)) => nodes.push(node),
+                None => clustered.push((label, vec![node])),
+            },
+            None => loose.push(node),
+        }
+    }
+
+    (clustered, loose)
+}
+
+/// Render a `GraphResult` as a Graphviz DOT digraph.
+pub fn render_dot(result: &GraphResult) -> String {
+    let mut out = String::new();
+    out.push_str("digraph calls {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box];\n");
+
+    let dot_node = |out: &mut String, node: &GraphNode, indent: &str| {
+        let style = if node.external { ", style=dashed" } else { "" };
+        let _ = writeln!(
+            out,
+            "{indent}{} [label=\"{}\"{style}];",
+            node.id,
+            dot_escape(&node.name)
+        );
+    };
+
+    let (clustered, loose) = clusters(result);
+    for (idx, (label, nodes)) in clustered.iter().enumerate() {
+        let _ = writeln!(out, "  subgraph cluster_{idx} {{");
+        let _ = writeln!(out, "    label=\"{}\";", dot_escape(label));
+        for node in nodes {
+            dot_node(&mut out, node, "    ");
+        }
+        out.push_str("  }\n");
+    }
+    for node in loose {
+        dot_node(&mut out, node, "  ");
+    }
+
+    for edge in &result.edges {
+        let _ = writeln!(out, "  {} -> {};", edge.from, edge.to);
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render a `GraphResult` as a Mermaid flowchart.
+pub fn render_mermaid(result: &GraphResult) -> String {
+    let mut out = String::new();
+    out.push_str("flowchart LR\n");
+
+    let mermaid_node = |out: &mut String, node: &GraphNode, indent: &str| {
+        let class = if node.external { ":::external" } else { "" };
+        let _ = writeln!(
+            out,
+            "{indent}{}[\"{}\"]{class}",
+            node.id,
+            mermaid_escape(&node.name)
+        );
+    };
+
+    let (clustered, loose) = clusters(result);
+    for (idx, (label, nodes)) in clustered.iter().enumerate() {
+        let _ = writeln!(out, "  subgraph c{idx}[\"{}\"]", mermaid_escape(label));
+        for node in nodes {
+            mermaid_node(&mut out, node, "    ");
+        }
+        out.push_str("  end\n");
+    }
+    for node in loose {
+        mermaid_node(&mut out, node, "  ");
+    }
+
+    for edge in &result.edges {
+        let _ = writeln!(out, "  {} --> {}", edge.from, edge.to);
+    }
+
+    if result.nodes.iter().any(|node| node.external) {
+        out.push_str("  classDef external stroke-dasharray: 5 5\n");
+    }
+
+    out
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
@@ -5,6 +5,7 @@ use reqwest::blocking::Client;
 use serde::Serialize;
 
 use crate::models::{
+    CheckDepsConfig, CheckDepsResult, DepsConfig, DepsResult, HierarchyConfig, HierarchyResult,
     IndexConfig, IndexSummary, SearchConfig, SearchResult, SymbolAttributesRequest,
     SymbolAttributesResponse,
 };
@@ -37,6 +38,30 @@ impl HttpSearchBackend {
         self.post_json("/v1/search", &config)
     }
 
+    /// Run a tree-sitter query via `POST /v1/query`, returning a
+    /// deserialized `SearchResult`.
+    pub fn query(&self, config: SearchConfig) -> Result<SearchResult> {
+        self.post_json("/v1/query", &config)
+    }
+
+    /// Compute a file's dependencies via `POST /v1/deps`, returning a
+    /// deserialized `DepsResult`.
+    pub fn deps(&self, config: DepsConfig) -> Result<DepsResult> {
+        self.post_json("/v1/deps", &config)
+    }
+
+    /// Check import cycles and layering rules via
+    /// `POST /v1/deps/check`, returning a deserialized `CheckDepsResult`.
+    pub fn check_deps(&self, config: CheckDepsConfig) -> Result<CheckDepsResult> {
+        self.post_json("/v1/deps/check", &config)
+    }
+
+    /// Walk a type hierarchy via `POST /v1/hierarchy`, returning a
+    /// deserialized `HierarchyResult`.
+    pub fn hierarchy(&self, config: HierarchyConfig) -> Result<HierarchyResult> {
+        self.post_json("/v1/hierarchy", &config)
+    }
+
     /// Execute an index operation via `POST /v1/index`, returning a
     /// deserialized `IndexSummary`.
     pub fn index(&self, config: IndexConfig) -> Result<IndexSummary> {
@@ -1,29 +1,36 @@
 use std::net::SocketAddr;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{CommandFactory, Parser};
 
-use crate::models::SEARCH_RESULT_VERSION;
+use crate::models::{SearchConfig, SearchResult, SEARCH_RESULT_VERSION};
 use crate::search::engine;
 use crate::server;
 
 mod args;
 mod follow;
+mod graph;
 mod format;
 mod http_backend;
 mod config;
+mod queries;
+mod sarif;
 
 pub use args::{
-    AnnotateArgs, Cli, Commands, FollowArgs, IndexArgs, IndexInfoArgs, OutputFormat, SearchArgs,
-    ServeArgs,
+    AnnotateArgs, CheckDepsArgs, Cli, Commands, DepsArgs, FollowArgs, GraphArgs, HierarchyArgs,
+    IndexArgs, IndexInfoArgs, OutputFormat, QueryArgs, RefsArgs, SearchArgs, ServeArgs,
 };
 
 use config::{
-    apply_annotate_config_defaults, apply_follow_config_defaults, apply_index_config_defaults,
-    apply_index_info_config_defaults, apply_search_config_defaults, apply_serve_config_defaults,
-    load_cli_config,
+    apply_annotate_config_defaults, apply_check_deps_config_defaults, apply_deps_config_defaults,
+    apply_follow_config_defaults, apply_graph_config_defaults, apply_hierarchy_config_defaults,
+    apply_index_config_defaults,
+    apply_index_info_config_defaults, apply_query_config_defaults, apply_refs_config_defaults,
+    apply_search_config_defaults,
+    apply_serve_config_defaults, load_cli_config,
 };
 use http_backend::HttpSearchBackend;
+use queries::expand_saved_queries;
 
 /// Entry point for the CLI binary.
 pub fn run() -> Result<()> {
@@ -43,6 +50,7 @@ pub fn run() -> Result<()> {
         Some(Commands::Search(mut search_args)) => {
             if let Some(ref config) = cli_config {
                 apply_search_config_defaults(config, &mut search_args);
+                search_args.pattern = expand_saved_queries(&search_args.pattern, &config.queries)?;
             }
 
             let config = args::search_config_from_args(&search_args)?;
@@ -60,7 +68,9 @@ pub fn run() -> Result<()> {
 
             match search_args.format {
                 OutputFormat::Text => format::print_text(&result, &search_args),
-                OutputFormat::Table => format::print_table(&result),
+                OutputFormat::Table => {
+                    format::print_table(&result, format::use_color(search_args.color))
+                }
                 OutputFormat::Json => {
                     serde_json::to_writer(std::io::stdout(), &result)?;
                     println!();
@@ -128,21 +138,60 @@ pub fn run() -> Result<()> {
         Some(Commands::Follow(mut follow_args)) => {
             if let Some(ref config) = cli_config {
                 apply_follow_config_defaults(config, &mut follow_args);
+                for pattern in [
+                    &mut follow_args.pattern,
+                    &mut follow_args.from,
+                    &mut follow_args.to,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    *pattern = expand_saved_queries(pattern, &config.queries)?;
+                }
             }
 
-            let search_config = args::follow_search_config_from_args(&follow_args)?;
-            let search_result = if let Some(server_url) = effective_server_url(
-                follow_args.server.as_deref(),
-                follow_args.no_server,
-            ) {
-                let backend = HttpSearchBackend::new(server_url)?;
-                backend.search(search_config)?
+            let server_url =
+                effective_server_url(follow_args.server.as_deref(), follow_args.no_server);
+            let run = |config: SearchConfig| -> Result<SearchResult> {
+                match &server_url {
+                    Some(url) => HttpSearchBackend::new(url.clone())?.search(config),
+                    None => engine::run_search(config),
+                }
+            };
+
+            let path_mode = follow_args.to.is_some();
+            let limits = follow::FollowLimits {
+                depth: follow_args.depth.map_or(
+                    if path_mode {
+                        follow::DEFAULT_PATH_DEPTH
+                    } else {
+                        1
+                    },
+                    |depth| depth as usize,
+                ),
+                max_nodes: follow_args.max_nodes.unwrap_or(follow::DEFAULT_MAX_NODES),
+            };
+
+            // Multi-level and path follows need the call relationships
+            // of every symbol, not only of the selected ones.
+            let graph = if path_mode || limits.depth > 1 {
+                let graph_result = run(args::follow_graph_config_from_args(&follow_args)?)?;
+                Some(follow::CallGraph::from_symbols(&graph_result.symbols))
             } else {
-                engine::run_search(search_config)?
+                None
             };
 
-            let direction = follow_args.direction.to_model();
-            let follow_result = follow::build_follow_result(&search_result, direction);
+            let search_result = run(args::follow_search_config_from_args(&follow_args)?)?;
+            let follow_result = match &graph {
+                Some(graph) if path_mode => {
+                    let to_result = run(args::follow_to_config_from_args(&follow_args)?)?;
+                    follow::build_follow_paths(&search_result, &to_result, graph, limits)
+                }
+                _ => {
+                    let direction = follow_args.direction.to_model();
+                    follow::build_follow_result(&search_result, direction, graph.as_ref(), limits)
+                }
+            };
 
             match follow_args.format {
                 OutputFormat::Json => {
@@ -157,6 +206,183 @@ pub fn run() -> Result<()> {
                 }
             }
         }
+        Some(Commands::Query(mut query_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_query_config_defaults(config, &mut query_args);
+            }
+
+            let config = args::query_config_from_args(&query_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(query_args.server.as_deref(), query_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.query(config)?
+            } else {
+                engine::run_query(config)?
+            };
+
+            match query_args.format {
+                OutputFormat::Text => {
+                    format::print_query_text(&result, format::use_color(query_args.color))
+                }
+                OutputFormat::Table => {
+                    format::print_table(&result, format::use_color(query_args.color))
+                }
+                OutputFormat::Json => {
+                    serde_json::to_writer(std::io::stdout(), &result)?;
+                    println!();
+                    Ok(())
+                }
+            }
+        }
+        Some(Commands::Graph(mut graph_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_graph_config_defaults(config, &mut graph_args);
+                graph_args.pattern = expand_saved_queries(&graph_args.pattern, &config.queries)?;
+            }
+
+            let config = args::graph_search_config_from_args(&graph_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(graph_args.server.as_deref(), graph_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.search(config)?
+            } else {
+                engine::run_search(config)?
+            };
+
+            let graph_result = graph::build_graph_result(
+                &result,
+                graph_args.cluster.map(|c| c.to_model()),
+                graph_args.external,
+            );
+
+            match graph_args.format {
+                args::GraphFormatArg::Dot => print!("{}", graph::render_dot(&graph_result)),
+                args::GraphFormatArg::Mermaid => {
+                    print!("{}", graph::render_mermaid(&graph_result))
+                }
+                args::GraphFormatArg::Json => {
+                    serde_json::to_writer(std::io::stdout(), &graph_result)?;
+                    println!();
+                }
+            }
+            Ok(())
+        }
+        Some(Commands::Deps(mut deps_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_deps_config_defaults(config, &mut deps_args);
+            }
+
+            let config = args::deps_config_from_args(&deps_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(deps_args.server.as_deref(), deps_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.deps(config)?
+            } else {
+                engine::run_deps(config)?
+            };
+
+            match deps_args.format {
+                OutputFormat::Text | OutputFormat::Table => format::print_deps_text(&result),
+                OutputFormat::Json => {
+                    serde_json::to_writer(std::io::stdout(), &result)?;
+                    println!();
+                    Ok(())
+                }
+            }
+        }
+        Some(Commands::CheckDeps(mut check_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_check_deps_config_defaults(config, &mut check_args);
+            }
+
+            let config = args::check_deps_config_from_args(&check_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(check_args.server.as_deref(), check_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.check_deps(config)?
+            } else {
+                engine::run_check_deps(config)?
+            };
+
+            match check_args.format {
+                args::CheckDepsFormatArg::Text => format::print_check_deps_text(&result)?,
+                args::CheckDepsFormatArg::Json => {
+                    serde_json::to_writer(std::io::stdout(), &result)?;
+                    println!();
+                }
+                args::CheckDepsFormatArg::Sarif => {
+                    serde_json::to_writer_pretty(
+                        std::io::stdout(),
+                        &sarif::render_check_deps(&result),
+                    )?;
+                    println!();
+                }
+            }
+
+            // The report is on stdout; failing the run gives CI a
+            // non-zero exit status.
+            if !result.cycles.is_empty() || !result.violations.is_empty() {
+                bail!(
+                    "dependency check failed: {}",
+                    format::check_deps_findings(&result)
+                );
+            }
+            Ok(())
+        }
+        Some(Commands::Hierarchy(mut hierarchy_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_hierarchy_config_defaults(config, &mut hierarchy_args);
+            }
+
+            let config = args::hierarchy_config_from_args(&hierarchy_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(hierarchy_args.server.as_deref(), hierarchy_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.hierarchy(config)?
+            } else {
+                engine::run_hierarchy(config)?
+            };
+
+            match hierarchy_args.format {
+                OutputFormat::Text | OutputFormat::Table => format::print_hierarchy_text(&result),
+                OutputFormat::Json => {
+                    serde_json::to_writer(std::io::stdout(), &result)?;
+                    println!();
+                    Ok(())
+                }
+            }
+        }
+        Some(Commands::Refs(mut refs_args)) => {
+            if let Some(ref config) = cli_config {
+                apply_refs_config_defaults(config, &mut refs_args);
+            }
+
+            let config = args::refs_search_config_from_args(&refs_args)?;
+            let result = if let Some(server_url) =
+                effective_server_url(refs_args.server.as_deref(), refs_args.no_server)
+            {
+                let backend = HttpSearchBackend::new(server_url)?;
+                backend.search(config)?
+            } else {
+                engine::run_search(config)?
+            };
+
+            let color = format::use_color(refs_args.color);
+            match refs_args.format {
+                OutputFormat::Text => format::print_refs_text(&result, color),
+                OutputFormat::Table => format::print_table(&result, color),
+                OutputFormat::Json => {
+                    serde_json::to_writer(std::io::stdout(), &result)?;
+                    println!();
+                    Ok(())
+                }
+            }
+        }
         Some(Commands::Serve(mut serve_args)) => {
             if let Some(ref config) = cli_config {
                 apply_serve_config_defaults(config, &mut serve_args);
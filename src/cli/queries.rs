@@ -0,0 +1,379 @@
+//! Saved query expansion for the `[queries]` config table.
+//!
+//! Saved queries are named DSL fragments defined in
+//! `.symgrep/config.toml`:
+//!
+//! ```toml
+//! [queries]
+//! handlers = "kind:function name:handle file:src/routes"
+//! by_owner = "keyword:owner-$1"
+//! ```
+//!
+//! A pattern token of the form `@name` or `@name(arg1, arg2)` is
+//! replaced by the saved query text before the pattern is parsed,
+//! with `$1`, `$2`, ... substituted by the call arguments. Because the
+//! DSL is a flat AND of OR groups, a saved query that expands to
+//! several terms can be combined with other terms using whitespace
+//! (AND) but not inside an `|` alternative.
+//!
+//! Only whole terms are references: in a pattern without `field:`
+//! terms, which the DSL reads as a single content search, references
+//! are expanded only when the pattern consists of nothing else. Tokens
+//! that do not name a saved query (for example a text search for a
+//! decorator such as `@Component`) are left untouched, and the
+//! spacing around expansions is kept.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use anyhow::{bail, Result};
+
+/// Maximum nesting depth when saved queries reference other saved
+/// queries. Cycles are reported separately; this only guards against
+/// pathological but acyclic definitions.
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// Expand `@name` / `@name(args)` references in `pattern` using the
+/// saved queries from project config.
+pub fn expand_saved_queries(pattern: &str, queries: &BTreeMap<String, String>) -> Result<String> {
+    if queries.is_empty() || !pattern.contains('@') {
+        return Ok(pattern.to_string());
+    }
+
+    let mut stack = Vec::new();
+    expand_pattern(pattern, queries, &mut stack)
+}
+
+fn expand_pattern(
+    pattern: &str,
+    queries: &BTreeMap<String, String>,
+    stack: &mut Vec<String>,
+) -> Result<String> {
+    let tokens: Vec<Vec<Range<usize>>> = token_spans(pattern)
+        .into_iter()
+        .map(|token| alternative_spans(pattern, token))
+        .collect();
+
+    // Without `field:` terms the DSL reads the whole pattern as one
+    // content search (`foo @handlers` looks for that text), so such a
+    // pattern is only expanded when it consists of references alone.
+    if !pattern.contains(':')
+        && !tokens
+            .iter()
+            .flatten()
+            .all(|alt| is_saved_reference(&pattern[alt.clone()], queries))
+    {
+        return Ok(pattern.to_string());
+    }
+
+    // Expansions are spliced into the original text so the spacing
+    // around them is kept.
+    let mut out = String::with_capacity(pattern.len());
+    let mut copied = 0;
+    for alternatives in &tokens {
+        for alt in alternatives {
+            let atom = &pattern[alt.clone()];
+            let Some(expanded) = expand_atom(atom, queries, stack)? else {
+                continue;
+            };
+            if alternatives.len() > 1 && token_spans(&expanded).len() > 1 {
+                bail!(
+                    "saved query in `{atom}` expands to multiple terms and cannot be used inside an `|` alternative"
+                );
+            }
+            out.push_str(&pattern[copied..alt.start]);
+            out.push_str(&expanded);
+            copied = alt.end;
+        }
+    }
+    out.push_str(&pattern[copied..]);
+
+    Ok(out)
+}
+
+/// Whether `atom` is a well-formed reference to a saved query.
+fn is_saved_reference(atom: &str, queries: &BTreeMap<String, String>) -> bool {
+    matches!(parse_reference(atom), Ok(Some((name, _))) if queries.contains_key(name))
+}
+
+/// Expand a single atom when it is a saved query reference; `None`
+/// for any other atom.
+fn expand_atom(
+    atom: &str,
+    queries: &BTreeMap<String, String>,
+    stack: &mut Vec<String>,
+) -> Result<Option<String>> {
+    let Some((name, args)) = parse_reference(atom)? else {
+        return Ok(None);
+    };
+
+    let Some(definition) = queries.get(name) else {
+        return Ok(None);
+    };
+
+    if stack.iter().any(|entry| entry == name) {
+        bail!(
+            "saved query @{name} references itself (via {})",
+            stack
+                .iter()
+                .map(|entry| format!("@{entry}"))
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        );
+    }
+    if stack.len() >= MAX_EXPANSION_DEPTH {
+        bail!("saved query @{name} exceeds the maximum nesting depth of {MAX_EXPANSION_DEPTH}");
+    }
+
+    let substituted = substitute_arguments(name, definition, &args)?;
+
+    stack.push(name.to_string());
+    let expanded = expand_pattern(&substituted, queries, stack);
+    stack.pop();
+
+    expanded.map(Some)
+}
+
+/// Parse `@name` or `@name(arg, ...)` into the name and its arguments.
+///
+/// Returns `Ok(None)` when the atom is not shaped like a reference.
+fn parse_reference(atom: &str) -> Result<Option<(&str, Vec<String>)>> {
+    let Some(rest) = atom.strip_prefix('@') else {
+        return Ok(None);
+    };
+
+    let name_len = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+    if name_len == 0 {
+        return Ok(None);
+    }
+
+    let (name, tail) = rest.split_at(name_len);
+    if tail.is_empty() {
+        return Ok(Some((name, Vec::new())));
+    }
+
+    let Some(inner) = tail.strip_prefix('(') else {
+        return Ok(None);
+    };
+    let Some(inner) = inner.strip_suffix(')') else {
+        bail!("unterminated argument list in saved query reference `{atom}`");
+    };
+
+    let args = if inner.trim().is_empty() {
+        Vec::new()
+    } else {
+        inner.split(',').map(|arg| arg.trim().to_string()).collect()
+    };
+
+    Ok(Some((name, args)))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+/// Replace `$1`, `$2`, ... in `definition` with the call arguments.
+///
+/// The number of arguments must match the highest placeholder used
+/// by the definition.
+fn substitute_arguments(name: &str, definition: &str, args: &[String]) -> Result<String> {
+    let mut out = String::with_capacity(definition.len());
+    let mut expected = 0usize;
+    let mut chars = definition.char_indices().peekable();
+
+    while let Some((idx, ch)) = chars.next() {
+        if ch != '$' {
+            out.push(ch);
+            continue;
+        }
+
+        let digits_start = idx + 1;
+        let mut digits_end = digits_start;
+        while let Some(&(next_idx, next)) = chars.peek() {
+            if next.is_ascii_digit() {
+                digits_end = next_idx + next.len_utf8();
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if digits_end == digits_start {
+            out.push('$');
+            continue;
+        }
+
+        let position: usize = definition[digits_start..digits_end].parse()?;
+        if position == 0 {
+            bail!("saved query @{name} uses `$0`; arguments are numbered from `$1`");
+        }
+        expected = expected.max(position);
+        if let Some(arg) = args.get(position - 1) {
+            out.push_str(arg);
+        }
+    }
+
+    if expected != args.len() {
+        bail!(
+            "saved query @{name} expects {expected} argument(s) but {} were given",
+            args.len()
+        );
+    }
+
+    Ok(out)
+}
+
+/// Byte ranges of the whitespace-separated tokens of a pattern,
+/// keeping double-quoted segments and parenthesized argument lists
+/// intact.
+fn token_spans(input: &str) -> Vec<Range<usize>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut depth = 0usize;
+
+    for (idx, ch) in input.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && !in_quotes && depth == 0 => {
+                if let Some(start) = start.take() {
+                    tokens.push(start..idx);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(idx);
+    }
+
+    if let Some(start) = start {
+        tokens.push(start..input.len());
+    }
+
+    tokens
+}
+
+/// Byte ranges of the `|` alternatives of the token at `token`,
+/// splitting outside of quotes and argument lists.
+fn alternative_spans(input: &str, token: Range<usize>) -> Vec<Range<usize>> {
+    let mut alts = Vec::new();
+    let mut start = token.start;
+    let mut in_quotes = false;
+    let mut depth = 0usize;
+
+    for (idx, ch) in input[token.clone()].char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            '|' if !in_quotes && depth == 0 => {
+                alts.push(start..token.start + idx);
+                start = token.start + idx + 1;
+            }
+            _ => {}
+        }
+    }
+    alts.push(start..token.end);
+
+    alts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queries(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn expands_bare_reference() {
+        let queries = queries(&[("handlers", "kind:function name:handle file:src/routes")]);
+        let expanded = expand_saved_queries("@handlers", &queries).unwrap();
+        assert_eq!(expanded, "kind:function name:handle file:src/routes");
+    }
+
+    #[test]
+    fn composes_with_other_terms() {
+        let queries = queries(&[("handlers", "kind:function name:handle")]);
+        let expanded = expand_saved_queries("@handlers content:auth", &queries).unwrap();
+        assert_eq!(expanded, "kind:function name:handle content:auth");
+    }
+
+    #[test]
+    fn substitutes_parameters() {
+        let queries = queries(&[("by_owner", "keyword:owner-$1 language:$2")]);
+        let expanded = expand_saved_queries("@by_owner(team-a, rust)", &queries).unwrap();
+        assert_eq!(expanded, "keyword:owner-team-a language:rust");
+    }
+
+    #[test]
+    fn expands_nested_references() {
+        let queries = queries(&[
+            ("fns", "kind:function|method"),
+            ("handlers", "@fns name:handle"),
+        ]);
+        let expanded = expand_saved_queries("@handlers", &queries).unwrap();
+        assert_eq!(expanded, "kind:function|method name:handle");
+    }
+
+    #[test]
+    fn single_term_queries_can_be_used_as_alternatives() {
+        let queries = queries(&[("rs", "language:rust"), ("ts", "language:typescript")]);
+        let expanded = expand_saved_queries("@rs|@ts name:add", &queries).unwrap();
+        assert_eq!(expanded, "language:rust|language:typescript name:add");
+    }
+
+    #[test]
+    fn rejects_multi_term_alternatives() {
+        let queries = queries(&[("handlers", "kind:function name:handle")]);
+        let err = expand_saved_queries("@handlers|name:foo", &queries).unwrap_err();
+        assert!(err.to_string().contains("cannot be used inside"));
+    }
+
+    #[test]
+    fn rejects_argument_count_mismatch() {
+        let queries = queries(&[("by_owner", "keyword:owner-$1")]);
+        let err = expand_saved_queries("@by_owner", &queries).unwrap_err();
+        assert!(err.to_string().contains("expects 1 argument(s)"));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let queries = queries(&[("a", "@b"), ("b", "@a")]);
+        let err = expand_saved_queries("@a", &queries).unwrap_err();
+        assert!(err.to_string().contains("references itself"));
+    }
+
+    #[test]
+    fn keeps_spacing_around_expansions() {
+        let queries = queries(&[("rs", "language:rust")]);
+        let expanded = expand_saved_queries("name:add   @rs", &queries).unwrap();
+        assert_eq!(expanded, "name:add   language:rust");
+    }
+
+    #[test]
+    fn expands_references_only_as_whole_terms() {
+        let queries = queries(&[("rs", "language:rust"), ("fns", "kind:function")]);
+        // A bare pattern is one content search unless it holds only
+        // references.
+        let expanded = expand_saved_queries("TODO  @rs", &queries).unwrap();
+        assert_eq!(expanded, "TODO  @rs");
+        let expanded = expand_saved_queries("@fns @rs", &queries).unwrap();
+        assert_eq!(expanded, "kind:function language:rust");
+        let expanded = expand_saved_queries("content:a@rs @rs", &queries).unwrap();
+        assert_eq!(expanded, "content:a@rs language:rust");
+    }
+
+    #[test]
+    fn leaves_unknown_references_untouched() {
+        let queries = queries(&[("handlers", "kind:function")]);
+        let expanded = expand_saved_queries("@Component  \"@handlers\"", &queries).unwrap();
+        assert_eq!(expanded, "@Component  \"@handlers\"");
+    }
+}
@@ -0,0 +1,92 @@
+//! SARIF 2.1.0 rendering for `symgrep check-deps`.
+//!
+//! Each import cycle and layering violation becomes one result so
+//! that code-scanning tools can annotate the offending statements.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::models::{CheckDepsResult, FileDependency};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+const CYCLE_RULE: &str = "import-cycle";
+const LAYERING_RULE: &str = "layering-violation";
+
+/// Render a `CheckDepsResult` as a SARIF log with a single run.
+pub fn render_check_deps(result: &CheckDepsResult) -> Value {
+    let mut results = Vec::new();
+
+    for cycle in &result.cycles {
+        let files: Vec<String> = cycle.files.iter().map(|file| uri(file)).collect();
+        results.push(json!({
+            "ruleId": CYCLE_RULE,
+            "ruleIndex": 0,
+            "level": "error",
+            "message": {
+                "text": format!("Import cycle between {}", files.join(", ")),
+            },
+            "locations": cycle.dependencies.iter().map(location).collect::<Vec<_>>(),
+        }));
+    }
+
+    for violation in &result.violations {
+        let dep = &violation.dependency;
+        let target = dep.target.as_deref().map(uri).unwrap_or_default();
+        results.push(json!({
+            "ruleId": LAYERING_RULE,
+            "ruleIndex": 1,
+            "level": "error",
+            "message": {
+                "text": format!(
+                    "{} imports {}, breaking rule `{}`",
+                    uri(&dep.file),
+                    target,
+                    violation.rule
+                ),
+            },
+            "locations": [location(dep)],
+        }));
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "symgrep",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": [
+                        {
+                            "id": CYCLE_RULE,
+                            "shortDescription": { "text": "Files import each other in a cycle" },
+                            "defaultConfiguration": { "level": "error" },
+                        },
+                        {
+                            "id": LAYERING_RULE,
+                            "shortDescription": { "text": "Import breaks a configured layering rule" },
+                            "defaultConfiguration": { "level": "error" },
+                        },
+                    ],
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn location(dep: &FileDependency) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": uri(&dep.file) },
+            "region": { "startLine": dep.line },
+        },
+    })
+}
+
+/// SARIF artifact URIs use forward slashes.
+fn uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
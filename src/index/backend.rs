@@ -5,11 +5,15 @@
 //! implementations. Phase 6A ships with a file-backed implementation;
 //! later phases will add a SQLite backend.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
-use crate::index::models::{FileRecord, IndexMeta, NewSymbolRecord, SymbolQuery, SymbolRecord};
+use crate::index::models::{
+    CallRecord, DependencyRecord, FileRecord, IndexMeta, MemberRecord, NewCallRecord,
+    NewDependencyRecord, NewMemberRecord, NewRelationRecord, NewSymbolRecord, RelationRecord,
+    SymbolQuery, SymbolRecord,
+};
 use crate::models::{IndexBackendKind, IndexConfig};
 
 /// Pluggable index backend used by the core engine.
@@ -45,14 +49,54 @@ pub trait IndexBackend {
         size: u64,
     ) -> Result<FileRecord>;
 
-    /// Remove a file and any associated symbols.
+    /// Remove a file and any associated symbols, calls, dependencies,
+    /// type relations and members.
     fn remove_file_by_path(&mut self, path: &Path) -> Result<()>;
 
-    /// Replace all symbols for a given file with new records.
-    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<()>;
+    /// Replace all symbols for a given file with new records,
+    /// returning the ids assigned to them in order.
+    ///
+    /// The file's calls are dropped as well since their caller ids no
+    /// longer exist; callers re-add them with `set_file_calls`.
+    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<Vec<u64>>;
 
     /// Query symbols using basic filters.
     fn query_symbols(&self, query: &SymbolQuery) -> Result<Vec<SymbolRecord>>;
+
+    /// Replace all calls made from a given file.
+    fn set_file_calls(&mut self, file_id: u64, calls: &[NewCallRecord]) -> Result<()>;
+
+    /// Calls made from any of the given files (all calls when empty),
+    /// ordered by file and then by insertion order.
+    fn query_calls(&self, file_ids: &[u64]) -> Result<Vec<CallRecord>>;
+
+    /// Recompute `callee_id` from the callee file and name for calls
+    /// made from or into any of the given files. Must run with every
+    /// file whose symbols were rewritten or removed.
+    fn relink_calls(&mut self, paths: &[PathBuf]) -> Result<()>;
+
+    /// Replace all dependency statements of a given file.
+    fn set_file_dependencies(&mut self, file_id: u64, deps: &[NewDependencyRecord]) -> Result<()>;
+
+    /// Dependencies of any of the given files (all dependencies when
+    /// empty), ordered by file and then by insertion order.
+    fn query_dependencies(&self, file_ids: &[u64]) -> Result<Vec<DependencyRecord>>;
+
+    /// Replace all type relations declared in a given file.
+    fn set_file_relations(&mut self, file_id: u64, relations: &[NewRelationRecord]) -> Result<()>;
+
+    /// Type relations declared in any of the given files (all
+    /// relations when empty), ordered by file and then by insertion
+    /// order.
+    fn query_relations(&self, file_ids: &[u64]) -> Result<Vec<RelationRecord>>;
+
+    /// Replace all member declarations and definitions of a given
+    /// file.
+    fn set_file_members(&mut self, file_id: u64, members: &[NewMemberRecord]) -> Result<()>;
+
+    /// Members of any of the given files (all members when empty),
+    /// ordered by file and then by insertion order.
+    fn query_members(&self, file_ids: &[u64]) -> Result<Vec<MemberRecord>>;
 }
 
 /// Helper to construct an appropriate backend from a generic config.
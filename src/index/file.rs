@@ -4,12 +4,16 @@
 //! - `meta.json`
 //! - `files.jsonl`
 //! - `symbols.jsonl`
+//! - `calls.jsonl`
+//! - `deps.jsonl`
+//! - `relations.jsonl`
+//! - `members.jsonl`
 //!
 //! The implementation is intentionally simple and optimized for
 //! clarity rather than micro-performance. It uses sequential scans
 //! and full rewrites of the JSONL files when updating symbols.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
@@ -18,8 +22,13 @@ use anyhow::Result;
 
 use crate::index::backend::IndexBackend;
 use crate::index::build_globset;
-use crate::index::models::{FileRecord, IndexMeta, NewSymbolRecord, SymbolQuery, SymbolRecord};
-use crate::models::IndexBackendKind;
+use crate::index::models::{
+    is_supported_schema_version, unsupported_schema_version, CallRecord, DependencyRecord,
+    FileRecord, IndexMeta, MemberRecord, NewCallRecord, NewDependencyRecord, NewMemberRecord,
+    NewRelationRecord, NewSymbolRecord, RelationRecord, SymbolQuery, SymbolRecord,
+    INDEX_SCHEMA_VERSION,
+};
+use crate::models::{IndexBackendKind, SymbolKind};
 
 type FileMaps = (
     Vec<FileRecord>,
@@ -47,11 +56,8 @@ impl FileIndexBackend {
         let meta = if meta_path.exists() {
             let file = File::open(&meta_path)?;
             let meta: IndexMeta = serde_json::from_reader(file)?;
-            if meta.schema_version != "1" && meta.schema_version != "2" {
-                anyhow::bail!(
-                    "unsupported index schema version {}; expected 1 or 2",
-                    meta.schema_version
-                );
+            if !is_supported_schema_version(&meta.schema_version) {
+                return Err(unsupported_schema_version(&meta.schema_version));
             }
             Some(meta)
         } else {
@@ -84,6 +90,22 @@ impl FileIndexBackend {
         self.root.join("symbols.jsonl")
     }
 
+    fn calls_path(&self) -> PathBuf {
+        self.root.join("calls.jsonl")
+    }
+
+    fn deps_path(&self) -> PathBuf {
+        self.root.join("deps.jsonl")
+    }
+
+    fn relations_path(&self) -> PathBuf {
+        self.root.join("relations.jsonl")
+    }
+
+    fn members_path(&self) -> PathBuf {
+        self.root.join("members.jsonl")
+    }
+
     fn load_files(root: &Path) -> Result<FileMaps> {
         let path = root.join("files.jsonl");
         if !path.exists() {
@@ -201,6 +223,194 @@ impl FileIndexBackend {
         Ok(())
     }
 
+    fn load_calls(&self) -> Result<Vec<CallRecord>> {
+        let path = self.calls_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&path)?;
+        let reader = BufReader::new(file);
+        let mut calls = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            calls.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(calls)
+    }
+
+    fn persist_calls(&self, calls: &[CallRecord]) -> Result<()> {
+        let path = self.calls_path();
+        let tmp_path = path.with_extension("jsonl.tmp");
+
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+
+        for record in calls {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn remove_calls_for_file(&self, file_id: u64) -> Result<()> {
+        if !self.calls_path().exists() {
+            return Ok(());
+        }
+        let mut calls = self.load_calls()?;
+        calls.retain(|call| call.file_id != file_id);
+        self.persist_calls(&calls)
+    }
+
+    fn load_deps(&self) -> Result<Vec<DependencyRecord>> {
+        let path = self.deps_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&path)?;
+        let reader = BufReader::new(file);
+        let mut deps = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            deps.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(deps)
+    }
+
+    fn persist_deps(&self, deps: &[DependencyRecord]) -> Result<()> {
+        let path = self.deps_path();
+        let tmp_path = path.with_extension("jsonl.tmp");
+
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+
+        for record in deps {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn remove_deps_for_file(&self, file_id: u64) -> Result<()> {
+        if !self.deps_path().exists() {
+            return Ok(());
+        }
+        let mut deps = self.load_deps()?;
+        deps.retain(|dep| dep.file_id != file_id);
+        self.persist_deps(&deps)
+    }
+
+    fn load_relations(&self) -> Result<Vec<RelationRecord>> {
+        let path = self.relations_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&path)?;
+        let reader = BufReader::new(file);
+        let mut relations = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            relations.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(relations)
+    }
+
+    fn persist_relations(&self, relations: &[RelationRecord]) -> Result<()> {
+        let path = self.relations_path();
+        let tmp_path = path.with_extension("jsonl.tmp");
+
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+
+        for record in relations {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn remove_relations_for_file(&self, file_id: u64) -> Result<()> {
+        if !self.relations_path().exists() {
+            return Ok(());
+        }
+        let mut relations = self.load_relations()?;
+        relations.retain(|relation| relation.file_id != file_id);
+        self.persist_relations(&relations)
+    }
+
+    fn load_members(&self) -> Result<Vec<MemberRecord>> {
+        let path = self.members_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&path)?;
+        let reader = BufReader::new(file);
+        let mut members = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            members.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(members)
+    }
+
+    fn persist_members(&self, members: &[MemberRecord]) -> Result<()> {
+        let path = self.members_path();
+        let tmp_path = path.with_extension("jsonl.tmp");
+
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+
+        for record in members {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn remove_members_for_file(&self, file_id: u64) -> Result<()> {
+        if !self.members_path().exists() {
+            return Ok(());
+        }
+        let mut members = self.load_members()?;
+        members.retain(|member| member.file_id != file_id);
+        self.persist_members(&members)
+    }
+
     fn allocate_file_id(&mut self) -> u64 {
         let id = self.next_file_id;
         self.next_file_id = self.next_file_id.saturating_add(1);
@@ -223,7 +433,7 @@ impl IndexBackend for FileIndexBackend {
         } else {
             let now = crate::index::current_epoch_seconds();
             Ok(IndexMeta {
-                schema_version: "2".to_string(),
+                schema_version: INDEX_SCHEMA_VERSION.to_string(),
                 tool_version: env!("CARGO_PKG_VERSION").to_string(),
                 root_path: String::new(),
                 created_at: now,
@@ -304,16 +514,21 @@ impl IndexBackend for FileIndexBackend {
             self.files_by_id.remove(&record.id);
             self.persist_files()?;
             self.rewrite_symbols_excluding_file(record.id)?;
+            self.remove_calls_for_file(record.id)?;
+            self.remove_deps_for_file(record.id)?;
+            self.remove_relations_for_file(record.id)?;
+            self.remove_members_for_file(record.id)?;
         }
 
         Ok(())
     }
 
-    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<()> {
+    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<Vec<u64>> {
         let path = self.symbols_path();
         let tmp_path = path.with_extension("jsonl.tmp");
 
         let mut next_id = self.next_symbol_id;
+        let first_id = next_id;
 
         if path.exists() {
             let in_file = File::open(&path)?;
@@ -383,8 +598,9 @@ impl IndexBackend for FileIndexBackend {
         fs::rename(tmp_path, path)?;
 
         self.next_symbol_id = next_id;
+        self.remove_calls_for_file(file_id)?;
 
-        Ok(())
+        Ok((first_id..next_id).collect())
     }
 
     fn query_symbols(&self, query: &SymbolQuery) -> Result<Vec<SymbolRecord>> {
@@ -451,13 +667,164 @@ impl IndexBackend for FileIndexBackend {
 
         Ok(results)
     }
+
+    fn set_file_calls(&mut self, file_id: u64, calls: &[NewCallRecord]) -> Result<()> {
+        let mut records = self.load_calls()?;
+        records.retain(|call| call.file_id != file_id);
+        records.extend(calls.iter().map(|call| CallRecord {
+            caller_id: call.caller_id,
+            file_id,
+            callee_name: call.callee_name.clone(),
+            callee_file: call.callee_file.clone(),
+            callee_id: None,
+            line: call.line,
+            column: call.column,
+            edge_kind: call.edge_kind,
+        }));
+        self.persist_calls(&records)
+    }
+
+    fn query_calls(&self, file_ids: &[u64]) -> Result<Vec<CallRecord>> {
+        let mut calls = self.load_calls()?;
+        if !file_ids.is_empty() {
+            calls.retain(|call| file_ids.contains(&call.file_id));
+        }
+        // Stable sort keeps insertion order within each file.
+        calls.sort_by_key(|call| call.file_id);
+        Ok(calls)
+    }
+
+    fn relink_calls(&mut self, paths: &[PathBuf]) -> Result<()> {
+        let mut calls = self.load_calls()?;
+        if calls.is_empty() || paths.is_empty() {
+            return Ok(());
+        }
+
+        let paths: HashSet<&PathBuf> = paths.iter().collect();
+        let file_ids: HashSet<u64> = self
+            .files_by_id
+            .values()
+            .filter(|file| paths.contains(&file.path))
+            .map(|file| file.id)
+            .collect();
+
+        // First symbol id for each (file, name), matching the order in
+        // which symbols were written.
+        let mut first_ids: HashMap<(PathBuf, String), u64> = HashMap::new();
+        let symbols_path = self.symbols_path();
+        if symbols_path.exists() {
+            let reader = BufReader::new(File::open(&symbols_path)?);
+            for line in reader.lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: SymbolRecord = serde_json::from_str(&line)?;
+                if record.kind == SymbolKind::Namespace {
+                    continue;
+                }
+                let Some(file) = self.files_by_id.get(&record.file_id) else {
+                    continue;
+                };
+                first_ids
+                    .entry((file.path.clone(), record.name))
+                    .and_modify(|id| *id = (*id).min(record.id))
+                    .or_insert(record.id);
+            }
+        }
+
+        let affected = calls.iter_mut().filter(|call| {
+            file_ids.contains(&call.file_id)
+                || call.callee_file.as_ref().is_some_and(|file| paths.contains(file))
+        });
+        for call in affected {
+            call.callee_id = call.callee_file.as_ref().and_then(|file| {
+                first_ids
+                    .get(&(file.clone(), call.callee_name.clone()))
+                    .copied()
+            });
+        }
+
+        self.persist_calls(&calls)
+    }
+
+    fn set_file_dependencies(&mut self, file_id: u64, deps: &[NewDependencyRecord]) -> Result<()> {
+        let mut records = self.load_deps()?;
+        records.retain(|dep| dep.file_id != file_id);
+        records.extend(deps.iter().map(|dep| DependencyRecord {
+            file_id,
+            module: dep.module.clone(),
+            kind: dep.kind,
+            target: dep.target.clone(),
+            line: dep.line,
+        }));
+        self.persist_deps(&records)
+    }
+
+    fn query_dependencies(&self, file_ids: &[u64]) -> Result<Vec<DependencyRecord>> {
+        let mut deps = self.load_deps()?;
+        if !file_ids.is_empty() {
+            deps.retain(|dep| file_ids.contains(&dep.file_id));
+        }
+        // Stable sort keeps insertion order within each file.
+        deps.sort_by_key(|dep| dep.file_id);
+        Ok(deps)
+    }
+
+    fn set_file_relations(&mut self, file_id: u64, relations: &[NewRelationRecord]) -> Result<()> {
+        let mut records = self.load_relations()?;
+        records.retain(|relation| relation.file_id != file_id);
+        records.extend(relations.iter().map(|relation| RelationRecord {
+            file_id,
+            subtype: relation.subtype.clone(),
+            supertype: relation.supertype.clone(),
+            kind: relation.kind,
+            line: relation.line,
+        }));
+        self.persist_relations(&records)
+    }
+
+    fn query_relations(&self, file_ids: &[u64]) -> Result<Vec<RelationRecord>> {
+        let mut relations = self.load_relations()?;
+        if !file_ids.is_empty() {
+            relations.retain(|relation| file_ids.contains(&relation.file_id));
+        }
+        // Stable sort keeps insertion order within each file.
+        relations.sort_by_key(|relation| relation.file_id);
+        Ok(relations)
+    }
+    fn set_file_members(&mut self, file_id: u64, members: &[NewMemberRecord]) -> Result<()> {
+        let mut records = self.load_members()?;
+        records.retain(|member| member.file_id != file_id);
+        records.extend(members.iter().map(|member| MemberRecord {
+            file_id,
+            scope: member.scope.clone(),
+            name: member.name.clone(),
+            arity: member.arity,
+            range: member.range,
+            definition: member.definition,
+        }));
+        self.persist_members(&records)
+    }
+
+    fn query_members(&self, file_ids: &[u64]) -> Result<Vec<MemberRecord>> {
+        let mut members = self.load_members()?;
+        if !file_ids.is_empty() {
+            members.retain(|member| file_ids.contains(&member.file_id));
+        }
+        // Stable sort keeps insertion order within each file.
+        members.sort_by_key(|member| member.file_id);
+        Ok(members)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::index::models::{NewSymbolRecord, SymbolQuery};
-    use crate::models::{SymbolKind, TextRange};
+    use crate::index::models::{
+        NewCallRecord, NewDependencyRecord, NewRelationRecord, NewSymbolRecord, SymbolQuery,
+    };
+    use crate::models::{CallEdgeKind, DependencyKind, SymbolKind, TextRange, TypeRelationKind};
     use std::path::PathBuf;
     use tempfile::tempdir;
 
@@ -532,4 +899,197 @@ mod tests {
         let results_after = backend.query_symbols(&query).expect("query symbols");
         assert!(results_after.is_empty());
     }
+
+    fn function_record(file_id: u64, name: &str, line: u32) -> NewSymbolRecord {
+        NewSymbolRecord {
+            file_id,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            language: "typescript".to_string(),
+            range: TextRange {
+                start_line: line,
+                start_column: 1,
+                end_line: line,
+                end_column: 20,
+            },
+            signature: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn file_backend_persists_and_relinks_calls() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = FileIndexBackend::open(&dir.path().join(".symgrep")).expect("backend");
+
+        let app = backend
+            .upsert_file(Path::new("src/app.ts"), "typescript", None, 1, 1)
+            .expect("app file");
+        let math = backend
+            .upsert_file(Path::new("src/math.ts"), "typescript", None, 1, 1)
+            .expect("math file");
+
+        let app_ids = backend
+            .set_file_symbols(app.id, &[function_record(app.id, "run", 1)])
+            .expect("app symbols");
+        let math_ids = backend
+            .set_file_symbols(math.id, &[function_record(math.id, "add", 1)])
+            .expect("math symbols");
+
+        backend
+            .set_file_calls(
+                app.id,
+                &[NewCallRecord {
+                    caller_id: app_ids[0],
+                    callee_name: "add".to_string(),
+                    callee_file: Some(PathBuf::from("src/math.ts")),
+                    line: 2,
+                    column: Some(5),
+                    edge_kind: CallEdgeKind::Call,
+                }],
+            )
+            .expect("set calls");
+        backend.relink_calls(&[PathBuf::from("src/app.ts")]).expect("relink");
+
+        let calls = backend.query_calls(&[app.id]).expect("query calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].caller_id, app_ids[0]);
+        assert_eq!(calls[0].callee_id, Some(math_ids[0]));
+        assert_eq!(calls[0].column, Some(5));
+
+        // Rewriting the callee's file assigns new ids; relinking
+        // follows them.
+        let math_ids = backend
+            .set_file_symbols(math.id, &[function_record(math.id, "add", 3)])
+            .expect("math symbols");
+        backend.relink_calls(&[PathBuf::from("src/math.ts")]).expect("relink");
+        let calls = backend.query_calls(&[]).expect("query calls");
+        assert_eq!(calls[0].callee_id, Some(math_ids[0]));
+
+        // Removing the caller's file drops its calls.
+        backend
+            .remove_file_by_path(Path::new("src/app.ts"))
+            .expect("remove file");
+        assert!(backend.query_calls(&[]).expect("query calls").is_empty());
+    }
+
+    #[test]
+    fn file_backend_persists_dependencies() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = FileIndexBackend::open(&dir.path().join(".symgrep")).expect("backend");
+
+        let app = backend
+            .upsert_file(Path::new("src/app.ts"), "typescript", None, 1, 1)
+            .expect("app file");
+        let deps = [
+            NewDependencyRecord {
+                module: "./math".to_string(),
+                kind: DependencyKind::Import,
+                target: Some(PathBuf::from("src/math.ts")),
+                line: 1,
+            },
+            NewDependencyRecord {
+                module: "lodash".to_string(),
+                kind: DependencyKind::Require,
+                target: None,
+                line: 2,
+            },
+        ];
+        backend
+            .set_file_dependencies(app.id, &deps)
+            .expect("set dependencies");
+
+        let stored = backend.query_dependencies(&[app.id]).expect("query deps");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].target, Some(PathBuf::from("src/math.ts")));
+        assert_eq!(stored[1].kind, DependencyKind::Require);
+        assert_eq!(stored[1].target, None);
+
+        // Replacing and removing the file drop its previous records.
+        backend
+            .set_file_dependencies(app.id, &deps[..1])
+            .expect("set dependencies");
+        assert_eq!(backend.query_dependencies(&[]).expect("query deps").len(), 1);
+        backend
+            .remove_file_by_path(Path::new("src/app.ts"))
+            .expect("remove file");
+        assert!(backend.query_dependencies(&[]).expect("query deps").is_empty());
+    }
+
+    #[test]
+    fn file_backend_persists_relations() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = FileIndexBackend::open(&dir.path().join(".symgrep")).expect("backend");
+
+        let square = backend
+            .upsert_file(Path::new("src/square.ts"), "typescript", None, 1, 1)
+            .expect("square file");
+        let relations = [
+            NewRelationRecord {
+                subtype: "Square".to_string(),
+                supertype: "Base".to_string(),
+                kind: TypeRelationKind::Extends,
+                line: 3,
+            },
+            NewRelationRecord {
+                subtype: "Square".to_string(),
+                supertype: "Polygon".to_string(),
+                kind: TypeRelationKind::Implements,
+                line: 3,
+            },
+        ];
+        backend
+            .set_file_relations(square.id, &relations)
+            .expect("set relations");
+
+        let stored = backend.query_relations(&[square.id]).expect("query relations");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].supertype, "Base");
+        assert_eq!(stored[1].kind, TypeRelationKind::Implements);
+
+        // Replacing and removing the file drop its previous records.
+        backend
+            .set_file_relations(square.id, &relations[..1])
+            .expect("set relations");
+        assert_eq!(backend.query_relations(&[]).expect("query relations").len(), 1);
+        backend
+            .remove_file_by_path(Path::new("src/square.ts"))
+            .expect("remove file");
+        assert!(backend.query_relations(&[]).expect("query relations").is_empty());
+    }
+    #[test]
+    fn file_backend_persists_members() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = FileIndexBackend::open(&dir.path().join(".symgrep")).expect("backend");
+
+        let shape = backend
+            .upsert_file(Path::new("src/shape.cpp"), "cpp", None, 1, 1)
+            .expect("shape file");
+        let member = NewMemberRecord {
+            scope: "geo::Shape".to_string(),
+            name: "area".to_string(),
+            arity: 0,
+            range: TextRange {
+                start_line: 5,
+                start_column: 1,
+                end_line: 7,
+                end_column: 2,
+            },
+            definition: true,
+        };
+        backend
+            .set_file_members(shape.id, std::slice::from_ref(&member))
+            .expect("set members");
+
+        let stored = backend.query_members(&[shape.id]).expect("query members");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].scope, "geo::Shape");
+        assert_eq!(stored[0].range, member.range);
+        assert!(stored[0].definition);
+
+        backend
+            .remove_file_by_path(Path::new("src/shape.cpp"))
+            .expect("remove file");
+        assert!(backend.query_members(&[]).expect("query members").is_empty());
+    }
 }
@@ -11,6 +11,7 @@
 mod backend;
 mod file;
 pub mod models;
+pub(crate) mod records;
 mod sqlite;
 
 pub use backend::{open_backend, IndexBackend};
@@ -24,13 +25,21 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{bail, Result};
 use globset::{Glob, GlobSet};
-use ignore::WalkBuilder;
 
-use crate::language::{backend_for_language, backend_for_path};
+use crate::language::{
+    add_import_targets, backend_for_language, backend_for_path, normalize_path,
+    resolve_cross_file_calls, scan_file, CompileDatabase, FileSymbols, LanguageBackend,
+    MAX_REEXPORT_DEPTH,
+};
 use crate::models::{
     IndexConfig, IndexSummary, Symbol, SymbolAttributes, SymbolAttributesRequest,
     SymbolAttributesResponse, SymbolKind,
 };
+use crate::walk::{collect_files, for_each_ordered};
+use models::{
+    is_supported_schema_version, unsupported_schema_version, NewCallRecord, INDEX_SCHEMA_VERSION,
+};
+use records::{dependency_records, member_records, new_call_records, relation_records};
 use serde_json::Value;
 
 /// Run indexing for the given configuration using the configured backend.
@@ -77,7 +86,7 @@ pub fn get_index_info(config: &IndexConfig) -> Result<IndexSummary> {
     let meta = backend.load_meta().unwrap_or_else(|_| {
         let now = current_epoch_seconds();
         IndexMeta {
-            schema_version: "2".to_string(),
+            schema_version: INDEX_SCHEMA_VERSION.to_string(),
             tool_version: env!("CARGO_PKG_VERSION").to_string(),
             root_path: String::new(),
             created_at: now,
@@ -143,7 +152,7 @@ pub(crate) fn build_index(
     let mut meta = backend.load_meta().unwrap_or_else(|_| {
         let now = current_epoch_seconds();
         IndexMeta {
-            schema_version: "2".to_string(),
+            schema_version: INDEX_SCHEMA_VERSION.to_string(),
             tool_version: env!("CARGO_PKG_VERSION").to_string(),
             root_path: String::new(),
             created_at: now,
@@ -165,16 +174,17 @@ pub(crate) fn build_index(
 
     // Upgrade older index metadata to the current logical schema
     // version while preserving other fields.
-    if meta.schema_version != "2" {
+    let upgrading = meta.schema() != INDEX_SCHEMA_VERSION;
+    if upgrading {
         // Older schema versions (e.g. "1") are still readable but
         // are upgraded in-place on the next successful index run.
-        if meta.schema_version != "1" {
-            bail!(
-                "unsupported index schema version {}; expected 1 or 2",
-                meta.schema_version
-            );
+        // They lack the data added by later versions (see
+        // `INDEX_SCHEMA_VERSION`), so every file is reparsed once to
+        // fill it in.
+        if !is_supported_schema_version(&meta.schema_version) {
+            return Err(unsupported_schema_version(&meta.schema_version));
         }
-        meta.schema_version = "2".to_string();
+        meta.schema_version = INDEX_SCHEMA_VERSION.to_string();
     }
 
     let include_globs = build_globset(&config.globs)?;
@@ -187,66 +197,60 @@ pub(crate) fn build_index(
         .collect();
     let mut seen_paths = HashSet::new();
 
-    let mut builder = WalkBuilder::new(&config.paths[0]);
-    for path in config.paths.iter().skip(1) {
-        builder.add(path);
-    }
-    let walker = builder.build();
-
-    let mut files_indexed: u64 = 0;
-    let mut symbols_indexed: u64 = 0;
-
-    for entry_result in walker {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-
-        if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-            continue;
-        }
-
-        if let Some(set) = &include_globs {
-            if !set.is_match(path) {
-                continue;
-            }
-        }
-        if let Some(set) = &exclude_globs {
-            if set.is_match(path) {
-                continue;
-            }
-        }
+    let selected_backend = match &config.language {
+        Some(lang) => Some(backend_for_language(lang).ok_or_else(|| {
+            anyhow::anyhow!(
+                "indexing is only supported for known languages (e.g., typescript, javascript, cpp); got {}",
+                lang
+            )
+        })?),
+        None => None,
+    };
 
-        let language_backend = if let Some(lang) = &config.language {
-            let backend = backend_for_language(lang).ok_or_else(|| {
-                anyhow::anyhow!(
-                    "indexing is only supported for known languages (e.g., typescript, javascript, cpp); got {}",
-                    lang
-                )
-            })?;
-
-            // If the file extension is not supported by the selected backend,
-            // skip this file.
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if !backend
-                .file_extensions()
-                .iter()
-                .any(|e| e.eq_ignore_ascii_case(ext))
-            {
-                continue;
+    let build = config
+        .compile_commands
+        .as_deref()
+        .map(CompileDatabase::load)
+        .transpose()?;
+
+    let files = collect_files(
+        &config.paths,
+        include_globs.as_ref(),
+        exclude_globs.as_ref(),
+    );
+
+    // First pass (serial, cheap): pick a language backend for each
+    // file and use mtime/size to decide which files need reparsing.
+    let mut candidates = Vec::new();
+
+    for path in files {
+        let language_backend = match selected_backend {
+            Some(backend) => {
+                // If the file extension is not supported by the selected
+                // backend, skip this file.
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                if !backend
+                    .file_extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(ext))
+                {
+                    continue;
+                }
+                backend
             }
-
-            backend
-        } else {
-            match backend_for_path(path) {
+            None => match backend_for_path(&path) {
                 Some(b) => b,
                 None => continue,
-            }
+            },
         };
 
-        let metadata = match std::fs::metadata(path) {
+        // C/C++ sources the build never compiles are left out (and
+        // dropped below if an earlier run indexed them).
+        if build.as_ref().is_some_and(|build| build.is_outside_build(&path)) {
+            continue;
+        }
+
+        let metadata = match std::fs::metadata(&path) {
             Ok(m) => m,
             Err(_) => continue,
         };
@@ -259,34 +263,123 @@ pub(crate) fn build_index(
             .unwrap_or(0);
         let size = metadata.len();
 
-        let path_buf = path.to_path_buf();
-        seen_paths.insert(path_buf.clone());
+        seen_paths.insert(path.clone());
 
-        let needs_reindex = match existing_by_path.get(&path_buf) {
-            Some(file_record) => file_record.mtime != mtime || file_record.size != size,
+        let needs_reindex = match existing_by_path.get(&path) {
+            Some(file_record) => {
+                upgrading || file_record.mtime != mtime || file_record.size != size
+            }
             None => true,
         };
 
-        if !needs_reindex {
-            continue;
-        }
+        candidates.push((
+            PendingFile {
+                path,
+                language_backend,
+                mtime,
+                size,
+            },
+            needs_reindex,
+        ));
+    }
 
-        let source = match std::fs::read_to_string(path) {
-            Ok(s) => s,
-            Err(_) => continue,
-        };
+    // Calls into a changed or removed file were resolved through the
+    // importer's imports (and any re-exports along the way), so those
+    // importers are reparsed too.
+    let mut changed: Vec<PathBuf> = candidates
+        .iter()
+        .filter(|(_, needs_reindex)| *needs_reindex)
+        .map(|(file, _)| normalize_path(&file.path))
+        .chain(
+            existing_files
+                .iter()
+                .filter(|f| !seen_paths.contains(&f.path))
+                .map(|f| normalize_path(&f.path)),
+        )
+        .collect();
+    if !upgrading && !changed.is_empty() {
+        let importers = importers_by_target(backend, &existing_files)?;
+        let mut rescan: HashSet<PathBuf> = HashSet::new();
+        for _ in 0..=MAX_REEXPORT_DEPTH {
+            changed = changed
+                .iter()
+                .filter_map(|path| importers.get(path))
+                .flatten()
+                .filter(|importer| rescan.insert((*importer).clone()))
+                .cloned()
+                .collect();
+            if changed.is_empty() {
+                break;
+            }
+        }
+        for (file, needs_reindex) in &mut candidates {
+            *needs_reindex |= rescan.contains(&normalize_path(&file.path));
+        }
+    }
+    let indexed_paths: HashMap<PathBuf, PathBuf> = candidates
+        .iter()
+        .map(|(file, _)| (normalize_path(&file.path), file.path.clone()))
+        .collect();
+    let pending: Vec<PendingFile> = candidates
+        .into_iter()
+        .filter_map(|(file, needs_reindex)| needs_reindex.then_some(file))
+        .collect();
 
-        let parsed = match language_backend.parse_file(path, &source) {
-            Ok(p) => p,
-            Err(_) => continue,
-        };
+    let mut files_indexed: u64 = 0;
+    let mut symbols_indexed: u64 = 0;
 
-        let symbols = match language_backend.index_symbols(&parsed) {
-            Ok(s) => s,
-            Err(_) => continue,
-        };
+    // Second pass: parse and extract symbols and imports on the worker
+    // pool. Files imported by the changed ones are parsed too (but not
+    // rewritten) so call edges can be resolved across files.
+    let mut written = Vec::new();
+    let mut scanned = Vec::new();
+    for_each_ordered(
+        &pending,
+        None,
+        |file| scan_file(&file.path, file.language_backend, build.as_ref()),
+        |file, symbols| {
+            if let Some(symbols) = symbols {
+                written.push(file.clone());
+                scanned.push(symbols);
+            }
+            Ok(true)
+        },
+    )?;
+    add_import_targets(&mut scanned, build.as_ref());
+    resolve_cross_file_calls(&mut scanned);
+
+    // Import targets are parsed under their resolved paths; calls into
+    // them are stored under the path the walk indexed them as so that
+    // `relink_calls` finds their symbols.
+    for symbol in scanned.iter_mut().flat_map(|file| file.symbols.iter_mut()) {
+        for target in symbol
+            .calls
+            .iter_mut()
+            .filter_map(|call| call.target_file.as_mut())
+        {
+            if let Some(path) = indexed_paths.get(&normalize_path(target)) {
+                target.clone_from(path);
+            }
+        }
+    }
 
-        let file_record = backend.upsert_file(path, language_backend.id(), None, mtime, size)?;
+    // Write each changed file to the backend in path order.
+    let mut changed_paths = Vec::new();
+    for (file, scanned) in written.into_iter().zip(scanned) {
+        let FileSymbols {
+            symbols,
+            imports,
+            relations,
+            members,
+            ..
+        } = scanned;
+        let file_record = backend.upsert_file(
+            &file.path,
+            file.language_backend.id(),
+            None,
+            file.mtime,
+            file.size,
+        )?;
 
         existing_by_path.insert(file_record.path.clone(), file_record.clone());
 
@@ -308,28 +401,36 @@ pub(crate) fn build_index(
         }
 
         let new_symbols: Vec<NewSymbolRecord> = symbols
-            .into_iter()
+            .iter()
             .map(|s| {
-                let identity = SymbolIdentity::from_symbol(&s);
+                let identity = SymbolIdentity::from_symbol(s);
                 let existing = existing_by_identity.get(&identity);
-                let merged_attrs = merge_symbol_attributes_for_index(existing, &s);
+                let merged_attrs = merge_symbol_attributes_for_index(existing, s);
 
                 NewSymbolRecord {
                     file_id: file_record.id,
-                    name: s.name,
+                    name: s.name.clone(),
                     kind: s.kind,
-                    language: s.language,
+                    language: s.language.clone(),
                     range: s.range,
-                    signature: s.signature,
+                    signature: s.signature.clone(),
                     extra: symbol_attributes_to_extra(&merged_attrs),
                 }
             })
             .collect();
 
-        backend.set_file_symbols(file_record.id, &new_symbols)?;
+        let ids = backend.set_file_symbols(file_record.id, &new_symbols)?;
+        backend.set_file_calls(file_record.id, &new_call_records(&symbols, &ids))?;
+        backend.set_file_dependencies(
+            file_record.id,
+            &dependency_records(&file.path, &imports),
+        )?;
+        backend.set_file_relations(file_record.id, &relation_records(&relations))?;
+        backend.set_file_members(file_record.id, &member_records(&members))?;
 
         files_indexed += 1;
         symbols_indexed += new_symbols.len() as u64;
+        changed_paths.push(file_record.path);
     }
 
     // Remove stale entries for files that no longer exist under the
@@ -337,9 +438,16 @@ pub(crate) fn build_index(
     for file in existing_files {
         if !seen_paths.contains(&file.path) && path_within_any(&file.path, &config.paths) {
             backend.remove_file_by_path(&file.path)?;
+            changed_paths.push(file.path);
         }
     }
 
+    // Symbol ids change whenever a file is rewritten, so resolved
+    // callee ids are recomputed from the stored callee file and name.
+    if !changed_paths.is_empty() {
+        backend.relink_calls(&changed_paths)?;
+    }
+
     meta.updated_at = current_epoch_seconds();
     backend.save_meta(&meta)?;
 
@@ -359,6 +467,38 @@ pub(crate) fn build_index(
     })
 }
 
+/// Map each indexed file to the indexed files that import it.
+fn importers_by_target(
+    backend: &dyn backend::IndexBackend,
+    files: &[FileRecord],
+) -> Result<HashMap<PathBuf, Vec<PathBuf>>> {
+    let paths_by_id: HashMap<u64, PathBuf> = files
+        .iter()
+        .map(|f| (f.id, normalize_path(&f.path)))
+        .collect();
+    let mut importers: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for dependency in backend.query_dependencies(&[])? {
+        if let (Some(target), Some(importer)) =
+            (dependency.target, paths_by_id.get(&dependency.file_id))
+        {
+            importers
+                .entry(normalize_path(&target))
+                .or_default()
+                .push(importer.clone());
+        }
+    }
+    Ok(importers)
+}
+
+/// A file selected for (re)indexing in the first pass of `build_index`.
+#[derive(Clone)]
+struct PendingFile {
+    path: PathBuf,
+    language_backend: &'static dyn LanguageBackend,
+    mtime: i64,
+    size: u64,
+}
+
 fn path_within_any(path: &Path, roots: &[PathBuf]) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
@@ -402,6 +542,9 @@ fn empty_symbol_attributes() -> SymbolAttributes {
         comment_range: None,
         keywords: Vec::new(),
         description: None,
+        annotations: Vec::new(),
+        is_test: false,
+        doc: None,
     }
 }
 
@@ -409,19 +552,20 @@ fn merge_symbol_attributes_for_index(
     existing: Option<&SymbolRecord>,
     symbol: &Symbol,
 ) -> SymbolAttributes {
-    let (new_comment, new_comment_range) = match symbol.attributes.as_ref() {
-        Some(attrs) => (attrs.comment.clone(), attrs.comment_range),
-        None => (None, None),
-    };
-
     let mut merged = empty_symbol_attributes();
-    merged.comment = new_comment;
-    merged.comment_range = new_comment_range;
+    if let Some(attrs) = symbol.attributes.as_ref() {
+        merged.comment = attrs.comment.clone();
+        merged.comment_range = attrs.comment_range;
+        merged.annotations = attrs.annotations.clone();
+        merged.is_test = attrs.is_test;
+        merged.doc = attrs.doc.clone();
+    }
 
     if let Some(record) = existing {
         if let Some(existing_attrs) = symbol_attributes_from_extra(&record.extra) {
             // Preserve externally-owned attributes across reindex
-            // runs; comments always come from fresh AST extraction.
+            // runs; comments, annotations, test classification and
+            // parsed docs always come from fresh AST extraction.
             merged.keywords = existing_attrs.keywords;
             merged.description = existing_attrs.description;
         }
@@ -437,8 +581,15 @@ fn symbol_attributes_to_extra(attrs: &SymbolAttributes) -> Option<Value> {
     let has_comment_range = attrs.comment_range.is_some();
     let has_keywords = !attrs.keywords.is_empty();
     let has_desc = attrs.description.is_some();
-
-    if !has_comment && !has_comment_range && !has_keywords && !has_desc {
+    let has_annotations = !attrs.annotations.is_empty();
+
+    if !has_comment
+        && !has_comment_range
+        && !has_keywords
+        && !has_desc
+        && !has_annotations
+        && !attrs.is_test
+    {
         return None;
     }
 
@@ -507,7 +658,7 @@ pub fn update_symbol_attributes(
         exclude_globs: Vec::new(),
     };
 
-    let mut records = backend.query_symbols(&symbol_query)?;
+    let records = backend.query_symbols(&symbol_query)?;
 
     if records.is_empty() {
         anyhow::bail!(
@@ -561,6 +712,8 @@ pub fn update_symbol_attributes(
     target_attrs.keywords = update.keywords;
     target_attrs.description = update.description;
 
+    let old_ids: Vec<u64> = records.iter().map(|r| r.id).collect();
+
     // Rewrite all symbols for this file, updating only the target
     // symbol's attributes.
     let mut new_symbols: Vec<NewSymbolRecord> = Vec::with_capacity(records.len());
@@ -585,7 +738,28 @@ pub fn update_symbol_attributes(
         });
     }
 
-    backend.set_file_symbols(file_record.id, &new_symbols)?;
+    // Rewriting the symbols drops the file's call edges; carry them
+    // over to the new symbol ids.
+    let calls = backend.query_calls(&[file_record.id])?;
+
+    let new_ids = backend.set_file_symbols(file_record.id, &new_symbols)?;
+
+    let id_map: HashMap<u64, u64> = old_ids.into_iter().zip(new_ids).collect();
+    let calls: Vec<NewCallRecord> = calls
+        .into_iter()
+        .filter_map(|call| {
+            Some(NewCallRecord {
+                caller_id: *id_map.get(&call.caller_id)?,
+                callee_name: call.callee_name,
+                callee_file: call.callee_file,
+                line: call.line,
+                column: call.column,
+                edge_kind: call.edge_kind,
+            })
+        })
+        .collect();
+    backend.set_file_calls(file_record.id, &calls)?;
+    backend.relink_calls(std::slice::from_ref(&file_record.path))?;
 
     let updated_symbol = Symbol {
         name: selector.name,
@@ -599,6 +773,9 @@ pub fn update_symbol_attributes(
         matches: Vec::new(),
          calls: Vec::new(),
          called_by: Vec::new(),
+         supertypes: Vec::new(),
+         definition: None,
+         modules: Vec::new(),
     };
 
     Ok(SymbolAttributesResponse {
@@ -7,7 +7,51 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::{SymbolKind, TextRange};
+use crate::models::{CallEdgeKind, DependencyKind, SymbolKind, TextRange, TypeRelationKind};
+
+/// Current on-disk index schema version.
+///
+/// Version 3 added the `calls` table, version 4 the `deps` table,
+/// version 5 the `relations` table, version 6 the `members` table,
+/// version 7 the `calls.kind` column, version 8 symbol annotations in
+/// `extra`, version 9 test classification in `extra` and version 10
+/// parsed doc comments in `extra`; indexes written by older versions
+/// (1 to 9) are still readable and are fully reparsed on the next
+/// index run so their call edges, dependencies, type relations, member
+/// keys, call kinds, annotations, test flags and docs get populated.
+pub const INDEX_SCHEMA_VERSION: u32 = 10;
+
+/// First schema version with call edges (the `calls` table).
+pub const SCHEMA_CALLS: u32 = 3;
+/// First schema version with file dependencies (the `deps` table).
+pub const SCHEMA_DEPS: u32 = 4;
+/// First schema version with type relations (the `relations` table).
+pub const SCHEMA_RELATIONS: u32 = 5;
+/// First schema version with member keys (the `members` table).
+pub const SCHEMA_MEMBERS: u32 = 6;
+/// First schema version with symbol annotations in `extra`.
+pub const SCHEMA_ANNOTATIONS: u32 = 8;
+/// First schema version with test classification in `extra`.
+pub const SCHEMA_TEST_FLAGS: u32 = 9;
+/// First schema version with parsed doc comments in `extra`.
+pub const SCHEMA_DOCS: u32 = 10;
+
+/// Parse a stored schema version, or `None` when it is not a number.
+pub fn parse_schema_version(version: &str) -> Option<u32> {
+    version.trim().parse().ok()
+}
+
+/// Whether an index with the given schema version can be opened.
+pub fn is_supported_schema_version(version: &str) -> bool {
+    parse_schema_version(version).is_some_and(|v| (1..=INDEX_SCHEMA_VERSION).contains(&v))
+}
+
+/// Error for an index whose schema version cannot be opened.
+pub fn unsupported_schema_version(version: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "unsupported index schema version {version}; expected 1 through {INDEX_SCHEMA_VERSION}"
+    )
+}
 
 /// Metadata for the entire project index.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +72,14 @@ pub struct IndexMeta {
     pub updated_at: u64,
 }
 
+impl IndexMeta {
+    /// The schema version as a number, or `0` when it is not one so
+    /// every feature gate treats the index as too old.
+    pub fn schema(&self) -> u32 {
+        parse_schema_version(&self.schema_version).unwrap_or(0)
+    }
+}
+
 /// Logical record for a single file in the index.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileRecord {
@@ -87,6 +139,139 @@ pub struct NewSymbolRecord {
     pub extra: Option<serde_json::Value>,
 }
 
+/// Logical record for a single call site in the index.
+///
+/// Calls are owned by the file of their caller and replaced together
+/// with that file's symbols.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallRecord {
+    /// Id of the calling symbol.
+    pub caller_id: u64,
+    /// Id of the file containing the caller (and the call site).
+    pub file_id: u64,
+    /// Callee name at the call site, or the definition's name when the
+    /// call was resolved through an aliased import.
+    pub callee_name: String,
+    /// File defining the callee: the caller's own file for same-file
+    /// calls, the imported file for resolved cross-file calls, and
+    /// `None` when the callee could not be resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callee_file: Option<PathBuf>,
+    /// Id of the first symbol named `callee_name` in `callee_file`.
+    ///
+    /// Recomputed by `IndexBackend::relink_calls` whenever symbols are
+    /// rewritten, since symbol ids are not stable across updates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callee_id: Option<u64>,
+    /// 1-based line of the call expression.
+    pub line: u32,
+    /// 1-based column of the call expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    /// Kind of usage; indexes before schema "7" only stored calls.
+    #[serde(default, skip_serializing_if = "CallEdgeKind::is_call")]
+    pub edge_kind: CallEdgeKind,
+}
+
+/// Non-persisted representation of a call ready to be inserted.
+#[derive(Debug, Clone)]
+pub struct NewCallRecord {
+    pub caller_id: u64,
+    pub callee_name: String,
+    pub callee_file: Option<PathBuf>,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub edge_kind: CallEdgeKind,
+}
+
+/// Logical record for a single dependency statement in the index.
+///
+/// Dependencies are owned by the importing file and replaced whenever
+/// that file is reindexed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyRecord {
+    /// Id of the importing file.
+    pub file_id: u64,
+    /// Module specifier or path as written in the source.
+    pub module: String,
+    /// Kind of statement (`import`, `use`, `include`, ...).
+    pub kind: DependencyKind,
+    /// File the module resolved to, when it resolved to one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<PathBuf>,
+    /// 1-based line of the statement.
+    pub line: u32,
+}
+
+/// Non-persisted representation of a dependency ready to be inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDependencyRecord {
+    pub module: String,
+    pub kind: DependencyKind,
+    pub target: Option<PathBuf>,
+    pub line: u32,
+}
+
+/// Logical record for a single `extends`/`implements` clause in the
+/// index.
+///
+/// Relations are owned by the file declaring them and replaced
+/// whenever that file is reindexed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationRecord {
+    /// Id of the declaring file.
+    pub file_id: u64,
+    /// Name of the extending or implementing type.
+    pub subtype: String,
+    /// Name of the extended or implemented type.
+    pub supertype: String,
+    /// Kind of relation.
+    pub kind: TypeRelationKind,
+    /// 1-based line of the supertype in the clause.
+    pub line: u32,
+}
+
+/// Non-persisted representation of a relation ready to be inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewRelationRecord {
+    pub subtype: String,
+    pub supertype: String,
+    pub kind: TypeRelationKind,
+    pub line: u32,
+}
+
+/// Logical record for a C++ member declaration or out-of-line
+/// definition in the index.
+///
+/// Members are owned by the file containing them and replaced
+/// whenever that file is reindexed; declarations and definitions are
+/// paired at query time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberRecord {
+    /// Id of the containing file.
+    pub file_id: u64,
+    /// Enclosing scope, joined with `::` (`geo::Shape`).
+    pub scope: String,
+    /// Unqualified member name.
+    pub name: String,
+    /// Number of declared parameters.
+    pub arity: u32,
+    /// Range of the declaration or definition.
+    pub range: TextRange,
+    /// Whether this is an out-of-line definition.
+    pub definition: bool,
+}
+
+/// Non-persisted representation of a member ready to be inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewMemberRecord {
+    pub scope: String,
+    pub name: String,
+    pub arity: u32,
+    pub range: TextRange,
+    pub definition: bool,
+}
+
 /// Query parameters for retrieving symbols from an index backend.
 #[derive(Debug, Clone)]
 pub struct SymbolQuery {
@@ -191,5 +376,19 @@ mod tests {
         assert_eq!(decoded.root_path, meta.root_path);
         assert_eq!(decoded.created_at, meta.created_at);
         assert_eq!(decoded.updated_at, meta.updated_at);
+        assert_eq!(decoded.schema(), 2);
+    }
+
+    #[test]
+    fn schema_versions_parse_as_numbers() {
+        assert!(is_supported_schema_version("1"));
+        assert!(is_supported_schema_version(
+            &INDEX_SCHEMA_VERSION.to_string()
+        ));
+        assert!(!is_supported_schema_version("0"));
+        assert!(!is_supported_schema_version(
+            &(INDEX_SCHEMA_VERSION + 1).to_string()
+        ));
+        assert!(!is_supported_schema_version("v3"));
     }
 }
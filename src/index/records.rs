@@ -0,0 +1,176 @@
+//! Conversion of extracted file data into index records.
+//!
+//! Indexing and scan-mode searches share these, so a search without an
+//! index sees the same calls, dependencies, relations and members as
+//! one answered from the index.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::index::models::{
+    NewCallRecord, NewDependencyRecord, NewMemberRecord, NewRelationRecord,
+};
+use crate::language::normalize_path;
+use crate::models::{ImportRef, MemberKey, Symbol, TypeRelation};
+
+/// Call records for `symbols` (the resolved symbols of one file),
+/// given the ids the index assigned to them in order.
+pub(crate) fn new_call_records(symbols: &[Symbol], ids: &[u64]) -> Vec<NewCallRecord> {
+    let local: HashSet<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+
+    symbols
+        .iter()
+        .zip(ids)
+        .flat_map(|(symbol, id)| {
+            symbol.calls.iter().filter_map(|call| {
+                let callee_file = match &call.target_file {
+                    Some(file) => Some(file.clone()),
+                    None if local.contains(call.name.as_str()) => Some(symbol.file.clone()),
+                    None => None,
+                };
+                Some(NewCallRecord {
+                    caller_id: *id,
+                    callee_name: call.name.clone(),
+                    callee_file,
+                    line: call.line?,
+                    column: call.column,
+                    edge_kind: call.edge_kind,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Dependency records for one file's imports: one per statement and
+/// resolved file, in source order.
+///
+/// Rust `use` lists produce one import per name; they collapse into a
+/// single dependency when they resolve to the same file. Imports that
+/// resolve to `file` itself (`use super::*` in an inline `mod tests`)
+/// are not dependencies and are dropped, as are test-only imports
+/// (`use` under `#[cfg(test)]`).
+pub(crate) fn dependency_records(file: &Path, imports: &[ImportRef]) -> Vec<NewDependencyRecord> {
+    let file = normalize_path(file);
+    let mut records: Vec<NewDependencyRecord> = Vec::new();
+
+    for import in imports.iter().filter(|import| !import.test_only) {
+        let targets: Vec<Option<PathBuf>> = if import.files.is_empty() {
+            vec![None]
+        } else {
+            import
+                .files
+                .iter()
+                .map(|target| normalize_path(target))
+                .filter(|target| *target != file)
+                .map(Some)
+                .collect()
+        };
+
+        for target in targets {
+            let duplicate = records.iter().any(|record| {
+                record.line == import.line
+                    && record.kind == import.kind
+                    && match (&record.target, &target) {
+                        (Some(a), Some(b)) => a == b,
+                        (None, None) => record.module == import.module,
+                        _ => false,
+                    }
+            });
+            if !duplicate {
+                records.push(NewDependencyRecord {
+                    module: import.module.clone(),
+                    kind: import.kind,
+                    target,
+                    line: import.line,
+                });
+            }
+        }
+    }
+
+    records
+}
+
+/// Relation records for one file's `extends`/`implements` clauses, in
+/// source order.
+pub(crate) fn relation_records(relations: &[TypeRelation]) -> Vec<NewRelationRecord> {
+    relations
+        .iter()
+        .map(|relation| NewRelationRecord {
+            subtype: relation.subtype.clone(),
+            supertype: relation.supertype.clone(),
+            kind: relation.kind,
+            line: relation.line,
+        })
+        .collect()
+}
+
+/// Member records for one file's declarations and definitions, in
+/// source order.
+pub(crate) fn member_records(members: &[MemberKey]) -> Vec<NewMemberRecord> {
+    members
+        .iter()
+        .map(|member| NewMemberRecord {
+            scope: member.scope.join("::"),
+            name: member.name.clone(),
+            arity: member.arity,
+            range: member.range,
+            definition: member.definition,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DependencyKind;
+
+    fn import(module: &str, kind: DependencyKind, files: &[&str], line: u32) -> ImportRef {
+        ImportRef {
+            module: module.to_string(),
+            kind,
+            names: Vec::new(),
+            wildcard: false,
+            reexport: false,
+            test_only: false,
+            files: files.iter().map(PathBuf::from).collect(),
+            line,
+        }
+    }
+
+    #[test]
+    fn dependency_records_collapse_use_lists() {
+        let records = dependency_records(
+            Path::new("src/lib.rs"),
+            &[
+                import("crate::util", DependencyKind::Use, &["src/./util.rs"], 3),
+                import("crate::util", DependencyKind::Use, &["src/util.rs"], 3),
+                import("std::fmt", DependencyKind::Use, &[], 4),
+                import("std::io", DependencyKind::Use, &[], 4),
+            ],
+        );
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].target, Some(PathBuf::from("src/util.rs")));
+        assert_eq!(records[1].module, "std::fmt");
+        assert_eq!(records[2].module, "std::io");
+    }
+
+    #[test]
+    fn dependency_records_drop_imports_of_the_file_itself() {
+        let records = dependency_records(
+            Path::new("./src/util.rs"),
+            &[
+                import("super", DependencyKind::Use, &["src/util.rs"], 11),
+                import(
+                    "super::super::net",
+                    DependencyKind::Use,
+                    &["src/net/mod.rs"],
+                    12,
+                ),
+            ],
+        );
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].target, Some(PathBuf::from("src/net/mod.rs")));
+    }
+}
@@ -8,6 +8,15 @@
 //! - `symbols(id INTEGER PRIMARY KEY, file_id INTEGER, name TEXT, kind TEXT, language TEXT,
 //!            start_line INTEGER, start_col INTEGER, end_line INTEGER, end_col INTEGER,
 //!            signature TEXT, extra TEXT)`
+//! - `calls(id INTEGER PRIMARY KEY, caller_id INTEGER, file_id INTEGER, callee_name TEXT,
+//!          callee_file TEXT, callee_id INTEGER, line INTEGER, col INTEGER, kind TEXT)`
+//! - `deps(id INTEGER PRIMARY KEY, file_id INTEGER, module TEXT, kind TEXT, target TEXT,
+//!         line INTEGER)`
+//! - `relations(id INTEGER PRIMARY KEY, file_id INTEGER, subtype TEXT, supertype TEXT,
+//!              kind TEXT, line INTEGER)`
+//! - `members(id INTEGER PRIMARY KEY, file_id INTEGER, scope TEXT, name TEXT, arity INTEGER,
+//!            start_line INTEGER, start_col INTEGER, end_line INTEGER, end_col INTEGER,
+//!            definition INTEGER)`
 //!
 //! The schema is intentionally close to the file-based backend's
 //! logical model. The backend uses write transactions for index
@@ -27,8 +36,15 @@ use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
 
 use crate::index::backend::IndexBackend;
 use crate::index::build_globset;
-use crate::index::models::{FileRecord, IndexMeta, NewSymbolRecord, SymbolQuery, SymbolRecord};
-use crate::models::{IndexBackendKind, SymbolKind, TextRange};
+use crate::index::models::{
+    is_supported_schema_version, unsupported_schema_version, CallRecord, DependencyRecord,
+    FileRecord, IndexMeta, MemberRecord, NewCallRecord, NewDependencyRecord, NewMemberRecord,
+    NewRelationRecord, NewSymbolRecord, RelationRecord, SymbolQuery, SymbolRecord,
+    INDEX_SCHEMA_VERSION,
+};
+use crate::models::{
+    CallEdgeKind, DependencyKind, IndexBackendKind, SymbolKind, TextRange, TypeRelationKind,
+};
 
 /// SQLite-backed implementation of `IndexBackend`.
 pub struct SqliteIndexBackend {
@@ -102,9 +118,84 @@ impl SqliteIndexBackend {
                 ON symbols(language);
             CREATE INDEX IF NOT EXISTS idx_symbols_file_id
                 ON symbols(file_id);
+
+            CREATE TABLE IF NOT EXISTS calls (
+                id          INTEGER PRIMARY KEY,
+                caller_id   INTEGER NOT NULL,
+                file_id     INTEGER NOT NULL,
+                callee_name TEXT NOT NULL,
+                callee_file TEXT,
+                callee_id   INTEGER,
+                line        INTEGER NOT NULL,
+                col         INTEGER,
+                kind        TEXT,
+                FOREIGN KEY(caller_id) REFERENCES symbols(id) ON DELETE CASCADE,
+                FOREIGN KEY(file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_calls_file_id
+                ON calls(file_id);
+            CREATE INDEX IF NOT EXISTS idx_calls_caller_id
+                ON calls(caller_id);
+            CREATE INDEX IF NOT EXISTS idx_calls_callee
+                ON calls(callee_file, callee_name);
+
+            CREATE TABLE IF NOT EXISTS deps (
+                id      INTEGER PRIMARY KEY,
+                file_id INTEGER NOT NULL,
+                module  TEXT NOT NULL,
+                kind    TEXT NOT NULL,
+                target  TEXT,
+                line    INTEGER NOT NULL,
+                FOREIGN KEY(file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_deps_file_id
+                ON deps(file_id);
+            CREATE INDEX IF NOT EXISTS idx_deps_target
+                ON deps(target);
+
+            CREATE TABLE IF NOT EXISTS relations (
+                id        INTEGER PRIMARY KEY,
+                file_id   INTEGER NOT NULL,
+                subtype   TEXT NOT NULL,
+                supertype TEXT NOT NULL,
+                kind      TEXT NOT NULL,
+                line      INTEGER NOT NULL,
+                FOREIGN KEY(file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_relations_file_id
+                ON relations(file_id);
+
+            CREATE TABLE IF NOT EXISTS members (
+                id         INTEGER PRIMARY KEY,
+                file_id    INTEGER NOT NULL,
+                scope      TEXT NOT NULL,
+                name       TEXT NOT NULL,
+                arity      INTEGER NOT NULL,
+                start_line INTEGER NOT NULL,
+                start_col  INTEGER NOT NULL,
+                end_line   INTEGER NOT NULL,
+                end_col    INTEGER NOT NULL,
+                definition INTEGER NOT NULL,
+                FOREIGN KEY(file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_members_file_id
+                ON members(file_id);
         "#,
         )?;
 
+        // `calls.kind` was added in schema "7"; older databases get the
+        // column here and read `NULL` as a plain call until reindexed.
+        let has_call_kind = conn
+            .prepare("SELECT 1 FROM pragma_table_info('calls') WHERE name = 'kind'")?
+            .exists([])?;
+        if !has_call_kind {
+            conn.execute("ALTER TABLE calls ADD COLUMN kind TEXT", [])?;
+        }
+
         Ok(())
     }
 
@@ -161,6 +252,35 @@ impl SqliteIndexBackend {
             other => bail!("unknown symbol kind in sqlite index: {other}"),
         }
     }
+
+    fn dependency_kind_from_str(s: &str) -> Result<DependencyKind> {
+        match s {
+            "import" => Ok(DependencyKind::Import),
+            "export" => Ok(DependencyKind::Export),
+            "require" => Ok(DependencyKind::Require),
+            "use" => Ok(DependencyKind::Use),
+            "mod" => Ok(DependencyKind::Mod),
+            "extern_crate" => Ok(DependencyKind::ExternCrate),
+            "include" => Ok(DependencyKind::Include),
+            other => bail!("unknown dependency kind in sqlite index: {other}"),
+        }
+    }
+
+    fn call_edge_kind_from_str(s: &str) -> Result<CallEdgeKind> {
+        match s {
+            "call" => Ok(CallEdgeKind::Call),
+            "render" => Ok(CallEdgeKind::Render),
+            other => bail!("unknown call kind in sqlite index: {other}"),
+        }
+    }
+
+    fn relation_kind_from_str(s: &str) -> Result<TypeRelationKind> {
+        match s {
+            "extends" => Ok(TypeRelationKind::Extends),
+            "implements" => Ok(TypeRelationKind::Implements),
+            other => bail!("unknown relation kind in sqlite index: {other}"),
+        }
+    }
 }
 
 impl IndexBackend for SqliteIndexBackend {
@@ -189,7 +309,7 @@ impl IndexBackend for SqliteIndexBackend {
         if map.is_empty() {
             let now = crate::index::current_epoch_seconds();
             return Ok(IndexMeta {
-                schema_version: "2".to_string(),
+                schema_version: INDEX_SCHEMA_VERSION.to_string(),
                 tool_version: env!("CARGO_PKG_VERSION").to_string(),
                 root_path: String::new(),
                 created_at: now,
@@ -202,11 +322,8 @@ impl IndexBackend for SqliteIndexBackend {
             .cloned()
             .unwrap_or_else(|| "1".to_string());
 
-        if schema_version != "1" && schema_version != "2" {
-            bail!(
-                "unsupported index schema version {}; expected 1 or 2",
-                schema_version
-            );
+        if !is_supported_schema_version(&schema_version) {
+            return Err(unsupported_schema_version(&schema_version));
         }
 
         let tool_version = map
@@ -395,9 +512,11 @@ impl IndexBackend for SqliteIndexBackend {
         Ok(())
     }
 
-    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<()> {
+    fn set_file_symbols(&mut self, file_id: u64, symbols: &[NewSymbolRecord]) -> Result<Vec<u64>> {
         let tx = self.conn.transaction()?;
+        let mut ids = Vec::with_capacity(symbols.len());
 
+        // Deleting the symbols cascades to the calls they made.
         tx.execute(
             "DELETE FROM symbols WHERE file_id = ?1",
             params![file_id as i64],
@@ -439,11 +558,12 @@ impl IndexBackend for SqliteIndexBackend {
                     symbol.signature,
                     extra_json,
                 ])?;
+                ids.push(tx.last_insert_rowid() as u64);
             }
         }
 
         tx.commit()?;
-        Ok(())
+        Ok(ids)
     }
 
     fn query_symbols(&self, query: &SymbolQuery) -> Result<Vec<SymbolRecord>> {
@@ -580,13 +700,345 @@ impl IndexBackend for SqliteIndexBackend {
 
         Ok(results)
     }
+
+    fn set_file_calls(&mut self, file_id: u64, calls: &[NewCallRecord]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM calls WHERE file_id = ?1",
+            params![file_id as i64],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO calls (caller_id, file_id, callee_name, callee_file, line, col, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for call in calls {
+                let callee_file = call
+                    .callee_file
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string());
+                stmt.execute(params![
+                    call.caller_id as i64,
+                    file_id as i64,
+                    call.callee_name,
+                    callee_file,
+                    call.line as i64,
+                    call.column.map(|c| c as i64),
+                    call.edge_kind.as_str(),
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query_calls(&self, file_ids: &[u64]) -> Result<Vec<CallRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT caller_id, file_id, callee_name, callee_file, callee_id, line, col, kind
+             FROM calls
+             WHERE (?1 IS NULL OR file_id = ?1)
+             ORDER BY file_id ASC, id ASC",
+        )?;
+
+        let filters: Vec<Option<i64>> = if file_ids.is_empty() {
+            vec![None]
+        } else {
+            file_ids.iter().map(|id| Some(*id as i64)).collect()
+        };
+
+        let mut calls = Vec::new();
+        for filter in filters {
+            let rows = stmt.query_map(params![filter], |row| {
+                let caller_id: i64 = row.get(0)?;
+                let file_id: i64 = row.get(1)?;
+                let callee_name: String = row.get(2)?;
+                let callee_file: Option<String> = row.get(3)?;
+                let callee_id: Option<i64> = row.get(4)?;
+                let line: i64 = row.get(5)?;
+                let column: Option<i64> = row.get(6)?;
+                let kind: Option<String> = row.get(7)?;
+                Ok((
+                    CallRecord {
+                        caller_id: caller_id as u64,
+                        file_id: file_id as u64,
+                        callee_name,
+                        callee_file: callee_file.map(PathBuf::from),
+                        callee_id: callee_id.map(|id| id as u64),
+                        line: line as u32,
+                        column: column.map(|c| c as u32),
+                        edge_kind: CallEdgeKind::Call,
+                    },
+                    kind,
+                ))
+            })?;
+
+            for row in rows {
+                let (mut call, kind) = row?;
+                if let Some(kind) = kind {
+                    call.edge_kind = Self::call_edge_kind_from_str(&kind)?;
+                }
+                calls.push(call);
+            }
+        }
+
+        Ok(calls)
+    }
+
+    fn relink_calls(&mut self, paths: &[PathBuf]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        {
+            // Calls into a file are found through `idx_calls_callee`,
+            // calls made from it through `idx_calls_file_id`.
+            let mut stmt = tx.prepare(
+                "UPDATE calls SET callee_id = (
+                    SELECT s.id
+                    FROM symbols s JOIN files f ON f.id = s.file_id
+                    WHERE f.path = calls.callee_file
+                      AND s.name = calls.callee_name
+                      AND s.kind != 'namespace'
+                    ORDER BY s.id ASC
+                    LIMIT 1
+                )
+                WHERE callee_file = ?1
+                   OR file_id IN (SELECT id FROM files WHERE path = ?1)",
+            )?;
+
+            for path in paths {
+                stmt.execute(params![path.to_string_lossy()])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn set_file_dependencies(&mut self, file_id: u64, deps: &[NewDependencyRecord]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM deps WHERE file_id = ?1",
+            params![file_id as i64],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO deps (file_id, module, kind, target, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+
+            for dep in deps {
+                let target = dep.target.as_ref().map(|p| p.to_string_lossy().to_string());
+                stmt.execute(params![
+                    file_id as i64,
+                    dep.module,
+                    dep.kind.as_str(),
+                    target,
+                    dep.line as i64,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query_dependencies(&self, file_ids: &[u64]) -> Result<Vec<DependencyRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file_id, module, kind, target, line
+             FROM deps
+             WHERE (?1 IS NULL OR file_id = ?1)
+             ORDER BY file_id ASC, id ASC",
+        )?;
+
+        let filters: Vec<Option<i64>> = if file_ids.is_empty() {
+            vec![None]
+        } else {
+            file_ids.iter().map(|id| Some(*id as i64)).collect()
+        };
+
+        let mut deps = Vec::new();
+        for filter in filters {
+            let rows = stmt.query_map(params![filter], |row| {
+                let file_id: i64 = row.get(0)?;
+                let module: String = row.get(1)?;
+                let kind: String = row.get(2)?;
+                let target: Option<String> = row.get(3)?;
+                let line: i64 = row.get(4)?;
+                Ok((file_id, module, kind, target, line))
+            })?;
+
+            for row in rows {
+                let (file_id, module, kind, target, line) = row?;
+                deps.push(DependencyRecord {
+                    file_id: file_id as u64,
+                    module,
+                    kind: Self::dependency_kind_from_str(&kind)?,
+                    target: target.map(PathBuf::from),
+                    line: line as u32,
+                });
+            }
+        }
+
+        Ok(deps)
+    }
+
+    fn set_file_relations(&mut self, file_id: u64, relations: &[NewRelationRecord]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM relations WHERE file_id = ?1",
+            params![file_id as i64],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO relations (file_id, subtype, supertype, kind, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+
+            for relation in relations {
+                stmt.execute(params![
+                    file_id as i64,
+                    relation.subtype,
+                    relation.supertype,
+                    relation.kind.as_str(),
+                    relation.line as i64,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query_relations(&self, file_ids: &[u64]) -> Result<Vec<RelationRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file_id, subtype, supertype, kind, line
+             FROM relations
+             WHERE (?1 IS NULL OR file_id = ?1)
+             ORDER BY file_id ASC, id ASC",
+        )?;
+
+        let filters: Vec<Option<i64>> = if file_ids.is_empty() {
+            vec![None]
+        } else {
+            file_ids.iter().map(|id| Some(*id as i64)).collect()
+        };
+
+        let mut relations = Vec::new();
+        for filter in filters {
+            let rows = stmt.query_map(params![filter], |row| {
+                let file_id: i64 = row.get(0)?;
+                let subtype: String = row.get(1)?;
+                let supertype: String = row.get(2)?;
+                let kind: String = row.get(3)?;
+                let line: i64 = row.get(4)?;
+                Ok((file_id, subtype, supertype, kind, line))
+            })?;
+
+            for row in rows {
+                let (file_id, subtype, supertype, kind, line) = row?;
+                relations.push(RelationRecord {
+                    file_id: file_id as u64,
+                    subtype,
+                    supertype,
+                    kind: Self::relation_kind_from_str(&kind)?,
+                    line: line as u32,
+                });
+            }
+        }
+
+        Ok(relations)
+    }
+
+    fn set_file_members(&mut self, file_id: u64, members: &[NewMemberRecord]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM members WHERE file_id = ?1",
+            params![file_id as i64],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO members (file_id, scope, name, arity, start_line, start_col,
+                                      end_line, end_col, definition)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+
+            for member in members {
+                stmt.execute(params![
+                    file_id as i64,
+                    member.scope,
+                    member.name,
+                    member.arity as i64,
+                    member.range.start_line as i64,
+                    member.range.start_column as i64,
+                    member.range.end_line as i64,
+                    member.range.end_column as i64,
+                    member.definition,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query_members(&self, file_ids: &[u64]) -> Result<Vec<MemberRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT file_id, scope, name, arity, start_line, start_col, end_line, end_col,
+                    definition
+             FROM members
+             WHERE (?1 IS NULL OR file_id = ?1)
+             ORDER BY file_id ASC, id ASC",
+        )?;
+
+        let filters: Vec<Option<i64>> = if file_ids.is_empty() {
+            vec![None]
+        } else {
+            file_ids.iter().map(|id| Some(*id as i64)).collect()
+        };
+
+        let mut members = Vec::new();
+        for filter in filters {
+            let rows = stmt.query_map(params![filter], |row| {
+                Ok(MemberRecord {
+                    file_id: row.get::<_, i64>(0)? as u64,
+                    scope: row.get(1)?,
+                    name: row.get(2)?,
+                    arity: row.get::<_, i64>(3)? as u32,
+                    range: TextRange {
+                        start_line: row.get::<_, i64>(4)? as u32,
+                        start_column: row.get::<_, i64>(5)? as u32,
+                        end_line: row.get::<_, i64>(6)? as u32,
+                        end_column: row.get::<_, i64>(7)? as u32,
+                    },
+                    definition: row.get(8)?,
+                })
+            })?;
+
+            for row in rows {
+                members.push(row?);
+            }
+        }
+
+        Ok(members)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::index::models::{NewSymbolRecord, SymbolQuery};
-    use crate::models::{SymbolKind, TextRange};
+    use crate::index::models::{
+        NewCallRecord, NewDependencyRecord, NewRelationRecord, NewSymbolRecord, SymbolQuery,
+    };
+    use crate::models::{CallEdgeKind, DependencyKind, SymbolKind, TextRange, TypeRelationKind};
     use tempfile::tempdir;
 
     #[test]
@@ -660,6 +1112,31 @@ mod tests {
         assert!(results_after.is_empty());
     }
 
+    #[test]
+    fn sqlite_backend_adds_call_kind_to_older_databases() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("index.sqlite");
+
+        {
+            let conn = Connection::open(&db_path).expect("connection");
+            conn.execute_batch(
+                "CREATE TABLE calls (
+                    id INTEGER PRIMARY KEY, caller_id INTEGER NOT NULL,
+                    file_id INTEGER NOT NULL, callee_name TEXT NOT NULL,
+                    callee_file TEXT, callee_id INTEGER, line INTEGER NOT NULL, col INTEGER
+                );
+                INSERT INTO calls (caller_id, file_id, callee_name, line)
+                    VALUES (1, 1, 'add', 2);",
+            )
+            .expect("schema 6 calls table");
+        }
+
+        let backend = SqliteIndexBackend::open(&db_path).expect("backend");
+        let calls = backend.query_calls(&[]).expect("query calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].edge_kind, CallEdgeKind::Call);
+    }
+
     #[test]
     fn sqlite_backend_initializes_and_reuses_meta() {
         let dir = tempdir().expect("tempdir");
@@ -668,7 +1145,7 @@ mod tests {
         {
             let mut backend = SqliteIndexBackend::open(&db_path).expect("backend");
             let mut meta = backend.load_meta().expect("load meta");
-            assert_eq!(meta.schema_version, "2");
+            assert_eq!(meta.schema(), INDEX_SCHEMA_VERSION);
             meta.tool_version = "0.0.0-test".to_string();
             backend.save_meta(&meta).expect("save meta");
         }
@@ -676,8 +1153,203 @@ mod tests {
         {
             let backend = SqliteIndexBackend::open(&db_path).expect("backend");
             let meta = backend.load_meta().expect("load meta");
-            assert_eq!(meta.schema_version, "2");
+            assert_eq!(meta.schema(), INDEX_SCHEMA_VERSION);
             assert_eq!(meta.tool_version, "0.0.0-test");
         }
     }
+
+    fn function_record(file_id: u64, name: &str, line: u32) -> NewSymbolRecord {
+        NewSymbolRecord {
+            file_id,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            language: "typescript".to_string(),
+            range: TextRange {
+                start_line: line,
+                start_column: 1,
+                end_line: line,
+                end_column: 20,
+            },
+            signature: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn sqlite_backend_persists_and_relinks_calls() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend =
+            SqliteIndexBackend::open(&dir.path().join("index.sqlite")).expect("backend");
+
+        let app = backend
+            .upsert_file(Path::new("src/app.ts"), "typescript", None, 1, 1)
+            .expect("app file");
+        let math = backend
+            .upsert_file(Path::new("src/math.ts"), "typescript", None, 1, 1)
+            .expect("math file");
+
+        let app_ids = backend
+            .set_file_symbols(app.id, &[function_record(app.id, "run", 1)])
+            .expect("app symbols");
+        let math_ids = backend
+            .set_file_symbols(math.id, &[function_record(math.id, "add", 1)])
+            .expect("math symbols");
+
+        backend
+            .set_file_calls(
+                app.id,
+                &[NewCallRecord {
+                    caller_id: app_ids[0],
+                    callee_name: "add".to_string(),
+                    callee_file: Some(PathBuf::from("src/math.ts")),
+                    line: 2,
+                    column: Some(5),
+                    edge_kind: CallEdgeKind::Render,
+                }],
+            )
+            .expect("set calls");
+        backend.relink_calls(&[PathBuf::from("src/app.ts")]).expect("relink");
+
+        let calls = backend.query_calls(&[app.id]).expect("query calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].caller_id, app_ids[0]);
+        assert_eq!(calls[0].callee_id, Some(math_ids[0]));
+        assert_eq!(calls[0].column, Some(5));
+        assert_eq!(calls[0].edge_kind, CallEdgeKind::Render);
+
+        // Rewriting the callee's file assigns new ids; relinking
+        // follows them.
+        let math_ids = backend
+            .set_file_symbols(math.id, &[function_record(math.id, "add", 3)])
+            .expect("math symbols");
+        backend.relink_calls(&[PathBuf::from("src/math.ts")]).expect("relink");
+        let calls = backend.query_calls(&[]).expect("query calls");
+        assert_eq!(calls[0].callee_id, Some(math_ids[0]));
+
+        // Removing the caller's file drops its calls.
+        backend
+            .remove_file_by_path(Path::new("src/app.ts"))
+            .expect("remove file");
+        assert!(backend.query_calls(&[]).expect("query calls").is_empty());
+    }
+
+    #[test]
+    fn sqlite_backend_persists_dependencies() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = SqliteIndexBackend::open(&dir.path().join("index.sqlite")).expect("backend");
+
+        let app = backend
+            .upsert_file(Path::new("src/app.ts"), "typescript", None, 1, 1)
+            .expect("app file");
+        let deps = [
+            NewDependencyRecord {
+                module: "./math".to_string(),
+                kind: DependencyKind::Import,
+                target: Some(PathBuf::from("src/math.ts")),
+                line: 1,
+            },
+            NewDependencyRecord {
+                module: "lodash".to_string(),
+                kind: DependencyKind::Require,
+                target: None,
+                line: 2,
+            },
+        ];
+        backend
+            .set_file_dependencies(app.id, &deps)
+            .expect("set dependencies");
+
+        let stored = backend.query_dependencies(&[app.id]).expect("query deps");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].target, Some(PathBuf::from("src/math.ts")));
+        assert_eq!(stored[1].kind, DependencyKind::Require);
+        assert_eq!(stored[1].target, None);
+
+        // Replacing and removing the file drop its previous records.
+        backend
+            .set_file_dependencies(app.id, &deps[..1])
+            .expect("set dependencies");
+        assert_eq!(backend.query_dependencies(&[]).expect("query deps").len(), 1);
+        backend
+            .remove_file_by_path(Path::new("src/app.ts"))
+            .expect("remove file");
+        assert!(backend.query_dependencies(&[]).expect("query deps").is_empty());
+    }
+
+    #[test]
+    fn sqlite_backend_persists_relations() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = SqliteIndexBackend::open(&dir.path().join("index.sqlite")).expect("backend");
+
+        let square = backend
+            .upsert_file(Path::new("src/square.ts"), "typescript", None, 1, 1)
+            .expect("square file");
+        let relations = [
+            NewRelationRecord {
+                subtype: "Square".to_string(),
+                supertype: "Base".to_string(),
+                kind: TypeRelationKind::Extends,
+                line: 3,
+            },
+            NewRelationRecord {
+                subtype: "Square".to_string(),
+                supertype: "Polygon".to_string(),
+                kind: TypeRelationKind::Implements,
+                line: 3,
+            },
+        ];
+        backend
+            .set_file_relations(square.id, &relations)
+            .expect("set relations");
+
+        let stored = backend.query_relations(&[square.id]).expect("query relations");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].supertype, "Base");
+        assert_eq!(stored[1].kind, TypeRelationKind::Implements);
+
+        // Replacing and removing the file drop its previous records.
+        backend
+            .set_file_relations(square.id, &relations[..1])
+            .expect("set relations");
+        assert_eq!(backend.query_relations(&[]).expect("query relations").len(), 1);
+        backend
+            .remove_file_by_path(Path::new("src/square.ts"))
+            .expect("remove file");
+        assert!(backend.query_relations(&[]).expect("query relations").is_empty());
+    }
+    #[test]
+    fn sqlite_backend_persists_members() {
+        let dir = tempdir().expect("tempdir");
+        let mut backend = SqliteIndexBackend::open(&dir.path().join("index.sqlite")).expect("backend");
+
+        let shape = backend
+            .upsert_file(Path::new("src/shape.cpp"), "cpp", None, 1, 1)
+            .expect("shape file");
+        let member = NewMemberRecord {
+            scope: "geo::Shape".to_string(),
+            name: "area".to_string(),
+            arity: 0,
+            range: TextRange {
+                start_line: 5,
+                start_column: 1,
+                end_line: 7,
+                end_column: 2,
+            },
+            definition: true,
+        };
+        backend
+            .set_file_members(shape.id, std::slice::from_ref(&member))
+            .expect("set members");
+
+        let stored = backend.query_members(&[shape.id]).expect("query members");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].scope, "geo::Shape");
+        assert_eq!(stored[0].range, member.range);
+        assert!(stored[0].definition);
+
+        backend
+            .remove_file_by_path(Path::new("src/shape.cpp"))
+            .expect("remove file");
+        assert!(backend.query_members(&[]).expect("query members").is_empty());
+    }
 }
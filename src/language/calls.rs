@@ -0,0 +1,395 @@
+//! Project-wide scanning and call resolution.
+//!
+//! Backends attach call edges per file: a call to `foo` links to every
+//! symbol named `foo` in the same file. This pass runs over all files
+//! of a search (or an indexing run) and links the remaining calls
+//! through the caller file's imports: when `app.ts` imports `add` from
+//! `./math`, its call to `add()` gains a `target_file` and `math.ts`'s
+//! `add` gains the matching `called_by` entry. Re-exports
+//! (`export { a } from`, `pub use`) are followed a few levels deep.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::language::{
+    backend_for_path, normalize_path, CompileDatabase, LanguageBackend, ParsedFile,
+};
+use crate::models::{CallRef, ImportRef, MemberKey, Symbol, SymbolKind, TypeRelation};
+
+/// How many re-export hops to follow when looking for a definition.
+pub(crate) const MAX_REEXPORT_DEPTH: usize = 4;
+
+/// Symbols and imports extracted from one file.
+#[derive(Debug)]
+pub(crate) struct FileSymbols {
+    pub(crate) path: PathBuf,
+    pub(crate) symbols: Vec<Symbol>,
+    pub(crate) imports: Vec<ImportRef>,
+    pub(crate) relations: Vec<TypeRelation>,
+    pub(crate) members: Vec<MemberKey>,
+}
+
+/// Paths of the files passed to `scan_parsed_file`, so tests can
+/// check which files a search parsed.
+#[cfg(test)]
+pub(crate) static SCANNED_PATHS: std::sync::Mutex<Vec<PathBuf>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Parse `path` and extract its symbols (with per-file call edges),
+/// imports, type relations and member keys. Imports are resolved with
+/// the compile database `build`, if any. Returns `None` for
+/// unreadable or unparsable files.
+pub(crate) fn scan_file(
+    path: &Path,
+    backend: &dyn LanguageBackend,
+    build: Option<&CompileDatabase>,
+) -> Option<FileSymbols> {
+    let source = fs::read_to_string(path).ok()?;
+    let parsed = backend.parse_file(path, &source).ok()?;
+    scan_parsed_file(&parsed, backend, build)
+}
+
+/// Like `scan_file`, for a file the caller has already parsed.
+pub(crate) fn scan_parsed_file(
+    parsed: &ParsedFile,
+    backend: &dyn LanguageBackend,
+    build: Option<&CompileDatabase>,
+) -> Option<FileSymbols> {
+    #[cfg(test)]
+    SCANNED_PATHS
+        .lock()
+        .expect("scanned paths mutex poisoned")
+        .push(parsed.path.clone());
+
+    let symbols = backend.index_symbols(parsed).ok()?;
+    let imports = backend.imports(parsed, build).unwrap_or_default();
+    let relations = backend.type_relations(parsed).unwrap_or_default();
+    let members = backend.member_keys(parsed).unwrap_or_default();
+    Some(FileSymbols {
+        path: parsed.path.clone(),
+        symbols,
+        imports,
+        relations,
+        members,
+    })
+}
+
+/// Append the files that `files` import (directly or through
+/// re-exports) but that are not part of it yet.
+///
+/// Used by incremental indexing, where only changed files are parsed
+/// but their calls must still resolve into unchanged ones.
+pub(crate) fn add_import_targets(files: &mut Vec<FileSymbols>, build: Option<&CompileDatabase>) {
+    let mut known: HashSet<PathBuf> = files.iter().map(|f| normalize_path(&f.path)).collect();
+    let mut frontier = 0;
+
+    for _ in 0..=MAX_REEXPORT_DEPTH {
+        let targets: Vec<PathBuf> = files[frontier..]
+            .iter()
+            .flat_map(|file| file.imports.iter())
+            .flat_map(|import| import.files.iter())
+            .filter(|path| known.insert(normalize_path(path)))
+            .cloned()
+            .collect();
+        frontier = files.len();
+
+        files.extend(targets.iter().filter_map(|path| {
+            let backend = backend_for_path(path)?;
+            scan_file(path, backend, build)
+        }));
+        if files.len() == frontier {
+            break;
+        }
+    }
+}
+
+/// A resolved cross-file call: caller `(file, symbol, call)` indices
+/// and the `(file, symbol)` of the definition it resolved to.
+struct Resolution {
+    caller: (usize, usize, usize),
+    target: (usize, usize),
+}
+
+/// Link calls to symbols defined in other files through imports.
+///
+/// Calls whose name is defined in the caller's own file are left to
+/// the backend's per-file edges.
+pub(crate) fn resolve_cross_file_calls(files: &mut [FileSymbols]) {
+    let by_path: HashMap<PathBuf, usize> = files
+        .iter()
+        .enumerate()
+        .map(|(idx, file)| (normalize_path(&file.path), idx))
+        .collect();
+
+    let mut resolutions = Vec::new();
+
+    for (file_idx, file) in files.iter().enumerate() {
+        if file.imports.is_empty() {
+            continue;
+        }
+        for (symbol_idx, symbol) in file.symbols.iter().enumerate() {
+            for (call_idx, call) in symbol.calls.iter().enumerate() {
+                if call.target_file.is_some()
+                    || file.symbols.iter().any(|local| local.name == call.name)
+                {
+                    continue;
+                }
+                if let Some(target) = resolve_imported_name(files, &by_path, file_idx, &call.name) {
+                    resolutions.push(Resolution {
+                        caller: (file_idx, symbol_idx, call_idx),
+                        target,
+                    });
+                }
+            }
+        }
+    }
+
+    for Resolution {
+        caller: (file_idx, symbol_idx, call_idx),
+        target: (target_file_idx, target_symbol_idx),
+    } in resolutions
+    {
+        let caller = &files[file_idx].symbols[symbol_idx];
+        let site = &caller.calls[call_idx];
+        let incoming = CallRef {
+            name: caller.name.clone(),
+            file: caller.file.clone(),
+            line: site.line,
+            column: site.column,
+            kind: Some(caller.kind),
+            target_file: None,
+            edge_kind: site.edge_kind,
+        };
+
+        let target = &mut files[target_file_idx].symbols[target_symbol_idx];
+        let (target_name, target_path, target_kind) =
+            (target.name.clone(), target.file.clone(), target.kind);
+        let already_linked = target.called_by.iter().any(|existing| {
+            existing.name == incoming.name
+                && existing.file == incoming.file
+                && existing.line == incoming.line
+        });
+        if !already_linked {
+            target.called_by.push(incoming);
+        }
+
+        let call = &mut files[file_idx].symbols[symbol_idx].calls[call_idx];
+        // Report the definition's name rather than a local alias.
+        call.name = target_name;
+        call.target_file = Some(target_path);
+        call.kind = Some(target_kind);
+    }
+}
+
+/// Find the definition `name` refers to in `file_idx` through its
+/// imports.
+fn resolve_imported_name(
+    files: &[FileSymbols],
+    by_path: &HashMap<PathBuf, usize>,
+    file_idx: usize,
+    name: &str,
+) -> Option<(usize, usize)> {
+    files[file_idx]
+        .imports
+        .iter()
+        .find_map(|import| resolve_through_import(files, by_path, import, name, 0))
+}
+
+/// Resolve a name visible locally as `local` through one import.
+fn resolve_through_import(
+    files: &[FileSymbols],
+    by_path: &HashMap<PathBuf, usize>,
+    import: &ImportRef,
+    local: &str,
+    depth: usize,
+) -> Option<(usize, usize)> {
+    let exported = match import.names.iter().find(|name| name.local_name() == local) {
+        Some(name) => name.name.as_str(),
+        None if import.wildcard => local,
+        None => return None,
+    };
+
+    import.files.iter().find_map(|path| {
+        let target_idx = *by_path.get(&normalize_path(path))?;
+        find_definition(files, by_path, target_idx, exported, depth)
+    })
+}
+
+/// Find `name` defined in file `file_idx`, following its re-exports.
+fn find_definition(
+    files: &[FileSymbols],
+    by_path: &HashMap<PathBuf, usize>,
+    file_idx: usize,
+    name: &str,
+    depth: usize,
+) -> Option<(usize, usize)> {
+    let file = &files[file_idx];
+    let local = file
+        .symbols
+        .iter()
+        .position(|symbol| symbol.name == name && symbol.kind != SymbolKind::Namespace);
+    if let Some(symbol_idx) = local {
+        return Some((file_idx, symbol_idx));
+    }
+
+    if depth >= MAX_REEXPORT_DEPTH {
+        return None;
+    }
+
+    file.imports
+        .iter()
+        .filter(|import| import.reexport)
+        .find_map(|import| resolve_through_import(files, by_path, import, name, depth + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CallEdgeKind, DependencyKind, ImportedName, TextRange};
+
+    fn symbol(name: &str, file: &str) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            language: "typescript".to_string(),
+            file: PathBuf::from(file),
+            range: TextRange {
+                start_line: 1,
+                start_column: 1,
+                end_line: 3,
+                end_column: 2,
+            },
+            signature: None,
+            attributes: None,
+            def_line_count: None,
+            matches: Vec::new(),
+            calls: Vec::new(),
+            called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
+        }
+    }
+
+    fn call(name: &str, file: &str, line: u32) -> CallRef {
+        CallRef {
+            name: name.to_string(),
+            file: PathBuf::from(file),
+            line: Some(line),
+            column: None,
+            kind: None,
+            target_file: None,
+            edge_kind: CallEdgeKind::Call,
+        }
+    }
+
+    fn import(module: &str, names: &[(&str, Option<&str>)], file: &str) -> ImportRef {
+        ImportRef {
+            module: module.to_string(),
+            kind: DependencyKind::Import,
+            names: names
+                .iter()
+                .map(|(name, alias)| ImportedName {
+                    name: name.to_string(),
+                    alias: alias.map(str::to_string),
+                })
+                .collect(),
+            wildcard: false,
+            reexport: false,
+            test_only: false,
+            files: vec![PathBuf::from(file)],
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn links_aliased_import_to_definition() {
+        let mut caller = symbol("run", "src/app.ts");
+        caller.calls.push(call("resize", "src/app.ts", 2));
+
+        let mut files = vec![
+            FileSymbols {
+                path: PathBuf::from("src/app.ts"),
+                symbols: vec![caller],
+                imports: vec![import(
+                    "./math",
+                    &[("scale", Some("resize"))],
+                    "src/./math.ts",
+                )],
+                relations: Vec::new(),
+                members: Vec::new(),
+            },
+            FileSymbols {
+                path: PathBuf::from("src/math.ts"),
+                symbols: vec![symbol("scale", "src/math.ts")],
+                imports: Vec::new(),
+                relations: Vec::new(),
+                members: Vec::new(),
+            },
+        ];
+
+        resolve_cross_file_calls(&mut files);
+        // Running the pass twice must not duplicate edges.
+        resolve_cross_file_calls(&mut files);
+
+        let call = &files[0].symbols[0].calls[0];
+        assert_eq!(call.name, "scale");
+        assert_eq!(call.target_file, Some(PathBuf::from("src/math.ts")));
+        assert_eq!(call.kind, Some(SymbolKind::Function));
+
+        let called_by = &files[1].symbols[0].called_by;
+        assert_eq!(called_by.len(), 1);
+        assert_eq!(called_by[0].name, "run");
+        assert_eq!(called_by[0].file, PathBuf::from("src/app.ts"));
+        assert_eq!(called_by[0].line, Some(2));
+    }
+
+    #[test]
+    fn follows_reexports_and_prefers_local_definitions() {
+        let mut caller = symbol("run", "app.ts");
+        caller.calls.push(call("format", "app.ts", 2));
+        caller.calls.push(call("helper", "app.ts", 3));
+
+        let mut index_reexport = import("./format", &[("format", None)], "lib/format.ts");
+        index_reexport.reexport = true;
+
+        let mut files = vec![
+            FileSymbols {
+                path: PathBuf::from("app.ts"),
+                symbols: vec![caller, symbol("helper", "app.ts")],
+                imports: vec![
+                    import("./lib", &[("format", None)], "lib/index.ts"),
+                    import("./other", &[("helper", None)], "lib/format.ts"),
+                ],
+                relations: Vec::new(),
+                members: Vec::new(),
+            },
+            FileSymbols {
+                path: PathBuf::from("lib/index.ts"),
+                symbols: Vec::new(),
+                imports: vec![index_reexport],
+                relations: Vec::new(),
+                members: Vec::new(),
+            },
+            FileSymbols {
+                path: PathBuf::from("lib/format.ts"),
+                symbols: vec![
+                    symbol("format", "lib/format.ts"),
+                    symbol("helper", "lib/format.ts"),
+                ],
+                imports: Vec::new(),
+                relations: Vec::new(),
+                members: Vec::new(),
+            },
+        ];
+
+        resolve_cross_file_calls(&mut files);
+
+        let calls = &files[0].symbols[0].calls;
+        assert_eq!(calls[0].target_file, Some(PathBuf::from("lib/format.ts")));
+        assert_eq!(calls[1].target_file, None, "local definition wins");
+        assert_eq!(files[2].symbols[0].called_by.len(), 1);
+        assert!(files[2].symbols[1].called_by.is_empty());
+    }
+}
@@ -0,0 +1,539 @@
+//! Cargo packages and module paths for the Rust backend.
+//!
+//! rustc learns a file's module path from the `mod` declarations that
+//! lead to it from a crate root, and Cargo decides which files are
+//! crate roots. This module mirrors both: it reads the `Cargo.toml` of
+//! the package enclosing a file, finds the package's targets (library,
+//! binaries, integration tests, examples and benches) and follows
+//! out-of-line `mod name;` declarations from each root, mapping files
+//! to module paths such as `symgrep::index::sqlite`.
+//!
+//! Files that no target reaches through `mod` declarations are not
+//! compiled and get no module path. Other crates of the workspace
+//! (`[workspace] members`) and path dependencies are known by their
+//! library name, so `use other_crate::module` resolves into them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use toml::Table;
+use tree_sitter::Node;
+
+use crate::language::project::{cached, expand_member_pattern, Cache, Stamp};
+use crate::language::{backend_for_language, normalize_path, ParsedFile};
+use crate::models::{DependencyKind, ImportRef};
+
+/// Crate and module path of a Rust file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RustModulePath {
+    /// Crate name as used in paths (`-` replaced by `_`).
+    pub(crate) crate_name: String,
+    /// Modules from the crate root to the file; empty for the root.
+    pub(crate) modules: Vec<String>,
+}
+
+impl RustModulePath {
+    /// The path written out, e.g. `symgrep::index::sqlite`.
+    pub(crate) fn to_path_string(&self) -> String {
+        std::iter::once(self.crate_name.as_str())
+            .chain(self.modules.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+}
+
+/// Crate and module path of `file`, or `None` when it is not part of a
+/// Cargo package target.
+pub(crate) fn rust_module_path(file: &Path) -> Option<RustModulePath> {
+    let file = normalize_path(file);
+    let package = enclosing_package(&file)?;
+    package.as_ref().as_ref()?.modules.get(&file).cloned()
+}
+
+/// Root file of the library crate `name` as seen from `importer`: the
+/// library of its own package, of another workspace member or of a
+/// path dependency.
+pub(crate) fn rust_crate_root(importer: &Path, name: &str) -> Option<PathBuf> {
+    let package = enclosing_package(&normalize_path(importer))?;
+    let package = package.as_ref().as_ref()?;
+    if let Some(root) = package.library_root(name) {
+        return Some(root.to_path_buf());
+    }
+
+    for (alias, dir) in &package.dependencies {
+        let dependency = load_package(&dir.join("Cargo.toml"));
+        let Some(library) = dependency.as_ref().as_ref().and_then(CargoPackage::library) else {
+            continue;
+        };
+        if alias == name || library.name == name {
+            return Some(library.root.clone());
+        }
+    }
+
+    let workspace = package.workspace.as_ref()?;
+    let members = cached(&WORKSPACES, workspace, |stamps| {
+        load_workspace_members(workspace, stamps)
+    });
+    members.iter().find_map(|dir| {
+        let member = load_package(&dir.join("Cargo.toml"));
+        let root = member.as_ref().as_ref()?.library_root(name)?;
+        Some(root.to_path_buf())
+    })
+}
+
+/// A crate root of a package.
+#[derive(Debug)]
+struct Target {
+    /// Crate name as used in paths.
+    name: String,
+    root: PathBuf,
+    is_library: bool,
+}
+
+/// A `[package]` manifest with its targets and module tree.
+#[derive(Debug, Default)]
+struct CargoPackage {
+    targets: Vec<Target>,
+    /// Module path of every file reached from a target root, keyed by
+    /// normalized path. A file reached from several targets keeps the
+    /// first (the library comes first).
+    modules: HashMap<PathBuf, RustModulePath>,
+    /// Directories of path dependencies, keyed by the name they are
+    /// imported under.
+    dependencies: Vec<(String, PathBuf)>,
+    /// Manifest of the enclosing `[workspace]`, if any.
+    workspace: Option<PathBuf>,
+}
+
+impl CargoPackage {
+    fn library(&self) -> Option<&Target> {
+        self.targets.iter().find(|target| target.is_library)
+    }
+
+    fn library_root(&self, name: &str) -> Option<&Path> {
+        self.library()
+            .filter(|library| library.name == name)
+            .map(|library| library.root.as_path())
+    }
+}
+
+/// Packages keyed by manifest path; `None` for manifests without a
+/// `[package]` table.
+static PACKAGES: Cache<Option<CargoPackage>> = Cache::new();
+/// Member directories keyed by workspace manifest path.
+static WORKSPACES: Cache<Vec<PathBuf>> = Cache::new();
+/// Closest `Cargo.toml` at or above a directory, keyed by directory.
+static MANIFESTS: Cache<Option<PathBuf>> = Cache::new();
+
+/// The package whose manifest is closest above `file`. The cached
+/// value is `None` when that manifest has no `[package]` table.
+fn enclosing_package(file: &Path) -> Option<Arc<Option<CargoPackage>>> {
+    let dir = file.parent()?;
+    let manifest = cached(&MANIFESTS, dir, |stamps| nearest_manifest(dir, stamps));
+    Some(load_package(manifest.as_ref().as_ref()?))
+}
+
+/// `Cargo.toml` in `dir` or its closest ancestor that has one. The
+/// directories searched are recorded in `stamps`, since adding or
+/// removing a manifest changes their modification time.
+fn nearest_manifest(dir: &Path, stamps: &mut Vec<Stamp>) -> Option<PathBuf> {
+    dir.ancestors().find_map(|ancestor| {
+        stamps.push(Stamp::of(ancestor));
+        let manifest = ancestor.join("Cargo.toml");
+        manifest.is_file().then_some(manifest)
+    })
+}
+
+fn load_package(manifest: &Path) -> Arc<Option<CargoPackage>> {
+    cached(&PACKAGES, manifest, |stamps| read_package(manifest, stamps))
+}
+
+fn read_toml(path: &Path) -> Option<Table> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Crate name for a package or target name.
+fn crate_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+fn read_package(manifest: &Path, stamps: &mut Vec<Stamp>) -> Option<CargoPackage> {
+    stamps.push(Stamp::of(manifest));
+    let value = read_toml(manifest)?;
+    let package_name = value.get("package")?.get("name")?.as_str()?.to_string();
+    let dir = manifest.parent().unwrap_or(Path::new(""));
+
+    let mut package = CargoPackage {
+        targets: package_targets(&value, &package_name, dir, stamps),
+        workspace: enclosing_workspace(dir),
+        ..CargoPackage::default()
+    };
+
+    for table in ["dependencies", "dev-dependencies"] {
+        let Some(dependencies) = value.get(table).and_then(|table| table.as_table()) else {
+            continue;
+        };
+        for (alias, spec) in dependencies {
+            if let Some(path) = spec.get("path").and_then(|path| path.as_str()) {
+                package
+                    .dependencies
+                    .push((crate_name(alias), normalize_path(&dir.join(path))));
+            }
+        }
+    }
+
+    for target in &package.targets {
+        walk_modules(target, &mut package.modules, stamps);
+    }
+    Some(package)
+}
+
+/// Targets declared in the manifest or discovered in Cargo's default
+/// locations, library first.
+fn package_targets(
+    value: &Table,
+    package_name: &str,
+    dir: &Path,
+    stamps: &mut Vec<Stamp>,
+) -> Vec<Target> {
+    let mut targets: Vec<Target> = Vec::new();
+    let mut push = |name: &str, root: PathBuf, is_library: bool| {
+        let root = normalize_path(&root);
+        if root.is_file() && !targets.iter().any(|target| target.root == root) {
+            targets.push(Target {
+                name: crate_name(name),
+                root,
+                is_library,
+            });
+        }
+    };
+
+    let lib = value.get("lib");
+    let lib_name = lib
+        .and_then(|lib| lib.get("name"))
+        .and_then(|name| name.as_str())
+        .unwrap_or(package_name);
+    let lib_path = lib
+        .and_then(|lib| lib.get("path"))
+        .and_then(|path| path.as_str())
+        .unwrap_or("src/lib.rs");
+    push(lib_name, dir.join(lib_path), true);
+
+    // Explicit `[[bin]]`, `[[test]]`, ... tables, then auto-discovered
+    // targets in their default directories.
+    for (kind, default_dir) in [
+        ("bin", "src/bin"),
+        ("test", "tests"),
+        ("example", "examples"),
+        ("bench", "benches"),
+    ] {
+        let declared = value
+            .get(kind)
+            .and_then(|targets| targets.as_array())
+            .into_iter()
+            .flatten();
+        for target in declared {
+            let Some(name) = target.get("name").and_then(|name| name.as_str()) else {
+                continue;
+            };
+            let path = match target.get("path").and_then(|path| path.as_str()) {
+                Some(path) => dir.join(path),
+                None if kind == "bin" && name == package_name => dir.join("src/main.rs"),
+                None => {
+                    let single = dir.join(default_dir).join(format!("{name}.rs"));
+                    if single.is_file() {
+                        single
+                    } else {
+                        dir.join(default_dir).join(name).join("main.rs")
+                    }
+                }
+            };
+            push(name, path, false);
+        }
+
+        if kind == "bin" {
+            push(package_name, dir.join("src/main.rs"), false);
+        }
+        let auto_dir = dir.join(default_dir);
+        stamps.push(Stamp::of(&auto_dir));
+        let Ok(entries) = fs::read_dir(&auto_dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let (name, root) = if path.is_dir() {
+                (path.file_name(), path.join("main.rs"))
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                (path.file_stem(), path.clone())
+            } else {
+                continue;
+            };
+            if let Some(name) = name.and_then(|name| name.to_str()) {
+                push(name, root, false);
+            }
+        }
+    }
+
+    targets
+}
+
+/// Follow `mod name;` declarations from `target`'s root, recording the
+/// module path of every file reached.
+fn walk_modules(
+    target: &Target,
+    modules: &mut HashMap<PathBuf, RustModulePath>,
+    stamps: &mut Vec<Stamp>,
+) {
+    let Some(backend) = backend_for_language("rust") else {
+        return;
+    };
+    let mut stack = vec![(target.root.clone(), Vec::new())];
+
+    while let Some((file, path)) = stack.pop() {
+        if modules.contains_key(&file) {
+            continue;
+        }
+        stamps.push(Stamp::of(&file));
+        modules.insert(
+            file.clone(),
+            RustModulePath {
+                crate_name: target.name.clone(),
+                modules: path.clone(),
+            },
+        );
+
+        let Some(parsed) = fs::read_to_string(&file)
+            .ok()
+            .and_then(|source| backend.parse_file(&file, &source).ok())
+        else {
+            continue;
+        };
+        // Child modules live next to a crate root or `mod.rs`, and in a
+        // directory named after any other module file.
+        let module_dir = if file == target.root {
+            file.parent().unwrap_or(Path::new("")).to_path_buf()
+        } else {
+            child_module_dir(&file)
+        };
+        for declaration in rust_mod_declarations(&parsed) {
+            let Some(child) = declaration.files.first() else {
+                continue;
+            };
+            let mut child_path = path.clone();
+            child_path.extend(module_segments(child, &module_dir, &declaration.module));
+            stack.push((normalize_path(child), child_path));
+        }
+    }
+}
+
+fn child_module_dir(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    match file.file_name().and_then(|name| name.to_str()) {
+        Some("mod.rs") => parent.to_path_buf(),
+        _ => match file.file_stem() {
+            Some(stem) => parent.join(stem),
+            None => parent.to_path_buf(),
+        },
+    }
+}
+
+/// Module names leading from `module_dir` to `child`: usually just
+/// the declared name, plus the names of inline modules enclosing the
+/// declaration (`mod a { mod b; }` declares `a::b` in `a/b.rs`).
+fn module_segments(child: &Path, module_dir: &Path, name: &str) -> Vec<String> {
+    let child = normalize_path(child);
+    let Ok(relative) = child.strip_prefix(normalize_path(module_dir)) else {
+        return vec![name.to_string()];
+    };
+    let mut segments: Vec<String> = relative
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .map(str::to_string)
+        .collect();
+    match segments.last().map(String::as_str) {
+        Some("mod.rs") => {
+            segments.pop();
+        }
+        Some(last) => {
+            let stem = last.strip_suffix(".rs").unwrap_or(last).to_string();
+            *segments.last_mut().expect("non-empty") = stem;
+        }
+        None => return vec![name.to_string()],
+    }
+    segments
+}
+
+fn node_text(file: &ParsedFile, node: Node) -> String {
+    node.utf8_text(file.source().as_bytes())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Collect only the out-of-line `mod name;` declarations of a Rust
+/// file, which define the crate's module tree.
+pub(crate) fn rust_mod_declarations(file: &ParsedFile) -> Vec<ImportRef> {
+    let mut declarations = Vec::new();
+    let mut stack = vec![file.tree.root_node()];
+
+    while let Some(node) = stack.pop() {
+        if node.kind() == "mod_item" && node.child_by_field_name("body").is_none() {
+            declarations.extend(rust_mod_import(file, node));
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+
+    declarations.sort_by_key(|declaration| declaration.line);
+    declarations
+}
+
+/// Dependency on the file of an out-of-line `mod name;` declaration.
+///
+/// Declarations nested in inline modules (`mod a { mod b; }`) resolve
+/// below the inline modules' directory, as rustc does.
+pub(crate) fn rust_mod_import(file: &ParsedFile, node: Node) -> Option<ImportRef> {
+    let name = node_text(file, node.child_by_field_name("name")?);
+
+    let mut dir = rust_module_dir(&normalize_path(&file.path));
+    let mut inline = Vec::new();
+    let mut parent = node.parent();
+    while let Some(current) = parent {
+        if current.kind() == "mod_item" {
+            if let Some(inline_name) = current.child_by_field_name("name") {
+                inline.push(node_text(file, inline_name));
+            }
+        }
+        parent = current.parent();
+    }
+    for segment in inline.iter().rev() {
+        dir.push(segment);
+    }
+
+    Some(ImportRef {
+        files: rust_file_for_module_dir(&dir.join(&name), None)
+            .into_iter()
+            .collect(),
+        module: name,
+        kind: DependencyKind::Mod,
+        names: Vec::new(),
+        wildcard: false,
+        reexport: false,
+        test_only: false,
+        line: node.start_position().row as u32 + 1,
+    })
+}
+
+/// Directory in which the child modules of `file` live.
+pub(crate) fn rust_module_dir(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    match file.file_name().and_then(|n| n.to_str()) {
+        Some("mod.rs" | "lib.rs" | "main.rs") => parent.to_path_buf(),
+        _ => match file.file_stem() {
+            Some(stem) => parent.join(stem),
+            None => parent.to_path_buf(),
+        },
+    }
+}
+
+/// File defining the module whose children live in `dir`.
+pub(crate) fn rust_file_for_module_dir(dir: &Path, crate_root: Option<&Path>) -> Option<PathBuf> {
+    if crate_root == Some(dir) {
+        return ["lib.rs", "main.rs"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file());
+    }
+    let as_file = dir.with_extension("rs");
+    if as_file.is_file() {
+        return Some(as_file);
+    }
+    let as_mod = dir.join("mod.rs");
+    as_mod.is_file().then_some(as_mod)
+}
+
+/// Manifest of the `[workspace]` enclosing a package directory.
+fn enclosing_workspace(package_dir: &Path) -> Option<PathBuf> {
+    package_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| {
+            manifest.is_file()
+                && read_toml(manifest).is_some_and(|value| value.contains_key("workspace"))
+        })
+}
+
+/// Member directories of the workspace declared in `manifest`.
+fn load_workspace_members(manifest: &Path, stamps: &mut Vec<Stamp>) -> Vec<PathBuf> {
+    stamps.push(Stamp::of(manifest));
+    let Some(value) = read_toml(manifest) else {
+        return Vec::new();
+    };
+    let root = manifest.parent().unwrap_or(Path::new(""));
+    let patterns = |key: &str| -> Vec<String> {
+        value
+            .get("workspace")
+            .and_then(|workspace| workspace.get(key))
+            .and_then(|patterns| patterns.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|pattern| pattern.as_str().map(str::to_string))
+            .collect()
+    };
+
+    let excluded: Vec<PathBuf> = patterns("exclude")
+        .iter()
+        .map(|pattern| normalize_path(&root.join(pattern)))
+        .collect();
+    let mut members = Vec::new();
+    for pattern in patterns("members") {
+        for dir in expand_member_pattern(root, &pattern, stamps) {
+            if !excluded.contains(&dir) && !members.contains(&dir) {
+                members.push(dir);
+            }
+        }
+    }
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_segments_follow_file_layout() {
+        let dir = Path::new("src/index");
+        assert_eq!(
+            module_segments(Path::new("src/index/sqlite.rs"), dir, "sqlite"),
+            vec!["sqlite"]
+        );
+        assert_eq!(
+            module_segments(Path::new("src/index/models/mod.rs"), dir, "models"),
+            vec!["models"]
+        );
+        assert_eq!(
+            module_segments(Path::new("src/index/inline/child.rs"), dir, "child"),
+            vec!["inline", "child"]
+        );
+    }
+
+    #[test]
+    fn this_crate_maps_files_to_module_paths() {
+        let path = rust_module_path(Path::new("src/index/sqlite.rs")).expect("module path");
+        assert_eq!(path.to_path_string(), "symgrep::index::sqlite");
+
+        let root = rust_module_path(Path::new("src/lib.rs")).expect("library root");
+        assert_eq!(root.to_path_string(), "symgrep");
+
+        // Fixtures are not declared as modules of any target.
+        assert_eq!(
+            rust_module_path(Path::new("tests/fixtures/rust_repo/lib.rs")),
+            None
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Compilation databases (`compile_commands.json`) for the C++ backend.
+//!
+//! Build systems such as CMake (`CMAKE_EXPORT_COMPILE_COMMANDS`) and
+//! Bear record the compiler invocation of every translation unit. The
+//! database gives the C++ backend what plain lexical resolution
+//! cannot know:
+//!
+//! - include search paths (`-I`, `-iquote`, `-isystem`, `-idirafter`),
+//!   used to resolve `#include` directives that are not relative to
+//!   the including file;
+//! - macros defined (`-D`) or undefined (`-U`) on the command line,
+//!   used to skip `#include`s in inactive `#ifdef` / `#ifndef`
+//!   branches;
+//! - which source files belong to the build at all.
+//!
+//! Headers have no entry of their own. Like clangd, they borrow the
+//! flags of the translation unit whose path shares the longest prefix
+//! with theirs (usually the matching `.cpp` next to or near them).
+//! The language standard (`-std=`) is not needed: one grammar parses
+//! every standard.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::language::{normalize_path, BackendError, BackendResult};
+
+/// Extensions of C/C++ translation units; files with any other
+/// extension handled by the C++ backend are treated as headers.
+const SOURCE_EXTENSIONS: &[&str] = &["c", "cc", "cpp", "cxx", "c++"];
+
+/// One entry of `compile_commands.json`. Either `arguments` or
+/// `command` holds the compiler invocation.
+#[derive(Debug, Deserialize)]
+struct RawEntry {
+    directory: PathBuf,
+    file: PathBuf,
+    #[serde(default)]
+    arguments: Vec<String>,
+    #[serde(default)]
+    command: Option<String>,
+}
+
+/// Flags of one translation unit that affect include resolution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileFlags {
+    /// Directories searched for quoted includes only (`-iquote`).
+    pub quote_dirs: Vec<PathBuf>,
+    /// Directories searched for quoted and angle-bracket includes, in
+    /// search order (`-I`, then `-isystem`, then `-idirafter`).
+    pub include_dirs: Vec<PathBuf>,
+    /// Macros set on the command line: `true` for `-D`, `false` for
+    /// `-U` (the last occurrence wins).
+    pub defines: HashMap<String, bool>,
+}
+
+impl CompileFlags {
+    /// Parse the include and macro flags out of a compiler argument
+    /// list. Relative directories are resolved against `directory`.
+    fn from_arguments(arguments: &[String], directory: &Path) -> Self {
+        let mut flags = Self::default();
+        let mut system_dirs = Vec::new();
+        let mut after_dirs = Vec::new();
+        let resolve = |dir: &str| normalize_path(&directory.join(dir));
+
+        let mut args = arguments.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| -> Option<String> {
+                let rest = arg.strip_prefix(flag)?;
+                if rest.is_empty() {
+                    args.next().cloned()
+                } else {
+                    Some(rest.to_string())
+                }
+            };
+
+            if let Some(dir) = value("-iquote") {
+                flags.quote_dirs.push(resolve(&dir));
+            } else if let Some(dir) = value("-isystem") {
+                system_dirs.push(resolve(&dir));
+            } else if let Some(dir) = value("-idirafter") {
+                after_dirs.push(resolve(&dir));
+            } else if let Some(dir) = value("-I") {
+                flags.include_dirs.push(resolve(&dir));
+            } else if let Some(define) = value("-D") {
+                let name = define.split('=').next().unwrap_or_default();
+                flags.defines.insert(name.to_string(), true);
+            } else if let Some(name) = value("-U") {
+                flags.defines.insert(name, false);
+            }
+        }
+
+        flags.include_dirs.extend(system_dirs);
+        flags.include_dirs.extend(after_dirs);
+        flags
+    }
+
+    /// Whether `name` is defined on the command line: `Some(true)` for
+    /// `-D`, `Some(false)` for `-U`, `None` when the flags say nothing
+    /// (the macro may still come from a header).
+    pub fn is_defined(&self, name: &str) -> Option<bool> {
+        self.defines.get(name).copied()
+    }
+}
+
+/// A loaded `compile_commands.json`.
+#[derive(Debug, Clone, Default)]
+pub struct CompileDatabase {
+    /// Canonical source path and flags of every entry, in file order.
+    entries: Vec<(PathBuf, CompileFlags)>,
+    /// Index into `entries` by canonical source path. A file compiled
+    /// several times keeps its first entry.
+    by_file: HashMap<PathBuf, usize>,
+    /// Canonical working directory, used to report resolved includes
+    /// relative to it like the walked files they are compared with.
+    cwd: PathBuf,
+}
+
+impl CompileDatabase {
+    /// Load and parse the database at `path`.
+    pub fn load(path: &Path) -> BackendResult<Self> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            BackendError::new(format!(
+                "failed to read compile database {}: {err}",
+                path.display()
+            ))
+        })?;
+        // Relative `directory` values are taken relative to the
+        // database itself.
+        let base = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&contents, base).map_err(|err| {
+            BackendError::new(format!(
+                "failed to parse compile database {}: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    fn parse(contents: &str, base: &Path) -> serde_json::Result<Self> {
+        let raw: Vec<RawEntry> = serde_json::from_str(contents)?;
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let mut database = Self {
+            cwd: canonical(&cwd),
+            ..Self::default()
+        };
+
+        for entry in raw {
+            let directory = canonical(&base.join(&entry.directory));
+            let arguments = if entry.arguments.is_empty() {
+                split_command(entry.command.as_deref().unwrap_or_default())
+            } else {
+                entry.arguments
+            };
+            let file = canonical(&directory.join(&entry.file));
+            let flags = CompileFlags::from_arguments(&arguments, &directory);
+
+            let idx = database.entries.len();
+            database.by_file.entry(file.clone()).or_insert(idx);
+            database.entries.push((file, flags));
+        }
+
+        Ok(database)
+    }
+
+    /// Whether `path` is a translation unit of the build.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.by_file.contains_key(&canonical(path))
+    }
+
+    /// Whether `path` is a C/C++ source file that the build does not
+    /// compile. Headers are never outside the build: they are only
+    /// reached through the translation units including them.
+    pub fn is_outside_build(&self, path: &Path) -> bool {
+        let is_source = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                SOURCE_EXTENSIONS
+                    .iter()
+                    .any(|source| source.eq_ignore_ascii_case(ext))
+            });
+        is_source && !self.contains(path)
+    }
+
+    /// Flags used for `path`: its own entry, or for files without one
+    /// (headers) the entry whose path shares the most leading
+    /// components with it. `None` only for an empty database.
+    pub fn flags_for(&self, path: &Path) -> Option<&CompileFlags> {
+        let path = canonical(path);
+        if let Some(&idx) = self.by_file.get(&path) {
+            return Some(&self.entries[idx].1);
+        }
+
+        let mut best: Option<(usize, &CompileFlags)> = None;
+        for (file, flags) in &self.entries {
+            let shared = path
+                .components()
+                .zip(file.components())
+                .take_while(|(a, b)| a == b)
+                .count();
+            if best.is_none_or(|(best_shared, _)| shared > best_shared) {
+                best = Some((shared, flags));
+            }
+        }
+        best.map(|(_, flags)| flags)
+    }
+
+    /// Resolve an `#include` of `module` from `importer` through the
+    /// include search paths of `flags`. Quoted includes also search
+    /// the `-iquote` directories first.
+    ///
+    /// The result is relative to the working directory when `importer`
+    /// is, so it compares equal to the path of the walked file.
+    pub fn resolve_include(
+        &self,
+        importer: &Path,
+        flags: &CompileFlags,
+        module: &str,
+        quoted: bool,
+    ) -> Option<PathBuf> {
+        let quote_dirs = if quoted { &flags.quote_dirs[..] } else { &[] };
+        let found = quote_dirs
+            .iter()
+            .chain(&flags.include_dirs)
+            .map(|dir| normalize_path(&dir.join(module)))
+            .find(|candidate| candidate.is_file())?;
+
+        if importer.is_relative() {
+            if let Ok(relative) = found.strip_prefix(&self.cwd) {
+                return Some(relative.to_path_buf());
+            }
+        }
+        Some(found)
+    }
+}
+
+/// Canonical form of `path`, or its lexical normalization when it
+/// does not exist.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| normalize_path(path))
+}
+
+/// Split a shell command line into arguments, honouring single and
+/// double quotes and backslash escapes.
+fn split_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_honours_quotes_and_escapes() {
+        assert_eq!(
+            split_command(r#"c++ -I"include dir" -DNAME='a b' -Dx\ y  main.cpp"#),
+            vec!["c++", "-Iinclude dir", "-DNAME=a b", "-Dx y", "main.cpp"]
+        );
+    }
+
+    #[test]
+    fn flags_collect_include_dirs_in_search_order_and_macros() {
+        let arguments: Vec<String> = [
+            "c++", "-isystem", "/sys", "-Iinc", "-iquote", "quoted", "-I", "/abs", "-DFOO=1", "-D",
+            "BAR", "-UBAR", "-c", "main.cpp",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        let flags = CompileFlags::from_arguments(&arguments, Path::new("/build"));
+
+        assert_eq!(flags.quote_dirs, vec![PathBuf::from("/build/quoted")]);
+        assert_eq!(
+            flags.include_dirs,
+            vec![
+                PathBuf::from("/build/inc"),
+                PathBuf::from("/abs"),
+                PathBuf::from("/sys")
+            ]
+        );
+        assert_eq!(flags.is_defined("FOO"), Some(true));
+        assert_eq!(flags.is_defined("BAR"), Some(false));
+        assert_eq!(flags.is_defined("BAZ"), None);
+    }
+
+    #[test]
+    fn headers_borrow_flags_of_the_closest_translation_unit() {
+        let database = CompileDatabase::parse(
+            r#"[
+                {"directory": "/repo/build", "file": "../app/main.cpp", "command": "c++ -I../app/include -c ../app/main.cpp"},
+                {"directory": "/repo/build", "file": "/repo/lib/lib.cpp", "arguments": ["c++", "-I/repo/lib/include", "-c", "/repo/lib/lib.cpp"]}
+            ]"#,
+            Path::new(""),
+        )
+        .expect("valid database");
+
+        assert!(database.contains(Path::new("/repo/app/main.cpp")));
+        assert!(database.is_outside_build(Path::new("/repo/app/other.cpp")));
+        assert!(!database.is_outside_build(Path::new("/repo/app/other.h")));
+
+        let header = database
+            .flags_for(Path::new("/repo/lib/include/lib.h"))
+            .expect("flags");
+        assert_eq!(
+            header.include_dirs,
+            vec![PathBuf::from("/repo/lib/include")]
+        );
+        let source = database
+            .flags_for(Path::new("/repo/app/main.cpp"))
+            .expect("flags");
+        assert_eq!(
+            source.include_dirs,
+            vec![PathBuf::from("/repo/app/include")]
+        );
+    }
+}
@@ -5,10 +5,12 @@ use tree_sitter_cpp::LANGUAGE;
 
 use crate::language::{
     collect_leading_comment, context_snippet_for_range, file_context_node, find_symbol_node,
-    node_text_range, BackendError, BackendResult, LanguageBackend, ParsedFile,
+    is_test_symbol, node_text_range, source_symbol_attributes, symbol_annotations, BackendError,
+    BackendResult, CompileDatabase, LanguageBackend, ParsedFile,
 };
 use crate::models::{
-    CallRef, ContextInfo, ContextKind, Symbol, SymbolAttributes, SymbolKind,
+    CallEdgeKind, CallRef, ContextInfo, ContextKind, ImportRef, MemberKey, Symbol, SymbolKind,
+    TypeRelation,
 };
 
 /// Tree-sitter backed language implementation for C++.
@@ -17,7 +19,43 @@ pub struct CppBackend;
 /// Singleton instance used by the language registry.
 pub static BACKEND: CppBackend = CppBackend;
 
+/// GoogleTest macros defining a test body: `TEST(Suite, Name) { ... }`.
+const GTEST_MACROS: &[&str] = &["TEST", "TEST_F", "TEST_P", "TYPED_TEST", "TYPED_TEST_P"];
+
+/// `Suite.Name` for a GoogleTest test definition such as
+/// `TEST_F(MathTest, Adds) { ... }`, which tree-sitter parses as a
+/// function named `TEST_F` with two parameters.
+fn cpp_gtest_name(file: &ParsedFile, node: Node) -> Option<String> {
+    if node.kind() != "function_definition" {
+        return None;
+    }
+    let source = file.source().as_bytes();
+    let declarator = node.child_by_field_name("declarator")?;
+    if declarator.kind() != "function_declarator" {
+        return None;
+    }
+    let name = declarator.child_by_field_name("declarator")?;
+    if name.kind() != "identifier" || !GTEST_MACROS.contains(&name.utf8_text(source).ok()?) {
+        return None;
+    }
+
+    let parameters = declarator.child_by_field_name("parameters")?;
+    let mut cursor = parameters.walk();
+    let parts = parameters
+        .named_children(&mut cursor)
+        .map(|param| param.utf8_text(source).ok().map(str::trim))
+        .collect::<Option<Vec<_>>>()?;
+    match parts.as_slice() {
+        [suite, test] => Some(format!("{suite}.{test}")),
+        _ => None,
+    }
+}
+
 fn cpp_symbol_name(file: &ParsedFile, node: Node) -> Option<String> {
+    if let Some(name) = cpp_gtest_name(file, node) {
+        return Some(name);
+    }
+
     let source = file.source();
 
     if let Some(name_node) = node.child_by_field_name("name") {
@@ -152,12 +190,10 @@ fn cpp_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut V
                     let trimmed = line.trim_start();
                     trimmed.starts_with("[[")
                 });
-                let attributes = comment.map(|(text, comment_range)| SymbolAttributes {
-                    comment: Some(text),
-                    comment_range: Some(comment_range),
-                    keywords: Vec::new(),
-                    description: None,
-                });
+                let annotations = symbol_annotations(file, node);
+                let is_test = cpp_gtest_name(file, node).is_some()
+                    || is_test_symbol(file, node, &annotations);
+                let attributes = source_symbol_attributes(comment, annotations, is_test);
                 symbols.push(Symbol {
                     name,
                     kind,
@@ -170,6 +206,9 @@ fn cpp_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut V
                     matches: Vec::new(),
                     calls: Vec::new(),
                     called_by: Vec::new(),
+                    supertypes: Vec::new(),
+                    definition: None,
+                    modules: Vec::new(),
                 });
             }
         }
@@ -185,6 +224,131 @@ fn cpp_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut V
     }
 }
 
+/// Follow `declarator` fields through pointer and reference
+/// declarators down to the function declarator, if any.
+fn cpp_function_declarator(node: Node) -> Option<Node> {
+    let mut current = node.child_by_field_name("declarator")?;
+    loop {
+        if current.kind() == "function_declarator" {
+            return Some(current);
+        }
+        current = current.child_by_field_name("declarator")?;
+    }
+}
+
+fn cpp_parameter_count(file: &ParsedFile, declarator: Node) -> u32 {
+    let Some(parameters) = declarator.child_by_field_name("parameters") else {
+        return 0;
+    };
+    let mut cursor = parameters.walk();
+    let params: Vec<Node> = parameters
+        .named_children(&mut cursor)
+        .filter(|param| param.kind() != "comment")
+        .collect();
+    // `f(void)` declares no parameters.
+    if let [param] = params.as_slice() {
+        if param.utf8_text(file.source().as_bytes()) == Ok("void") {
+            return 0;
+        }
+    }
+    params.len() as u32
+}
+
+/// Names of the namespaces and classes enclosing `node`, outermost
+/// first.
+fn cpp_enclosing_scope(file: &ParsedFile, node: Node) -> Vec<String> {
+    let mut scope = Vec::new();
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if matches!(
+            parent.kind(),
+            "namespace_definition" | "class_specifier" | "struct_specifier"
+        ) {
+            if let Some(name) = parent
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(file.source().as_bytes()).ok())
+            {
+                scope.push(name.to_string());
+            }
+        }
+        current = parent.parent();
+    }
+    scope.reverse();
+    scope
+}
+
+/// Split a qualified declarator name (`geo::Shape::area`) into its
+/// qualifier segments and the unqualified name. Template arguments are
+/// dropped from the qualifier (`Box<T>::put` is qualified by `Box`).
+fn cpp_split_qualified(file: &ParsedFile, node: Node) -> Option<(Vec<String>, String)> {
+    let source = file.source().as_bytes();
+    let mut qualifier = Vec::new();
+    let mut current = node;
+    while current.kind() == "qualified_identifier" {
+        if let Some(scope) = current.child_by_field_name("scope") {
+            let scope = match scope.kind() {
+                "template_type" => scope.child_by_field_name("name").unwrap_or(scope),
+                _ => scope,
+            };
+            qualifier.push(scope.utf8_text(source).ok()?.to_string());
+        }
+        current = current.child_by_field_name("name")?;
+    }
+    Some((qualifier, current.utf8_text(source).ok()?.to_string()))
+}
+
+/// Collect member function declarations in class bodies and
+/// out-of-line member definitions (`void Shape::area() { ... }`).
+fn cpp_member_keys(file: &ParsedFile) -> Vec<MemberKey> {
+    fn visit(file: &ParsedFile, node: Node, keys: &mut Vec<MemberKey>) {
+        match node.kind() {
+            "field_declaration" if cpp_field_is_function(node) => {
+                if let (Some(declarator), Some(name)) =
+                    (cpp_function_declarator(node), cpp_symbol_name(file, node))
+                {
+                    keys.push(MemberKey {
+                        scope: cpp_enclosing_scope(file, node),
+                        name,
+                        arity: cpp_parameter_count(file, declarator),
+                        range: node_text_range(&node),
+                        definition: false,
+                    });
+                }
+            }
+            "function_definition" if !cpp_is_method(node) => {
+                let qualified = cpp_function_declarator(node).and_then(|declarator| {
+                    let name = declarator.child_by_field_name("declarator")?;
+                    (name.kind() == "qualified_identifier")
+                        .then(|| cpp_split_qualified(file, name))
+                        .flatten()
+                        .map(|(qualifier, name)| (declarator, qualifier, name))
+                });
+                if let Some((declarator, qualifier, name)) = qualified {
+                    let mut scope = cpp_enclosing_scope(file, node);
+                    scope.extend(qualifier);
+                    keys.push(MemberKey {
+                        scope,
+                        name,
+                        arity: cpp_parameter_count(file, declarator),
+                        range: node_text_range(&node),
+                        definition: true,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            visit(file, child, keys);
+        }
+    }
+
+    let mut keys = Vec::new();
+    visit(file, file.tree.root_node(), &mut keys);
+    keys
+}
+
 fn cpp_enclosing_symbol_index(
     symbols: &[Symbol],
     file_path: &Path,
@@ -242,13 +406,13 @@ fn cpp_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
 
     let root = file.tree.root_node();
     let mut cursor = root.walk();
-    let mut edges: Vec<(usize, String, u32)> = Vec::new();
+    let mut edges: Vec<(usize, String, u32, u32)> = Vec::new();
 
     fn visit(
         file: &ParsedFile,
         symbols: &[Symbol],
         cursor: &mut TreeCursor,
-        edges: &mut Vec<(usize, String, u32)>,
+        edges: &mut Vec<(usize, String, u32, u32)>,
     ) {
         loop {
             let node = cursor.node();
@@ -258,7 +422,7 @@ fn cpp_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
                     cpp_enclosing_symbol_index(symbols, &file.path, range)
                 {
                     if let Some(callee) = cpp_callee_name(file, node) {
-                        edges.push((caller_idx, callee, range.start_line));
+                        edges.push((caller_idx, callee, range.start_line, range.start_column));
                     }
                 }
             }
@@ -276,7 +440,7 @@ fn cpp_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
 
     visit(file, symbols, &mut cursor, &mut edges);
 
-    for (caller_idx, callee_name, line) in edges {
+    for (caller_idx, callee_name, line, column) in edges {
         if caller_idx >= symbols.len() {
             continue;
         }
@@ -288,7 +452,10 @@ fn cpp_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
             name: callee_name.clone(),
             file: caller_file.clone(),
             line: Some(line),
+            column: Some(column),
             kind: None,
+            target_file: None,
+            edge_kind: CallEdgeKind::Call,
         });
 
         for symbol in symbols.iter_mut() {
@@ -297,7 +464,10 @@ fn cpp_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
                     name: caller_name.clone(),
                     file: caller_file.clone(),
                     line: Some(line),
+                    column: Some(column),
                     kind: None,
+                    target_file: None,
+                    edge_kind: CallEdgeKind::Call,
                 });
             }
         }
@@ -358,6 +528,10 @@ impl LanguageBackend for CppBackend {
         &["cpp", "cc", "cxx", "hpp", "hh", "h", "hxx"]
     }
 
+    fn grammar(&self, _path: &Path) -> tree_sitter::Language {
+        LANGUAGE.into()
+    }
+
     fn parse_file(&self, path: &Path, source: &str) -> BackendResult<ParsedFile> {
         let mut parser = Parser::new();
         let language = LANGUAGE.into();
@@ -384,6 +558,26 @@ impl LanguageBackend for CppBackend {
         Ok(symbols)
     }
 
+    fn imports(
+        &self,
+        file: &ParsedFile,
+        build: Option<&CompileDatabase>,
+    ) -> BackendResult<Vec<ImportRef>> {
+        Ok(crate::language::imports::cpp_includes(file, build))
+    }
+
+    fn type_relations(&self, file: &ParsedFile) -> BackendResult<Vec<TypeRelation>> {
+        Ok(crate::language::relations::cpp_type_relations(file))
+    }
+
+    fn member_keys(&self, file: &ParsedFile) -> BackendResult<Vec<MemberKey>> {
+        Ok(cpp_member_keys(file))
+    }
+
+    fn pairs_members_across_files(&self) -> bool {
+        true
+    }
+
     fn get_context_snippet(
         &self,
         file: &ParsedFile,
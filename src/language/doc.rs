@@ -0,0 +1,480 @@
+//! Structured parsing of normalized doc comments.
+//!
+//! `collect_leading_comment` strips comment delimiters and keeps
+//! paragraph breaks as empty lines; this module splits the text into
+//! a summary, JSDoc/Doxygen `@tag` (or `\tag`) blocks and rustdoc
+//! `# Heading` sections, and collects intra-doc and `{@link}`
+//! references.
+
+use crate::models::{DocComment, DocParam, DocTag};
+
+/// A run of comment lines belonging to the description, a tag or a
+/// section.
+enum Block {
+    Description(Vec<String>),
+    Tag(String, Vec<String>),
+    Section(String, Vec<String>),
+}
+
+/// Parse a symbol's normalized doc comment and annotations into a
+/// `DocComment`, or `None` when neither carries any documentation.
+///
+/// Deprecation comes from an `@deprecated` tag, or else from a
+/// `deprecated` attribute (`#[deprecated(note = "...")]`,
+/// `[[deprecated("...")]]`).
+pub(crate) fn parse_doc_comment(
+    comment: Option<&str>,
+    annotations: &[String],
+) -> Option<DocComment> {
+    let mut doc = DocComment::default();
+
+    if let Some(comment) = comment {
+        let mut description = Vec::new();
+        let mut brief = None;
+        for block in split_blocks(comment) {
+            match block {
+                Block::Description(lines) => description.extend(lines),
+                Block::Tag(tag, lines) => apply_tag(&mut doc, &mut brief, &tag, &lines),
+                Block::Section(title, lines) => {
+                    let text = lines.join("\n").trim().to_string();
+                    match title.as_str() {
+                        "errors" => doc.errors = Some(text),
+                        "panics" => doc.panics = Some(text),
+                        "safety" => doc.safety = Some(text),
+                        _ => doc.tags.push(DocTag { tag: title, text }),
+                    }
+                }
+            }
+        }
+        doc.summary = brief.or_else(|| summary_sentence(&description));
+        doc.links = collect_links(comment);
+    }
+
+    if doc.deprecated.is_none() {
+        doc.deprecated = annotations
+            .iter()
+            .find_map(|annotation| deprecation_note(annotation));
+    }
+
+    (doc != DocComment::default()).then_some(doc)
+}
+
+/// Split comment text into description, tag and section blocks.
+/// Lines inside fenced code blocks never start a tag or section, so
+/// rustdoc's hidden `# use ...;` example lines stay in their section.
+fn split_blocks(comment: &str) -> Vec<Block> {
+    let mut blocks = vec![Block::Description(Vec::new())];
+    let mut in_fence = false;
+
+    for line in comment.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            // Leftover of a `*/` terminator in normalized block comments.
+            if trimmed == "/" {
+                continue;
+            }
+            if let Some(title) = section_heading(trimmed) {
+                blocks.push(Block::Section(title, Vec::new()));
+                continue;
+            }
+            if let Some((tag, rest)) = tag_line(trimmed) {
+                blocks.push(Block::Tag(tag, vec![rest.to_string()]));
+                continue;
+            }
+        }
+
+        let lines = match blocks.last_mut().expect("at least one block") {
+            Block::Description(lines) | Block::Tag(_, lines) | Block::Section(_, lines) => lines,
+        };
+        lines.push(line.to_string());
+    }
+
+    blocks
+}
+
+/// Lowercased title of a Markdown heading line (`# Errors`).
+fn section_heading(line: &str) -> Option<String> {
+    let title = line.trim_start_matches('#');
+    if title.len() == line.len() || !title.starts_with(' ') {
+        return None;
+    }
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.to_ascii_lowercase())
+}
+
+/// Split a `@tag rest` / `\tag rest` line into its lowercased tag name
+/// and the rest of the line.
+fn tag_line(line: &str) -> Option<(String, &str)> {
+    let body = line.strip_prefix('@').or_else(|| line.strip_prefix('\\'))?;
+    let end = body
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(body.len());
+    if end == 0 || !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some((body[..end].to_ascii_lowercase(), body[end..].trim()))
+}
+
+fn apply_tag(doc: &mut DocComment, brief: &mut Option<String>, tag: &str, lines: &[String]) {
+    let text = join_words(lines);
+    match tag {
+        "param" | "arg" | "argument" | "tparam" => doc.params.extend(parse_param(&text)),
+        "returns" | "return" => doc.returns = Some(strip_type(&text)),
+        "throws" | "throw" | "exception" => doc.throws.push(strip_type(&text)),
+        "deprecated" => doc.deprecated = Some(text),
+        "see" | "sa" => doc.see.push(text),
+        // The brief ends at its first sentence or paragraph break.
+        "brief" | "summary" => *brief = summary_sentence(lines),
+        _ => doc.tags.push(DocTag {
+            tag: tag.to_string(),
+            text,
+        }),
+    }
+}
+
+/// Join lines into a single space-separated string.
+fn join_words(lines: &[String]) -> String {
+    lines
+        .iter()
+        .flat_map(|line| line.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a leading JSDoc `{type}` off `text`.
+fn split_type(text: &str) -> (Option<&str>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+    let mut depth = 0;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (Some(text[1..idx].trim()), text[idx + 1..].trim_start());
+                }
+            }
+            _ => {}
+        }
+    }
+    (None, text)
+}
+
+/// `@returns {number} the sum` → `number the sum`-style text without
+/// braces; a bare type is kept as the whole text.
+fn strip_type(text: &str) -> String {
+    match split_type(text) {
+        (Some(ty), "") => ty.to_string(),
+        (Some(ty), rest) => format!("{ty} {rest}"),
+        (None, rest) => rest.to_string(),
+    }
+}
+
+/// Parse `@param` text: Doxygen `[in]` direction, JSDoc `{type}`,
+/// a name (`[name=default]` for optional JSDoc parameters) and an
+/// optional `-` before the description.
+fn parse_param(text: &str) -> Option<DocParam> {
+    let mut rest = text.trim();
+    for direction in ["[in]", "[out]", "[in,out]", "[in, out]"] {
+        if let Some(stripped) = rest.strip_prefix(direction) {
+            rest = stripped.trim_start();
+            break;
+        }
+    }
+    rest = split_type(rest).1;
+
+    let (name, description) = if let Some(optional) = rest.strip_prefix('[') {
+        let (inner, after) = optional.split_once(']')?;
+        (inner.split('=').next().unwrap_or_default().trim(), after)
+    } else {
+        rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    let description = description.trim_start();
+    let description = description.strip_prefix('-').unwrap_or(description).trim();
+    Some(DocParam {
+        name: name.to_string(),
+        description: description.to_string(),
+    })
+}
+
+/// First sentence of the first paragraph of the description lines.
+fn summary_sentence(lines: &[String]) -> Option<String> {
+    let paragraph: Vec<String> = lines
+        .iter()
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| !line.trim().is_empty())
+        .cloned()
+        .collect();
+    let text = join_words(&paragraph);
+    if text.is_empty() {
+        return None;
+    }
+    let end = text
+        .match_indices(". ")
+        .next()
+        .map_or(text.len(), |(idx, _)| idx + 1);
+    Some(text[..end].to_string())
+}
+
+/// Deprecation message of a `deprecated` attribute: its `note = "..."`
+/// or first string argument, or empty when it has none.
+fn deprecation_note(annotation: &str) -> Option<String> {
+    let path_end = annotation
+        .find(|c: char| c == '(' || c == '=' || c.is_whitespace())
+        .unwrap_or(annotation.len());
+    if !annotation[..path_end].eq_ignore_ascii_case("deprecated") {
+        return None;
+    }
+
+    let args = &annotation[path_end..];
+    let quoted = |text: &str| -> Option<String> {
+        let start = text.find('"')? + 1;
+        let len = text[start..].find('"')?;
+        Some(text[start..start + len].to_string())
+    };
+    let note = match args.find("note") {
+        Some(idx) => quoted(&args[idx..]),
+        None => quoted(args),
+    };
+    Some(note.unwrap_or_default())
+}
+
+/// Intra-doc link targets (`[`Config`]`, `[Config::open]`,
+/// `[text](crate::io)`) and `{@link Target}` references, deduplicated
+/// in order of appearance. Inline code spans and fenced code blocks
+/// are skipped.
+fn collect_links(comment: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut push = |target: &str| {
+        let target = target.trim().trim_matches('`');
+        if is_link_target(target) && !links.iter().any(|link| link == target) {
+            links.push(target.to_string());
+        }
+    };
+
+    let mut in_fence = false;
+    for line in comment.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(idx) = rest.find(['`', '[', '{']) {
+            let tail = &rest[idx..];
+            if let Some(code) = tail.strip_prefix('`') {
+                // Skip an inline code span.
+                rest = match code.find('`') {
+                    Some(end) => &code[end + 1..],
+                    None => "",
+                };
+            } else if let Some(link) = tail.strip_prefix("{@link") {
+                let Some(end) = link.find('}') else {
+                    break;
+                };
+                let inner = link[..end].trim_start_matches(|c: char| c.is_ascii_alphabetic());
+                let target = inner.split(['|', ' ']).find(|part| !part.is_empty());
+                push(target.unwrap_or_default());
+                rest = &link[end + 1..];
+            } else if tail.starts_with('[')
+                && rest[..idx]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            {
+                // Indexing or a Doxygen direction (`\param[in]`).
+                rest = &tail[1..];
+            } else if tail.starts_with('[') {
+                let Some(end) = tail.find(']') else {
+                    break;
+                };
+                let inner = &tail[1..end];
+                let after = &tail[end + 1..];
+                if let Some(target) = after.strip_prefix('(') {
+                    match target.find(')') {
+                        Some(close) => {
+                            push(&target[..close]);
+                            rest = &target[close + 1..];
+                        }
+                        None => rest = after,
+                    }
+                } else {
+                    // `[label]: target` definitions and `[a][b]`
+                    // references name their target elsewhere.
+                    if !after.starts_with(':') && !after.starts_with('[') {
+                        push(inner);
+                    }
+                    rest = after;
+                }
+            } else {
+                rest = &tail[1..];
+            }
+        }
+    }
+
+    links
+}
+
+/// Whether `target` looks like a code path (`Config`, `io::Error`,
+/// `Vec<T>`, `foo()`, `vec!`, `struct@Config`) rather than a URL,
+/// anchor or plain bracketed text.
+fn is_link_target(target: &str) -> bool {
+    target.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && target.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '<' | '>' | '!' | '(' | ')' | '@')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(comment: &str) -> DocComment {
+        parse_doc_comment(Some(comment), &[]).expect("doc")
+    }
+
+    #[test]
+    fn parses_jsdoc_tags() {
+        let doc = parse(
+            "Adds two numbers. Overflow wraps.\n\
+             @param {number} a - first operand\n\
+             @param [b=0] second operand,\n\
+             defaults to zero\n\
+             @returns {number} the sum\n\
+             @throws {RangeError} when a is negative\n\
+             @deprecated use {@link sum} instead\n\
+             @see Calculator\n\
+             @since 2.0\n\
+             /",
+        );
+
+        assert_eq!(doc.summary.as_deref(), Some("Adds two numbers."));
+        assert_eq!(
+            doc.params,
+            vec![
+                DocParam {
+                    name: "a".to_string(),
+                    description: "first operand".to_string(),
+                },
+                DocParam {
+                    name: "b".to_string(),
+                    description: "second operand, defaults to zero".to_string(),
+                },
+            ]
+        );
+        assert_eq!(doc.returns.as_deref(), Some("number the sum"));
+        assert_eq!(doc.throws, vec!["RangeError when a is negative"]);
+        assert_eq!(doc.deprecated.as_deref(), Some("use {@link sum} instead"));
+        assert_eq!(doc.see, vec!["Calculator"]);
+        assert_eq!(doc.links, vec!["sum"]);
+        assert_eq!(
+            doc.tags,
+            vec![DocTag {
+                tag: "since".to_string(),
+                text: "2.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_doxygen_commands() {
+        let doc = parse(
+            "\\brief Opens the device.\n\
+             Longer description.\n\
+             \\param[in] path device path, see [`Device`]\n\
+             \\return zero on success",
+        );
+
+        assert_eq!(doc.summary.as_deref(), Some("Opens the device."));
+        assert_eq!(doc.params[0].name, "path");
+        assert_eq!(doc.params[0].description, "device path, see [`Device`]");
+        assert_eq!(doc.returns.as_deref(), Some("zero on success"));
+        assert_eq!(doc.links, vec!["Device"]);
+    }
+
+    #[test]
+    fn parses_rustdoc_sections_and_links() {
+        let doc = parse(
+            "Opens a [`Store`] at `path[0]`, see [the module](crate::storage).\n\
+             # Errors\n\
+             Returns [`io::Error`] when the file is missing.\n\
+             # Panics\n\
+             Panics if `path` is empty.\n\
+             # Examples\n\
+             ```\n\
+             # use acme::Store;\n\
+             let store = Store::open(\"db\")?;\n\
+             ```\n\
+             # Safety\n\
+             Callers must hold the lock. See [RFC](https://example.com) and [1].",
+        );
+
+        assert_eq!(
+            doc.summary.as_deref(),
+            Some("Opens a [`Store`] at `path[0]`, see [the module](crate::storage).")
+        );
+        assert_eq!(
+            doc.errors.as_deref(),
+            Some("Returns [`io::Error`] when the file is missing.")
+        );
+        assert_eq!(doc.panics.as_deref(), Some("Panics if `path` is empty."));
+        assert_eq!(
+            doc.safety.as_deref(),
+            Some("Callers must hold the lock. See [RFC](https://example.com) and [1].")
+        );
+        assert_eq!(doc.tags.len(), 1);
+        assert_eq!(doc.tags[0].tag, "examples");
+        assert!(doc.tags[0].text.contains("# use acme::Store;"));
+        assert_eq!(doc.links, vec!["Store", "crate::storage", "io::Error"]);
+    }
+
+    #[test]
+    fn summary_ends_at_first_paragraph_break() {
+        let doc = parse(
+            "Opens the store without a trailing period\n\
+             \n\
+             Longer description. It spans sentences.",
+        );
+        assert_eq!(
+            doc.summary.as_deref(),
+            Some("Opens the store without a trailing period")
+        );
+
+        let doc = parse(
+            "\\brief Opens the device\n\
+             \n\
+             Longer description.",
+        );
+        assert_eq!(doc.summary.as_deref(), Some("Opens the device"));
+    }
+
+    #[test]
+    fn deprecation_falls_back_to_attributes() {
+        let annotations = [
+            "must_use".to_string(),
+            "deprecated(since = \"1.2\", note = \"use open\")".to_string(),
+        ];
+        let doc = parse_doc_comment(None, &annotations).expect("doc");
+        assert_eq!(doc.deprecated.as_deref(), Some("use open"));
+
+        let doc = parse_doc_comment(None, &["deprecated".to_string()]).expect("doc");
+        assert_eq!(doc.deprecated.as_deref(), Some(""));
+
+        let doc = parse_doc_comment(None, &["deprecated(\"gone\")".to_string()]).expect("doc");
+        assert_eq!(doc.deprecated.as_deref(), Some("gone"));
+
+        assert!(parse_doc_comment(None, &["deprecated_api".to_string()]).is_none());
+        assert!(parse_doc_comment(None, &[]).is_none());
+    }
+}
@@ -0,0 +1,509 @@
+//! ES module specifier resolution for the TypeScript and JavaScript
+//! backends.
+//!
+//! Relative specifiers (`./util`) name a file next to the importer.
+//! Bare specifiers are resolved the way the TypeScript compiler and
+//! Node would inside the code base:
+//!
+//! - `compilerOptions.paths` aliases of the nearest `tsconfig.json` or
+//!   `jsconfig.json`, following relative `extends` (`@app/*` →
+//!   `src/*`), then `compilerOptions.baseUrl`;
+//! - packages of the enclosing workspace (`workspaces` in a
+//!   `package.json` above the importer), through their `exports` map
+//!   or their `source` / `module` / `main` / `types` entry points.
+//!
+//! Packages installed under `node_modules` are not resolved: they are
+//! not part of the code base. Configuration files are cached through
+//! `project`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::language::normalize_path;
+use crate::language::project::{cached, expand_member_pattern, Cache, Stamp};
+
+/// Extensions tried, in order, when an ES module specifier omits one.
+const ES_EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// Configuration files searched for `paths` aliases, in order.
+const TSCONFIG_NAMES: &[&str] = &["tsconfig.json", "jsconfig.json"];
+
+/// `exports` conditions tried first, in order; the remaining
+/// conditions of an object follow in their declared order. Source
+/// conditions come before build outputs, which may not exist.
+const PREFERRED_CONDITIONS: &[&str] = &["source", "import", "module", "require", "default"];
+
+/// `package.json` entry point fields, in order.
+const ENTRY_FIELDS: &[&str] = &["source", "module", "main", "types", "typings"];
+
+/// Nested `extends` followed before a configuration is considered
+/// cyclic.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Resolve an ES module specifier imported from `importer` to a file.
+pub(crate) fn resolve_es_specifier(importer: &Path, specifier: &str) -> Option<PathBuf> {
+    let dir = importer.parent().unwrap_or(Path::new(""));
+    if specifier.starts_with('.') {
+        return resolve_es_file(&dir.join(specifier));
+    }
+    if specifier.starts_with('/') {
+        return resolve_es_file(Path::new(specifier));
+    }
+
+    nearest_tsconfig(dir)
+        .and_then(|config| config.resolve(specifier))
+        .or_else(|| resolve_workspace_package(dir, specifier))
+}
+
+/// Find the file a module path without (or with a compiled)
+/// extension refers to.
+fn resolve_es_file(base: &Path) -> Option<PathBuf> {
+    let base = normalize_path(base);
+
+    let mut candidates = vec![base.clone()];
+    // TypeScript sources commonly import `./util.js` to mean `./util.ts`.
+    if let Some(ext) = base.extension().and_then(|e| e.to_str()) {
+        if matches!(ext, "js" | "jsx" | "mjs" | "cjs") {
+            candidates.push(base.with_extension("ts"));
+            candidates.push(base.with_extension("tsx"));
+        }
+    }
+    let base_str = base.to_string_lossy();
+    for ext in ES_EXTENSIONS {
+        candidates.push(PathBuf::from(format!("{base_str}.{ext}")));
+    }
+    for ext in ES_EXTENSIONS {
+        candidates.push(base.join(format!("index.{ext}")));
+    }
+
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+/// Module resolution options of a `tsconfig.json`, after `extends`.
+#[derive(Debug, Default)]
+struct TsConfig {
+    /// `compilerOptions.baseUrl`, relative to the configuration that
+    /// set it.
+    base_url: Option<PathBuf>,
+    /// `compilerOptions.paths` patterns and their targets, in
+    /// declaration order.
+    paths: Vec<(String, Vec<String>)>,
+    /// Directory of the configuration that set `paths`; targets are
+    /// relative to it unless `baseUrl` is set.
+    paths_dir: PathBuf,
+}
+
+impl TsConfig {
+    /// Read `path` and the configurations it extends. Options set in
+    /// `path` override inherited ones.
+    fn load(path: &Path, depth: usize, stamps: &mut Vec<Stamp>) -> Self {
+        stamps.push(Stamp::of(path));
+        let Some(value) = read_jsonc(path) else {
+            return Self::default();
+        };
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let mut config = Self::default();
+        if depth < MAX_EXTENDS_DEPTH {
+            let parents = match value.get("extends") {
+                Some(Value::String(parent)) => vec![parent.as_str()],
+                Some(Value::Array(parents)) => parents.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            // Shared configurations published as packages are outside
+            // the code base; only relative `extends` are followed.
+            for parent in parents.into_iter().filter(|parent| parent.starts_with('.')) {
+                let mut parent_path = dir.join(parent);
+                if parent_path.extension().is_none() {
+                    parent_path.set_extension("json");
+                }
+                let parent = Self::load(&normalize_path(&parent_path), depth + 1, stamps);
+                if parent.base_url.is_some() {
+                    config.base_url = parent.base_url;
+                }
+                if !parent.paths.is_empty() {
+                    config.paths = parent.paths;
+                    config.paths_dir = parent.paths_dir;
+                }
+            }
+        }
+
+        let options = value.get("compilerOptions");
+        if let Some(base_url) = options
+            .and_then(|options| options.get("baseUrl"))
+            .and_then(Value::as_str)
+        {
+            config.base_url = Some(normalize_path(&dir.join(base_url)));
+        }
+        if let Some(paths) = options
+            .and_then(|options| options.get("paths"))
+            .and_then(Value::as_object)
+        {
+            config.paths = paths
+                .iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect();
+                    (pattern.clone(), targets)
+                })
+                .collect();
+            config.paths_dir = dir.to_path_buf();
+        }
+        config
+    }
+
+    /// Resolve a bare specifier through `paths`, then `baseUrl`.
+    fn resolve(&self, specifier: &str) -> Option<PathBuf> {
+        let paths_base = self.base_url.as_deref().unwrap_or(&self.paths_dir);
+        let patterns = self.paths.iter().map(|(pattern, _)| pattern.as_str());
+        if let Some((idx, star)) = best_pattern_match(patterns, specifier) {
+            for target in &self.paths[idx].1 {
+                let target = target.replacen('*', star, 1);
+                if let Some(found) = resolve_es_file(&paths_base.join(target)) {
+                    return Some(found);
+                }
+            }
+        }
+
+        self.base_url
+            .as_ref()
+            .and_then(|base_url| resolve_es_file(&base_url.join(specifier)))
+    }
+}
+
+/// Module resolution options of the `tsconfig.json` or
+/// `jsconfig.json` closest to `dir`.
+fn nearest_tsconfig(dir: &Path) -> Option<Arc<TsConfig>> {
+    let path = dir.ancestors().find_map(|ancestor| {
+        TSCONFIG_NAMES
+            .iter()
+            .map(|name| ancestor.join(name))
+            .find(|candidate| candidate.is_file())
+    })?;
+    Some(cached(&TSCONFIGS, &path, |stamps| {
+        TsConfig::load(&path, 0, stamps)
+    }))
+}
+
+/// A package of a workspace.
+#[derive(Debug)]
+struct Package {
+    name: String,
+    dir: PathBuf,
+    manifest: Value,
+}
+
+impl Package {
+    /// Resolve `subpath` (`.` for the package itself, `./rest`
+    /// otherwise) through `exports`, falling back to the entry point
+    /// fields and plain paths inside the package.
+    fn resolve(&self, subpath: &str) -> Option<PathBuf> {
+        if let Some(exports) = self.manifest.get("exports") {
+            let found = export_targets(exports, subpath)
+                .iter()
+                .find_map(|target| resolve_es_file(&self.dir.join(target)));
+            if found.is_some() {
+                return found;
+            }
+        }
+
+        if subpath == "." {
+            ENTRY_FIELDS
+                .iter()
+                .filter_map(|field| self.manifest.get(*field).and_then(Value::as_str))
+                .find_map(|entry| resolve_es_file(&self.dir.join(entry)))
+                .or_else(|| resolve_es_file(&self.dir))
+        } else {
+            resolve_es_file(&self.dir.join(subpath))
+        }
+    }
+}
+
+/// Resolve a bare specifier to a package of the workspace enclosing
+/// `dir`, if any.
+fn resolve_workspace_package(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let workspace = dir.ancestors().find_map(|ancestor| {
+        let manifest = ancestor.join("package.json");
+        if !manifest.is_file() {
+            return None;
+        }
+        let packages = cached(&WORKSPACES, &manifest, |stamps| {
+            load_workspace(&manifest, stamps)
+        });
+        packages.is_some().then_some(packages)
+    })?;
+
+    // `@scope/pkg/sub` belongs to `@scope/pkg`, not to a package
+    // named `@scope/p`.
+    let package = workspace
+        .iter()
+        .flatten()
+        .filter(|package| {
+            specifier
+                .strip_prefix(package.name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .max_by_key(|package| package.name.len())?;
+    let subpath = format!(".{}", &specifier[package.name.len()..]);
+    package.resolve(&subpath)
+}
+
+/// Packages of the workspace whose root manifest is `manifest`, or
+/// `None` when it declares no `workspaces`.
+fn load_workspace(manifest: &Path, stamps: &mut Vec<Stamp>) -> Option<Vec<Package>> {
+    stamps.push(Stamp::of(manifest));
+    let value = read_jsonc(manifest)?;
+    // npm and Yarn accept an array of globs; Yarn classic also nests
+    // it under `packages`.
+    let patterns = match value.get("workspaces")? {
+        Value::Object(workspaces) => workspaces.get("packages")?.as_array()?,
+        Value::Array(patterns) => patterns,
+        _ => return None,
+    };
+    let root = manifest.parent().unwrap_or(Path::new(""));
+
+    let mut packages = Vec::new();
+    for pattern in patterns.iter().filter_map(Value::as_str) {
+        if pattern.starts_with('!') {
+            continue;
+        }
+        for dir in expand_member_pattern(root, pattern, stamps) {
+            let package_manifest = dir.join("package.json");
+            stamps.push(Stamp::of(&package_manifest));
+            let Some(manifest) = read_jsonc(&package_manifest) else {
+                continue;
+            };
+            let Some(name) = manifest.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            if packages.iter().any(|package: &Package| package.dir == dir) {
+                continue;
+            }
+            packages.push(Package {
+                name: name.to_string(),
+                dir,
+                manifest,
+            });
+        }
+    }
+    Some(packages)
+}
+
+/// Targets `exports` maps `subpath` to, most preferred first.
+///
+/// `exports` is either a single target for the package itself, a map
+/// of conditions, or a map of subpaths (`.`, `./utils`, `./*`) to
+/// targets.
+fn export_targets(exports: &Value, subpath: &str) -> Vec<String> {
+    let subpaths = match exports {
+        Value::Object(map) if map.keys().all(|key| key.starts_with('.')) => map,
+        target if subpath == "." => return condition_targets(target, ""),
+        _ => return Vec::new(),
+    };
+    if let Some(target) = subpaths.get(subpath) {
+        return condition_targets(target, "");
+    }
+
+    let keys = subpaths.keys().map(String::as_str);
+    match best_pattern_match(keys, subpath) {
+        Some((idx, star)) => subpaths
+            .values()
+            .nth(idx)
+            .map(|target| condition_targets(target, star))
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Flatten a (possibly conditional or fallback-array) export target,
+/// substituting `star` for `*`.
+fn condition_targets(target: &Value, star: &str) -> Vec<String> {
+    match target {
+        Value::String(target) => vec![target.replace('*', star)],
+        Value::Array(targets) => targets
+            .iter()
+            .flat_map(|target| condition_targets(target, star))
+            .collect(),
+        Value::Object(conditions) => {
+            let preferred = PREFERRED_CONDITIONS
+                .iter()
+                .filter_map(|condition| conditions.get(*condition));
+            let rest = conditions
+                .iter()
+                .filter(|(condition, _)| !PREFERRED_CONDITIONS.contains(&condition.as_str()))
+                .map(|(_, target)| target);
+            preferred
+                .chain(rest)
+                .flat_map(|target| condition_targets(target, star))
+                .collect()
+        }
+        // `null` excludes a subpath.
+        _ => Vec::new(),
+    }
+}
+
+/// Index of the pattern matching `specifier` and the text its `*`
+/// matched. An exact pattern wins; otherwise the pattern with the
+/// longest prefix before its `*`.
+fn best_pattern_match<'a, 's>(
+    patterns: impl Iterator<Item = &'a str>,
+    specifier: &'s str,
+) -> Option<(usize, &'s str)> {
+    let mut best: Option<(usize, usize, &'s str)> = None;
+    for (idx, pattern) in patterns.enumerate() {
+        match pattern.split_once('*') {
+            None if pattern == specifier => return Some((idx, "")),
+            None => {}
+            Some((prefix, suffix)) => {
+                let Some(star) = specifier
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix))
+                else {
+                    continue;
+                };
+                if best.is_none_or(|(_, len, _)| prefix.len() > len) {
+                    best = Some((idx, prefix.len(), star));
+                }
+            }
+        }
+    }
+    best.map(|(idx, _, star)| (idx, star))
+}
+
+/// Read a JSON file that may contain comments and trailing commas, as
+/// `tsconfig.json` files commonly do.
+fn read_jsonc(path: &Path) -> Option<Value> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&text)).ok()
+}
+
+/// Remove `//` and `/* */` comments and trailing commas outside of
+/// strings.
+fn strip_jsonc(text: &str) -> String {
+    let mut without_comments = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            without_comments.push(c);
+            match c {
+                '\\' => without_comments.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|&next| next != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                without_comments.push(c);
+            }
+        }
+    }
+
+    let chars: Vec<char> = without_comments.chars().collect();
+    let mut out = String::with_capacity(chars.len());
+    let mut in_string = false;
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                idx += 1;
+                out.extend(chars.get(idx));
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else {
+            let trailing = c == ',' && {
+                let next = chars[idx + 1..].iter().find(|next| !next.is_whitespace());
+                matches!(next, Some('}' | ']'))
+            };
+            if !trailing {
+                in_string = c == '"';
+                out.push(c);
+            }
+        }
+        idx += 1;
+    }
+    out
+}
+
+/// Parsed `tsconfig.json` files keyed by path.
+static TSCONFIGS: Cache<TsConfig> = Cache::new();
+/// Workspace packages keyed by the `package.json` declaring them;
+/// `None` for manifests without `workspaces`.
+static WORKSPACES: Cache<Option<Vec<Package>>> = Cache::new();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn strip_jsonc_removes_comments_and_trailing_commas() {
+        let text = r#"{
+            // line comment
+            "a": "http://x/*y*/", /* block */
+            "b": [1, 2,],
+            "c": "quote \" , }",
+        }"#;
+        assert_eq!(
+            serde_json::from_str::<Value>(&strip_jsonc(text)).expect("valid json"),
+            json!({"a": "http://x/*y*/", "b": [1, 2], "c": "quote \" , }"})
+        );
+    }
+
+    #[test]
+    fn best_pattern_match_prefers_exact_then_longest_prefix() {
+        let patterns = ["@app/*", "@app/utils/*", "@app/config"];
+        assert_eq!(
+            best_pattern_match(patterns.into_iter(), "@app/utils/format"),
+            Some((1, "format"))
+        );
+        assert_eq!(
+            best_pattern_match(patterns.into_iter(), "@app/config"),
+            Some((2, ""))
+        );
+        assert_eq!(best_pattern_match(patterns.into_iter(), "react"), None);
+    }
+
+    #[test]
+    fn export_targets_handle_subpaths_patterns_and_conditions() {
+        let exports = json!({
+            ".": {"types": "./dist/index.d.ts", "import": "./src/index.ts"},
+            "./widgets/*": "./src/widgets/*.ts",
+            "./internal/*": null,
+        });
+        assert_eq!(
+            export_targets(&exports, "."),
+            vec!["./src/index.ts", "./dist/index.d.ts"]
+        );
+        assert_eq!(
+            export_targets(&exports, "./widgets/button"),
+            vec!["./src/widgets/button.ts"]
+        );
+        assert!(export_targets(&exports, "./internal/x").is_empty());
+        assert_eq!(export_targets(&json!("./main.js"), "."), vec!["./main.js"]);
+    }
+}
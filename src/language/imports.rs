@@ -0,0 +1,826 @@
+//! Import extraction and module resolution shared by the backends.
+//!
+//! Each language has its own way of naming another file: ES module
+//! specifiers (`./util`), Rust module paths (`crate::util`) and C++
+//! `#include "util.h"`. The helpers here turn those statements into
+//! `ImportRef`s whose `files` point at the files on disk that define
+//! the imported names, so call edges can be resolved across files and
+//! file-level dependencies can be reported.
+//! Resolution is best-effort: it only checks which candidate files
+//! exist. The build configuration consulted is a C++ compile database,
+//! when one is configured, and the `tsconfig.json` / `package.json`
+//! files ES module specifiers are resolved through (see
+//! `es_modules`).
+
+use std::path::{Component, Path, PathBuf};
+
+use tree_sitter::Node;
+
+use crate::language::cargo::{
+    rust_crate_root, rust_file_for_module_dir, rust_mod_import, rust_module_dir,
+};
+use crate::language::es_modules::resolve_es_specifier;
+use crate::language::{
+    is_test_symbol, symbol_annotations, CompileDatabase, CompileFlags, ParsedFile,
+};
+use crate::models::{DependencyKind, ImportRef, ImportedName};
+
+/// Lexically normalize a path: drop `.` components and resolve `..`
+/// against preceding components without touching the filesystem.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let popped =
+                    matches!(out.components().next_back(), Some(Component::Normal(_))) && out.pop();
+                if !popped {
+                    out.push("..");
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+fn node_text(file: &ParsedFile, node: Node) -> String {
+    node.utf8_text(file.source().as_bytes())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn string_literal_value(file: &ParsedFile, node: Node) -> String {
+    node_text(file, node)
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .to_string()
+}
+
+/// Collect ES module imports and re-exports (`import ... from`,
+/// `export ... from`) plus CommonJS `require("...")` and dynamic
+/// `import("...")` calls from a TypeScript or JavaScript file.
+pub(crate) fn es_module_imports(file: &ParsedFile) -> Vec<ImportRef> {
+    let root = file.tree.root_node();
+    let mut imports = Vec::new();
+    let mut cursor = root.walk();
+
+    for statement in root.named_children(&mut cursor) {
+        let (kind, reexport) = match statement.kind() {
+            "import_statement" => (DependencyKind::Import, false),
+            "export_statement" => (DependencyKind::Export, true),
+            _ => continue,
+        };
+        let Some(source) = statement.child_by_field_name("source") else {
+            continue;
+        };
+
+        let module = string_literal_value(file, source);
+        let mut names = Vec::new();
+        let mut wildcard = false;
+
+        let mut inner = statement.walk();
+        for child in statement.children(&mut inner) {
+            match child.kind() {
+                "import_clause" => {
+                    let mut clause = child.walk();
+                    for part in child.named_children(&mut clause) {
+                        match part.kind() {
+                            // `import foo from`: the default export,
+                            // visible under the local name.
+                            "identifier" => names.push(ImportedName {
+                                name: node_text(file, part),
+                                alias: None,
+                            }),
+                            "named_imports" => es_collect_specifiers(file, part, &mut names),
+                            "namespace_import" => wildcard = true,
+                            _ => {}
+                        }
+                    }
+                }
+                "export_clause" => es_collect_specifiers(file, child, &mut names),
+                "*" | "namespace_export" => wildcard = true,
+                _ => {}
+            }
+        }
+
+        // Side-effect imports (`import "./polyfill"`) bring no names
+        // into scope but are still dependencies of the file.
+        imports.push(ImportRef {
+            files: resolve_es_specifier(&file.path, &module)
+                .into_iter()
+                .collect(),
+            module,
+            kind,
+            names,
+            wildcard,
+            reexport,
+            test_only: false,
+            line: statement.start_position().row as u32 + 1,
+        });
+    }
+
+    es_call_imports(file, root, &mut imports);
+    imports.sort_by_key(|import| import.line);
+    imports
+}
+
+/// Collect `require("...")` and `import("...")` calls anywhere in the
+/// file. Only string-literal specifiers are recorded.
+fn es_call_imports(file: &ParsedFile, root: Node, imports: &mut Vec<ImportRef>) {
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+
+        let (kind, source) = match node.kind() {
+            // TypeScript `import fs = require("fs")`.
+            "import_require_clause" => {
+                (DependencyKind::Require, node.child_by_field_name("source"))
+            }
+            "call_expression" => {
+                let Some(function) = node.child_by_field_name("function") else {
+                    continue;
+                };
+                let kind = match function.kind() {
+                    "identifier" if node_text(file, function) == "require" => {
+                        DependencyKind::Require
+                    }
+                    "import" => DependencyKind::Import,
+                    _ => continue,
+                };
+                let source = node
+                    .child_by_field_name("arguments")
+                    .and_then(|arguments| arguments.named_child(0));
+                (kind, source)
+            }
+            _ => continue,
+        };
+        let Some(source) = source.filter(|source| source.kind() == "string") else {
+            continue;
+        };
+
+        let module = string_literal_value(file, source);
+        let mut names = Vec::new();
+        // `const { a, b: c } = require("./x")` brings `a` and `c` into
+        // scope; a plain `const x = require(...)` binds a namespace
+        // object, which call resolution does not track.
+        if let Some(pattern) = node
+            .parent()
+            .filter(|parent| parent.kind() == "variable_declarator")
+            .and_then(|declarator| declarator.child_by_field_name("name"))
+            .filter(|name| name.kind() == "object_pattern")
+        {
+            es_collect_pattern_names(file, pattern, &mut names);
+        }
+
+        imports.push(ImportRef {
+            files: resolve_es_specifier(&file.path, &module)
+                .into_iter()
+                .collect(),
+            module,
+            kind,
+            names,
+            wildcard: false,
+            reexport: false,
+            test_only: false,
+            line: node.start_position().row as u32 + 1,
+        });
+    }
+}
+
+fn es_collect_pattern_names(file: &ParsedFile, pattern: Node, names: &mut Vec<ImportedName>) {
+    let mut cursor = pattern.walk();
+    for property in pattern.named_children(&mut cursor) {
+        match property.kind() {
+            "shorthand_property_identifier_pattern" => names.push(ImportedName {
+                name: node_text(file, property),
+                alias: None,
+            }),
+            "pair_pattern" => {
+                let key = property.child_by_field_name("key");
+                let value = property
+                    .child_by_field_name("value")
+                    .filter(|value| value.kind() == "identifier");
+                if let (Some(key), Some(value)) = (key, value) {
+                    names.push(ImportedName {
+                        name: string_literal_value(file, key),
+                        alias: Some(node_text(file, value)),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn es_collect_specifiers(file: &ParsedFile, list: Node, names: &mut Vec<ImportedName>) {
+    let mut cursor = list.walk();
+    for specifier in list.named_children(&mut cursor) {
+        if !matches!(specifier.kind(), "import_specifier" | "export_specifier") {
+            continue;
+        }
+        let Some(name) = specifier.child_by_field_name("name") else {
+            continue;
+        };
+        names.push(ImportedName {
+            name: string_literal_value(file, name),
+            alias: specifier
+                .child_by_field_name("alias")
+                .map(|alias| string_literal_value(file, alias)),
+        });
+    }
+}
+
+/// Collect `use`, out-of-line `mod` and `extern crate` declarations
+/// from a Rust file.
+///
+/// Each imported path becomes its own `ImportRef`. A path that names
+/// a module file (`use crate::util;`) is treated like a wildcard
+/// import of that module so that `util::helper()` resolves.
+pub(crate) fn rust_imports(file: &ParsedFile) -> Vec<ImportRef> {
+    let root = file.tree.root_node();
+    let mut imports = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "use_declaration" => {}
+            "mod_item" if node.child_by_field_name("body").is_none() => {
+                imports.extend(rust_mod_import(file, node));
+                continue;
+            }
+            "extern_crate_declaration" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    let module = node_text(file, name);
+                    imports.push(ImportRef {
+                        files: rust_crate_root(&file.path, &module).into_iter().collect(),
+                        module,
+                        kind: DependencyKind::ExternCrate,
+                        names: Vec::new(),
+                        wildcard: false,
+                        reexport: false,
+                        test_only: false,
+                        line: node.start_position().row as u32 + 1,
+                    });
+                }
+                continue;
+            }
+            _ => {
+                let mut cursor = node.walk();
+                // Function bodies and inline modules may contain
+                // declarations too.
+                stack.extend(node.named_children(&mut cursor));
+                continue;
+            }
+        }
+
+        let Some(argument) = node.child_by_field_name("argument") else {
+            continue;
+        };
+        let mut cursor = node.walk();
+        let reexport = node
+            .named_children(&mut cursor)
+            .any(|child| child.kind() == "visibility_modifier");
+
+        let mut items = Vec::new();
+        rust_flatten_use(file, argument, Vec::new(), &mut items);
+
+        let inline_depth = rust_inline_depth(node);
+        let test_only = is_test_symbol(file, node, &symbol_annotations(file, node));
+        for item in items {
+            if let Some(import) = rust_import_for_item(&file.path, item, reexport, inline_depth) {
+                imports.push(ImportRef {
+                    line: node.start_position().row as u32 + 1,
+                    test_only,
+                    ..import
+                });
+            }
+        }
+    }
+
+    imports.sort_by_key(|import| import.line);
+    imports
+}
+
+/// A single flattened `use` path: segments, optional alias, and
+/// whether it ends in `::*`.
+struct UseItem {
+    segments: Vec<String>,
+    alias: Option<String>,
+    wildcard: bool,
+}
+
+fn rust_path_segments(file: &ParsedFile, node: Node, out: &mut Vec<String>) {
+    match node.kind() {
+        "scoped_identifier" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                rust_path_segments(file, path, out);
+            }
+            if let Some(name) = node.child_by_field_name("name") {
+                out.push(node_text(file, name));
+            }
+        }
+        _ => out.push(node_text(file, node)),
+    }
+}
+
+fn rust_flatten_use(file: &ParsedFile, node: Node, prefix: Vec<String>, out: &mut Vec<UseItem>) {
+    match node.kind() {
+        "use_as_clause" => {
+            let mut segments = prefix;
+            if let Some(path) = node.child_by_field_name("path") {
+                rust_path_segments(file, path, &mut segments);
+            }
+            out.push(UseItem {
+                segments,
+                alias: node
+                    .child_by_field_name("alias")
+                    .map(|alias| node_text(file, alias)),
+                wildcard: false,
+            });
+        }
+        "scoped_use_list" => {
+            let mut segments = prefix;
+            if let Some(path) = node.child_by_field_name("path") {
+                rust_path_segments(file, path, &mut segments);
+            }
+            if let Some(list) = node.child_by_field_name("list") {
+                rust_flatten_use(file, list, segments, out);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                rust_flatten_use(file, child, prefix.clone(), out);
+            }
+        }
+        "use_wildcard" => {
+            let mut segments = prefix;
+            let mut cursor = node.walk();
+            if let Some(path) = node.named_children(&mut cursor).next() {
+                rust_path_segments(file, path, &mut segments);
+            }
+            out.push(UseItem {
+                segments,
+                alias: None,
+                wildcard: true,
+            });
+        }
+        "identifier" | "scoped_identifier" | "crate" | "self" | "super" => {
+            let mut segments = prefix;
+            rust_path_segments(file, node, &mut segments);
+            out.push(UseItem {
+                segments,
+                alias: None,
+                wildcard: false,
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Number of inline `mod name { ... }` blocks enclosing `node`.
+fn rust_inline_depth(node: Node) -> usize {
+    let mut depth = 0;
+    let mut parent = node.parent();
+    while let Some(current) = parent {
+        if current.kind() == "mod_item" {
+            depth += 1;
+        }
+        parent = current.parent();
+    }
+    depth
+}
+
+/// Rewrite `segments` written inside `inline_depth` inline modules so
+/// that they are relative to the file's own module: the first
+/// `inline_depth` `super`s stay within the file (`mod tests { use
+/// super::*; }` imports from the file itself).
+fn rust_file_relative_path(segments: &[String], inline_depth: usize) -> Vec<String> {
+    let supers = segments
+        .iter()
+        .take_while(|segment| *segment == "super")
+        .count();
+    let within_file = supers.min(inline_depth);
+    if within_file == 0 {
+        return segments.to_vec();
+    }
+
+    let mut relative = Vec::with_capacity(segments.len());
+    if within_file == supers {
+        relative.push("self".to_string());
+    }
+    relative.extend(segments[within_file..].iter().cloned());
+    relative
+}
+
+fn rust_import_for_item(
+    importer: &Path,
+    item: UseItem,
+    reexport: bool,
+    inline_depth: usize,
+) -> Option<ImportRef> {
+    let mut segments = item.segments;
+    // `use a::b::{self}` imports the module `a::b` itself.
+    if segments.last().is_some_and(|last| last == "self") && segments.len() > 1 {
+        segments.pop();
+    }
+    let resolvable = rust_file_relative_path(&segments, inline_depth);
+
+    if item.wildcard {
+        return Some(ImportRef {
+            module: segments.join("::"),
+            kind: DependencyKind::Use,
+            names: Vec::new(),
+            wildcard: true,
+            reexport,
+            test_only: false,
+            files: resolve_rust_module(importer, &resolvable)
+                .into_iter()
+                .collect(),
+            line: 0,
+        });
+    }
+
+    let (name, module) = segments.split_last()?;
+
+    // A path naming a whole module file behaves like a glob import of
+    // that module for the purposes of call resolution.
+    if let Some(module_file) = resolve_rust_module_file(importer, &resolvable) {
+        return Some(ImportRef {
+            module: segments.join("::"),
+            kind: DependencyKind::Use,
+            names: Vec::new(),
+            wildcard: true,
+            reexport,
+            test_only: false,
+            files: vec![module_file],
+            line: 0,
+        });
+    }
+
+    Some(ImportRef {
+        module: module.join("::"),
+        kind: DependencyKind::Use,
+        names: vec![ImportedName {
+            name: name.clone(),
+            alias: item.alias.filter(|alias| alias != "_"),
+        }],
+        wildcard: false,
+        reexport,
+        test_only: false,
+        files: resolve_rust_module(importer, &resolvable[..resolvable.len() - 1])
+            .into_iter()
+            .collect(),
+        line: 0,
+    })
+}
+
+/// Directory holding the crate root (`lib.rs` or `main.rs`) for
+/// `file`, searching upward from its directory.
+fn rust_crate_root_dir(file: &Path) -> Option<PathBuf> {
+    let mut dir = file.parent();
+    while let Some(current) = dir {
+        if current.join("lib.rs").is_file() || current.join("main.rs").is_file() {
+            return Some(current.to_path_buf());
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// Resolve a module path to the innermost file that exists on disk.
+///
+/// Segments past the last module file (inline `mod` blocks, or the
+/// item itself) are ignored, so `crate::a::inner` resolves to `a.rs`
+/// when `inner` is declared inline there.
+fn resolve_rust_module(importer: &Path, segments: &[String]) -> Option<PathBuf> {
+    resolve_rust_path(importer, segments).map(|(file, _)| file)
+}
+
+/// Like `resolve_rust_module`, but only succeeds when every segment
+/// maps to a module file.
+fn resolve_rust_module_file(importer: &Path, segments: &[String]) -> Option<PathBuf> {
+    match resolve_rust_path(importer, segments) {
+        Some((file, consumed)) if consumed == segments.len() => Some(file),
+        _ => None,
+    }
+}
+
+/// Walk `segments` from the module they are anchored at, returning the
+/// last module file reached and how many segments it consumed.
+fn resolve_rust_path(importer: &Path, segments: &[String]) -> Option<(PathBuf, usize)> {
+    let importer = normalize_path(importer);
+    let crate_root = rust_crate_root_dir(&importer);
+    let mut dir = rust_module_dir(&importer);
+    let mut file = importer.clone();
+    let mut consumed = 0;
+
+    match segments.first().map(String::as_str) {
+        Some("crate") => {
+            dir = crate_root.clone()?;
+            file = rust_file_for_module_dir(&dir, crate_root.as_deref())?;
+            consumed = 1;
+        }
+        Some("self") => consumed = 1,
+        Some("super") => {
+            while segments.get(consumed).map(String::as_str) == Some("super") {
+                dir = dir.parent()?.to_path_buf();
+                consumed += 1;
+            }
+            file = rust_file_for_module_dir(&dir, crate_root.as_deref())?;
+        }
+        // Paths that start with a module declared in the current file
+        // (2018-style relative paths), or with another crate of the
+        // package or workspace. Anything else names an external crate
+        // and is left unresolved below.
+        Some(first) => {
+            let is_local = rust_file_for_module_dir(&dir.join(first), None).is_some();
+            if let Some(root) = rust_crate_root(&importer, first).filter(|_| !is_local) {
+                dir = root.parent().unwrap_or(Path::new("")).to_path_buf();
+                file = root;
+                consumed = 1;
+            }
+        }
+        None => {}
+    }
+
+    let anchored = consumed > 0;
+    for segment in &segments[consumed..] {
+        let child = dir.join(segment);
+        match rust_file_for_module_dir(&child, None) {
+            Some(child_file) => {
+                dir = child;
+                file = child_file;
+                consumed += 1;
+            }
+            None => break,
+        }
+    }
+
+    (anchored || consumed > 0).then_some((file, consumed))
+}
+
+/// Collect `#include` directives from a C/C++ file.
+///
+/// Quoted includes are resolved relative to the including file. With a
+/// compile database, includes that are not found there are looked up
+/// in the file's include search paths, and `#ifdef` / `#ifndef`
+/// branches ruled out by command-line macros are skipped. Includes
+/// that cannot be resolved (usually system headers) are recorded
+/// without a file.
+pub(crate) fn cpp_includes(file: &ParsedFile, build: Option<&CompileDatabase>) -> Vec<ImportRef> {
+    let flags = build.and_then(|build| Some((build, build.flags_for(&file.path)?)));
+    let root = file.tree.root_node();
+    let mut imports = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if node.kind() == "preproc_ifdef" {
+            if let Some(active) = flags.and_then(|(_, flags)| cpp_ifdef_active(file, node, flags)) {
+                let alternative = node.child_by_field_name("alternative");
+                if !active {
+                    stack.extend(alternative);
+                    continue;
+                }
+                let mut cursor = node.walk();
+                stack.extend(
+                    node.named_children(&mut cursor)
+                        .filter(|child| Some(*child) != alternative),
+                );
+                continue;
+            }
+        }
+        if node.kind() != "preproc_include" {
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+            continue;
+        }
+        let Some(path) = node.child_by_field_name("path") else {
+            continue;
+        };
+        let (module, quoted) = match path.kind() {
+            "string_literal" => (string_literal_value(file, path), true),
+            "system_lib_string" => (
+                node_text(file, path)
+                    .trim_matches(|c| c == '<' || c == '>')
+                    .to_string(),
+                false,
+            ),
+            _ => continue,
+        };
+        let dir = file.path.parent().unwrap_or(Path::new(""));
+        let local = quoted
+            .then(|| normalize_path(&dir.join(&module)))
+            .filter(|candidate| candidate.is_file());
+        let files = local.or_else(|| {
+            let (build, flags) = flags?;
+            build.resolve_include(&file.path, flags, &module, quoted)
+        });
+
+        imports.push(ImportRef {
+            files: files.into_iter().collect(),
+            module,
+            kind: DependencyKind::Include,
+            names: Vec::new(),
+            wildcard: true,
+            reexport: false,
+            test_only: false,
+            line: node.start_position().row as u32 + 1,
+        });
+    }
+
+    imports.sort_by_key(|import| import.line);
+    imports
+}
+
+/// Whether the body of an `#ifdef NAME` / `#ifndef NAME` block is
+/// compiled, given the command-line macros. `None` when the flags do
+/// not mention the macro.
+fn cpp_ifdef_active(file: &ParsedFile, node: Node, flags: &CompileFlags) -> Option<bool> {
+    let name = node_text(file, node.child_by_field_name("name")?);
+    let defined = flags.is_defined(&name)?;
+    let negated = node.child(0).is_some_and(|token| token.kind() == "#ifndef");
+    Some(defined != negated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::backend_for_path;
+    use std::fs;
+
+    fn parse(path: &str) -> ParsedFile {
+        let path = Path::new(path);
+        let source = fs::read_to_string(path).expect("fixture source");
+        backend_for_path(path)
+            .expect("backend")
+            .parse_file(path, &source)
+            .expect("parsed")
+    }
+
+    #[test]
+    fn normalize_path_resolves_dot_components() {
+        assert_eq!(
+            normalize_path(Path::new("a/./b/../c.ts")),
+            PathBuf::from("a/c.ts")
+        );
+        assert_eq!(normalize_path(Path::new("../a/b")), PathBuf::from("../a/b"));
+    }
+
+    #[test]
+    fn es_imports_resolve_relative_specifiers() {
+        let file = parse("tests/fixtures/cross_file_repo/ts/app.ts");
+        let imports = es_module_imports(&file);
+
+        let math = imports
+            .iter()
+            .find(|import| import.module == "./math")
+            .expect("./math import");
+        assert_eq!(
+            math.files,
+            vec![PathBuf::from("tests/fixtures/cross_file_repo/ts/math.ts")]
+        );
+        assert!(math
+            .names
+            .iter()
+            .any(|name| name.name == "scale" && name.alias.as_deref() == Some("resize")));
+
+        let lib = imports
+            .iter()
+            .find(|import| import.module == "./lib")
+            .expect("./lib import");
+        assert_eq!(
+            lib.files,
+            vec![PathBuf::from(
+                "tests/fixtures/cross_file_repo/ts/lib/index.ts"
+            )]
+        );
+    }
+
+    #[test]
+    fn es_imports_include_side_effects_and_require_calls() {
+        let file = parse("tests/fixtures/deps_repo/ts/app.ts");
+        let imports = es_module_imports(&file);
+
+        let modules: Vec<(&str, DependencyKind)> = imports
+            .iter()
+            .map(|import| (import.module.as_str(), import.kind))
+            .collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("./parser", DependencyKind::Import),
+                ("./polyfill", DependencyKind::Import),
+                ("express", DependencyKind::Import),
+                ("./logger", DependencyKind::Require),
+            ]
+        );
+        assert!(imports[2].files.is_empty(), "packages are not resolved");
+
+        // Destructured `require` bindings resolve calls like imports.
+        let logger = &imports[3];
+        assert_eq!(logger.names[0].name, "log");
+        assert_eq!(
+            logger.files,
+            vec![PathBuf::from("tests/fixtures/deps_repo/ts/logger.ts")]
+        );
+    }
+
+    #[test]
+    fn rust_imports_include_mod_and_extern_crate_declarations() {
+        let file = parse("tests/fixtures/deps_repo/rust/src/lib.rs");
+        let imports = rust_imports(&file);
+
+        let alloc = &imports[0];
+        assert_eq!(
+            (alloc.module.as_str(), alloc.kind),
+            ("alloc", DependencyKind::ExternCrate)
+        );
+        assert!(alloc.files.is_empty());
+
+        let net = imports
+            .iter()
+            .find(|import| import.kind == DependencyKind::Mod && import.module == "net")
+            .expect("mod net");
+        assert_eq!(
+            net.files,
+            vec![PathBuf::from("tests/fixtures/deps_repo/rust/src/net/mod.rs")]
+        );
+        assert!(net.names.is_empty() && !net.wildcard);
+    }
+
+    #[test]
+    fn rust_super_inside_inline_modules_starts_from_the_file() {
+        let file = parse("tests/fixtures/deps_repo/rust/src/util.rs");
+        let imports = rust_imports(&file);
+
+        let own = imports
+            .iter()
+            .find(|import| import.module == "super")
+            .expect("use super::*");
+        assert_eq!(
+            own.files,
+            vec![PathBuf::from("tests/fixtures/deps_repo/rust/src/util.rs")]
+        );
+
+        let net = imports
+            .iter()
+            .find(|import| import.module == "super::super::net")
+            .expect("use super::super::net");
+        assert_eq!(
+            net.files,
+            vec![PathBuf::from("tests/fixtures/deps_repo/rust/src/net/mod.rs")]
+        );
+        // Both statements sit in the `#[cfg(test)]` module.
+        assert!(own.test_only && net.test_only);
+    }
+
+    #[test]
+    fn rust_use_paths_resolve_to_module_files() {
+        let file = parse("tests/fixtures/cross_file_repo/rust/src/main.rs");
+        let imports = rust_imports(&file);
+
+        let helper = imports
+            .iter()
+            .find(|import| import.names.iter().any(|name| name.name == "normalize"))
+            .expect("normalize import");
+        assert_eq!(helper.module, "crate::util");
+        assert_eq!(
+            helper.files,
+            vec![PathBuf::from(
+                "tests/fixtures/cross_file_repo/rust/src/util.rs"
+            )]
+        );
+
+        let nested = imports
+            .iter()
+            .find(|import| import.module == "crate::util::text")
+            .expect("module import");
+        assert!(nested.wildcard);
+        assert_eq!(
+            nested.files,
+            vec![PathBuf::from(
+                "tests/fixtures/cross_file_repo/rust/src/util/text/mod.rs"
+            )]
+        );
+    }
+
+    #[test]
+    fn cpp_includes_resolve_quoted_paths() {
+        let file = parse("tests/fixtures/cross_file_repo/cpp/main.cpp");
+        let imports = cpp_includes(&file, None);
+
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].module, "cstdio");
+        assert!(imports[0].files.is_empty(), "system includes are external");
+        assert_eq!(imports[1].module, "math.h");
+        assert_eq!(imports[1].kind, DependencyKind::Include);
+        assert_eq!(
+            imports[1].files,
+            vec![PathBuf::from("tests/fixtures/cross_file_repo/cpp/math.h")]
+        );
+    }
+}
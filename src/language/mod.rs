@@ -14,13 +14,34 @@ use std::path::{Path, PathBuf};
 
 use tree_sitter::{Node, Point, Tree};
 
-use crate::models::{ContextInfo, ContextKind, Symbol, TextRange};
-
+use crate::models::{
+    ContextInfo, ContextKind, ImportRef, MemberKey, Symbol, SymbolAttributes, SyntaxScope,
+    TextRange, TypeRelation,
+};
+
+mod calls;
+mod cargo;
+mod compile_commands;
 mod cpp;
+mod doc;
+mod es_modules;
+mod imports;
 mod javascript;
+mod project;
+mod relations;
 mod rust;
 mod typescript;
 
+pub(crate) use calls::{
+    add_import_targets, resolve_cross_file_calls, scan_file, scan_parsed_file, FileSymbols,
+    MAX_REEXPORT_DEPTH,
+};
+#[cfg(test)]
+pub(crate) use calls::SCANNED_PATHS;
+pub(crate) use cargo::{rust_module_dir, rust_module_path};
+pub use compile_commands::{CompileDatabase, CompileFlags};
+pub(crate) use imports::normalize_path;
+
 /// Minimal error type for language backends.
 ///
 /// This keeps details internal to the language layer while allowing
@@ -147,6 +168,27 @@ pub(crate) fn find_symbol_node<'a>(file: &'a ParsedFile, symbol: &Symbol) -> Opt
     root.descendant_for_point_range(start, end)
 }
 
+/// Name of the component a JSX opening or self-closing element renders.
+///
+/// `<Button />` renders `Button` and `<Menu.Item>` renders `Menu`.
+/// Lowercase tags such as `<div>` are intrinsic elements and fragments
+/// have no name, so both yield `None`.
+pub(crate) fn jsx_component_name(file: &ParsedFile, element: Node) -> Option<String> {
+    let tag = element.child_by_field_name("name")?;
+    let mut name = tag;
+    while name.kind() == "member_expression" {
+        name = name.child_by_field_name("object")?;
+    }
+    if name.kind() != "identifier" {
+        return None;
+    }
+
+    let text = name.utf8_text(file.source().as_bytes()).ok()?;
+    let is_component = tag.kind() == "member_expression"
+        || text.chars().next().is_some_and(|c| c.is_ascii_uppercase());
+    is_component.then(|| text.to_string())
+}
+
 /// Construct a file-level context node for use as the outermost entry
 /// in a parent chain.
 pub(crate) fn file_context_node(file: &ParsedFile) -> crate::models::ContextNode {
@@ -259,7 +301,8 @@ fn classify_comment_line(line: &str) -> CommentLineKind {
 /// The returned `TextRange` covers the full comment block in the
 /// original source (including delimiters and indentation), while the
 /// normalized text strips comment delimiters for use in queries and
-/// JSON APIs.
+/// JSON APIs. Empty comment lines inside the block are kept as a
+/// single empty line so paragraph breaks survive.
 pub(crate) fn collect_leading_comment<F>(
     source: &str,
     start_line: u32,
@@ -329,6 +372,10 @@ where
             }
             CommentLineKind::Delimiter => {
                 saw_any = true;
+                // Empty comment lines separate paragraphs.
+                if collected.last().is_some_and(|line| !line.is_empty()) {
+                    collected.push(String::new());
+                }
                 min_idx = Some(min_idx.map_or(idx, |current| current.min(idx)));
                 max_idx = Some(max_idx.map_or(idx, |current| current.max(idx)));
                 if idx == 0 {
@@ -340,6 +387,10 @@ where
         }
     }
 
+    while collected.last().is_some_and(|line| line.is_empty()) {
+        collected.pop();
+    }
+
     if collected.is_empty() {
         None
     } else {
@@ -364,6 +415,218 @@ where
     }
 }
 
+/// Collect the attributes and decorators attached to a symbol node,
+/// in source order and without their delimiters.
+///
+/// Handles Rust outer attributes (`#[derive(Debug)]` → `derive(Debug)`,
+/// preceding `attribute_item` siblings), TS/JS decorators
+/// (`@Component({...})` → `Component({...})`, on the class, its
+/// `export` statement, or preceding a method in the class body) and
+/// C++ attributes (`[[nodiscard, gnu::cold]]` → `nodiscard`,
+/// `gnu::cold`). Whitespace runs are collapsed to single spaces.
+pub(crate) fn symbol_annotations(file: &ParsedFile, node: Node) -> Vec<String> {
+    let source = file.source().as_bytes();
+    let mut nodes = Vec::new();
+
+    // Preceding siblings: Rust attribute items and decorators of
+    // methods, stopping at the previous item. Comments between
+    // attributes are skipped.
+    let mut preceding = Vec::new();
+    let mut sibling = node.prev_sibling();
+    while let Some(prev) = sibling {
+        match prev.kind() {
+            "attribute_item" => preceding.extend(prev.named_child(0)),
+            "decorator" => preceding.push(prev),
+            "line_comment" | "block_comment" | "comment" => {}
+            _ => break,
+        }
+        sibling = prev.prev_sibling();
+    }
+    preceding.reverse();
+    nodes.extend(preceding);
+
+    // Decorators of an exported class sit on the `export` statement.
+    let owners = node
+        .parent()
+        .filter(|parent| parent.kind() == "export_statement")
+        .into_iter()
+        .chain(std::iter::once(node));
+    for owner in owners {
+        let mut cursor = owner.walk();
+        for child in owner.named_children(&mut cursor) {
+            match child.kind() {
+                "decorator" => nodes.push(child),
+                "attribute_declaration" => {
+                    let mut inner = child.walk();
+                    nodes.extend(
+                        child
+                            .named_children(&mut inner)
+                            .filter(|attr| attr.kind() == "attribute"),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    nodes
+        .into_iter()
+        .filter_map(|annotation| {
+            // A decorator's expression follows its `@`.
+            let annotation = match annotation.kind() {
+                "decorator" => annotation.named_child(0)?,
+                _ => annotation,
+            };
+            let text = annotation.utf8_text(source).ok()?;
+            Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+        .collect()
+}
+
+/// Test-framework functions whose callbacks hold test code
+/// (Jest/Mocha/Vitest/Jasmine), including `describe.each(...)` and
+/// `it.only` forms.
+const TEST_CALLEES: &[&str] = &[
+    "describe",
+    "fdescribe",
+    "xdescribe",
+    "it",
+    "fit",
+    "xit",
+    "test",
+    "xtest",
+    "suite",
+    "specify",
+    "beforeEach",
+    "afterEach",
+    "beforeAll",
+    "afterAll",
+];
+
+/// Whether an annotation marks test code: a test attribute (`test`,
+/// `tokio::test`, `rstest`, `bench`) or `cfg(test)`.
+fn is_test_annotation(annotation: &str) -> bool {
+    let compact: String = annotation.chars().filter(|c| !c.is_whitespace()).collect();
+    let path = compact.split('(').next().unwrap_or_default();
+    compact == "cfg(test)"
+        || matches!(path, "test" | "bench" | "rstest" | "test_case")
+        || path.ends_with("::test")
+}
+
+/// Root identifier of a call's callee: `describe` for `describe(...)`,
+/// `describe.each(table)(...)` and `it.only(...)`.
+fn callee_root(call: Node) -> Option<Node> {
+    let mut current = call.child_by_field_name("function")?;
+    loop {
+        current = match current.kind() {
+            "identifier" => return Some(current),
+            "member_expression" => current.child_by_field_name("object")?,
+            "call_expression" => current.child_by_field_name("function")?,
+            _ => return None,
+        };
+    }
+}
+
+/// Whether a symbol node is test code, judging from its own
+/// annotations (`annotations`, see `symbol_annotations`) and its
+/// enclosing items: Rust items under a `#[cfg(test)]` module or a
+/// `#[test]` function, and TS/JS symbols inside a `describe`/`it`/
+/// `test` callback.
+pub(crate) fn is_test_symbol(file: &ParsedFile, node: Node, annotations: &[String]) -> bool {
+    if annotations.iter().any(|annotation| is_test_annotation(annotation)) {
+        return true;
+    }
+
+    let source = file.source().as_bytes();
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        let is_test = match parent.kind() {
+            "mod_item" | "function_item" | "impl_item" => symbol_annotations(file, parent)
+                .iter()
+                .any(|annotation| is_test_annotation(annotation)),
+            "call_expression" => callee_root(parent)
+                .and_then(|callee| callee.utf8_text(source).ok())
+                .is_some_and(|callee| TEST_CALLEES.contains(&callee)),
+            _ => false,
+        };
+        if is_test {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether a path, relative to the search root, names a test file by
+/// convention: a file under a `test`, `tests` or `__tests__`
+/// directory, `*.test.*` / `*.spec.*`, `*_test.*` / `*_tests.*` /
+/// `*_unittest.*` / `*_spec.*`, `test_*.*`, or a `tests.rs` module.
+pub(crate) fn is_test_path(path: &Path) -> bool {
+    let in_test_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|component| {
+            matches!(
+                component.as_os_str().to_str(),
+                Some("test" | "tests" | "__tests__")
+            )
+        })
+    });
+    if in_test_dir {
+        return true;
+    }
+
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let stem = name.split('.').next().unwrap_or_default();
+    name.contains(".test.")
+        || name.contains(".spec.")
+        || ["_test", "_tests", "_unittest", "_spec"]
+            .iter()
+            .any(|suffix| stem.ends_with(suffix))
+        || stem.starts_with("test_")
+        || stem == "tests"
+}
+
+/// Whether `path` is a test file by convention (see `is_test_path`),
+/// judged relative to whichever of `roots` contains it so directories
+/// above the search root (such as a fixture tree under `tests/`) do
+/// not count.
+pub(crate) fn is_test_file(path: &Path, roots: &[PathBuf]) -> bool {
+    let relative = roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    if relative.as_os_str().is_empty() {
+        // The root is the file itself.
+        return path.file_name().is_some_and(|name| is_test_path(Path::new(name)));
+    }
+    is_test_path(relative)
+}
+
+/// Build a symbol's source-derived attributes from its leading comment,
+/// annotations and test classification, or `None` when it has none of
+/// them. The comment and annotations are also parsed into `doc`.
+pub(crate) fn source_symbol_attributes(
+    comment: Option<(String, TextRange)>,
+    annotations: Vec<String>,
+    is_test: bool,
+) -> Option<SymbolAttributes> {
+    if comment.is_none() && annotations.is_empty() && !is_test {
+        return None;
+    }
+    let (comment, comment_range) = comment.unzip();
+    let doc = doc::parse_doc_comment(comment.as_deref(), &annotations);
+    Some(SymbolAttributes {
+        comment,
+        comment_range,
+        keywords: Vec::new(),
+        description: None,
+        annotations,
+        is_test,
+        doc,
+    })
+}
+
 /// Helper to construct a basic context snippet for a symbol using its
 /// recorded `TextRange`.
 pub(crate) fn basic_context_snippet(
@@ -425,6 +688,43 @@ pub(crate) fn basic_context_snippet(
     }
 }
 
+/// Classify the syntactic region containing a 0-based `row` and byte
+/// `column`.
+///
+/// Node kinds are matched by name so this works across grammars:
+/// anything whose kind ends in `comment` is a comment, and the usual
+/// string/char literal kinds are strings. Interpolations inside
+/// template strings (`${...}`) count as code.
+pub(crate) fn syntax_scope_at(tree: &Tree, row: usize, column: usize) -> SyntaxScope {
+    let point = Point { row, column };
+    let mut current = tree.root_node().descendant_for_point_range(point, point);
+
+    while let Some(node) = current {
+        let kind = node.kind();
+        if kind.ends_with("comment") {
+            return SyntaxScope::Comments;
+        }
+        if matches!(kind, "template_substitution" | "interpolation") {
+            return SyntaxScope::Code;
+        }
+        if matches!(
+            kind,
+            "string"
+                | "string_literal"
+                | "raw_string_literal"
+                | "char_literal"
+                | "template_string"
+                | "concatenated_string"
+                | "system_lib_string"
+        ) {
+            return SyntaxScope::Strings;
+        }
+        current = node.parent();
+    }
+
+    SyntaxScope::Code
+}
+
 /// Common interface implemented by all language backends.
 ///
 /// The trait is intentionally minimal in Phase 2: it focuses on
@@ -440,6 +740,24 @@ pub trait LanguageBackend: Sync + Send {
     /// Examples: `["ts", "tsx"]`, `["js", "jsx"]`.
     fn file_extensions(&self) -> &'static [&'static str];
 
+    /// Tree-sitter grammar used for `path` (some backends pick a
+    /// dialect by extension, e.g. TSX).
+    fn grammar(&self, path: &Path) -> tree_sitter::Language;
+
+    /// Parse source into a syntax tree, keeping trees that contain
+    /// parse errors.
+    ///
+    /// Unlike `parse_file`, this is meant for purely syntactic
+    /// questions (e.g. "is this position inside a comment?") where an
+    /// error elsewhere in the file should not discard the whole tree.
+    fn parse_syntax_tree(&self, path: &Path, source: &str) -> BackendResult<Tree> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&self.grammar(path))?;
+        parser
+            .parse(source, None)
+            .ok_or_else(|| BackendError::new(format!("failed to parse {} source", self.id())))
+    }
+
     /// Parse a file's source into a `ParsedFile`.
     ///
     /// Implementations should return an error if tree-sitter fails to
@@ -454,6 +772,48 @@ pub trait LanguageBackend: Sync + Send {
         Ok(Vec::new())
     }
 
+    /// Import/`use`/`#include` statements in a parsed file, with
+    /// module specifiers resolved to files on disk where possible.
+    ///
+    /// `build` is the project's compile database, when one is
+    /// configured; only the C++ backend uses it (for include paths and
+    /// command-line macros).
+    ///
+    /// Used to resolve call edges across files. The default
+    /// implementation returns an empty list.
+    fn imports(
+        &self,
+        _file: &ParsedFile,
+        _build: Option<&CompileDatabase>,
+    ) -> BackendResult<Vec<ImportRef>> {
+        Ok(Vec::new())
+    }
+
+    /// `extends`/`implements` relations declared in a parsed file.
+    ///
+    /// Used to build type hierarchies. The default implementation
+    /// returns an empty list.
+    fn type_relations(&self, _file: &ParsedFile) -> BackendResult<Vec<TypeRelation>> {
+        Ok(Vec::new())
+    }
+
+    /// Member functions declared in class bodies and defined out of
+    /// line in a parsed file.
+    ///
+    /// Used to pair declarations with their definitions across files.
+    /// The default implementation returns an empty list.
+    fn member_keys(&self, _file: &ParsedFile) -> BackendResult<Vec<MemberKey>> {
+        Ok(Vec::new())
+    }
+
+    /// Whether `member_keys` can pair declarations with definitions in
+    /// other files, so symbol searches must scan every file before
+    /// reporting any symbol. The default implementation returns
+    /// `false`.
+    fn pairs_members_across_files(&self) -> bool {
+        false
+    }
+
     /// Return a context snippet for a given symbol.
     ///
     /// By default this returns an error; backends that implement
@@ -482,6 +842,11 @@ static BACKENDS: [&'static dyn LanguageBackend; 4] = [
     &rust::BACKEND,
 ];
 
+/// Iterate over every registered backend.
+pub(crate) fn all_backends() -> impl Iterator<Item = &'static dyn LanguageBackend> {
+    BACKENDS.iter().copied()
+}
+
 /// Look up a backend by file path, using the extension to infer
 /// language.
 ///
@@ -926,11 +1291,11 @@ mod tests {
 
         assert_eq!(names[0], "sample.cpp");
         assert!(
-            names.iter().any(|n| *n == "util"),
+            names.contains(&"util"),
             "expected namespace 'util' in parent_chain"
         );
         assert!(
-            names.iter().any(|n| *n == "Widget"),
+            names.contains(&"Widget"),
             "expected class 'Widget' in parent_chain"
         );
 
@@ -967,11 +1332,11 @@ mod tests {
 
         assert_eq!(names[0], "lib.rs");
         assert!(
-            names.iter().any(|n| *n == "my_mod"),
+            names.contains(&"my_mod"),
             "expected module 'my_mod' in parent_chain"
         );
         assert!(
-            names.iter().any(|n| *n == "Widget"),
+            names.contains(&"Widget"),
             "expected type 'Widget' in parent_chain"
         );
 
@@ -1039,6 +1404,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn leading_comment_keeps_paragraph_breaks() {
+        let source = "/**\n * Opens the store\n *\n *\n * Longer description.\n */\nfunction open() {}\n";
+        let (text, range) = collect_leading_comment(source, 7, |_| false).expect("comment");
+        assert_eq!(text, "Opens the store\n\nLonger description.\n/");
+        assert_eq!((range.start_line, range.end_line), (1, 6));
+
+        let source = "/// Opens the store\n///\n/// Longer description.\nfn open() {}\n";
+        let (text, _) = collect_leading_comment(source, 4, |_| false).expect("comment");
+        assert_eq!(text, "Opens the store\n\nLonger description.");
+    }
+
+    #[test]
+    fn is_test_file_uses_conventions_below_the_search_root() {
+        let roots = [PathBuf::from("tests/fixtures/repo")];
+        let is_test = |path: &str| is_test_file(Path::new(path), &roots);
+
+        assert!(is_test("tests/fixtures/repo/web/math.test.ts"));
+        assert!(is_test("tests/fixtures/repo/web/math.spec.js"));
+        assert!(is_test("tests/fixtures/repo/web/__tests__/math.ts"));
+        assert!(is_test("tests/fixtures/repo/tests/cli.rs"));
+        assert!(is_test("tests/fixtures/repo/src/parser/tests.rs"));
+        assert!(is_test("tests/fixtures/repo/cpp/math_test.cc"));
+        assert!(is_test("tests/fixtures/repo/cpp/test_math.py"));
+
+        // `tests/` above the root does not count.
+        assert!(!is_test("tests/fixtures/repo/src/math.ts"));
+        assert!(!is_test("tests/fixtures/repo/src/latest.rs"));
+        assert!(!is_test("tests/fixtures/repo/src/contest/mod.rs"));
+        assert!(is_test_file(
+            Path::new("web/math.test.ts"),
+            &[PathBuf::from("web/math.test.ts")]
+        ));
+    }
+
     #[test]
     fn rust_backend_attaches_leading_doc_comment() {
         let (path, source) = rust_fixture("lib.rs");
@@ -0,0 +1,177 @@
+//! Project files shared by the module resolvers.
+//!
+//! Resolving imports the way a build tool would means reading its
+//! configuration: `tsconfig.json` and `package.json` for ES modules,
+//! `Cargo.toml` for Rust. Those files are parsed once per process,
+//! shared by the worker threads, and kept alongside the modification
+//! times of every file and directory they were built from, so a
+//! long-running daemon reads them again shortly after an edit.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use globset::Glob;
+
+use crate::language::normalize_path;
+
+/// Modification time of a file or directory a cached value was built
+/// from (`None` when it did not exist).
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Stamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl Stamp {
+    pub(crate) fn of(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            modified: fs::metadata(path).and_then(|meta| meta.modified()).ok(),
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        *self == Self::of(&self.path)
+    }
+}
+
+/// How long a cached value is trusted before its stamps are checked
+/// again. Values built from many files (a crate's module tree) are
+/// looked up once per symbol, so they are not re-validated on every
+/// lookup.
+const REVALIDATE_AFTER: Duration = Duration::from_secs(1);
+
+/// How many values a cache keeps. A daemon serving searches over many
+/// projects drops the least recently used values beyond this.
+const CACHE_CAPACITY: usize = 4096;
+
+struct CacheEntry<T> {
+    stamps: Vec<Stamp>,
+    checked: Instant,
+    used: Instant,
+    value: Arc<T>,
+}
+
+/// Values built from project files, keyed by the path they were
+/// loaded for and shared by all threads.
+pub(crate) struct Cache<T> {
+    entries: OnceLock<Mutex<HashMap<PathBuf, CacheEntry<T>>>>,
+}
+
+impl<T> Cache<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: OnceLock::new(),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, CacheEntry<T>>> {
+        self.entries
+            .get_or_init(Default::default)
+            .lock()
+            .expect("project cache mutex poisoned")
+    }
+}
+
+/// The value cached for `path`, built with `load` when missing or
+/// stale. `load` records the files and directories it reads, and may
+/// itself look up other values of the same cache; the cache is not
+/// locked while it runs, so threads racing on a missing value may
+/// each build it.
+pub(crate) fn cached<T>(
+    cache: &Cache<T>,
+    path: &Path,
+    load: impl FnOnce(&mut Vec<Stamp>) -> T,
+) -> Arc<T> {
+    let current = {
+        let mut entries = cache.entries();
+        entries.get_mut(path).and_then(|entry| {
+            if entry.checked.elapsed() >= REVALIDATE_AFTER {
+                if !entry.stamps.iter().all(Stamp::is_current) {
+                    return None;
+                }
+                entry.checked = Instant::now();
+            }
+            entry.used = Instant::now();
+            Some(Arc::clone(&entry.value))
+        })
+    };
+    if let Some(value) = current {
+        return value;
+    }
+
+    let mut stamps = Vec::new();
+    let value = Arc::new(load(&mut stamps));
+    let mut entries = cache.entries();
+    if entries.len() >= CACHE_CAPACITY && !entries.contains_key(path) {
+        let oldest = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.used)
+            .map(|(path, _)| path.clone());
+        if let Some(oldest) = oldest {
+            entries.remove(&oldest);
+        }
+    }
+    let now = Instant::now();
+    entries.insert(
+        path.to_path_buf(),
+        CacheEntry {
+            stamps,
+            checked: now,
+            used: now,
+            value: Arc::clone(&value),
+        },
+    );
+    value
+}
+
+/// Directories matching a workspace member glob such as `packages/*`
+/// or `crates/*`, relative to `root`. Each glob segment matches one
+/// directory level; hidden directories and `node_modules` are
+/// skipped. Directories read are recorded in `stamps`.
+pub(crate) fn expand_member_pattern(
+    root: &Path,
+    pattern: &str,
+    stamps: &mut Vec<Stamp>,
+) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        if !segment.contains(['*', '?', '[', '{']) {
+            dirs = dirs.into_iter().map(|dir| dir.join(segment)).collect();
+            continue;
+        }
+        let Ok(glob) = Glob::new(segment) else {
+            return Vec::new();
+        };
+        let matcher = glob.compile_matcher();
+
+        let mut next = Vec::new();
+        for dir in dirs {
+            stamps.push(Stamp::of(&dir));
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut children: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| {
+                            !name.starts_with('.')
+                                && name != "node_modules"
+                                && matcher.is_match(name)
+                        })
+                })
+                .collect();
+            children.sort();
+            next.extend(children);
+        }
+        dirs = next;
+    }
+    dirs.into_iter().map(|dir| normalize_path(&dir)).collect()
+}
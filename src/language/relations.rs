@@ -0,0 +1,314 @@
+//! Type relation extraction shared by the backends.
+//!
+//! Collects the `extends`/`implements` clauses of a file: TypeScript
+//! and JavaScript class heritage and interface `extends`, C++ base
+//! class clauses, and Rust supertraits and `impl Trait for T` blocks.
+//! Type names are reduced to their last identifier so that
+//! `ns::Base<T>` and `Base` name the same type; resolving names to
+//! definitions is left to the search layer.
+
+use tree_sitter::Node;
+
+use crate::language::ParsedFile;
+use crate::models::{TypeRelation, TypeRelationKind};
+
+fn node_text(file: &ParsedFile, node: Node) -> String {
+    node.utf8_text(file.source().as_bytes())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Simple name of a type (or class-valued expression) node, without
+/// namespace qualifiers or generic arguments.
+///
+/// Returns `None` for expressions that do not name a type, such as
+/// mixin calls (`extends mixin(Base)`).
+fn simple_type_name(file: &ParsedFile, node: Node) -> Option<String> {
+    match node.kind() {
+        "identifier" | "type_identifier" | "property_identifier" => Some(node_text(file, node)),
+        "member_expression" => simple_type_name(file, node.child_by_field_name("property")?),
+        "nested_type_identifier"
+        | "qualified_identifier"
+        | "scoped_type_identifier"
+        | "template_type" => simple_type_name(file, node.child_by_field_name("name")?),
+        // TypeScript names the generic's base `name`, Rust `type`.
+        "generic_type" => node
+            .child_by_field_name("name")
+            .or_else(|| node.child_by_field_name("type"))
+            .and_then(|base| simple_type_name(file, base)),
+        _ => None,
+    }
+}
+
+fn push_relation(
+    file: &ParsedFile,
+    relations: &mut Vec<TypeRelation>,
+    subtype: &str,
+    supertype: Node,
+    kind: TypeRelationKind,
+) {
+    if let Some(name) = simple_type_name(file, supertype) {
+        relations.push(TypeRelation {
+            subtype: subtype.to_string(),
+            supertype: name,
+            kind,
+            line: supertype.start_position().row as u32 + 1,
+        });
+    }
+}
+
+/// Visit `node` and its descendants in document order.
+fn walk(node: Node, visit: &mut dyn FnMut(Node)) {
+    visit(node);
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        walk(child, visit);
+    }
+}
+
+/// Name of a TypeScript/JavaScript class, falling back to the variable
+/// an anonymous class expression is assigned to.
+fn es_class_name(file: &ParsedFile, class: Node) -> Option<String> {
+    if let Some(name) = class.child_by_field_name("name") {
+        return Some(node_text(file, name));
+    }
+    let parent = class.parent()?;
+    if parent.kind() != "variable_declarator" {
+        return None;
+    }
+    Some(node_text(file, parent.child_by_field_name("name")?))
+}
+
+/// Collect class `extends`/`implements` and interface `extends`
+/// clauses from a TypeScript or JavaScript file.
+pub(crate) fn es_type_relations(file: &ParsedFile) -> Vec<TypeRelation> {
+    let mut relations = Vec::new();
+
+    walk(file.tree.root_node(), &mut |node| match node.kind() {
+        "class_declaration" | "abstract_class_declaration" | "class" => {
+            let Some(name) = es_class_name(file, node) else {
+                return;
+            };
+            let mut cursor = node.walk();
+            let Some(heritage) = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "class_heritage")
+            else {
+                return;
+            };
+
+            let mut clauses = heritage.walk();
+            for clause in heritage.named_children(&mut clauses) {
+                match clause.kind() {
+                    "extends_clause" => {
+                        let mut values = clause.walk();
+                        for value in clause.children_by_field_name("value", &mut values) {
+                            push_relation(
+                                file,
+                                &mut relations,
+                                &name,
+                                value,
+                                TypeRelationKind::Extends,
+                            );
+                        }
+                    }
+                    "implements_clause" => {
+                        let mut types = clause.walk();
+                        for ty in clause.named_children(&mut types) {
+                            push_relation(
+                                file,
+                                &mut relations,
+                                &name,
+                                ty,
+                                TypeRelationKind::Implements,
+                            );
+                        }
+                    }
+                    // JavaScript grammars put the superclass expression
+                    // directly under `class_heritage`.
+                    _ => push_relation(
+                        file,
+                        &mut relations,
+                        &name,
+                        clause,
+                        TypeRelationKind::Extends,
+                    ),
+                }
+            }
+        }
+        "interface_declaration" => {
+            let Some(name) = node.child_by_field_name("name") else {
+                return;
+            };
+            let name = node_text(file, name);
+            let mut cursor = node.walk();
+            for clause in node.named_children(&mut cursor) {
+                if clause.kind() != "extends_type_clause" {
+                    continue;
+                }
+                let mut types = clause.walk();
+                for ty in clause.children_by_field_name("type", &mut types) {
+                    push_relation(file, &mut relations, &name, ty, TypeRelationKind::Extends);
+                }
+            }
+        }
+        _ => {}
+    });
+
+    relations
+}
+
+/// Collect base classes of C++ classes and structs. Every base is
+/// reported as `extends`, whatever its access specifier.
+pub(crate) fn cpp_type_relations(file: &ParsedFile) -> Vec<TypeRelation> {
+    let mut relations = Vec::new();
+
+    walk(file.tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class_specifier" | "struct_specifier") {
+            return;
+        }
+        let Some(name) = node
+            .child_by_field_name("name")
+            .and_then(|name| simple_type_name(file, name))
+        else {
+            return;
+        };
+        let mut cursor = node.walk();
+        for clause in node.named_children(&mut cursor) {
+            if clause.kind() != "base_class_clause" {
+                continue;
+            }
+            let mut bases = clause.walk();
+            for base in clause.named_children(&mut bases) {
+                push_relation(file, &mut relations, &name, base, TypeRelationKind::Extends);
+            }
+        }
+    });
+
+    relations
+}
+
+/// Collect Rust supertraits (`trait A: B`, reported as `extends`) and
+/// trait implementations (`impl Trait for T`, reported as
+/// `implements`).
+pub(crate) fn rust_type_relations(file: &ParsedFile) -> Vec<TypeRelation> {
+    let mut relations = Vec::new();
+
+    walk(file.tree.root_node(), &mut |node| match node.kind() {
+        "trait_item" => {
+            let (Some(name), Some(bounds)) = (
+                node.child_by_field_name("name"),
+                node.child_by_field_name("bounds"),
+            ) else {
+                return;
+            };
+            let name = node_text(file, name);
+            let mut cursor = bounds.walk();
+            for bound in bounds.named_children(&mut cursor) {
+                push_relation(
+                    file,
+                    &mut relations,
+                    &name,
+                    bound,
+                    TypeRelationKind::Extends,
+                );
+            }
+        }
+        "impl_item" => {
+            let (Some(trait_node), Some(ty)) = (
+                node.child_by_field_name("trait"),
+                node.child_by_field_name("type"),
+            ) else {
+                return;
+            };
+            if let Some(name) = simple_type_name(file, ty) {
+                push_relation(
+                    file,
+                    &mut relations,
+                    &name,
+                    trait_node,
+                    TypeRelationKind::Implements,
+                );
+            }
+        }
+        _ => {}
+    });
+
+    relations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::backend_for_path;
+    use std::fs;
+    use std::path::Path;
+
+    fn relations(path: &str) -> Vec<(String, String, TypeRelationKind, u32)> {
+        let path = Path::new(path);
+        let source = fs::read_to_string(path).expect("fixture source");
+        let backend = backend_for_path(path).expect("backend");
+        let file = backend.parse_file(path, &source).expect("parsed");
+        backend
+            .type_relations(&file)
+            .expect("relations")
+            .into_iter()
+            .map(|r| (r.subtype, r.supertype, r.kind, r.line))
+            .collect()
+    }
+
+    fn relation(
+        sub: &str,
+        sup: &str,
+        kind: TypeRelationKind,
+        line: u32,
+    ) -> (String, String, TypeRelationKind, u32) {
+        (sub.to_string(), sup.to_string(), kind, line)
+    }
+
+    #[test]
+    fn typescript_classes_and_interfaces_record_heritage() {
+        use TypeRelationKind::*;
+        assert_eq!(
+            relations("tests/fixtures/hierarchy_repo/ts/shapes.ts"),
+            vec![
+                relation("Polygon", "Shape", Extends, 9),
+                relation("Base", "Named", Implements, 13),
+            ]
+        );
+        assert_eq!(
+            relations("tests/fixtures/hierarchy_repo/ts/square.ts"),
+            vec![
+                relation("Square", "Base", Extends, 3),
+                relation("Square", "Polygon", Implements, 3),
+                relation("Cube", "Square", Extends, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn javascript_cpp_and_rust_record_supertypes() {
+        use TypeRelationKind::*;
+        assert_eq!(
+            relations("tests/fixtures/hierarchy_repo/js/widgets.js"),
+            vec![relation("Button", "Widget", Extends, 3)]
+        );
+        assert_eq!(
+            relations("tests/fixtures/hierarchy_repo/cpp/shapes.cpp"),
+            vec![
+                relation("Circle", "Shape", Extends, 7),
+                relation("Ring", "Circle", Extends, 12),
+                relation("Ring", "Tracked", Extends, 12),
+            ]
+        );
+        assert_eq!(
+            relations("tests/fixtures/hierarchy_repo/rust/lib.rs"),
+            vec![
+                relation("Solid", "Shape", Extends, 5),
+                relation("Solid", "Debug", Extends, 5),
+                relation("Cube", "Shape", Implements, 10),
+                relation("Cube", "Solid", Implements, 16),
+            ]
+        );
+    }
+}
@@ -5,9 +5,12 @@ use tree_sitter_rust::LANGUAGE;
 
 use crate::language::{
     collect_leading_comment, context_snippet_for_range, file_context_node, find_symbol_node,
-    node_text_range, BackendError, BackendResult, LanguageBackend, ParsedFile,
+    is_test_symbol, node_text_range, rust_module_path, source_symbol_attributes,
+    symbol_annotations, BackendError, BackendResult, CompileDatabase, LanguageBackend, ParsedFile,
+};
+use crate::models::{
+    CallEdgeKind, CallRef, ContextInfo, ContextKind, ImportRef, Symbol, SymbolKind, TypeRelation,
 };
-use crate::models::{ContextInfo, ContextKind, Symbol, SymbolAttributes, SymbolKind};
 
 /// Tree-sitter backed language implementation for Rust.
 pub struct RustBackend;
@@ -161,12 +164,9 @@ fn rust_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut
                     let trimmed = line.trim_start();
                     trimmed.starts_with("#[") || trimmed.starts_with("#![")
                 });
-                let attributes = comment.map(|(text, comment_range)| SymbolAttributes {
-                    comment: Some(text),
-                    comment_range: Some(comment_range),
-                    keywords: Vec::new(),
-                    description: None,
-                });
+                let annotations = symbol_annotations(file, node);
+                let is_test = is_test_symbol(file, node, &annotations);
+                let attributes = source_symbol_attributes(comment, annotations, is_test);
                 symbols.push(Symbol {
                     name,
                     kind,
@@ -179,6 +179,9 @@ fn rust_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut
                     matches: Vec::new(),
                     calls: Vec::new(),
                     called_by: Vec::new(),
+                    supertypes: Vec::new(),
+                    definition: None,
+                    modules: Vec::new(),
                 });
             }
         }
@@ -194,6 +197,143 @@ fn rust_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut
     }
 }
 
+fn rust_enclosing_symbol_index(
+    symbols: &[Symbol],
+    file_path: &Path,
+    range: crate::models::TextRange,
+) -> Option<usize> {
+    let mut best: Option<(usize, u32)> = None;
+
+    for (idx, symbol) in symbols.iter().enumerate() {
+        if symbol.file != file_path {
+            continue;
+        }
+
+        if !matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method) {
+            continue;
+        }
+
+        if symbol.range.start_line <= range.start_line && symbol.range.end_line >= range.end_line {
+            let span = symbol.range.end_line.saturating_sub(symbol.range.start_line);
+            match best {
+                None => best = Some((idx, span)),
+                Some((_, best_span)) => {
+                    if span <= best_span {
+                        best = Some((idx, span));
+                    }
+                }
+            }
+        }
+    }
+
+    best.map(|(idx, _)| idx)
+}
+
+fn rust_callee_name_from_node(file: &ParsedFile, function: Node) -> Option<String> {
+    let source = file.source();
+
+    match function.kind() {
+        "identifier" => function.utf8_text(source.as_bytes()).ok().map(|s| s.to_string()),
+        "scoped_identifier" => function
+            .child_by_field_name("name")?
+            .utf8_text(source.as_bytes())
+            .ok()
+            .map(|s| s.to_string()),
+        "field_expression" => function
+            .child_by_field_name("field")?
+            .utf8_text(source.as_bytes())
+            .ok()
+            .map(|s| s.to_string()),
+        "generic_function" => {
+            rust_callee_name_from_node(file, function.child_by_field_name("function")?)
+        }
+        _ => None,
+    }
+}
+
+fn rust_callee_name(file: &ParsedFile, call_node: Node) -> Option<String> {
+    let function = call_node.child_by_field_name("function")?;
+    rust_callee_name_from_node(file, function)
+}
+
+fn rust_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
+    if symbols.is_empty() {
+        return;
+    }
+
+    for symbol in symbols.iter_mut() {
+        symbol.calls.clear();
+        symbol.called_by.clear();
+    }
+
+    let root = file.tree.root_node();
+    let mut cursor = root.walk();
+    let mut edges: Vec<(usize, String, u32, u32)> = Vec::new();
+
+    fn visit(
+        file: &ParsedFile,
+        symbols: &[Symbol],
+        cursor: &mut TreeCursor,
+        edges: &mut Vec<(usize, String, u32, u32)>,
+    ) {
+        loop {
+            let node = cursor.node();
+            if node.kind() == "call_expression" {
+                let range = node_text_range(&node);
+                if let Some(caller_idx) = rust_enclosing_symbol_index(symbols, &file.path, range) {
+                    if let Some(callee) = rust_callee_name(file, node) {
+                        edges.push((caller_idx, callee, range.start_line, range.start_column));
+                    }
+                }
+            }
+
+            if cursor.goto_first_child() {
+                visit(file, symbols, cursor, edges);
+                cursor.goto_parent();
+            }
+
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    visit(file, symbols, &mut cursor, &mut edges);
+
+    for (caller_idx, callee_name, line, column) in edges {
+        if caller_idx >= symbols.len() {
+            continue;
+        }
+
+        let caller_name = symbols[caller_idx].name.clone();
+        let caller_file = symbols[caller_idx].file.clone();
+
+        symbols[caller_idx].calls.push(CallRef {
+            name: callee_name.clone(),
+            file: caller_file.clone(),
+            line: Some(line),
+            column: Some(column),
+            kind: None,
+            target_file: None,
+            edge_kind: CallEdgeKind::Call,
+        });
+
+        for symbol in symbols.iter_mut() {
+            if symbol.name == callee_name {
+                symbol.called_by.push(CallRef {
+                    name: caller_name.clone(),
+                    file: caller_file.clone(),
+                    line: Some(line),
+                    column: Some(column),
+                    kind: None,
+                    target_file: None,
+                    edge_kind: CallEdgeKind::Call,
+                });
+            }
+        }
+    }
+}
+
 fn rust_context_node_for_ancestor(
     file: &ParsedFile,
     node: Node,
@@ -238,8 +378,18 @@ fn rust_parent_info<'a>(
         current = node.parent();
     }
 
-    let mut chain = Vec::new();
-    chain.push(file_context_node(file));
+    // Files of a Cargo package start at their crate and module path
+    // (`symgrep::index::sqlite`); other files at the file name.
+    let mut chain = match rust_module_path(&file.path) {
+        Some(path) => std::iter::once(path.crate_name)
+            .chain(path.modules)
+            .map(|name| crate::models::ContextNode {
+                name,
+                kind: Some(SymbolKind::Namespace),
+            })
+            .collect(),
+        None => vec![file_context_node(file)],
+    };
     chain_rev.reverse();
     chain.extend(chain_rev);
 
@@ -256,6 +406,10 @@ impl LanguageBackend for RustBackend {
         &["rs"]
     }
 
+    fn grammar(&self, _path: &Path) -> tree_sitter::Language {
+        LANGUAGE.into()
+    }
+
     fn parse_file(&self, path: &Path, source: &str) -> BackendResult<ParsedFile> {
         let mut parser = Parser::new();
         let language = LANGUAGE.into();
@@ -278,9 +432,22 @@ impl LanguageBackend for RustBackend {
         let mut symbols = Vec::new();
         let mut cursor: TreeCursor = file.tree.root_node().walk();
         rust_visit_symbols(file, &mut cursor, &mut symbols);
+        rust_attach_call_metadata(file, &mut symbols);
         Ok(symbols)
     }
 
+    fn imports(
+        &self,
+        file: &ParsedFile,
+        _build: Option<&CompileDatabase>,
+    ) -> BackendResult<Vec<ImportRef>> {
+        Ok(crate::language::imports::rust_imports(file))
+    }
+
+    fn type_relations(&self, file: &ParsedFile) -> BackendResult<Vec<TypeRelation>> {
+        Ok(crate::language::relations::rust_type_relations(file))
+    }
+
     fn get_context_snippet(
         &self,
         file: &ParsedFile,
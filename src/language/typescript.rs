@@ -5,10 +5,11 @@ use tree_sitter_typescript::{LANGUAGE_TSX, LANGUAGE_TYPESCRIPT};
 
 use crate::language::{
     collect_leading_comment, context_snippet_for_range, file_context_node, find_symbol_node,
-    node_text_range, BackendError, BackendResult, LanguageBackend, ParsedFile,
+    is_test_symbol, jsx_component_name, node_text_range, source_symbol_attributes,
+    symbol_annotations, BackendError, BackendResult, CompileDatabase, LanguageBackend, ParsedFile,
 };
 use crate::models::{
-    CallRef, ContextInfo, ContextKind, Symbol, SymbolAttributes, SymbolKind,
+    CallEdgeKind, CallRef, ContextInfo, ContextKind, ImportRef, Symbol, SymbolKind, TypeRelation,
 };
 
 /// Tree-sitter backed language implementation for TypeScript/TSX.
@@ -123,12 +124,9 @@ fn ts_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut Ve
                 let comment = collect_leading_comment(file.source(), range.start_line, |line| {
                     line.trim_start().starts_with('@')
                 });
-                let attributes = comment.map(|(text, comment_range)| SymbolAttributes {
-                    comment: Some(text),
-                    comment_range: Some(comment_range),
-                    keywords: Vec::new(),
-                    description: None,
-                });
+                let annotations = symbol_annotations(file, node);
+                let is_test = is_test_symbol(file, node, &annotations);
+                let attributes = source_symbol_attributes(comment, annotations, is_test);
                 symbols.push(Symbol {
                     name,
                     kind,
@@ -141,6 +139,9 @@ fn ts_visit_symbols(file: &ParsedFile, cursor: &mut TreeCursor, symbols: &mut Ve
                     matches: Vec::new(),
                     calls: Vec::new(),
                     called_by: Vec::new(),
+                    supertypes: Vec::new(),
+                    definition: None,
+                    modules: Vec::new(),
                 });
             }
         }
@@ -232,24 +233,37 @@ fn ts_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
 
     let root = file.tree.root_node();
     let mut cursor = root.walk();
-    let mut edges: Vec<(usize, String, u32)> = Vec::new();
+    let mut edges: Vec<(usize, String, u32, u32, CallEdgeKind)> = Vec::new();
 
     fn visit(
         file: &ParsedFile,
         symbols: &[Symbol],
         cursor: &mut TreeCursor,
-        edges: &mut Vec<(usize, String, u32)>,
+        edges: &mut Vec<(usize, String, u32, u32, CallEdgeKind)>,
     ) {
         loop {
             let node = cursor.node();
-            if node.kind() == "call_expression" {
+            // JSX elements render a component rather than call it, but
+            // are the closest equivalent of a call site.
+            let callee = match node.kind() {
+                "call_expression" => {
+                    ts_callee_name(file, node).map(|name| (name, CallEdgeKind::Call))
+                }
+                "jsx_opening_element" | "jsx_self_closing_element" => {
+                    jsx_component_name(file, node).map(|name| (name, CallEdgeKind::Render))
+                }
+                _ => None,
+            };
+            if let Some((callee, edge_kind)) = callee {
                 let range = crate::language::node_text_range(&node);
-                if let Some(caller_idx) =
-                    ts_enclosing_symbol_index(symbols, &file.path, range)
-                {
-                    if let Some(callee) = ts_callee_name(file, node) {
-                        edges.push((caller_idx, callee, range.start_line));
-                    }
+                if let Some(caller_idx) = ts_enclosing_symbol_index(symbols, &file.path, range) {
+                    edges.push((
+                        caller_idx,
+                        callee,
+                        range.start_line,
+                        range.start_column,
+                        edge_kind,
+                    ));
                 }
             }
 
@@ -266,7 +280,7 @@ fn ts_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
 
     visit(file, symbols, &mut cursor, &mut edges);
 
-    for (caller_idx, callee_name, line) in edges {
+    for (caller_idx, callee_name, line, column, edge_kind) in edges {
         if caller_idx >= symbols.len() {
             continue;
         }
@@ -278,7 +292,10 @@ fn ts_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
             name: callee_name.clone(),
             file: caller_file.clone(),
             line: Some(line),
+            column: Some(column),
             kind: None,
+            target_file: None,
+            edge_kind,
         });
 
         for symbol in symbols.iter_mut() {
@@ -287,7 +304,10 @@ fn ts_attach_call_metadata(file: &ParsedFile, symbols: &mut [Symbol]) {
                     name: caller_name.clone(),
                     file: caller_file.clone(),
                     line: Some(line),
+                    column: Some(column),
                     kind: None,
+                    target_file: None,
+                    edge_kind,
                 });
             }
         }
@@ -352,6 +372,10 @@ impl LanguageBackend for TypeScriptBackend {
         &["ts", "tsx"]
     }
 
+    fn grammar(&self, path: &Path) -> tree_sitter::Language {
+        self.select_language(path)
+    }
+
     fn parse_file(&self, path: &Path, source: &str) -> BackendResult<ParsedFile> {
         let mut parser = Parser::new();
         let language = self.select_language(path);
@@ -382,6 +406,18 @@ impl LanguageBackend for TypeScriptBackend {
         Ok(symbols)
     }
 
+    fn imports(
+        &self,
+        file: &ParsedFile,
+        _build: Option<&CompileDatabase>,
+    ) -> BackendResult<Vec<ImportRef>> {
+        Ok(crate::language::imports::es_module_imports(file))
+    }
+
+    fn type_relations(&self, file: &ParsedFile) -> BackendResult<Vec<TypeRelation>> {
+        Ok(crate::language::relations::es_type_relations(file))
+    }
+
     fn get_context_snippet(
         &self,
         file: &ParsedFile,
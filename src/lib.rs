@@ -4,3 +4,4 @@ pub mod language;
 pub mod models;
 pub mod search;
 pub mod server;
+mod walk;
@@ -3,6 +3,7 @@
 //! These types form the stable JSON API surface used by the CLI
 //! and future daemon/server modes.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -17,14 +18,36 @@ use serde::{Deserialize, Serialize};
 /// Clients consuming `--format=json` output should check this version
 /// to ensure compatibility and handle newer minor versions
 /// conservatively.
-pub const SEARCH_RESULT_VERSION: &str = "1.2.0";
+pub const SEARCH_RESULT_VERSION: &str = "1.16.0";
 
 /// Schema version for `FollowResult` JSON payloads.
 ///
 /// This version is independent from `SEARCH_RESULT_VERSION` since
 /// follow responses use a separate top-level schema. Additive
 /// changes (new optional fields) should bump the MINOR component.
-pub const FOLLOW_RESULT_VERSION: &str = "1.0.0";
+pub const FOLLOW_RESULT_VERSION: &str = "1.4.0";
+
+/// Schema version for `GraphResult` JSON payloads (`symgrep graph`).
+///
+/// Versioned independently like `FOLLOW_RESULT_VERSION`.
+pub const GRAPH_RESULT_VERSION: &str = "1.1.0";
+
+/// Schema version for `DepsResult` JSON payloads (`symgrep deps`).
+///
+/// Versioned independently like `FOLLOW_RESULT_VERSION`.
+pub const DEPS_RESULT_VERSION: &str = "1.0.0";
+
+/// Schema version for `CheckDepsResult` JSON payloads
+/// (`symgrep check-deps`).
+///
+/// Versioned independently like `FOLLOW_RESULT_VERSION`.
+pub const CHECK_DEPS_RESULT_VERSION: &str = "1.0.0";
+
+/// Schema version for `HierarchyResult` JSON payloads
+/// (`symgrep hierarchy`).
+///
+/// Versioned independently like `FOLLOW_RESULT_VERSION`.
+pub const HIERARCHY_RESULT_VERSION: &str = "1.0.0";
 
 /// High-level search mode.
 ///
@@ -55,9 +78,41 @@ pub enum SymbolView {
     Def,
     Parent,
     Comment,
+    /// The summary line of the symbol's doc comment.
+    Doc,
     Matches,
 }
 
+/// Syntactic region of source code that a text match falls in.
+///
+/// Used by `--in` / `scope:` to restrict text matches to comments,
+/// string literals, or the remaining code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyntaxScope {
+    /// Anything that is not a comment or string literal.
+    Code,
+    Comments,
+    Strings,
+}
+
+/// How an identifier usage found by `refs:` uses the symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    /// Any other use of the value, e.g. passing a function as an
+    /// argument or reading a field.
+    Read,
+    /// Assignment target, increment/decrement, or variable binding.
+    Write,
+    /// Use in a type position (annotations, generics, struct literals).
+    Type,
+    /// Mention inside an import/`use` statement.
+    Import,
+    /// Callee of a call or `new` expression.
+    Call,
+}
+
 /// Kind of a symbol in a source file.
 ///
 /// This initial set is intentionally small and focused on the TS/JS
@@ -116,14 +171,206 @@ pub struct CallRef {
     /// Optional 1-based line number for the call site or symbol.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<u32>,
+    /// Optional 1-based column of the call expression.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
     /// Optional kind of the caller/callee symbol.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<SymbolKind>,
+    /// File defining the callee, for `calls` entries that were
+    /// resolved through an import to a symbol in another file.
+    ///
+    /// Absent for same-file edges and for callees that could not be
+    /// resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_file: Option<PathBuf>,
+    /// How the caller uses the callee; omitted for ordinary calls.
+    #[serde(default, skip_serializing_if = "CallEdgeKind::is_call")]
+    pub edge_kind: CallEdgeKind,
+}
+
+/// Kind of usage a call edge stands for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallEdgeKind {
+    /// Call or `new` expression.
+    #[default]
+    Call,
+    /// JSX element rendering a component, e.g. `<Button />` or
+    /// `<Menu.Item>` (a usage of `Menu`).
+    Render,
+}
+
+impl CallEdgeKind {
+    /// Stable lowercase name, matching the JSON representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CallEdgeKind::Call => "call",
+            CallEdgeKind::Render => "render",
+        }
+    }
+
+    /// Whether this is an ordinary call; used to omit the field from
+    /// JSON output.
+    pub fn is_call(&self) -> bool {
+        *self == CallEdgeKind::Call
+    }
+}
+
+/// Kind of statement a file dependency comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// ES `import ... from` / `import "..."` / `import("...")`.
+    Import,
+    /// ES `export ... from` re-export.
+    Export,
+    /// CommonJS `require("...")`.
+    Require,
+    /// Rust `use` declaration.
+    Use,
+    /// Rust out-of-line module declaration (`mod util;`).
+    Mod,
+    /// Rust `extern crate` declaration.
+    ExternCrate,
+    /// C/C++ `#include`.
+    Include,
+}
+
+impl DependencyKind {
+    /// Stable lowercase name, matching the JSON representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DependencyKind::Import => "import",
+            DependencyKind::Export => "export",
+            DependencyKind::Require => "require",
+            DependencyKind::Use => "use",
+            DependencyKind::Mod => "mod",
+            DependencyKind::ExternCrate => "extern_crate",
+            DependencyKind::Include => "include",
+        }
+    }
+}
+
+/// A single import/`use`/`#include` statement in a source file.
+///
+/// Imports are used to resolve call edges across files and are the
+/// source of the per-file dependencies reported by `symgrep deps`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRef {
+    /// Module specifier or path as written in the source (e.g.
+    /// `./util`, `crate::util`, `util.h`).
+    pub module: String,
+    /// Kind of statement the import comes from.
+    pub kind: DependencyKind,
+    /// Names brought into scope by this import.
+    pub names: Vec<ImportedName>,
+    /// Whether every exported name of the module is in scope
+    /// (`use a::*`, `import * as ns`, `#include`).
+    pub wildcard: bool,
+    /// Whether the names are re-exported rather than used locally
+    /// (`export { a } from`, `pub use`).
+    pub reexport: bool,
+    /// Whether the statement only compiles into test builds (a Rust
+    /// `use` under `#[cfg(test)]` or inside a `#[test]` function).
+    pub test_only: bool,
+    /// Candidate files the module resolved to; empty for external
+    /// packages and unresolvable paths.
+    pub files: Vec<PathBuf>,
+    /// 1-based line of the statement.
+    pub line: u32,
+}
+
+/// Kind of relation between a type and one of its supertypes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeRelationKind {
+    /// Class or interface inheritance (`class A extends B`,
+    /// `interface A extends B`, C++ base classes, Rust supertraits).
+    Extends,
+    /// Interface implementation (`class A implements I`,
+    /// `impl Trait for T`).
+    Implements,
+}
+
+impl TypeRelationKind {
+    /// Stable lowercase name, matching the JSON representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TypeRelationKind::Extends => "extends",
+            TypeRelationKind::Implements => "implements",
+        }
+    }
+}
+
+/// A single `extends`/`implements` clause in a source file.
+///
+/// Names are simple type names with generic arguments and namespace
+/// qualifiers stripped (`ns::Base<T>` is recorded as `Base`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeRelation {
+    /// Name of the type declaring the relation.
+    pub subtype: String,
+    /// Name of the extended or implemented type.
+    pub supertype: String,
+    /// Kind of relation.
+    pub kind: TypeRelationKind,
+    /// 1-based line of the supertype in the clause.
+    pub line: u32,
+}
+
+/// A C++ member function declared in a class body or defined out of
+/// line, keyed for pairing declarations with their definitions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberKey {
+    /// Enclosing namespaces and classes of a declaration, or the
+    /// enclosing namespaces and qualifier of a definition, outermost
+    /// first (`["geo", "Shape"]`).
+    pub scope: Vec<String>,
+    /// Unqualified member name.
+    pub name: String,
+    /// Number of declared parameters.
+    pub arity: u32,
+    /// Range of the declaration or definition, equal to the range of
+    /// the corresponding `Symbol`.
+    pub range: TextRange,
+    /// Whether this is an out-of-line definition rather than an
+    /// in-class declaration.
+    pub definition: bool,
+}
+
+/// A supertype of a class, interface or trait symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupertypeRef {
+    /// Name of the supertype.
+    pub name: String,
+    /// Whether the symbol extends or implements it.
+    pub kind: TypeRelationKind,
+    /// File defining the supertype; absent for types defined outside
+    /// the searched files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+/// A name brought into scope by an [`ImportRef`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedName {
+    /// Name as exported by the imported module.
+    pub name: String,
+    /// Local alias (`import { a as b }`, `use a as b`), if any.
+    pub alias: Option<String>,
+}
+
+impl ImportedName {
+    /// Name under which the import is visible in the importing file.
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
 }
 
 /// A half-open range in a source file, expressed as 1-based
 /// line/column positions.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TextRange {
     /// 1-based starting line (inclusive).
     pub start_line: u32,
@@ -141,7 +388,7 @@ pub struct TextRange {
 /// search queries and are persisted in the index. All fields are
 /// optional and are omitted from JSON when empty to preserve
 /// backward compatibility.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SymbolAttributes {
     /// Leading doc comment or comment block attached to the symbol,
     /// extracted from source code.
@@ -161,6 +408,106 @@ pub struct SymbolAttributes {
     /// Longer free-form description managed by an external owner.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Attributes and decorators attached to the symbol in source,
+    /// without their `#[...]`, `@` or `[[...]]` delimiters (e.g.
+    /// `derive(Debug, Serialize)`, `Component({ selector: "app" })`,
+    /// `nodiscard`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<String>,
+    /// Whether the symbol is test code: a Rust `#[test]` function or
+    /// an item of a `#[cfg(test)]` module, a symbol inside a Jest/Mocha
+    /// `describe`/`it`/`test` callback, a GoogleTest `TEST`/`TEST_F`
+    /// body, or any symbol of a file named like a test.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_test: bool,
+    /// Structured view of the doc comment (JSDoc/Doxygen tags and
+    /// rustdoc sections), plus deprecation from `#[deprecated]` /
+    /// `[[deprecated]]` attributes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<DocComment>,
+}
+
+/// A doc comment parsed into its summary, tags and sections.
+///
+/// Built from `SymbolAttributes::comment`: JSDoc/Doxygen `@tag` (or
+/// `\tag`) lines and rustdoc `# Heading` sections. Fields absent from
+/// the comment are omitted from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocComment {
+    /// First sentence of the description (or `@brief`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// `@param` entries in source order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<DocParam>,
+    /// `@returns` / `@return` text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub returns: Option<String>,
+    /// `@throws` / `@exception` entries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub throws: Vec<String>,
+    /// Deprecation message; empty when deprecated without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// `@see` references.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub see: Vec<String>,
+    /// Rustdoc `# Errors` section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<String>,
+    /// Rustdoc `# Panics` section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panics: Option<String>,
+    /// Rustdoc `# Safety` section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety: Option<String>,
+    /// Intra-doc link targets (`[`Config`]`, `[text](crate::io)`) and
+    /// JSDoc `{@link Target}` references, in order of appearance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    /// Other tags and sections (`@example`, `@since`, `# Examples`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<DocTag>,
+}
+
+impl DocComment {
+    /// Whether the comment has the given tag or section, named
+    /// case-insensitively with or without its `@` / `#` (`param`,
+    /// `@returns`, `errors`, `example`). `return`, `throw`,
+    /// `exception` and `arg` are accepted as aliases and `link` checks
+    /// for references.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches(['@', '#', '\\']).to_ascii_lowercase();
+        match tag.as_str() {
+            "param" | "arg" | "argument" => !self.params.is_empty(),
+            "returns" | "return" => self.returns.is_some(),
+            "throws" | "throw" | "exception" => !self.throws.is_empty(),
+            "deprecated" => self.deprecated.is_some(),
+            "see" => !self.see.is_empty(),
+            "errors" => self.errors.is_some(),
+            "panics" => self.panics.is_some(),
+            "safety" => self.safety.is_some(),
+            "link" | "links" => !self.links.is_empty(),
+            _ => self.tags.iter().any(|other| other.tag == tag),
+        }
+    }
+}
+
+/// A documented parameter (`@param name description`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocParam {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+/// A doc tag or section without a dedicated `DocComment` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocTag {
+    /// Tag or section name, lowercased and without `@` / `#`.
+    pub tag: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
 }
 
 /// A language-level symbol such as a function, method, or class.
@@ -213,6 +560,35 @@ pub struct Symbol {
     /// payloads or when call relationships are not available.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub called_by: Vec<CallRef>,
+    /// Types this class, interface or trait extends or implements.
+    ///
+    /// This field is additive and may be absent or empty in older
+    /// payloads or for symbols without supertypes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supertypes: Vec<SupertypeRef>,
+    /// Out-of-line definition of a member declared in a class body
+    /// (C++ `void Shape::area() { ... }` for `void area();`).
+    ///
+    /// This field is additive and absent for symbols that are their
+    /// own definition or whose definition was not found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<SymbolLocation>,
+    /// Module specifiers other searched files import this symbol's
+    /// file by (`@app/utils`, `./math`), sorted.
+    ///
+    /// This field is additive and only filled in for queries with
+    /// `module:` terms.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<String>,
+}
+
+/// Location of a symbol defined apart from its declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolLocation {
+    /// File containing the definition.
+    pub file: PathBuf,
+    /// Source range of the definition.
+    pub range: TextRange,
 }
 
 /// A concrete snippet of source representing a particular context
@@ -272,6 +648,17 @@ pub enum QueryField {
     Description,
     Calls,
     CalledBy,
+    Scope,
+    Ast,
+    References,
+    Extends,
+    Implements,
+    Module,
+    Crate,
+    Attr,
+    Is,
+    Deprecated,
+    DocTag,
 }
 
 /// A single atomic query term such as `name:foo` or `kind:function`.
@@ -334,6 +721,7 @@ pub struct SearchConfig {
     /// - When `matches` is present in `symbol_views`, the primary
     ///   region defaults to `def` when no explicit decl/def/parent
     ///   view is requested.
+    ///
     /// The legacy `context` field has been removed; symbol views are
     /// now the only control for snippet and match behavior.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -349,6 +737,46 @@ pub struct SearchConfig {
     /// defaults to `false` for backward compatibility.
     #[serde(default)]
     pub reindex_on_search: bool,
+    /// Text mode only: treat the pattern as a regular expression that
+    /// may match across line boundaries.
+    ///
+    /// Each match is reported once, at the line and column where it
+    /// starts, with the matched lines as the snippet.
+    #[serde(default)]
+    pub multiline: bool,
+    /// Text mode only: encoding label (e.g. `latin1`, `utf-16le`,
+    /// `shift_jis`) used to transcode files that are not UTF-8.
+    ///
+    /// When omitted, files are read as UTF-8 unless they start with a
+    /// UTF-8/UTF-16 byte-order mark.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    /// Text mode only: annotate each match in a supported language
+    /// with its innermost enclosing symbol (`SearchMatch.enclosing`).
+    ///
+    /// This parses every file that has matches, so it is opt-in.
+    #[serde(default)]
+    pub enclosing: bool,
+    /// Symbol mode only: resolve call edges across every file under
+    /// `paths` so each symbol's `calls` / `called_by` are complete.
+    ///
+    /// Queries with `calls:` / `called-by:` terms always do this;
+    /// other searches only report edges within a file unless this is
+    /// set (as `follow` and `graph` do).
+    #[serde(default)]
+    pub call_graph: bool,
+    /// Text mode only: keep only occurrences inside these syntactic
+    /// regions (comments, string literals, or other code).
+    ///
+    /// Empty means no restriction. Files without a language backend
+    /// have no syntax tree and yield no matches when this is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<SyntaxScope>,
+    /// Reference kinds to keep for `refs:` searches.
+    ///
+    /// Empty means every kind.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reference_kinds: Vec<ReferenceKind>,
     /// Optional index configuration to use during search.
     ///
     /// When present and the backend is available, symbol-mode
@@ -357,6 +785,10 @@ pub struct SearchConfig {
     /// compatible.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub index: Option<IndexConfig>,
+    /// C++ compile database (`compile_commands.json`) used to resolve
+    /// `#include` directives through the build's include paths.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_commands: Option<PathBuf>,
     /// Parsed representation of the structured query/DSL, when used.
     ///
     /// This is built from the raw `pattern` string by the CLI or
@@ -380,6 +812,113 @@ pub struct SearchMatch {
     pub column: Option<u32>,
     /// Optional line or snippet text for the match.
     pub snippet: Option<String>,
+    /// Every occurrence of the pattern on the matched line, in order.
+    ///
+    /// `column` is the start of the first span. Empty when spans were
+    /// not computed (e.g. results from an older server).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<MatchSpan>,
+    /// Innermost symbol containing the match, when requested via
+    /// `SearchConfig.enclosing` and the file's language is supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosing: Option<EnclosingSymbol>,
+    /// Metavariable bindings for `ast:` pattern matches, keyed by
+    /// metavariable name without the `$` prefix.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub bindings: BTreeMap<String, AstBinding>,
+    /// Capture reported by `symgrep query`; `line`, `column`, and
+    /// `spans` locate the captured node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture: Option<QueryCapture>,
+    /// Classification of a `refs:` usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_kind: Option<ReferenceKind>,
+}
+
+/// Node captured by a raw tree-sitter query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCapture {
+    /// Capture name without the `@` prefix.
+    pub name: String,
+    /// Source text of the captured node.
+    pub text: String,
+    /// Source range of the captured node.
+    pub range: TextRange,
+    /// 0-based index of the query pattern that produced the capture.
+    pub pattern: u32,
+}
+
+/// Source text captured by an `ast:` metavariable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstBinding {
+    /// Captured source text. For `$$$NAME` sequence metavariables this
+    /// spans all captured nodes and may be empty.
+    pub text: String,
+    /// Source range of the capture; omitted for empty sequences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<TextRange>,
+}
+
+/// Symbol that encloses a text match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnclosingSymbol {
+    /// Simple name of the symbol.
+    pub name: String,
+    /// High-level kind of the symbol.
+    pub kind: SymbolKind,
+    /// Source range covering the symbol's declaration/definition.
+    pub range: TextRange,
+    /// Enclosing scopes of the symbol itself, ordered from outermost
+    /// (file) to innermost, as in `ContextInfo.parent_chain`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_chain: Vec<ContextNode>,
+}
+
+/// Location of a single occurrence within a text match.
+///
+/// Offsets are 0-based and relative to the start of the matched line
+/// (after decoding to UTF-8); `end` is exclusive. In `--multiline`
+/// mode `end` may extend past the end of the first line. Character
+/// and UTF-16 offsets are provided for editors that do not address
+/// text by byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    /// Byte offset where the occurrence starts.
+    pub start: u32,
+    /// Byte offset just past the end of the occurrence.
+    pub end: u32,
+    /// Offset of `start` in Unicode scalar values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_char: Option<u32>,
+    /// Offset of `end` in Unicode scalar values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_char: Option<u32>,
+    /// Offset of `start` in UTF-16 code units (as used by LSP).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_utf16: Option<u32>,
+    /// Offset of `end` in UTF-16 code units (as used by LSP).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_utf16: Option<u32>,
+}
+
+impl MatchSpan {
+    /// Build a span for `text[start..end]`, filling in the character
+    /// and UTF-16 offsets. `start` and `end` must be char boundaries.
+    pub fn in_text(text: &str, start: usize, end: usize) -> Self {
+        let prefix = &text[..start];
+        let matched = &text[start..end];
+        let start_char = prefix.chars().count();
+        let start_utf16 = prefix.encode_utf16().count();
+
+        MatchSpan {
+            start: start as u32,
+            end: end as u32,
+            start_char: Some(start_char as u32),
+            end_char: Some((start_char + matched.chars().count()) as u32),
+            start_utf16: Some(start_utf16 as u32),
+            end_utf16: Some((start_utf16 + matched.encode_utf16().count()) as u32),
+        }
+    }
 }
 
 /// Summary information for a search result set.
@@ -442,6 +981,17 @@ pub struct FollowResult {
     /// Resolved target symbols and their call relationships.
     #[serde(default)]
     pub targets: Vec<FollowTarget>,
+    /// Pattern selecting the symbols that call paths must reach, in
+    /// path mode (`--from` / `--to`). `query` then holds the `--from`
+    /// pattern.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Call paths from `query` symbols to `to` symbols (path mode only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<FollowPath>,
+    /// True if expansion stopped early because `--max-nodes` was reached.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 /// A single target symbol plus its callers and/or callees.
@@ -465,6 +1015,24 @@ pub struct FollowEdge {
     /// One or more call sites in source code where the relationship occurs.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub call_sites: Vec<FollowCallSite>,
+    /// Next level of callers/callees of this symbol when following
+    /// more than one level (`--depth`), in the same direction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<FollowEdge>,
+    /// True if this symbol already appears higher up the same branch;
+    /// such edges are not expanded further.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+}
+
+/// A chain of calls from a `--from` symbol to a `--to` symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowPath {
+    /// Symbol the path starts from.
+    pub from: FollowSymbolRef,
+    /// Successive callees; each step's call sites are in the previous
+    /// symbol, and the last step is a `--to` symbol.
+    pub steps: Vec<FollowEdge>,
 }
 
 /// Lightweight reference to a symbol used in follow results.
@@ -480,8 +1048,8 @@ pub struct FollowSymbolRef {
     pub kind: Option<SymbolKind>,
     /// File containing this symbol's definition.
     ///
-    /// In the current per-file call graph implementation, this is
-    /// also the file where all `call_sites` for this edge occur.
+    /// For callees resolved through an import this differs from the
+    /// file of the edge's `call_sites`.
     pub file: PathBuf,
 }
 
@@ -493,11 +1061,310 @@ pub struct FollowCallSite {
     /// 1-based line number of the call expression.
     pub line: u32,
     /// Optional 1-based column number of the call expression.
-    ///
-    /// This field is currently omitted in CLI JSON output but may be
-    /// populated by future versions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<u32>,
+    /// Kind of usage at this site; omitted for ordinary calls.
+    #[serde(default, skip_serializing_if = "CallEdgeKind::is_call")]
+    pub edge_kind: CallEdgeKind,
+}
+
+/// How `symgrep graph` groups nodes into clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphClusterBy {
+    /// One cluster per defining file.
+    File,
+    /// One cluster per enclosing namespace/module, from the symbol's
+    /// `parent_chain`; top-level symbols are not clustered.
+    Namespace,
+}
+
+/// Top-level result for a `symgrep graph` invocation: the call graph
+/// between the symbols selected by a query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphResult {
+    /// Schema version for this graph payload.
+    pub version: String,
+    /// Original pattern or query string used to select symbols.
+    pub query: String,
+    /// Clustering requested by the caller, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster_by: Option<GraphClusterBy>,
+    /// Graph nodes, one per distinct symbol name and file.
+    #[serde(default)]
+    pub nodes: Vec<GraphNode>,
+    /// Call edges between nodes, one per caller/callee pair.
+    #[serde(default)]
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A symbol in a `GraphResult`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Stable identifier referenced by `GraphEdge.from` / `to`.
+    pub id: String,
+    /// Simple name of the symbol.
+    pub name: String,
+    /// High-level kind of the symbol, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<SymbolKind>,
+    /// File defining the symbol.
+    pub file: PathBuf,
+    /// 1-based first line of the definition; omitted for external nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// Cluster label (file path or namespace path) when clustering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<String>,
+    /// True for callees outside the selected symbols (`--external`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+}
+
+/// A caller → callee relationship in a `GraphResult`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// Id of the calling node.
+    pub from: String,
+    /// Id of the called node.
+    pub to: String,
+    /// Call sites in the caller, ordered by position.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_sites: Vec<FollowCallSite>,
+}
+
+/// Configuration for a `symgrep deps` invocation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepsConfig {
+    /// File whose dependencies are reported.
+    pub file: PathBuf,
+    /// Filesystem roots searched for the file's importers.
+    pub paths: Vec<PathBuf>,
+    /// Inclusion globs applied to candidate files.
+    #[serde(default)]
+    pub globs: Vec<String>,
+    /// Exclusion globs applied to candidate files.
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// Optional language filter for candidate files.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Also compute the transitive closure of imports and importers.
+    #[serde(default)]
+    pub transitive: bool,
+    /// Index to read dependencies from instead of parsing every file.
+    #[serde(default)]
+    pub index: Option<IndexConfig>,
+    /// C++ compile database, as in [`SearchConfig::compile_commands`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_commands: Option<PathBuf>,
+}
+
+/// Top-level result for a `symgrep deps` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepsResult {
+    /// Schema version for this deps payload.
+    pub version: String,
+    /// File the dependencies were computed for.
+    pub file: PathBuf,
+    /// Dependency statements in `file`, in source order.
+    #[serde(default)]
+    pub imports: Vec<FileDependency>,
+    /// Statements in other files that resolve to `file`, ordered by
+    /// importing file and line.
+    #[serde(default)]
+    pub importers: Vec<FileDependency>,
+    /// Files reachable from `file` through resolved imports, nearest
+    /// first; only present with `transitive`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transitive_imports: Option<Vec<TransitiveDependency>>,
+    /// Files that reach `file` through resolved imports, nearest first;
+    /// only present with `transitive`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transitive_importers: Option<Vec<TransitiveDependency>>,
+}
+
+/// One dependency statement of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDependency {
+    /// File containing the statement.
+    pub file: PathBuf,
+    /// Module specifier or path as written in the source.
+    pub module: String,
+    /// Kind of statement.
+    pub kind: DependencyKind,
+    /// File the module resolved to; absent for external packages,
+    /// system headers and unresolvable paths.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<PathBuf>,
+    /// 1-based line of the statement.
+    pub line: u32,
+}
+
+/// A file in the transitive closure of a `DepsResult`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitiveDependency {
+    /// The reachable file.
+    pub file: PathBuf,
+    /// Number of import hops between it and the queried file (>= 1).
+    pub depth: u32,
+}
+
+/// Configuration for a `symgrep check-deps` invocation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckDepsConfig {
+    /// Filesystem roots whose files are checked.
+    pub paths: Vec<PathBuf>,
+    /// Inclusion globs applied to candidate files.
+    #[serde(default)]
+    pub globs: Vec<String>,
+    /// Exclusion globs applied to candidate files.
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// Optional language filter for candidate files.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Layering rules of the form `<glob> must not import <glob>`.
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// Only check layering rules, not import cycles.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_cycles: bool,
+    /// Index to read dependencies from instead of parsing every file.
+    #[serde(default)]
+    pub index: Option<IndexConfig>,
+    /// C++ compile database, as in [`SearchConfig::compile_commands`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_commands: Option<PathBuf>,
+}
+
+/// Top-level result for a `symgrep check-deps` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckDepsResult {
+    /// Schema version for this payload.
+    pub version: String,
+    /// Number of files whose dependencies were checked.
+    pub files_checked: u64,
+    /// Import cycles, ordered by their first file.
+    #[serde(default)]
+    pub cycles: Vec<DependencyCycle>,
+    /// Dependencies breaking a layering rule, ordered by file and line.
+    #[serde(default)]
+    pub violations: Vec<LayerViolation>,
+}
+
+/// A set of files that import each other (a strongly connected
+/// component of the import graph).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyCycle {
+    /// Files in the cycle, sorted by path.
+    pub files: Vec<PathBuf>,
+    /// The statements linking files of the cycle, ordered by file and
+    /// line.
+    pub dependencies: Vec<FileDependency>,
+}
+
+/// A dependency statement that breaks a layering rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerViolation {
+    /// The rule as written in the configuration.
+    pub rule: String,
+    /// The offending statement.
+    pub dependency: FileDependency,
+}
+
+/// Direction in which `symgrep hierarchy` walks type relations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HierarchyDirection {
+    /// Types the symbol extends or implements, and theirs in turn.
+    Supertypes,
+    /// Types extending or implementing the symbol, and theirs in turn.
+    Subtypes,
+}
+
+/// Configuration for a `symgrep hierarchy` invocation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchyConfig {
+    /// Name of the class, interface or trait to start from.
+    pub symbol: String,
+    /// Filesystem roots whose files are scanned for type relations.
+    pub paths: Vec<PathBuf>,
+    /// Inclusion globs applied to candidate files.
+    #[serde(default)]
+    pub globs: Vec<String>,
+    /// Exclusion globs applied to candidate files.
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// Optional language filter for candidate files.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Whether to walk towards supertypes or subtypes.
+    pub direction: HierarchyDirection,
+    /// Maximum number of relation hops; unlimited when absent.
+    #[serde(default)]
+    pub depth: Option<u32>,
+    /// Index to read relations from instead of parsing every file.
+    #[serde(default)]
+    pub index: Option<IndexConfig>,
+    /// C++ compile database, as in [`SearchConfig::compile_commands`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_commands: Option<PathBuf>,
+}
+
+/// Top-level result for a `symgrep hierarchy` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchyResult {
+    /// Schema version for this payload.
+    pub version: String,
+    /// The queried type name.
+    pub symbol: String,
+    /// Direction the hierarchy was walked in.
+    pub direction: HierarchyDirection,
+    /// Definitions of the queried type, ordered by file and line.
+    #[serde(default)]
+    pub definitions: Vec<HierarchyType>,
+    /// Relations reached from the queried type, nearest first.
+    #[serde(default)]
+    pub edges: Vec<HierarchyEdge>,
+}
+
+/// A class, interface or trait definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchyType {
+    /// Type name.
+    pub name: String,
+    /// Symbol kind of the definition.
+    pub kind: SymbolKind,
+    /// File defining the type.
+    pub file: PathBuf,
+    /// 1-based line of the definition.
+    pub line: u32,
+}
+
+/// One `extends`/`implements` relation in a `HierarchyResult`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchyEdge {
+    /// Name of the extending or implementing type.
+    pub subtype: String,
+    /// File defining the subtype, when it is defined in the searched
+    /// files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtype_file: Option<PathBuf>,
+    /// Name of the extended or implemented type.
+    pub supertype: String,
+    /// File defining the supertype, when it is defined in the searched
+    /// files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supertype_file: Option<PathBuf>,
+    /// Kind of relation.
+    pub kind: TypeRelationKind,
+    /// File containing the `extends`/`implements` clause.
+    pub file: PathBuf,
+    /// 1-based line of the clause.
+    pub line: u32,
+    /// Number of relation hops from the queried type (>= 1).
+    pub depth: u32,
 }
 
 /// Backend kind for indexing.
@@ -528,6 +1395,10 @@ pub struct IndexConfig {
     pub index_path: PathBuf,
     /// Optional language filter for indexing.
     pub language: Option<String>,
+    /// C++ compile database, as in [`SearchConfig::compile_commands`];
+    /// C/C++ sources it does not list are not indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_commands: Option<PathBuf>,
 }
 
 /// Summary information about an index operation.
@@ -0,0 +1,441 @@
+//! Structural search for `ast:` query terms.
+//!
+//! An `ast:` pattern is a code snippet in the searched file's language
+//! with metavariables in place of arbitrary sub-trees:
+//!
+//! - `$NAME` matches any single node and binds it to `NAME`. Repeating
+//!   a name requires every occurrence to capture the same text.
+//! - `$$$NAME` matches a (possibly empty) run of sibling nodes, such as
+//!   call arguments or statements in a block.
+//! - `$_` and `$$$` match without binding.
+//!
+//! Metavariables are replaced by placeholder identifiers before the
+//! snippet is parsed with the file's tree-sitter grammar, so the
+//! pattern has to be syntactically valid in that language (statement
+//! snippets are also tried inside a function body). The pattern tree
+//! is then compared node by node against every node of the same kind
+//! in the file: node kinds must agree, leaves must have the same text,
+//! and comments are ignored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use tree_sitter::{Node, Tree};
+
+use crate::language::{backend_for_language, backend_for_path, node_text_range, LanguageBackend};
+use crate::models::{AstBinding, MatchSpan, QueryExpr, QueryField, SearchConfig, SearchMatch};
+use crate::search::query::exclusive_term;
+
+const PLACEHOLDER_PREFIX: &str = "__sgmv_";
+const SEQUENCE_PLACEHOLDER_PREFIX: &str = "__sgmvs_";
+
+/// Extract the `ast:` snippet from a query expression.
+///
+/// Returns `Ok(None)` when the query has no `ast:` term. An `ast:`
+/// term may only be combined (with AND) with `file:` and `language:`
+/// filters.
+pub(crate) fn ast_snippet(expr: &QueryExpr) -> Result<Option<&str>> {
+    exclusive_term(expr, QueryField::Ast, "ast")
+}
+
+/// Whether `backend` passes the `language:` terms of the query and the
+/// `--language` option.
+pub(crate) fn language_allowed(
+    expr: &QueryExpr,
+    config: &SearchConfig,
+    backend: &dyn LanguageBackend,
+) -> bool {
+    fn same_backend(value: &str, backend: &dyn LanguageBackend) -> bool {
+        backend_for_language(value).is_some_and(|b| b.id() == backend.id())
+    }
+
+    fn terms_allow(expr: &QueryExpr, backend: &dyn LanguageBackend) -> bool {
+        match expr {
+            QueryExpr::Term(term) if term.field == QueryField::Language => {
+                same_backend(&term.value, backend)
+            }
+            QueryExpr::Term(_) => true,
+            QueryExpr::And(clauses) => clauses.iter().all(|c| terms_allow(c, backend)),
+            QueryExpr::Or(clauses) => clauses.iter().any(|c| terms_allow(c, backend)),
+        }
+    }
+
+    config
+        .language
+        .as_deref()
+        .is_none_or(|lang| same_backend(lang, backend))
+        && terms_allow(expr, backend)
+}
+
+/// Outcome of matching a pattern against one file.
+pub(crate) enum FileOutcome {
+    /// The file has no supported language or could not be read.
+    Skipped,
+    /// The pattern is not valid syntax in the file's language.
+    PatternInvalid,
+    Matched(Vec<SearchMatch>),
+}
+
+/// Find up to `cap` structural matches of `snippet` in `path`.
+pub(crate) fn search_file(
+    path: &Path,
+    snippet: &str,
+    expr: &QueryExpr,
+    config: &SearchConfig,
+    cap: usize,
+) -> FileOutcome {
+    let Some(backend) = backend_for_path(path) else {
+        return FileOutcome::Skipped;
+    };
+    if !language_allowed(expr, config, backend) {
+        return FileOutcome::Skipped;
+    }
+    let Ok(source) = fs::read_to_string(path) else {
+        return FileOutcome::Skipped;
+    };
+    let Some(pattern) = Pattern::compile(backend, path, snippet) else {
+        return FileOutcome::PatternInvalid;
+    };
+    let Ok(tree) = backend.parse_syntax_tree(path, &source) else {
+        return FileOutcome::Skipped;
+    };
+
+    let pattern_root = pattern.root();
+    let mut matches = Vec::new();
+    let mut cursor = tree.root_node().walk();
+
+    'walk: loop {
+        let node = cursor.node();
+        if node.kind_id() == pattern_root.kind_id() {
+            let mut matcher = Matcher {
+                pattern_source: pattern.source.as_bytes(),
+                source: source.as_bytes(),
+                bindings: BTreeMap::new(),
+            };
+            if matcher.match_node(pattern_root, node) {
+                matches.push(build_match(path, &source, node, matcher.bindings, config));
+                if matches.len() >= cap {
+                    break;
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+
+    FileOutcome::Matched(matches)
+}
+
+fn build_match(
+    path: &Path,
+    source: &str,
+    node: Node,
+    bindings: BTreeMap<String, Binding>,
+    config: &SearchConfig,
+) -> SearchMatch {
+    let start = node.start_byte();
+    let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = source[node.end_byte()..]
+        .find('\n')
+        .map_or(source.len(), |idx| node.end_byte() + idx);
+
+    let mut snippet_lines: Vec<&str> = source[line_start..line_end].lines().collect();
+    if let Some(max) = config.max_lines {
+        snippet_lines.truncate(max);
+    }
+    let snippet = match config.max_lines {
+        Some(0) => None,
+        _ => Some(snippet_lines.join("\n")),
+    };
+
+    let span = MatchSpan::in_text(
+        &source[line_start..],
+        start - line_start,
+        node.end_byte() - line_start,
+    );
+
+    let bindings = bindings
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty() && name != "_")
+        .map(|(name, binding)| {
+            let range = binding.range.map(|(first, last)| {
+                let mut range = node_text_range(&first);
+                let end = node_text_range(&last);
+                range.end_line = end.end_line;
+                range.end_column = end.end_column;
+                range
+            });
+            (
+                name,
+                AstBinding {
+                    text: binding.text,
+                    range,
+                },
+            )
+        })
+        .collect();
+
+    SearchMatch {
+        path: path.to_path_buf(),
+        line: node.start_position().row as u32 + 1,
+        column: Some(node.start_position().column as u32 + 1),
+        snippet,
+        spans: vec![span],
+        enclosing: None,
+        bindings,
+        capture: None,
+        reference_kind: None,
+    }
+}
+
+/// A parsed `ast:` snippet for one grammar.
+struct Pattern {
+    tree: Tree,
+    source: String,
+    start: usize,
+    end: usize,
+}
+
+impl Pattern {
+    /// Parse the snippet, trying it as-is and then inside a function
+    /// body. Returns `None` when no wrapper yields an error-free tree or
+    /// the snippet is a bare metavariable.
+    fn compile(backend: &dyn LanguageBackend, path: &Path, snippet: &str) -> Option<Self> {
+        let substituted = substitute_metavariables(snippet);
+        let wrappers: &[(&str, &str)] = match backend.id() {
+            "rust" => &[("", ""), ("fn __sgp() { ", " }"), ("fn __sgp() { ", "; }")],
+            "cpp" => &[
+                ("", ""),
+                ("void __sgp() { ", " }"),
+                ("void __sgp() { ", "; }"),
+            ],
+            _ => &[("", ""), ("function __sgp() { ", " }")],
+        };
+
+        for (prefix, suffix) in wrappers {
+            let source = format!("{prefix}{substituted}{suffix}");
+            let Ok(tree) = backend.parse_syntax_tree(path, &source) else {
+                continue;
+            };
+            if tree.root_node().has_error() {
+                continue;
+            }
+
+            let pattern = Pattern {
+                tree,
+                source,
+                start: prefix.len(),
+                end: prefix.len() + substituted.len(),
+            };
+            if metavariable(pattern.root(), pattern.source.as_bytes()).is_some() {
+                return None;
+            }
+            return Some(pattern);
+        }
+
+        None
+    }
+
+    /// The smallest node covering the snippet.
+    fn root(&self) -> Node<'_> {
+        let root = self.tree.root_node();
+        root.descendant_for_byte_range(self.start, self.end)
+            .unwrap_or(root)
+    }
+}
+
+/// Replace `$NAME`, `$$$NAME`, `$_`, and `$$$` with placeholder
+/// identifiers that parse in every supported grammar.
+fn substitute_metavariables(snippet: &str) -> String {
+    let mut out = String::with_capacity(snippet.len() + 16);
+    let mut rest = snippet;
+
+    while let Some(idx) = rest.find('$') {
+        out.push_str(&rest[..idx]);
+        let after = &rest[idx + 1..];
+
+        let (prefix, name_source) = match after.strip_prefix("$$") {
+            Some(seq) => (SEQUENCE_PLACEHOLDER_PREFIX, seq),
+            None => (PLACEHOLDER_PREFIX, after),
+        };
+        let name_len = name_source
+            .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(name_source.len());
+        let name = &name_source[..name_len];
+
+        let valid = if prefix == SEQUENCE_PLACEHOLDER_PREFIX {
+            name.is_empty() || !name.starts_with(|c: char| c.is_ascii_digit())
+        } else {
+            !name.is_empty() && !name.starts_with(|c: char| c.is_ascii_digit())
+        };
+
+        if valid {
+            out.push_str(prefix);
+            out.push_str(name);
+            rest = &name_source[name_len..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+enum Metavariable<'a> {
+    Single(&'a str),
+    Sequence(&'a str),
+}
+
+/// Recognize a pattern node that stands for a metavariable. A node
+/// wrapping only a placeholder (such as an expression statement
+/// `$X;`) also counts, so the metavariable matches a whole statement.
+fn metavariable<'a>(node: Node, source: &'a [u8]) -> Option<Metavariable<'a>> {
+    let text = node.utf8_text(source).ok()?.trim();
+    let text = text.strip_suffix(';').unwrap_or(text).trim_end();
+    let is_name = |name: &str| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if let Some(name) = text.strip_prefix(SEQUENCE_PLACEHOLDER_PREFIX) {
+        return is_name(name).then_some(Metavariable::Sequence(name));
+    }
+    if let Some(name) = text.strip_prefix(PLACEHOLDER_PREFIX) {
+        return is_name(name).then_some(Metavariable::Single(name));
+    }
+    None
+}
+
+struct Binding<'t> {
+    text: String,
+    /// First and last captured nodes; `None` for an empty sequence.
+    range: Option<(Node<'t>, Node<'t>)>,
+}
+
+impl Clone for Binding<'_> {
+    fn clone(&self) -> Self {
+        Binding {
+            text: self.text.clone(),
+            range: self.range,
+        }
+    }
+}
+
+struct Matcher<'p, 't> {
+    pattern_source: &'p [u8],
+    source: &'t [u8],
+    bindings: BTreeMap<String, Binding<'t>>,
+}
+
+impl<'p, 't> Matcher<'p, 't> {
+    fn match_node(&mut self, pattern: Node<'p>, target: Node<'t>) -> bool {
+        match metavariable(pattern, self.pattern_source) {
+            Some(Metavariable::Single(name)) | Some(Metavariable::Sequence(name)) => {
+                return self.bind(name, &[target]);
+            }
+            None => {}
+        }
+
+        if pattern.kind_id() != target.kind_id() {
+            return false;
+        }
+
+        let pattern_children = significant_children(pattern);
+        if pattern_children.is_empty() {
+            return pattern.utf8_text(self.pattern_source).ok()
+                == target.utf8_text(self.source).ok();
+        }
+
+        let target_children = significant_children(target);
+        self.match_sequence(&pattern_children, &target_children)
+    }
+
+    fn match_sequence(&mut self, pattern: &[Node<'p>], target: &[Node<'t>]) -> bool {
+        let Some((first, rest)) = pattern.split_first() else {
+            return target.is_empty();
+        };
+
+        if let Some(Metavariable::Sequence(name)) = metavariable(*first, self.pattern_source) {
+            for take in 0..=target.len() {
+                let saved = self.bindings.clone();
+                if self.bind(name, &target[..take]) && self.match_sequence(rest, &target[take..]) {
+                    return true;
+                }
+                self.bindings = saved;
+            }
+            return false;
+        }
+
+        let Some((target_first, target_rest)) = target.split_first() else {
+            return false;
+        };
+
+        let saved = self.bindings.clone();
+        if self.match_node(*first, *target_first) && self.match_sequence(rest, target_rest) {
+            return true;
+        }
+        self.bindings = saved;
+        false
+    }
+
+    /// Bind `name` to the source spanned by `nodes`, or check that an
+    /// existing binding captured the same text.
+    fn bind(&mut self, name: &str, nodes: &[Node<'t>]) -> bool {
+        let binding = match (nodes.first(), nodes.last()) {
+            (Some(first), Some(last)) => Binding {
+                text: String::from_utf8_lossy(&self.source[first.start_byte()..last.end_byte()])
+                    .into_owned(),
+                range: Some((*first, *last)),
+            },
+            _ => Binding {
+                text: String::new(),
+                range: None,
+            },
+        };
+
+        if name == "_" || name.is_empty() {
+            return true;
+        }
+
+        match self.bindings.get(name) {
+            Some(existing) => existing.text == binding.text,
+            None => {
+                self.bindings.insert(name.to_string(), binding);
+                true
+            }
+        }
+    }
+}
+
+/// Children that take part in matching: everything except comments
+/// and other "extra" nodes.
+fn significant_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_metavariables() {
+        assert_eq!(substitute_metavariables("$X.unwrap()"), "__sgmv_X.unwrap()");
+        assert_eq!(
+            substitute_metavariables("foo($$$ARGS, $_)"),
+            "foo(__sgmvs_ARGS, __sgmv__)"
+        );
+        assert_eq!(substitute_metavariables("f($$$)"), "f(__sgmvs_)");
+        assert_eq!(substitute_metavariables("`${a}` + $1"), "`${a}` + $1");
+    }
+}
@@ -0,0 +1,121 @@
+//! Call edges read back from an index.
+//!
+//! Indexes persist the edges resolved by `language::resolve_cross_file_calls`
+//! as `CallRecord`s; `IndexedCallGraph` turns those back into `calls` /
+//! `called_by`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::index::models::{CallRecord, SymbolRecord};
+use crate::models::{CallRef, Symbol};
+
+/// `calls` / `called_by` edges rebuilt from an index's call records.
+///
+/// Only symbols among the records passed to `build` take part, which
+/// mirrors the non-indexed search: callers and callees outside the
+/// searched paths are not linked.
+#[derive(Debug, Default)]
+pub(crate) struct IndexedCallGraph {
+    calls: HashMap<u64, Vec<CallRef>>,
+    called_by: HashMap<u64, Vec<CallRef>>,
+}
+
+impl IndexedCallGraph {
+    pub(crate) fn build(
+        symbols: &[SymbolRecord],
+        file_paths: &HashMap<u64, PathBuf>,
+        records: Vec<CallRecord>,
+    ) -> Self {
+        let by_id: HashMap<u64, &SymbolRecord> = symbols.iter().map(|s| (s.id, s)).collect();
+        let mut by_file_name: HashMap<(u64, &str), Vec<u64>> = HashMap::new();
+        for symbol in symbols {
+            by_file_name
+                .entry((symbol.file_id, symbol.name.as_str()))
+                .or_default()
+                .push(symbol.id);
+        }
+
+        let mut graph = Self::default();
+        let mut cross_file = Vec::new();
+
+        for record in records {
+            let Some(caller) = by_id.get(&record.caller_id) else {
+                continue;
+            };
+            let Some(caller_file) = file_paths.get(&record.file_id) else {
+                continue;
+            };
+
+            let local = record.callee_file.as_ref() == Some(caller_file);
+            let target = record
+                .callee_id
+                .filter(|_| !local)
+                .and_then(|id| by_id.get(&id));
+
+            graph.calls.entry(caller.id).or_default().push(CallRef {
+                name: record.callee_name.clone(),
+                file: caller_file.clone(),
+                line: Some(record.line),
+                column: record.column,
+                kind: target.map(|t| t.kind),
+                target_file: target.and_then(|t| file_paths.get(&t.file_id).cloned()),
+                edge_kind: record.edge_kind,
+            });
+
+            let incoming = CallRef {
+                name: caller.name.clone(),
+                file: caller_file.clone(),
+                line: Some(record.line),
+                column: record.column,
+                kind: None,
+                target_file: None,
+                edge_kind: record.edge_kind,
+            };
+            if local {
+                // Same-file edges link every symbol of that name, like
+                // the backends' per-file call metadata.
+                let key = (record.file_id, record.callee_name.as_str());
+                for id in by_file_name.get(&key).into_iter().flatten() {
+                    graph
+                        .called_by
+                        .entry(*id)
+                        .or_default()
+                        .push(incoming.clone());
+                }
+            } else if let Some(target) = target {
+                cross_file.push((
+                    target.id,
+                    CallRef {
+                        kind: Some(caller.kind),
+                        ..incoming
+                    },
+                ));
+            }
+        }
+
+        // Cross-file callers follow same-file ones in caller path order,
+        // as in the non-indexed search; file ids depend on indexing order.
+        cross_file.sort_by(|a, b| a.1.file.cmp(&b.1.file));
+        for (id, incoming) in cross_file {
+            graph.called_by.entry(id).or_default().push(incoming);
+        }
+
+        graph
+    }
+
+    /// Attach the edges recorded for symbol `id`.
+    pub(crate) fn attach(&self, id: u64, symbol: &mut Symbol) {
+        symbol.calls = self.calls.get(&id).cloned().unwrap_or_default();
+        symbol.called_by = self.called_by.get(&id).cloned().unwrap_or_default();
+    }
+
+    /// Append the edges recorded for symbol `id`, such as those of a
+    /// member's out-of-line definition.
+    pub(crate) fn append(&self, id: u64, symbol: &mut Symbol) {
+        symbol.calls.extend(self.calls.get(&id).into_iter().flatten().cloned());
+        symbol
+            .called_by
+            .extend(self.called_by.get(&id).into_iter().flatten().cloned());
+    }
+}
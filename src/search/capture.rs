@@ -0,0 +1,146 @@
+//! Raw tree-sitter query passthrough for `symgrep query`.
+//!
+//! The query source is compiled once for every grammar that can be
+//! searched (one per file extension, since e.g. `.ts` and `.tsx` use
+//! different grammars). Files whose grammar rejected the query are
+//! skipped, so a query written against Rust node names simply ignores
+//! TypeScript files unless a language was requested explicitly.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use tree_sitter::{Query, QueryCursor, StreamingIterator};
+
+use crate::language::{all_backends, backend_for_language, backend_for_path, node_text_range};
+use crate::models::{MatchSpan, QueryCapture, SearchConfig, SearchMatch};
+
+/// A query compiled for each searchable file extension.
+pub(crate) struct CompiledQuery {
+    by_extension: HashMap<&'static str, Query>,
+}
+
+impl CompiledQuery {
+    /// Compile `source` for every grammar allowed by `language`.
+    ///
+    /// Fails when a requested language rejects the query, or when no
+    /// grammar accepts it at all.
+    pub(crate) fn compile(source: &str, language: Option<&str>) -> Result<Self> {
+        let backends: Vec<_> = match language {
+            Some(language) => match backend_for_language(language) {
+                Some(backend) => vec![backend],
+                None => bail!("unsupported language `{language}`"),
+            },
+            None => all_backends().collect(),
+        };
+
+        let mut by_extension = HashMap::new();
+        let mut first_error = None;
+
+        for backend in backends {
+            for ext in backend.file_extensions() {
+                let grammar = backend.grammar(Path::new(&format!("query.{ext}")));
+                match Query::new(&grammar, source) {
+                    Ok(query) => {
+                        by_extension.insert(*ext, query);
+                    }
+                    Err(err) => {
+                        if language.is_some() {
+                            bail!("invalid tree-sitter query for {}: {err}", backend.id());
+                        }
+                        first_error.get_or_insert_with(|| format!("{}: {err}", backend.id()));
+                    }
+                }
+            }
+        }
+
+        if by_extension.is_empty() {
+            bail!(
+                "tree-sitter query is not valid for any supported language ({})",
+                first_error.unwrap_or_default()
+            );
+        }
+
+        Ok(Self { by_extension })
+    }
+
+    fn for_path(&self, path: &Path) -> Option<&Query> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.by_extension.get(ext.as_str())
+    }
+}
+
+/// Run the query over `path`, returning up to `cap` captures in
+/// document order.
+pub(crate) fn search_file(
+    path: &Path,
+    query: &CompiledQuery,
+    config: &SearchConfig,
+    cap: usize,
+) -> Vec<SearchMatch> {
+    let (Some(compiled), Some(backend)) = (query.for_path(path), backend_for_path(path)) else {
+        return Vec::new();
+    };
+    let Ok(source) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(tree) = backend.parse_syntax_tree(path, &source) else {
+        return Vec::new();
+    };
+
+    let names = compiled.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut captures = cursor.captures(compiled, tree.root_node(), source.as_bytes());
+    let mut matches = Vec::new();
+
+    while let Some((query_match, index)) = captures.next() {
+        let capture = query_match.captures[*index];
+        let node = capture.node;
+        let start = node.start_byte();
+        let end = node.end_byte();
+
+        let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = source[end..]
+            .find('\n')
+            .map_or(source.len(), |idx| end + idx);
+        let snippet = match config.max_lines {
+            Some(0) => None,
+            Some(max) => Some(
+                source[line_start..line_end]
+                    .lines()
+                    .take(max)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            None => Some(source[line_start..line_end].to_string()),
+        };
+
+        matches.push(SearchMatch {
+            path: path.to_path_buf(),
+            line: node.start_position().row as u32 + 1,
+            column: Some(node.start_position().column as u32 + 1),
+            snippet,
+            spans: vec![MatchSpan::in_text(
+                &source[line_start..],
+                start - line_start,
+                end - line_start,
+            )],
+            enclosing: None,
+            bindings: Default::default(),
+            capture: Some(QueryCapture {
+                name: names[capture.index as usize].to_string(),
+                text: source[start..end].to_string(),
+                range: node_text_range(&node),
+                pattern: query_match.pattern_index as u32,
+            }),
+            reference_kind: None,
+        });
+
+        if matches.len() >= cap {
+            break;
+        }
+    }
+
+    matches
+}
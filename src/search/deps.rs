@@ -0,0 +1,756 @@
+//! File-level dependency graph for `symgrep deps`.
+//!
+//! Dependencies are the import/`use`/`#include` statements the
+//! language backends already extract for cross-file call resolution,
+//! reduced to one record per statement and resolved target file. The
+//! graph is built either by parsing every candidate file or from the
+//! `deps` table of an index.
+//!
+//! `symgrep check-deps` runs over the same graph: it reports import
+//! cycles (strongly connected components) and dependencies breaking
+//! layering rules such as `src/language/** must not import src/cli/**`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use globset::{Glob, GlobMatcher};
+
+use crate::index::models::NewDependencyRecord;
+use crate::index::records::dependency_records;
+use crate::index::IndexBackend;
+use crate::language::{
+    backend_for_language, backend_for_path, normalize_path, rust_module_dir, CompileDatabase,
+    FileSymbols, LanguageBackend,
+};
+use crate::models::{
+    CheckDepsConfig, CheckDepsResult, DependencyCycle, DependencyKind, DepsConfig, DepsResult,
+    FileDependency, LayerViolation, Symbol, TransitiveDependency, CHECK_DEPS_RESULT_VERSION,
+    DEPS_RESULT_VERSION,
+};
+use crate::walk::{collect_files, for_each_ordered};
+
+/// Parse `path` and extract its dependency records, resolving
+/// includes with the compile database `build`, if any. Returns `None`
+/// for unreadable or unparsable files.
+fn scan_dependencies(
+    path: &Path,
+    backend: &dyn LanguageBackend,
+    build: Option<&CompileDatabase>,
+) -> Option<Vec<NewDependencyRecord>> {
+    let source = fs::read_to_string(path).ok()?;
+    let parsed = backend.parse_file(path, &source).ok()?;
+    let imports = backend.imports(&parsed, build).ok()?;
+    Some(dependency_records(path, &imports))
+}
+
+/// Module specifiers each file is imported by, keyed by its
+/// normalized path.
+pub(crate) type ModuleSpecifierMap = HashMap<PathBuf, BTreeSet<String>>;
+
+/// Fill in `Symbol.modules` for the symbols of `files` from their
+/// imports of each other.
+pub(crate) fn attach_module_specifiers(files: &mut [FileSymbols]) {
+    let mut graph = DependencyGraph::default();
+    for file in files.iter() {
+        graph.files.insert(
+            normalize_path(&file.path),
+            dependency_records(&file.path, &file.imports),
+        );
+    }
+    let specifiers = graph.module_specifiers();
+    for file in files.iter_mut() {
+        for symbol in &mut file.symbols {
+            attach_modules(&specifiers, symbol);
+        }
+    }
+}
+
+/// Set `symbol.modules` from a map built by
+/// `DependencyGraph::module_specifiers`.
+pub(crate) fn attach_modules(specifiers: &ModuleSpecifierMap, symbol: &mut Symbol) {
+    if let Some(modules) = specifiers.get(&normalize_path(&symbol.file)) {
+        symbol.modules = modules.iter().cloned().collect();
+    }
+}
+
+/// Dependencies of every file known to a query, keyed by normalized
+/// path.
+#[derive(Debug, Default)]
+pub(crate) struct DependencyGraph {
+    files: BTreeMap<PathBuf, Vec<NewDependencyRecord>>,
+    /// Compile database used for files parsed on demand.
+    build: Option<CompileDatabase>,
+}
+
+impl DependencyGraph {
+    /// Parse every supported file under `paths`.
+    pub(crate) fn scan(
+        paths: &[PathBuf],
+        globs: &[String],
+        exclude_globs: &[String],
+        language: Option<&str>,
+        build: Option<CompileDatabase>,
+    ) -> Result<Self> {
+        let include_globs = crate::index::build_globset(globs)?;
+        let exclude_globs = crate::index::build_globset(exclude_globs)?;
+
+        let selected_backend = match language {
+            Some(lang) => Some(backend_for_language(lang).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "deps is only supported for known languages (e.g., typescript, javascript, rust, cpp); got {}",
+                    lang
+                )
+            })?),
+            None => None,
+        };
+        let backend_for = |path: &Path| match selected_backend {
+            Some(backend) => {
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                backend
+                    .file_extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(ext))
+                    .then_some(backend)
+            }
+            None => backend_for_path(path),
+        };
+
+        let files = collect_files(paths, include_globs.as_ref(), exclude_globs.as_ref());
+
+        let mut graph = Self::default();
+        for_each_ordered(
+            &files,
+            None,
+            |path| {
+                backend_for(path)
+                    .and_then(|backend| scan_dependencies(path, backend, build.as_ref()))
+            },
+            |path, deps| {
+                if let Some(deps) = deps {
+                    graph.files.insert(normalize_path(path), deps);
+                }
+                Ok(true)
+            },
+        )?;
+
+        graph.build = build;
+        Ok(graph)
+    }
+
+    /// Load the dependencies of indexed files under `roots`.
+    pub(crate) fn from_index(
+        backend: &dyn IndexBackend,
+        roots: &[PathBuf],
+        build: Option<CompileDatabase>,
+    ) -> Result<Self> {
+        let roots: Vec<PathBuf> = roots.iter().map(|root| normalize_path(root)).collect();
+        let paths: BTreeMap<u64, PathBuf> = backend
+            .list_files()?
+            .into_iter()
+            .map(|file| (file.id, normalize_path(&file.path)))
+            .filter(|(_, path)| roots.iter().any(|root| path.starts_with(root)))
+            .collect();
+
+        let mut graph = Self {
+            build,
+            ..Self::default()
+        };
+        for path in paths.values() {
+            graph.files.insert(path.clone(), Vec::new());
+        }
+        for record in backend.query_dependencies(&[])? {
+            let Some(path) = paths.get(&record.file_id) else {
+                continue;
+            };
+            if let Some(deps) = graph.files.get_mut(path) {
+                deps.push(NewDependencyRecord {
+                    module: record.module,
+                    kind: record.kind,
+                    target: record.target,
+                    line: record.line,
+                });
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Dependencies of `path`, parsing it on demand when it was not
+    /// part of the scanned or indexed files (e.g. an import target
+    /// outside the searched paths).
+    fn deps_of(&mut self, path: &Path) -> &[NewDependencyRecord] {
+        if !self.files.contains_key(path) {
+            let deps = backend_for_path(path)
+                .and_then(|backend| scan_dependencies(path, backend, self.build.as_ref()))
+                .unwrap_or_default();
+            self.files.insert(path.to_path_buf(), deps);
+        }
+        &self.files[path]
+    }
+
+    /// Specifiers the known files import each resolved target by.
+    pub(crate) fn module_specifiers(&self) -> ModuleSpecifierMap {
+        let mut map = ModuleSpecifierMap::new();
+        for dep in self.files.values().flatten() {
+            if let Some(target) = &dep.target {
+                map.entry(target.clone())
+                    .or_default()
+                    .insert(dep.module.clone());
+            }
+        }
+        map
+    }
+
+    /// Compute the `DepsResult` for `file`.
+    pub(crate) fn result_for(mut self, file: &Path, transitive: bool) -> DepsResult {
+        let file = normalize_path(file);
+
+        let imports = self
+            .deps_of(&file)
+            .iter()
+            .map(|dep| file_dependency(&file, dep))
+            .collect();
+
+        let importers = self
+            .files
+            .iter()
+            .filter(|(path, _)| **path != file)
+            .flat_map(|(path, deps)| {
+                deps.iter()
+                    .filter(|dep| dep.target.as_deref() == Some(file.as_path()))
+                    .map(|dep| file_dependency(path, dep))
+            })
+            .collect();
+
+        let (transitive_imports, transitive_importers) = if transitive {
+            let reachable = self.closure(&file, |graph, path| graph.targets_of(path));
+            let reaching = self.closure(&file, |graph, path| graph.importers_of(path));
+            (Some(reachable), Some(reaching))
+        } else {
+            (None, None)
+        };
+
+        DepsResult {
+            version: DEPS_RESULT_VERSION.to_string(),
+            file,
+            imports,
+            importers,
+            transitive_imports,
+            transitive_importers,
+        }
+    }
+
+    /// Distinct resolved targets of `path`, in source order.
+    fn targets_of(&mut self, path: &Path) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        self.deps_of(path)
+            .iter()
+            .filter_map(|dep| dep.target.clone())
+            .filter(|target| seen.insert(target.clone()))
+            .collect()
+    }
+
+    /// Files with a dependency resolving to `path`, in path order.
+    fn importers_of(&mut self, path: &Path) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .filter(|(_, deps)| deps.iter().any(|dep| dep.target.as_deref() == Some(path)))
+            .map(|(importer, _)| importer.clone())
+            .collect()
+    }
+
+    /// Breadth-first closure of `file` under `next`, excluding `file`
+    /// itself, ordered by depth and then path.
+    fn closure(
+        &mut self,
+        file: &Path,
+        next: impl Fn(&mut Self, &Path) -> Vec<PathBuf>,
+    ) -> Vec<TransitiveDependency> {
+        let mut seen: HashSet<PathBuf> = HashSet::from([file.to_path_buf()]);
+        let mut queue = VecDeque::from([(file.to_path_buf(), 0u32)]);
+        let mut out = Vec::new();
+
+        while let Some((path, depth)) = queue.pop_front() {
+            for neighbour in next(self, &path) {
+                if seen.insert(neighbour.clone()) {
+                    out.push(TransitiveDependency {
+                        file: neighbour.clone(),
+                        depth: depth + 1,
+                    });
+                    queue.push_back((neighbour, depth + 1));
+                }
+            }
+        }
+
+        out.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.file.cmp(&b.file)));
+        out
+    }
+
+    /// Check the graph for import cycles (unless `skip_cycles`) and
+    /// layering violations.
+    ///
+    /// Only files that were scanned or indexed take part; `mod`
+    /// declarations are structure rather than imports and are ignored,
+    /// and cycles skip edges along the Rust module tree.
+    pub(crate) fn check(
+        &self,
+        rules: &[LayerRule],
+        roots: &[PathBuf],
+        skip_cycles: bool,
+    ) -> CheckDepsResult {
+        let roots: Vec<PathBuf> = roots.iter().map(|root| normalize_path(root)).collect();
+
+        let mut violations = Vec::new();
+        for (file, deps) in &self.files {
+            for dep in deps.iter().filter(|dep| dep.kind != DependencyKind::Mod) {
+                let Some(target) = &dep.target else {
+                    continue;
+                };
+                for rule in rules {
+                    if rule.forbids(file, target, &roots) {
+                        violations.push(LayerViolation {
+                            rule: rule.text.clone(),
+                            dependency: file_dependency(file, dep),
+                        });
+                    }
+                }
+            }
+        }
+
+        CheckDepsResult {
+            version: CHECK_DEPS_RESULT_VERSION.to_string(),
+            files_checked: self.files.len() as u64,
+            cycles: if skip_cycles {
+                Vec::new()
+            } else {
+                self.cycles()
+            },
+            violations,
+        }
+    }
+
+    /// Import cycles: strongly connected components with more than one
+    /// file.
+    fn cycles(&self) -> Vec<DependencyCycle> {
+        let files: Vec<&Path> = self.files.keys().map(PathBuf::as_path).collect();
+        let mut cycles = Vec::new();
+        for component in strongly_connected(&files, |file| self.cycle_edges(file)) {
+            if component.len() < 2 {
+                continue;
+            }
+            let members: BTreeSet<&Path> = component.iter().copied().collect();
+
+            let members = &members;
+            let dependencies = members
+                .iter()
+                .flat_map(|file| {
+                    let edges = self.cycle_edges(file);
+                    self.files[*file]
+                        .iter()
+                        .filter(|dep| dep.kind != DependencyKind::Mod)
+                        .filter(move |dep| {
+                            dep.target.as_deref().is_some_and(|target| {
+                                edges.contains(target) && members.contains(target)
+                            })
+                        })
+                        .map(|dep| file_dependency(file, dep))
+                })
+                .collect();
+            cycles.push(DependencyCycle {
+                files: members.iter().map(|file| file.to_path_buf()).collect(),
+                dependencies,
+            });
+        }
+
+        cycles.sort_by(|a, b| a.files.cmp(&b.files));
+        cycles
+    }
+
+    /// Targets of `file` that can close a cycle: imports of other
+    /// checked files. Rust `use self::...` paths resolve to the file
+    /// itself and are not cycles, and neither are edges along the
+    /// module tree (see `is_module_nesting`).
+    fn cycle_edges<'a>(&'a self, file: &Path) -> BTreeSet<&'a Path> {
+        self.files[file]
+            .iter()
+            .filter(|dep| dep.kind != DependencyKind::Mod)
+            .filter_map(|dep| dep.target.as_deref())
+            .filter(|target| *target != file && self.files.contains_key(*target))
+            .filter(|target| !is_module_nesting(file, target))
+            .collect()
+    }
+}
+
+/// Whether a dependency between two Rust files follows the module
+/// tree: a module using its own submodules (`pub use calls::scan_file`
+/// in `language/mod.rs`) or a submodule using an ancestor
+/// (`use crate::language::...` in `language/calls.rs`).
+fn is_module_nesting(file: &Path, target: &Path) -> bool {
+    let is_rust = |path: &Path| path.extension().is_some_and(|ext| ext == "rs");
+    let contains = |module: &Path, other: &Path| other.starts_with(rust_module_dir(module));
+    is_rust(file) && is_rust(target) && (contains(file, target) || contains(target, file))
+}
+
+/// Tarjan's strongly connected components over `nodes`, iterative so
+/// that long import chains cannot overflow the stack.
+fn strongly_connected<'a>(
+    nodes: &[&'a Path],
+    edges: impl Fn(&'a Path) -> BTreeSet<&'a Path>,
+) -> Vec<Vec<&'a Path>> {
+    struct Frame<'a> {
+        node: &'a Path,
+        next: Vec<&'a Path>,
+    }
+
+    let mut index: HashMap<&Path, usize> = HashMap::new();
+    let mut lowlink: HashMap<&Path, usize> = HashMap::new();
+    let mut on_stack: HashSet<&Path> = HashSet::new();
+    let mut stack: Vec<&Path> = Vec::new();
+    let mut components = Vec::new();
+
+    for &root in nodes {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        let mut frames: Vec<Frame> = Vec::new();
+        let visit = |node: &'a Path,
+                     index: &mut HashMap<&'a Path, usize>,
+                     lowlink: &mut HashMap<&'a Path, usize>,
+                     on_stack: &mut HashSet<&'a Path>,
+                     stack: &mut Vec<&'a Path>| {
+            let order = index.len();
+            index.insert(node, order);
+            lowlink.insert(node, order);
+            on_stack.insert(node);
+            stack.push(node);
+            Frame {
+                node,
+                next: edges(node).into_iter().rev().collect(),
+            }
+        };
+        frames.push(visit(
+            root,
+            &mut index,
+            &mut lowlink,
+            &mut on_stack,
+            &mut stack,
+        ));
+
+        while let Some(frame) = frames.last_mut() {
+            let node = frame.node;
+            if let Some(next) = frame.next.pop() {
+                if !index.contains_key(next) {
+                    frames.push(visit(
+                        next,
+                        &mut index,
+                        &mut lowlink,
+                        &mut on_stack,
+                        &mut stack,
+                    ));
+                } else if on_stack.contains(next) {
+                    let low = lowlink[node].min(index[next]);
+                    lowlink.insert(node, low);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(parent) = frames.last() {
+                let low = lowlink[parent.node].min(lowlink[node]);
+                lowlink.insert(parent.node, low);
+            }
+            if lowlink[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// A layering rule: files matching `from` must not import files
+/// matching `to`.
+#[derive(Debug, Clone)]
+pub(crate) struct LayerRule {
+    text: String,
+    from: GlobMatcher,
+    to: GlobMatcher,
+}
+
+impl LayerRule {
+    /// Parse `<glob> must not import <glob>`.
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow!(
+                "invalid dependency rule `{}`: expected `<glob> must not import <glob>`",
+                text
+            )
+        };
+        let (from, to) = text.split_once(" must not import ").ok_or_else(invalid)?;
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() || to.is_empty() {
+            return Err(invalid());
+        }
+
+        let matcher = |glob: &str| -> Result<GlobMatcher> {
+            Glob::new(glob)
+                .map(|glob| glob.compile_matcher())
+                .map_err(|err| anyhow!("invalid glob in dependency rule `{}`: {}", text, err))
+        };
+        Ok(Self {
+            text: text.trim().to_string(),
+            from: matcher(from)?,
+            to: matcher(to)?,
+        })
+    }
+
+    /// Whether `file` importing `target` breaks this rule.
+    fn forbids(&self, file: &Path, target: &Path, roots: &[PathBuf]) -> bool {
+        glob_matches(&self.from, file, roots) && glob_matches(&self.to, target, roots)
+    }
+}
+
+/// Rule globs match either the path as reported or the path relative
+/// to one of the checked roots, so rules written against the project
+/// layout work however the roots are spelled.
+fn glob_matches(glob: &GlobMatcher, path: &Path, roots: &[PathBuf]) -> bool {
+    glob.is_match(path)
+        || roots
+            .iter()
+            .filter_map(|root| path.strip_prefix(root).ok())
+            .any(|relative| glob.is_match(relative))
+}
+
+fn file_dependency(file: &Path, dep: &NewDependencyRecord) -> FileDependency {
+    FileDependency {
+        file: file.to_path_buf(),
+        module: dep.module.clone(),
+        kind: dep.kind,
+        target: dep.target.clone(),
+        line: dep.line,
+    }
+}
+
+/// Validate the inputs of a deps query.
+pub(crate) fn check_config(config: &DepsConfig) -> Result<()> {
+    if !config.file.is_file() {
+        bail!("deps file does not exist: {}", config.file.display());
+    }
+    check_paths(&config.paths)
+}
+
+/// Validate the inputs of a check-deps run and parse its rules.
+pub(crate) fn check_rules(config: &CheckDepsConfig) -> Result<Vec<LayerRule>> {
+    check_paths(&config.paths)?;
+    config
+        .rules
+        .iter()
+        .map(|rule| LayerRule::parse(rule))
+        .collect()
+}
+
+fn check_paths(paths: &[PathBuf]) -> Result<()> {
+    if paths.is_empty() {
+        bail!("at least one search path is required");
+    }
+    for path in paths {
+        if !path.exists() {
+            bail!("search path does not exist: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(module: &str, target: Option<&str>) -> NewDependencyRecord {
+        NewDependencyRecord {
+            module: module.to_string(),
+            kind: DependencyKind::Import,
+            target: target.map(PathBuf::from),
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn result_reports_importers_and_transitive_closure() {
+        let mut graph = DependencyGraph::default();
+        graph
+            .files
+            .insert(PathBuf::from("a.ts"), vec![dep("./b", Some("b.ts"))]);
+        graph.files.insert(
+            PathBuf::from("b.ts"),
+            vec![dep("./c", Some("c.ts")), dep("lodash", None)],
+        );
+        graph
+            .files
+            .insert(PathBuf::from("c.ts"), vec![dep("./a", Some("a.ts"))]);
+
+        let result = graph.result_for(Path::new("./b.ts"), true);
+
+        assert_eq!(result.file, PathBuf::from("b.ts"));
+        assert_eq!(result.imports.len(), 2);
+        assert_eq!(result.imports[1].target, None);
+        assert_eq!(result.importers.len(), 1);
+        assert_eq!(result.importers[0].file, PathBuf::from("a.ts"));
+
+        let reachable: Vec<(PathBuf, u32)> = result
+            .transitive_imports
+            .expect("transitive imports")
+            .into_iter()
+            .map(|dep| (dep.file, dep.depth))
+            .collect();
+        assert_eq!(
+            reachable,
+            vec![(PathBuf::from("c.ts"), 1), (PathBuf::from("a.ts"), 2)]
+        );
+
+        let reaching = result.transitive_importers.expect("transitive importers");
+        assert_eq!(reaching[0].file, PathBuf::from("a.ts"));
+        assert_eq!(reaching[1].file, PathBuf::from("c.ts"));
+        assert_eq!(reaching[1].depth, 2);
+    }
+
+    fn graph(edges: &[(&str, &[&str])]) -> DependencyGraph {
+        let mut graph = DependencyGraph::default();
+        for (file, targets) in edges {
+            let deps = targets
+                .iter()
+                .map(|target| dep(target, Some(target)))
+                .collect();
+            graph.files.insert(PathBuf::from(file), deps);
+        }
+        graph
+    }
+
+    #[test]
+    fn check_reports_cycles_between_files() {
+        let mut graph = graph(&[
+            ("a.ts", &["b.ts"]),
+            ("b.ts", &["c.ts", "d.ts"]),
+            ("c.ts", &["a.ts"]),
+            ("d.ts", &[]),
+            // Importing yourself (e.g. Rust `use self::...`) is not a
+            // cycle.
+            ("e.ts", &["e.ts"]),
+        ]);
+        // A `mod` declaration pointing back at its parent is structure,
+        // not a cycle.
+        graph.files.insert(
+            PathBuf::from("lib.rs"),
+            vec![NewDependencyRecord {
+                kind: DependencyKind::Mod,
+                ..dep("util", Some("util.rs"))
+            }],
+        );
+        graph.files.insert(
+            PathBuf::from("util.rs"),
+            vec![NewDependencyRecord {
+                kind: DependencyKind::Use,
+                ..dep("crate", Some("lib.rs"))
+            }],
+        );
+
+        let result = graph.check(&[], &[], false);
+        assert!(graph.check(&[], &[], true).cycles.is_empty());
+
+        assert_eq!(result.files_checked, 7);
+        assert!(result.violations.is_empty());
+        let cycles: Vec<Vec<PathBuf>> = result.cycles.iter().map(|c| c.files.clone()).collect();
+        assert_eq!(
+            cycles,
+            vec![vec![
+                PathBuf::from("a.ts"),
+                PathBuf::from("b.ts"),
+                PathBuf::from("c.ts")
+            ]]
+        );
+        let links: Vec<(&Path, Option<&Path>)> = result.cycles[0]
+            .dependencies
+            .iter()
+            .map(|dep| (dep.file.as_path(), dep.target.as_deref()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (Path::new("a.ts"), Some(Path::new("b.ts"))),
+                (Path::new("b.ts"), Some(Path::new("c.ts"))),
+                (Path::new("c.ts"), Some(Path::new("a.ts"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_ignores_rust_module_nesting() {
+        let graph = graph(&[
+            // A module re-exporting its submodules, which use it back.
+            ("src/index/mod.rs", &["src/index/file.rs"]),
+            ("src/index/file.rs", &["src/index/mod.rs", "src/lib.rs"]),
+            ("src/lib.rs", &["src/index/mod.rs"]),
+            // Siblings importing each other are still a cycle.
+            ("src/language/cargo.rs", &["src/language/imports.rs"]),
+            ("src/language/imports.rs", &["src/language/cargo.rs"]),
+        ]);
+
+        let result = graph.check(&[], &[], false);
+        let cycles: Vec<Vec<PathBuf>> = result.cycles.iter().map(|c| c.files.clone()).collect();
+        assert_eq!(
+            cycles,
+            vec![vec![
+                PathBuf::from("src/language/cargo.rs"),
+                PathBuf::from("src/language/imports.rs")
+            ]]
+        );
+    }
+
+    #[test]
+    fn check_reports_layering_violations_relative_to_roots() {
+        let graph = graph(&[
+            ("repo/src/language/rust.rs", &["repo/src/cli/args.rs"]),
+            ("repo/src/cli/args.rs", &["repo/src/models/mod.rs"]),
+            ("repo/src/models/mod.rs", &[]),
+        ]);
+        let rules = vec![
+            LayerRule::parse("src/language/** must not import src/cli/**").unwrap(),
+            LayerRule::parse("src/models/** must not import src/**").unwrap(),
+        ];
+
+        let result = graph.check(&rules, &[PathBuf::from("./repo")], false);
+
+        assert!(result.cycles.is_empty());
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(
+            result.violations[0].rule,
+            "src/language/** must not import src/cli/**"
+        );
+        assert_eq!(
+            result.violations[0].dependency.file,
+            PathBuf::from("repo/src/language/rust.rs")
+        );
+    }
+
+    #[test]
+    fn layer_rule_rejects_malformed_rules() {
+        let err = LayerRule::parse("src/language/** imports src/cli/**").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected `<glob> must not import <glob>`"));
+
+        let err = LayerRule::parse("src/[ must not import src/cli/**").unwrap_err();
+        assert!(err.to_string().contains("invalid glob"));
+    }
+}
@@ -3,24 +3,45 @@
 //! These functions provide the "search as a function" API used by the
 //! CLI and, in later phases, the daemon/server.
 
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use anyhow::{bail, Result};
 use globset::{Glob, GlobSet};
-use ignore::WalkBuilder;
 
-use crate::index::models::SymbolQuery;
+use crate::index::models::{
+    SymbolQuery, SCHEMA_ANNOTATIONS, SCHEMA_CALLS, SCHEMA_DEPS, SCHEMA_DOCS, SCHEMA_MEMBERS,
+    SCHEMA_RELATIONS, SCHEMA_TEST_FLAGS,
+};
 use crate::index::open_backend;
-use crate::language::{backend_for_language, backend_for_path};
+use crate::language::{
+    backend_for_language, backend_for_path, is_test_file, resolve_cross_file_calls, scan_file,
+    scan_parsed_file, CompileDatabase, FileSymbols, ParsedFile,
+};
 use crate::models::{
-    ContextKind, IndexConfig, IndexSummary, SearchConfig, SearchMatch, SearchMode, SearchResult,
-    SearchSummary, SymbolMatch, SymbolView, SEARCH_RESULT_VERSION,
+    CheckDepsConfig, CheckDepsResult, ContextInfo, ContextKind, DepsConfig, DepsResult, HierarchyConfig, HierarchyResult, IndexConfig, IndexSummary, QueryExpr, SearchConfig, SearchMode,
+    SearchResult, SearchSummary, Symbol, SymbolMatch, SymbolView, SEARCH_RESULT_VERSION,
 };
 use crate::search::query::{
-    expr_has_call_terms, expr_has_content_terms, parse_query_expr, symbol_matches_metadata,
-    symbol_matches_with_text,
+    check_file_globs, expr_has_call_terms, expr_has_content_terms, expr_has_hierarchy_terms,
+    expr_has_attr_terms, expr_has_doc_terms, expr_has_is_terms, expr_has_module_terms,
+    expr_has_scope_terms,
+    find_invalid_scope_term, parse_query_expr, scope_matches_terms,
+    path_matches_file_terms, symbol_matches_metadata, symbol_matches_with_text,
+};
+use crate::search::text::{
+    attach_enclosing_symbols, expr_is_text_only, retain_in_scopes, TextSearcher,
 };
+use crate::search::ast::{self, ast_snippet, FileOutcome};
+use crate::search::calls::IndexedCallGraph;
+use crate::search::capture::{self, CompiledQuery};
+use crate::search::deps::{self, DependencyGraph};
+use crate::search::hierarchy::{self, attach_supertypes, TypeGraph};
+use crate::search::members::{pair_member_definitions, MemberPairs};
+use crate::search::refs::{self, refs_target};
+use crate::walk::{collect_files, for_each_ordered, parallel_batch_size};
 
 /// Execute a search based on the provided configuration.
 ///
@@ -35,14 +56,36 @@ pub fn run_search(mut config: SearchConfig) -> Result<SearchResult> {
         config.query_expr = parse_query_expr(&config.pattern);
     }
 
+    if let Some(expr) = &config.query_expr {
+        check_file_globs(expr)?;
+        if let Some(value) = find_invalid_scope_term(expr) {
+            bail!("unknown scope `{value}`; expected code, comments, or strings");
+        }
+    }
+
+    if let Some(expr) = &config.query_expr {
+        if let Some(snippet) = ast_snippet(expr)? {
+            let snippet = snippet.to_string();
+            return run_ast_search(config, &snippet);
+        }
+        if let Some(name) = refs_target(expr)? {
+            let name = name.to_string();
+            return run_refs_search(config, &name);
+        }
+    }
+
     let has_call_terms = config
         .query_expr
         .as_ref()
-        .is_some_and(|expr| expr_has_call_terms(expr));
+        .is_some_and(expr_has_call_terms);
+    let has_hierarchy_terms = config
+        .query_expr
+        .as_ref()
+        .is_some_and(expr_has_hierarchy_terms);
 
     let mode = effective_mode(&config);
 
-    if config.reindex_on_search && matches!(mode, SearchMode::Symbol) && !has_call_terms {
+    if config.reindex_on_search && matches!(mode, SearchMode::Symbol) {
         if let Some(index_cfg) = config.index.as_ref() {
             let effective_index =
                 resolve_effective_index_config(&config).unwrap_or_else(|| index_cfg.clone());
@@ -53,8 +96,8 @@ pub fn run_search(mut config: SearchConfig) -> Result<SearchResult> {
     match mode {
         SearchMode::Text => run_text_search(config),
         SearchMode::Symbol => {
-            if config.index.is_some() && !has_call_terms {
-                run_symbol_search_with_index(config)
+            if config.index.is_some() {
+                run_symbol_search_with_index(config, has_call_terms, has_hierarchy_terms)
             } else {
                 run_symbol_search_without_index(config)
             }
@@ -63,6 +106,320 @@ pub fn run_search(mut config: SearchConfig) -> Result<SearchResult> {
     }
 }
 
+/// Structural search for queries with an `ast:` term, regardless of
+/// the configured mode.
+fn run_ast_search(config: SearchConfig, snippet: &str) -> Result<SearchResult> {
+    let enclosing = config.enclosing;
+    let (result, pattern_invalid) =
+        run_per_file_search(config, enclosing, |path, expr, config, cap| {
+            ast::search_file(path, snippet, expr, config, cap)
+        })?;
+
+    if pattern_invalid {
+        bail!("ast: pattern `{snippet}` is not valid syntax in any of the searched languages");
+    }
+
+    Ok(result)
+}
+
+/// Identifier usages for queries with a `refs:` term, regardless of
+/// the configured mode. Usages always carry their enclosing symbol.
+fn run_refs_search(config: SearchConfig, name: &str) -> Result<SearchResult> {
+    let (result, _) = run_per_file_search(config, true, |path, expr, config, cap| {
+        refs::search_file(path, name, expr, config, cap)
+    })?;
+    Ok(result)
+}
+
+/// Shared driver for searches that match each file on its own
+/// syntax tree (`ast:` and `refs:`).
+///
+/// Also returns whether the pattern was rejected by every file it was
+/// tried on.
+fn run_per_file_search<F>(
+    config: SearchConfig,
+    enclosing: bool,
+    search_file: F,
+) -> Result<(SearchResult, bool)>
+where
+    F: Fn(&Path, &QueryExpr, &SearchConfig, usize) -> FileOutcome + Sync,
+{
+    let include_globs = build_globset(&config.globs)?;
+    let exclude_globs = build_globset(&config.exclude_globs)?;
+
+    for path in &config.paths {
+        if !path.exists() {
+            bail!("search path does not exist: {}", path.display());
+        }
+    }
+
+    let expr = config
+        .query_expr
+        .clone()
+        .expect("per-file search requires a parsed query");
+    let files = collect_files(
+        &config.paths,
+        include_globs.as_ref(),
+        exclude_globs.as_ref(),
+    );
+
+    let limit = config.limit.unwrap_or(usize::MAX);
+    let batch_size = config.limit.map(|_| parallel_batch_size());
+    let per_file_cap = limit.saturating_add(1);
+
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    let mut pattern_parsed = false;
+    let mut pattern_failed = false;
+
+    for_each_ordered(
+        &files,
+        batch_size,
+        |path| {
+            if !path_matches_file_terms(&expr, path, &config.paths) {
+                return FileOutcome::Skipped;
+            }
+            let mut outcome = search_file(path, &expr, &config, per_file_cap);
+            if let FileOutcome::Matched(file_matches) = &mut outcome {
+                if enclosing && !file_matches.is_empty() {
+                    let source = fs::read_to_string(path).ok();
+                    attach_enclosing_symbols(path, source.as_deref(), file_matches);
+                }
+            }
+            outcome
+        },
+        |_, outcome| {
+            let file_matches = match outcome {
+                FileOutcome::Skipped => return Ok(true),
+                FileOutcome::PatternInvalid => {
+                    pattern_failed = true;
+                    return Ok(true);
+                }
+                FileOutcome::Matched(file_matches) => file_matches,
+            };
+            pattern_parsed = true;
+
+            for search_match in file_matches {
+                if matches.len() >= limit {
+                    truncated = true;
+                    return Ok(false);
+                }
+                matches.push(search_match);
+            }
+            Ok(true)
+        },
+    )?;
+
+    let summary = SearchSummary {
+        total_matches: matches.len() as u64,
+        truncated,
+    };
+
+    let result = SearchResult {
+        version: SEARCH_RESULT_VERSION.to_string(),
+        query: config.pattern,
+        matches,
+        symbols: Vec::new(),
+        contexts: Vec::new(),
+        summary,
+    };
+
+    Ok((result, pattern_failed && !pattern_parsed))
+}
+
+/// Run a raw tree-sitter query over the configured paths.
+///
+/// `config.pattern` holds the query source. Each capture is reported
+/// as one `SearchMatch` with `capture` set, in file and document order;
+/// `enclosing` symbols are attached when `config.enclosing` is set.
+pub fn run_query(config: SearchConfig) -> Result<SearchResult> {
+    if config.pattern.trim().is_empty() {
+        bail!("tree-sitter query must not be empty");
+    }
+
+    let include_globs = build_globset(&config.globs)?;
+    let exclude_globs = build_globset(&config.exclude_globs)?;
+
+    for path in &config.paths {
+        if !path.exists() {
+            bail!("search path does not exist: {}", path.display());
+        }
+    }
+
+    let query = CompiledQuery::compile(&config.pattern, config.language.as_deref())?;
+    let files = collect_files(
+        &config.paths,
+        include_globs.as_ref(),
+        exclude_globs.as_ref(),
+    );
+
+    let limit = config.limit.unwrap_or(usize::MAX);
+    let batch_size = config.limit.map(|_| parallel_batch_size());
+    let per_file_cap = limit.saturating_add(1);
+
+    let mut matches = Vec::new();
+    let mut truncated = false;
+
+    for_each_ordered(
+        &files,
+        batch_size,
+        |path| {
+            let mut file_matches = capture::search_file(path, &query, &config, per_file_cap);
+            if config.enclosing && !file_matches.is_empty() {
+                let source = fs::read_to_string(path).ok();
+                attach_enclosing_symbols(path, source.as_deref(), &mut file_matches);
+            }
+            file_matches
+        },
+        |_, file_matches| {
+            for search_match in file_matches {
+                if matches.len() >= limit {
+                    truncated = true;
+                    return Ok(false);
+                }
+                matches.push(search_match);
+            }
+            Ok(true)
+        },
+    )?;
+
+    let summary = SearchSummary {
+        total_matches: matches.len() as u64,
+        truncated,
+    };
+
+    Ok(SearchResult {
+        version: SEARCH_RESULT_VERSION.to_string(),
+        query: config.pattern,
+        matches,
+        symbols: Vec::new(),
+        contexts: Vec::new(),
+        summary,
+    })
+}
+
+/// Report the dependencies of a single file: the statements it
+/// imports, the files importing it and, optionally, the transitive
+/// closure of both.
+///
+/// Reads the `deps` table of the configured index when one is
+/// available and current, and parses the files under `config.paths`
+/// otherwise.
+pub fn run_deps(config: DepsConfig) -> Result<DepsResult> {
+    deps::check_config(&config)?;
+
+    let graph = load_dependency_graph(
+        &config.paths,
+        &config.globs,
+        &config.exclude_globs,
+        config.language.as_deref(),
+        config.index.as_ref(),
+        config.compile_commands.as_deref(),
+    )?;
+    Ok(graph.result_for(&config.file, config.transitive))
+}
+
+/// Check import cycles and layering rules across the configured paths.
+///
+/// Findings are part of the result rather than an error; callers
+/// decide how to surface them (the CLI exits non-zero).
+pub fn run_check_deps(config: CheckDepsConfig) -> Result<CheckDepsResult> {
+    let rules = deps::check_rules(&config)?;
+
+    let graph = load_dependency_graph(
+        &config.paths,
+        &config.globs,
+        &config.exclude_globs,
+        config.language.as_deref(),
+        config.index.as_ref(),
+        config.compile_commands.as_deref(),
+    )?;
+    Ok(graph.check(&rules, &config.paths, config.skip_cycles))
+}
+
+/// Dependency graph of the files under `paths`, read from the index
+/// when one is configured and current, parsed otherwise.
+fn load_dependency_graph(
+    paths: &[std::path::PathBuf],
+    globs: &[String],
+    exclude_globs: &[String],
+    language: Option<&str>,
+    index: Option<&IndexConfig>,
+    compile_commands: Option<&Path>,
+) -> Result<DependencyGraph> {
+    let build = compile_commands.map(CompileDatabase::load).transpose()?;
+
+    // Like symbol search, fall back to parsing when the index cannot
+    // be opened or predates dependencies.
+    let backend = index
+        .and_then(effective_index)
+        .and_then(|index_cfg| open_backend(&index_cfg).ok())
+        .filter(|backend| {
+            backend
+                .load_meta()
+                .is_ok_and(|meta| meta.schema() >= SCHEMA_DEPS)
+        });
+    match backend {
+        Some(backend) => DependencyGraph::from_index(backend.as_ref(), paths, build),
+        None => DependencyGraph::scan(paths, globs, exclude_globs, language, build),
+    }
+}
+
+/// Walk the type hierarchy of `config.symbol` towards its supertypes
+/// or subtypes.
+///
+/// Reads the `relations` table of the configured index when one is
+/// available and current, and parses the files under `config.paths`
+/// otherwise.
+pub fn run_hierarchy(config: HierarchyConfig) -> Result<HierarchyResult> {
+    if config.symbol.is_empty() {
+        bail!("hierarchy symbol must not be empty");
+    }
+    for path in &config.paths {
+        if !path.exists() {
+            bail!("search path does not exist: {}", path.display());
+        }
+    }
+
+    // Indexes that predate type relations cannot answer this; parse
+    // the files instead, like symbol search does for call edges.
+    let backend = config
+        .index
+        .as_ref()
+        .and_then(effective_index)
+        .and_then(|index_cfg| open_backend(&index_cfg).ok())
+        .filter(|backend| {
+            backend
+                .load_meta()
+                .is_ok_and(|meta| meta.schema() >= SCHEMA_RELATIONS)
+        });
+    let graph = match backend {
+        Some(backend) => TypeGraph::from_index(
+            backend.as_ref(),
+            &config.paths,
+            &config.globs,
+            &config.exclude_globs,
+            config.language.as_deref(),
+        )?,
+        None => {
+            let build = config
+                .compile_commands
+                .as_deref()
+                .map(CompileDatabase::load)
+                .transpose()?;
+            TypeGraph::scan(
+                &config.paths,
+                &config.globs,
+                &config.exclude_globs,
+                config.language.as_deref(),
+                build.as_ref(),
+            )?
+        }
+    };
+
+    Ok(graph.result_for(&config.symbol, config.direction, config.depth))
+}
+
 fn effective_mode(config: &SearchConfig) -> SearchMode {
     match config.mode {
         SearchMode::Text => SearchMode::Text,
@@ -92,106 +449,87 @@ fn run_text_search(config: SearchConfig) -> Result<SearchResult> {
         }
     }
 
-    let mut builder = WalkBuilder::new(&config.paths[0]);
-    for path in config.paths.iter().skip(1) {
-        builder.add(path);
-    }
-
-    let walker = builder.build();
+    let files = collect_files(
+        &config.paths,
+        include_globs.as_ref(),
+        exclude_globs.as_ref(),
+    );
 
     let mut matches = Vec::new();
     let mut total_matches: u64 = 0;
     let mut truncated = false;
 
     let limit = config.limit.unwrap_or(usize::MAX);
+    let batch_size = config.limit.map(|_| parallel_batch_size());
 
     // For text mode, we optionally interpret the pattern via the DSL
-    // when it parses into a content-only expression. This enables
-    // `foo|bar` OR semantics while preserving legacy behavior for
-    // more complex patterns.
+    // when it parses into a content-only expression (optionally
+    // narrowed by `file:` terms). This enables `foo|bar` OR semantics
+    // while preserving legacy behavior for more complex patterns.
     let query_expr = config
         .query_expr
         .clone()
         .or_else(|| parse_query_expr(&config.pattern));
 
-    let text_only_expr = query_expr.filter(|expr| expr_is_text_only(expr));
-
-    'walk: for entry_result in walker {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-
-        // Only search regular files.
-        if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-            continue;
-        }
-
-        // Apply inclusion/exclusion globs.
-        if let Some(set) = &include_globs {
-            if !set.is_match(path) {
-                continue;
-            }
-        }
-        if let Some(set) = &exclude_globs {
-            if set.is_match(path) {
-                continue;
-            }
-        }
-
-        let file = match File::open(path) {
-            Ok(f) => f,
-            Err(_) => continue,
-        };
-        let reader = BufReader::new(file);
-
-        for (idx, line_result) in reader.lines().enumerate() {
-            let line_number = (idx + 1) as u32;
-            let line = match line_result {
-                Ok(l) => l,
-                Err(_) => break,
-            };
-
-            let column = if let Some(expr) = &text_only_expr {
-                find_in_line(expr, &line, config.literal).map(|idx| idx as u32 + 1)
-            } else if config.literal {
-                find_literal_identifier(&line, &config.pattern)
-                    .map(|byte_index| byte_index as u32 + 1)
-            } else {
-                if !line.contains(&config.pattern) {
-                    continue;
+    // Multiline patterns are regular expressions rather than DSL
+    // queries, so they bypass the content-term interpretation.
+    let text_only_expr = if config.multiline {
+        None
+    } else {
+        query_expr.filter(expr_is_text_only)
+    };
+    let text_searcher = TextSearcher::new(&config, text_only_expr.as_ref())?;
+
+    // `--in` and `scope:` filter occurrences after a file is scanned.
+    let scope_filtered = !config.scopes.is_empty()
+        || text_only_expr.as_ref().is_some_and(expr_has_scope_terms);
+
+    // Each file needs at most one match past the limit to tell whether
+    // the result was truncated. Scope filtering may drop matches, so
+    // those files are scanned fully and capped afterwards.
+    let per_file_cap = limit.saturating_add(1);
+    let scan_cap = if scope_filtered { usize::MAX } else { per_file_cap };
+
+    for_each_ordered(
+        &files,
+        batch_size,
+        |path| {
+            if let Some(expr) = &text_only_expr {
+                if !path_matches_file_terms(expr, path, &config.paths) {
+                    return Vec::new();
                 }
-                line.find(&config.pattern).map(|col| col as u32 + 1)
-            };
-
-            if column.is_none() {
-                continue;
             }
-
-            total_matches += 1;
-
-            if matches.len() < limit {
-                let snippet = match config.max_lines {
-                    Some(0) => None,
-                    _ => Some(line.clone()),
-                };
-
-                matches.push(SearchMatch {
-                    path: path.to_path_buf(),
-                    line: line_number,
-                    column,
-                    snippet,
+            let mut file_matches =
+                text_searcher.search_file(path, &config, text_only_expr.as_ref(), scan_cap);
+            let mut source = None;
+            if scope_filtered && !file_matches.is_empty() {
+                source = text_searcher.decoded_source(path);
+                retain_in_scopes(path, source.as_deref(), &mut file_matches, |scope| {
+                    (config.scopes.is_empty() || config.scopes.contains(&scope))
+                        && text_only_expr
+                            .as_ref()
+                            .is_none_or(|expr| scope_matches_terms(expr, scope))
                 });
+                file_matches.truncate(per_file_cap);
             }
-
-            if matches.len() >= limit {
-                truncated = config.limit.is_some();
-                break 'walk;
+            if config.enclosing && !file_matches.is_empty() {
+                let source = source.or_else(|| text_searcher.decoded_source(path));
+                attach_enclosing_symbols(path, source.as_deref(), &mut file_matches);
             }
-        }
-    }
+            file_matches
+        },
+        |_, file_matches| {
+            for search_match in file_matches {
+                if matches.len() >= limit {
+                    truncated = true;
+                    return Ok(false);
+                }
+                total_matches += 1;
+                matches.push(search_match);
+            }
+            Ok(true)
+        },
+    )?;
 
     let summary = SearchSummary {
         total_matches,
@@ -208,63 +546,6 @@ fn run_text_search(config: SearchConfig) -> Result<SearchResult> {
     })
 }
 
-/// Whether the expression is composed only of content-like terms that
-/// can be evaluated directly against a single line of text.
-fn expr_is_text_only(expr: &crate::models::QueryExpr) -> bool {
-    use crate::models::QueryExpr::*;
-    match expr {
-        Term(term) => matches!(term.field, crate::models::QueryField::Content),
-        And(clauses) | Or(clauses) => clauses.iter().all(expr_is_text_only),
-    }
-}
-
-/// Find the first match column (0-based) for a content-only query
-/// expression within a single line, honoring `--literal` for
-/// identifier-style matching.
-fn find_in_line(expr: &crate::models::QueryExpr, line: &str, literal: bool) -> Option<usize> {
-    use crate::models::QueryExpr::*;
-    match expr {
-        Term(term) => {
-            let value = term.value.as_str();
-            if let Some(exact) = value.strip_prefix('=') {
-                if line == exact {
-                    return Some(0);
-                }
-                return None;
-            }
-
-            if literal {
-                find_literal_identifier(line, value)
-            } else {
-                line.find(value)
-            }
-        }
-        And(clauses) => {
-            let mut best: Option<usize> = None;
-            for clause in clauses {
-                let idx = find_in_line(clause, line, literal)?;
-                best = Some(match best {
-                    Some(current) => current.min(idx),
-                    None => idx,
-                });
-            }
-            best
-        }
-        Or(clauses) => {
-            let mut best: Option<usize> = None;
-            for clause in clauses {
-                if let Some(idx) = find_in_line(clause, line, literal) {
-                    best = Some(match best {
-                        Some(current) => current.min(idx),
-                        None => idx,
-                    });
-                }
-            }
-            best
-        }
-    }
-}
-
 fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult> {
     let include_globs = build_globset(&config.globs)?;
     let exclude_globs = build_globset(&config.exclude_globs)?;
@@ -292,12 +573,16 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
         None
     };
 
-    let mut builder = WalkBuilder::new(&config.paths[0]);
-    for path in config.paths.iter().skip(1) {
-        builder.add(path);
-    }
-
-    let walker = builder.build();
+    let files = collect_files(
+        &config.paths,
+        include_globs.as_ref(),
+        exclude_globs.as_ref(),
+    );
+    let build = config
+        .compile_commands
+        .as_deref()
+        .map(CompileDatabase::load)
+        .transpose()?;
 
     let query_expr = config
         .query_expr
@@ -305,6 +590,12 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
         .or_else(|| parse_query_expr(&config.pattern));
 
     let has_content_terms = query_expr.as_ref().is_some_and(expr_has_content_terms);
+    let has_call_terms = query_expr.as_ref().is_some_and(expr_has_call_terms);
+    let has_hierarchy_terms = query_expr.as_ref().is_some_and(expr_has_hierarchy_terms);
+    let has_module_terms = query_expr.as_ref().is_some_and(expr_has_module_terms);
+    // Tests are listed after production symbols unless the query
+    // selects on `is:` itself.
+    let tests_last = !query_expr.as_ref().is_some_and(expr_has_is_terms);
     let primary_context = primary_context_kind_from_views(&config.symbol_views);
     let want_matches = config
         .symbol_views
@@ -318,67 +609,65 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
     let mut truncated = false;
 
     let limit = config.limit.unwrap_or(usize::MAX);
+    let batch_size = config.limit.map(|_| parallel_batch_size());
 
-    'walk: for entry_result in walker {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-
-        if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-            continue;
+    let backend_for = |path: &Path| match selected_backend {
+        Some(backend) => {
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            backend
+                .file_extensions()
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(ext))
+                .then_some(backend)
         }
+        None => backend_for_path(path),
+    };
 
-        if let Some(set) = &include_globs {
-            if !set.is_match(path) {
-                continue;
-            }
-        }
-        if let Some(set) = &exclude_globs {
-            if set.is_match(path) {
-                continue;
-            }
+    // Call graphs and call/hierarchy/module terms need every candidate
+    // file parsed up front so edges are resolved across files before
+    // any symbol is filtered on them. Other searches parse and filter
+    // one batch of files at a time, so `--limit` can stop the walk
+    // early.
+    let project_wide =
+        config.call_graph || has_call_terms || has_hierarchy_terms || has_module_terms;
+    let mark_tests = |file: &mut FileSymbols| {
+        if is_test_file(&file.path, &config.paths) {
+            file.symbols.iter_mut().for_each(mark_test_symbol);
         }
-
-        let backend = match selected_backend {
-            Some(backend) => {
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                if !backend
-                    .file_extensions()
-                    .iter()
-                    .any(|e| e.eq_ignore_ascii_case(ext))
-                {
-                    continue;
-                }
-                backend
-            }
-            None => match backend_for_path(path) {
-                Some(b) => b,
-                None => continue,
+    };
+    let scan_files = |paths: &[PathBuf]| -> Result<Vec<FileSymbols>> {
+        let mut scanned = Vec::new();
+        for_each_ordered(
+            paths,
+            None,
+            |path| scan_file(path, backend_for(path)?, build.as_ref()),
+            |_, file| {
+                scanned.extend(file);
+                Ok(true)
             },
-        };
-
-        let source = match fs::read_to_string(path) {
-            Ok(s) => s,
-            Err(_) => continue,
-        };
-
-        let parsed = match backend.parse_file(path, &source) {
-            Ok(p) => p,
-            Err(_) => continue,
-        };
+        )?;
+        scanned.iter_mut().for_each(mark_tests);
+        Ok(scanned)
+    };
 
-        let indexed_symbols = match backend.index_symbols(&parsed) {
-            Ok(syms) => syms,
-            Err(_) => continue,
+    // Filtering runs per file on the worker pool; results are merged
+    // below in file order so symbol indices and `--limit` cut-offs are
+    // deterministic. Files scanned up front are parsed again only when
+    // a matching symbol needs a context snippet.
+    let search_file = |file: &FileSymbols,
+                       parsed: Option<&ParsedFile>|
+     -> Result<Vec<SymbolHit>> {
+        let mut hits = Vec::new();
+
+        let Some(backend) = backend_for(&file.path) else {
+            return Ok(hits);
         };
+        let mut reparsed = None;
 
-        for mut symbol in indexed_symbols {
+        for symbol in &file.symbols {
             // First apply metadata-only filters (name/kind/file/language).
             let metadata_matches = if let Some(expr) = &query_expr {
-                symbol_matches_metadata(expr, &symbol, config.literal)
+                symbol_matches_metadata(expr, symbol, &config.paths, config.literal)
             } else if config.literal {
                 symbol.name == config.pattern
             } else {
@@ -389,6 +678,8 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
                 continue;
             }
 
+            let mut symbol = symbol.clone();
+
             // Only materialize a context snippet when either requested
             // by views or required for content-like terms. When views
             // are `meta`-only, we intentionally avoid fetching any
@@ -403,16 +694,30 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
                 {
                     let kind_for_snippet = primary_context.unwrap_or(ContextKind::Def);
 
-                    let context = backend
-                        .get_context_snippet(&parsed, &symbol, kind_for_snippet)
-                        .map_err(|err| {
-                            anyhow::anyhow!(
-                                "failed to get context snippet for symbol {} in {}: {}",
-                                symbol.name,
-                                symbol.file.display(),
-                                err
-                            )
-                        })?;
+                    let context = match out_of_line_definition_context(&symbol, kind_for_snippet)? {
+                        Some(context) => context,
+                        None => {
+                            if parsed.is_none() && reparsed.is_none() {
+                                reparsed = fs::read_to_string(&file.path)
+                                    .ok()
+                                    .and_then(|source| backend.parse_file(&file.path, &source).ok());
+                            }
+                            let Some(parsed) = parsed.or(reparsed.as_ref()) else {
+                                return Ok(hits);
+                            };
+
+                            backend
+                                .get_context_snippet(parsed, &symbol, kind_for_snippet)
+                                .map_err(|err| {
+                                    anyhow::anyhow!(
+                                        "failed to get context snippet for symbol {} in {}: {}",
+                                        symbol.name,
+                                        symbol.file.display(),
+                                        err
+                                    )
+                                })?
+                        }
+                    };
 
                     let context_snippet = context.snippet.clone();
 
@@ -423,53 +728,133 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
                             expr,
                             &symbol,
                             Some(context_snippet.as_str()),
+                            &config.paths,
                             config.literal,
                         )
                     {
                         continue;
                     }
-                } else if !symbol_matches_with_text(expr, &symbol, None, config.literal) {
+                } else if !symbol_matches_with_text(expr, &symbol, None, &config.paths, config.literal) {
                     // Expression without content-like terms or context/snippet
                     // requirements – evaluate against metadata only.
                     continue;
                 }
             }
 
-            let symbol_matches = if want_matches && !views_meta_only {
-                compute_symbol_matches(
+            if want_matches && !views_meta_only {
+                symbol.matches = compute_symbol_matches(
                     query_expr.as_ref(),
                     &symbol,
                     context_for_result.as_ref(),
-                )
-            } else {
-                Vec::new()
-            };
-
-            total_matches += 1;
+                );
+            }
 
-            if symbols.len() < limit {
-                let symbol_index = symbols.len();
+            hits.push((symbol, context_for_result));
+        }
 
-                if let Some(mut context) = context_for_result {
-                    if matches!(context.kind, ContextKind::Def) {
-                        let range = context.range;
-                        let lines = range.end_line.saturating_sub(range.start_line) + 1;
-                        symbol.def_line_count = Some(lines);
-                    }
-                    context.symbol_index = Some(symbol_index);
-                    contexts.push(context);
-                }
+        Ok(hits)
+    };
 
-                let mut symbol_with_matches = symbol;
-                symbol_with_matches.matches = symbol_matches;
-                symbols.push(symbol_with_matches);
+    let mut tests = Vec::new();
+    let mut consume = |file_hits: Result<Vec<SymbolHit>>| -> Result<bool> {
+        for (symbol, context_for_result) in file_hits? {
+            if tests_last && symbol_is_test(&symbol) {
+                tests.push((symbol, context_for_result));
+                continue;
             }
-
             if symbols.len() >= limit {
-                truncated = config.limit.is_some();
-                break 'walk;
+                truncated = true;
+                return Ok(false);
             }
+
+            total_matches += 1;
+            push_symbol_hit(&mut symbols, &mut contexts, symbol, context_for_result);
+        }
+        Ok(true)
+    };
+
+    if project_wide {
+        let mut scanned = scan_files(&files)?;
+        resolve_cross_file_calls(&mut scanned);
+        attach_supertypes(&mut scanned);
+        pair_member_definitions(&mut scanned);
+        if has_module_terms {
+            deps::attach_module_specifiers(&mut scanned);
         }
+        for_each_ordered(
+            &scanned,
+            batch_size,
+            |file| search_file(file, None),
+            |_, file_hits| consume(file_hits),
+        )?;
+    } else {
+        // Files of languages that pair members across files are scanned
+        // together (calls resolved, members paired) once the first of
+        // them has a match; that file is then searched again. Each
+        // search returns whether it still needs to be.
+        let paired_files: OnceLock<HashMap<PathBuf, FileSymbols>> = OnceLock::new();
+        let search_path = |path: &PathBuf| -> Result<(Vec<SymbolHit>, bool)> {
+            let Some(backend) = backend_for(path) else {
+                return Ok((Vec::new(), false));
+            };
+            let pairs_members = backend.pairs_members_across_files();
+            if let Some(paired) = paired_files.get().filter(|_| pairs_members) {
+                let hits = match paired.get(path) {
+                    Some(file) => search_file(file, None)?,
+                    None => Vec::new(),
+                };
+                return Ok((hits, false));
+            }
+
+            let Some(parsed) = fs::read_to_string(path)
+                .ok()
+                .and_then(|source| backend.parse_file(path, &source).ok())
+            else {
+                return Ok((Vec::new(), false));
+            };
+            let Some(mut file) = scan_parsed_file(&parsed, backend, build.as_ref()) else {
+                return Ok((Vec::new(), false));
+            };
+            mark_tests(&mut file);
+            attach_supertypes(std::slice::from_mut(&mut file));
+            let hits = search_file(&file, Some(&parsed))?;
+            let unpaired = pairs_members && !hits.is_empty();
+            Ok((hits, unpaired))
+        };
+
+        for_each_ordered(&files, batch_size, search_path, |path, result| {
+            let file_hits = match result {
+                Ok((_, true)) => {
+                    if paired_files.get().is_none() {
+                        let pairing: Vec<PathBuf> = files
+                            .iter()
+                            .filter(|path| {
+                                backend_for(path)
+                                    .is_some_and(|backend| backend.pairs_members_across_files())
+                            })
+                            .cloned()
+                            .collect();
+                        let mut scanned = scan_files(&pairing)?;
+                        resolve_cross_file_calls(&mut scanned);
+                        attach_supertypes(&mut scanned);
+                        pair_member_definitions(&mut scanned);
+                        let paired = scanned.into_iter().map(|file| (file.path.clone(), file));
+                        let _ = paired_files.set(paired.collect());
+                    }
+                    search_path(path).map(|(hits, _)| hits)
+                }
+                result => result.map(|(hits, _)| hits),
+            };
+            consume(file_hits)
+        })?;
+    }
+    for (symbol, context_for_result) in tests {
+        if symbols.len() >= limit {
+            truncated = true;
+            break;
+        }
+        total_matches += 1;
+        push_symbol_hit(&mut symbols, &mut contexts, symbol, context_for_result);
     }
 
     let summary = SearchSummary {
@@ -487,7 +872,11 @@ fn run_symbol_search_without_index(config: SearchConfig) -> Result<SearchResult>
     })
 }
 
-fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
+fn run_symbol_search_with_index(
+    config: SearchConfig,
+    has_call_terms: bool,
+    has_hierarchy_terms: bool,
+) -> Result<SearchResult> {
     if config.paths.is_empty() {
         bail!("at least one search path is required");
     }
@@ -515,12 +904,44 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
         .clone()
         .or_else(|| parse_query_expr(&config.pattern));
     let has_content_terms = query_expr.as_ref().is_some_and(expr_has_content_terms);
+    let has_module_terms = query_expr.as_ref().is_some_and(expr_has_module_terms);
+    let has_attr_terms = query_expr.as_ref().is_some_and(expr_has_attr_terms);
+    let has_is_terms = query_expr.as_ref().is_some_and(expr_has_is_terms);
+    let has_doc_terms = query_expr.as_ref().is_some_and(expr_has_doc_terms);
+
+    // Indexes written before call edges (type relations, dependencies,
+    // annotations, test flags, docs) were persisted cannot answer
+    // `calls:` / `called-by:` (`extends:` / `implements:`, `module:`,
+    // `attr:`, `is:`, `deprecated:` / `doc-tag:`); scan the files
+    // directly instead.
+    let schema = backend
+        .load_meta()
+        .map(|meta| meta.schema())
+        .unwrap_or_default();
+    let has_call_edges = schema >= SCHEMA_CALLS;
+    let has_deps = schema >= SCHEMA_DEPS;
+    let has_relations = schema >= SCHEMA_RELATIONS;
+    let has_members = schema >= SCHEMA_MEMBERS;
+    let has_annotations = schema >= SCHEMA_ANNOTATIONS;
+    let has_test_flags = schema >= SCHEMA_TEST_FLAGS;
+    let has_docs = schema >= SCHEMA_DOCS;
+    if ((has_call_terms || config.call_graph) && !has_call_edges)
+        || (has_hierarchy_terms && !has_relations)
+        || (has_module_terms && !has_deps)
+        || (has_attr_terms && !has_annotations)
+        || (has_is_terms && !has_test_flags)
+        || (has_doc_terms && !has_docs)
+    {
+        return run_symbol_search_without_index(config);
+    }
+
     let primary_context = primary_context_kind_from_views(&config.symbol_views);
     let want_matches = config
         .symbol_views
         .iter()
         .any(|v| matches!(v, SymbolView::Matches));
     let views_meta_only = views_are_meta_only(&config.symbol_views);
+    let tests_last = !has_is_terms;
 
     // Use the index to retrieve candidate symbols, filtering only by
     // language and path-level constraints. The full DSL evaluation is
@@ -548,19 +969,71 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
     let mut truncated = false;
 
     let limit = config.limit.unwrap_or(usize::MAX);
+    let mut tests = Vec::new();
 
     use std::collections::HashMap;
     use std::path::PathBuf;
 
     let mut parsed_cache: HashMap<PathBuf, crate::language::ParsedFile> = HashMap::new();
 
-    for record in indexed_symbols {
-        let file_record = match backend.get_file_by_id(record.file_id)? {
-            Some(f) => f,
+    let mut file_paths: HashMap<u64, PathBuf> = HashMap::new();
+    for record in &indexed_symbols {
+        if let std::collections::hash_map::Entry::Vacant(entry) = file_paths.entry(record.file_id) {
+            if let Some(file_record) = backend.get_file_by_id(record.file_id)? {
+                entry.insert(file_record.path);
+            }
+        }
+    }
+
+    let call_graph = if has_call_edges {
+        let file_ids: Vec<u64> = file_paths.keys().copied().collect();
+        let calls = backend.query_calls(&file_ids)?;
+        IndexedCallGraph::build(&indexed_symbols, &file_paths, calls)
+    } else {
+        IndexedCallGraph::default()
+    };
+    let member_pairs = if has_members {
+        let file_ids: Vec<u64> = file_paths.keys().copied().collect();
+        MemberPairs::from_records(backend.query_members(&file_ids)?, &file_paths)
+    } else {
+        MemberPairs::default()
+    };
+    // Ids of paired out-of-line definitions, whose call edges are
+    // reported on their declarations.
+    let mut definition_ids: HashMap<(PathBuf, crate::models::TextRange), u64> = HashMap::new();
+    for record in &indexed_symbols {
+        if let Some(path) = file_paths.get(&record.file_id) {
+            if member_pairs.is_paired_definition(path, record.range) {
+                definition_ids.insert((path.clone(), record.range), record.id);
+            }
+        }
+    }
+    let supertypes = if has_relations {
+        TypeGraph::from_index(
+            backend.as_ref(),
+            &config.paths,
+            &config.globs,
+            &config.exclude_globs,
+            config.language.as_deref(),
+        )?
+        .supertypes()
+    } else {
+        Default::default()
+    };
+    let module_specifiers = if has_module_terms {
+        DependencyGraph::from_index(backend.as_ref(), &config.paths, None)?.module_specifiers()
+    } else {
+        Default::default()
+    };
+
+    for record in &indexed_symbols {
+        let path = match file_paths.get(&record.file_id) {
+            Some(path) => path.clone(),
             None => continue,
         };
-
-        let path = file_record.path.clone();
+        if definition_ids.contains_key(&(path.clone(), record.range)) {
+            continue;
+        }
 
         // Reconstruct the core `Symbol` type from the indexed record,
         // hydrating attributes from the persisted `extra` payload.
@@ -577,11 +1050,26 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
+        if is_test_file(&path, &config.paths) {
+            mark_test_symbol(&mut symbol);
+        }
+        call_graph.attach(record.id, &mut symbol);
+        hierarchy::attach(&supertypes, &mut symbol);
+        deps::attach_modules(&module_specifiers, &mut symbol);
+        if let Some(location) = member_pairs.definition_of(&path, record.range) {
+            if let Some(id) = definition_ids.get(&(location.file.clone(), location.range)) {
+                call_graph.append(*id, &mut symbol);
+            }
+            symbol.definition = Some(location.clone());
+        }
 
         // First apply metadata-only filters (name/kind/file/language).
         let metadata_matches = if let Some(expr) = &query_expr {
-            symbol_matches_metadata(expr, &symbol, config.literal)
+            symbol_matches_metadata(expr, &symbol, &config.paths, config.literal)
         } else if config.literal {
             symbol.name == config.pattern
         } else {
@@ -638,16 +1126,19 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
                         )
                     })?;
 
-                let context = language_backend
-                    .get_context_snippet(parsed, &symbol, kind_for_snippet)
-                    .map_err(|err| {
-                        anyhow::anyhow!(
-                            "failed to get context snippet for symbol {} in {}: {}",
-                            symbol.name,
-                            symbol.file.display(),
-                            err
-                        )
-                    })?;
+                let context = match out_of_line_definition_context(&symbol, kind_for_snippet)? {
+                    Some(context) => context,
+                    None => language_backend
+                        .get_context_snippet(parsed, &symbol, kind_for_snippet)
+                        .map_err(|err| {
+                            anyhow::anyhow!(
+                                "failed to get context snippet for symbol {} in {}: {}",
+                                symbol.name,
+                                symbol.file.display(),
+                                err
+                            )
+                        })?,
+                };
 
                 let context_snippet = context.snippet.clone();
 
@@ -656,6 +1147,7 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
                         expr,
                         &symbol,
                         Some(context_snippet.as_str()),
+                        &config.paths,
                         config.literal,
                     )
                 {
@@ -663,7 +1155,7 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
                 }
 
                 context_for_result = Some(context);
-        } else if !symbol_matches_with_text(expr, &symbol, None, config.literal) {
+        } else if !symbol_matches_with_text(expr, &symbol, None, &config.paths, config.literal) {
                 // Expression without content-like terms or context/snippet
                 // requirements – evaluate against metadata only.
                 continue;
@@ -680,24 +1172,16 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
             Vec::new()
         };
 
+        symbol.matches = symbol_matches;
+        if tests_last && symbol_is_test(&symbol) {
+            tests.push((symbol, context_for_result));
+            continue;
+        }
+
         total_matches += 1;
 
         if symbols.len() < limit {
-            let symbol_index = symbols.len();
-
-            if let Some(mut context) = context_for_result {
-                if matches!(context.kind, ContextKind::Def) {
-                    let range = context.range;
-                    let lines = range.end_line.saturating_sub(range.start_line) + 1;
-                    symbol.def_line_count = Some(lines);
-                }
-                context.symbol_index = Some(symbol_index);
-                contexts.push(context);
-            }
-
-            let mut symbol_with_matches = symbol;
-            symbol_with_matches.matches = symbol_matches;
-            symbols.push(symbol_with_matches);
+            push_symbol_hit(&mut symbols, &mut contexts, symbol, context_for_result);
         }
 
         if symbols.len() >= limit {
@@ -705,6 +1189,14 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
             break;
         }
     }
+    for (symbol, context_for_result) in tests {
+        if symbols.len() >= limit {
+            truncated = config.limit.is_some();
+            break;
+        }
+        total_matches += 1;
+        push_symbol_hit(&mut symbols, &mut contexts, symbol, context_for_result);
+    }
 
     Ok(SearchResult {
         version: SEARCH_RESULT_VERSION.to_string(),
@@ -719,6 +1211,76 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
     })
 }
 
+/// Whether a symbol was classified as test code.
+fn symbol_is_test(symbol: &Symbol) -> bool {
+    symbol.attributes.as_ref().is_some_and(|attrs| attrs.is_test)
+}
+
+/// Classify a symbol of a test file as test code.
+fn mark_test_symbol(symbol: &mut Symbol) {
+    symbol.attributes.get_or_insert_with(Default::default).is_test = true;
+}
+
+/// A matched symbol and its context snippet, if one was fetched.
+type SymbolHit = (Symbol, Option<ContextInfo>);
+
+/// Append a matched symbol and its context snippet to a result,
+/// linking the context to the symbol and recording the definition's
+/// line count.
+fn push_symbol_hit(
+    symbols: &mut Vec<Symbol>,
+    contexts: &mut Vec<ContextInfo>,
+    mut symbol: Symbol,
+    context: Option<ContextInfo>,
+) {
+    if let Some(mut context) = context {
+        if matches!(context.kind, ContextKind::Def) {
+            let range = context.range;
+            let lines = range.end_line.saturating_sub(range.start_line) + 1;
+            symbol.def_line_count = Some(lines);
+        }
+        context.symbol_index = Some(symbols.len());
+        contexts.push(context);
+    }
+    symbols.push(symbol);
+}
+
+/// `def` context of a member declared apart from its out-of-line
+/// definition, taken from the file containing the definition.
+///
+/// Returns `None` for other views and for symbols without a separate
+/// definition.
+fn out_of_line_definition_context(
+    symbol: &Symbol,
+    kind: ContextKind,
+) -> Result<Option<ContextInfo>> {
+    let Some(definition) = symbol.definition.as_ref().filter(|_| kind == ContextKind::Def) else {
+        return Ok(None);
+    };
+    let Some(backend) = backend_for_path(&definition.file) else {
+        return Ok(None);
+    };
+
+    let source = fs::read_to_string(&definition.file)?;
+    let parsed = backend.parse_file(&definition.file, &source)?;
+    let target = Symbol {
+        file: definition.file.clone(),
+        range: definition.range,
+        ..symbol.clone()
+    };
+    let context = backend
+        .get_context_snippet(&parsed, &target, ContextKind::Def)
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "failed to get context snippet for symbol {} in {}: {}",
+                symbol.name,
+                definition.file.display(),
+                err
+            )
+        })?;
+    Ok(Some(context))
+}
+
 /// Resolve the effective index configuration to use for a symbol search.
 ///
 /// Backend selection rules:
@@ -733,11 +1295,13 @@ fn run_symbol_search_with_index(config: SearchConfig) -> Result<SearchResult> {
 ///   - Else, use the file backend if `.symgrep/` exists.
 ///   - Else, fall back to non-indexed search.
 fn resolve_effective_index_config(config: &SearchConfig) -> Option<IndexConfig> {
-    let index = match &config.index {
-        Some(cfg) => cfg.clone(),
-        None => return None,
-    };
+    config.index.as_ref().and_then(effective_index)
+}
 
+/// Apply the default-path auto selection described on
+/// `resolve_effective_index_config` to a configured index.
+fn effective_index(index: &IndexConfig) -> Option<IndexConfig> {
+    let index = index.clone();
     let default_root = std::path::PathBuf::from(".symgrep");
 
     if index.backend == crate::models::IndexBackendKind::File && index.index_path == default_root {
@@ -761,37 +1325,6 @@ fn resolve_effective_index_config(config: &SearchConfig) -> Option<IndexConfig>
     Some(index)
 }
 
-fn is_identifier_char(ch: char) -> bool {
-    ch.is_ascii_alphanumeric() || ch == '_'
-}
-
-fn find_literal_identifier(haystack: &str, needle: &str) -> Option<usize> {
-    if needle.is_empty() {
-        return None;
-    }
-
-    let mut search_start = 0;
-
-    while let Some(rel_idx) = haystack[search_start..].find(needle) {
-        let start = search_start + rel_idx;
-        let end = start + needle.len();
-
-        let prev_char = haystack[..start].chars().rev().next();
-        let next_char = haystack[end..].chars().next();
-
-        let left_ok = prev_char.map_or(true, |ch| !is_identifier_char(ch));
-        let right_ok = next_char.map_or(true, |ch| !is_identifier_char(ch));
-
-        if left_ok && right_ok {
-            return Some(start);
-        }
-
-        search_start = end;
-    }
-
-    None
-}
-
 fn primary_context_kind_from_views(views: &[SymbolView]) -> Option<ContextKind> {
     if views.iter().any(|v| matches!(v, SymbolView::Def)) {
         Some(ContextKind::Def)
@@ -928,7 +1461,10 @@ pub fn run_index(_config: IndexConfig) -> Result<IndexSummary> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{IndexBackendKind, IndexConfig, SearchConfig, SearchMode, SymbolView};
+    use crate::models::{
+        IndexBackendKind, IndexConfig, ReferenceKind, SearchConfig, SearchMode, SymbolKind, SymbolView,
+        SyntaxScope,
+    };
     use std::path::PathBuf;
     use tempfile::tempdir;
 
@@ -945,8 +1481,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         }
     }
 
@@ -982,6 +1525,479 @@ mod tests {
         assert!(result.summary.truncated);
     }
 
+    #[test]
+    fn run_search_skips_binary_files_and_survives_invalid_utf8() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("blob.bin"), b"foo\x00\x01\x02foo\n").expect("write");
+        std::fs::write(dir.path().join("mixed.txt"), b"bad \xff\xfe bytes\nfoo here\n")
+            .expect("write");
+
+        let config = base_config(dir.path().to_path_buf());
+        let result = run_search(config).expect("search result");
+
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].path.ends_with("mixed.txt"));
+        assert_eq!(result.matches[0].line, 2);
+        assert_eq!(result.matches[0].snippet.as_deref(), Some("foo here"));
+    }
+
+    #[test]
+    fn run_search_transcodes_utf16_and_configured_encodings() {
+        let dir = tempdir().expect("tempdir");
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "x\nfoo\n".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        std::fs::write(dir.path().join("utf16.txt"), utf16).expect("write");
+
+        let config = base_config(dir.path().to_path_buf());
+        let result = run_search(config).expect("search result");
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].line, 2);
+        assert_eq!(result.matches[0].snippet.as_deref(), Some("foo"));
+
+        let latin1_dir = tempdir().expect("tempdir");
+        std::fs::write(latin1_dir.path().join("latin1.txt"), b"caf\xe9 foo\n").expect("write");
+
+        let mut config = base_config(latin1_dir.path().to_path_buf());
+        config.encoding = Some("latin1".to_string());
+        let result = run_search(config).expect("search result");
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].snippet.as_deref(), Some("caf\u{e9} foo"));
+        assert_eq!(result.matches[0].column, Some(7));
+    }
+
+    #[test]
+    fn run_search_multiline_matches_across_lines() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("code.rs"),
+            "fn one() {}\nlet x = foo(\n    bar);\nfoo(bar);\n",
+        )
+        .expect("write");
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.pattern = r"foo\(\s*bar\)".to_string();
+        config.multiline = true;
+        let result = run_search(config).expect("search result");
+
+        assert_eq!(result.matches.len(), 2);
+        assert_eq!(result.matches[0].line, 2);
+        assert_eq!(result.matches[0].column, Some(9));
+        assert_eq!(
+            result.matches[0].snippet.as_deref(),
+            Some("let x = foo(\n    bar);")
+        );
+        assert_eq!(result.matches[1].line, 4);
+        assert_eq!(result.matches[1].column, Some(1));
+        assert_eq!(result.matches[0].spans.len(), 1);
+        assert_eq!(
+            (result.matches[0].spans[0].start, result.matches[0].spans[0].end),
+            (8, 21)
+        );
+    }
+
+    #[test]
+    fn run_search_reports_every_occurrence_with_spans() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("a.txt"), "é foo(foo) foobar\n").expect("write");
+
+        let config = base_config(dir.path().to_path_buf());
+        let result = run_search(config.clone()).expect("search result");
+        assert_eq!(result.matches.len(), 1);
+        let m = &result.matches[0];
+        assert_eq!(m.column, Some(4));
+        let spans: Vec<_> = m.spans.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(spans, vec![(3, 6), (7, 10), (12, 15)]);
+        assert_eq!(m.spans[0].start_char, Some(2));
+        assert_eq!(m.spans[0].end_utf16, Some(5));
+
+        let mut literal = config;
+        literal.literal = true;
+        let result = run_search(literal).expect("search result");
+        let spans: Vec<_> = result.matches[0]
+            .spans
+            .iter()
+            .map(|s| (s.start, s.end))
+            .collect();
+        assert_eq!(spans, vec![(3, 6), (7, 10)]);
+    }
+
+    #[test]
+    fn run_search_spans_cover_all_matching_query_terms() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("a.txt"), "bar foo baz foo\n").expect("write");
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.pattern = "content:foo content:baz|qux".to_string();
+        let result = run_search(config).expect("search result");
+
+        let spans: Vec<_> = result.matches[0]
+            .spans
+            .iter()
+            .map(|s| (s.start, s.end))
+            .collect();
+        assert_eq!(spans, vec![(4, 7), (8, 11), (12, 15)]);
+        assert_eq!(result.matches[0].column, Some(5));
+    }
+
+    #[test]
+    fn run_search_annotates_enclosing_symbols() {
+        let mut config = base_config(PathBuf::from("tests/fixtures/rust_repo"));
+        config.pattern = "self.value".to_string();
+        config.enclosing = true;
+        let result = run_search(config).expect("search result");
+
+        let enclosing: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| m.enclosing.as_ref().expect("enclosing symbol"))
+            .collect();
+        assert_eq!(enclosing.len(), 2);
+        assert_eq!(enclosing[0].name, "increment");
+        assert_eq!(enclosing[0].kind, SymbolKind::Method);
+        let chain: Vec<_> = enclosing[0]
+            .parent_chain
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect();
+        assert_eq!(chain, vec!["lib.rs", "my_mod", "Widget"]);
+        assert_eq!(enclosing[1].name, "depth");
+
+        let mut plain = base_config(PathBuf::from("tests/fixtures/rust_repo"));
+        plain.pattern = "self.value".to_string();
+        let result = run_search(plain).expect("search result");
+        assert!(result.matches.iter().all(|m| m.enclosing.is_none()));
+    }
+
+    #[test]
+    fn run_search_restricts_matches_to_syntax_scopes() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "// TODO: handle \"error\" here\nconst msg = \"fatal error\"; // error path\nfunction error() { return `v ${error()} error`; }\n",
+        )
+        .expect("write");
+        std::fs::write(dir.path().join("notes.txt"), "error\n").expect("write");
+
+        let spans_for = |scopes: Vec<SyntaxScope>, pattern: &str| {
+            let mut config = base_config(dir.path().to_path_buf());
+            config.pattern = pattern.to_string();
+            config.scopes = scopes;
+            run_search(config)
+                .expect("search result")
+                .matches
+                .iter()
+                .map(|m| {
+                    let spans: Vec<_> = m.spans.iter().map(|s| s.start).collect();
+                    (m.line, m.column, spans)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            spans_for(vec![SyntaxScope::Code], "error"),
+            vec![(3, Some(10), vec![9, 31])]
+        );
+        assert_eq!(
+            spans_for(vec![SyntaxScope::Comments], "error"),
+            vec![(1, Some(18), vec![17]), (2, Some(31), vec![30])]
+        );
+        assert_eq!(
+            spans_for(vec![SyntaxScope::Strings], "error"),
+            vec![(2, Some(20), vec![19]), (3, Some(41), vec![40])]
+        );
+        assert_eq!(
+            spans_for(Vec::new(), "content:error scope:comments|strings"),
+            vec![
+                (1, Some(18), vec![17]),
+                (2, Some(20), vec![19, 30]),
+                (3, Some(41), vec![40])
+            ]
+        );
+    }
+
+    #[test]
+    fn run_search_restricts_utf16_matches_to_syntax_scopes() {
+        let dir = tempdir().expect("tempdir");
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "// error here\nfn error() {}\n".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        std::fs::write(dir.path().join("lib.rs"), utf16).expect("write");
+
+        let lines_for = |scopes: Vec<SyntaxScope>| {
+            let mut config = base_config(dir.path().to_path_buf());
+            config.pattern = "error".to_string();
+            config.scopes = scopes;
+            run_search(config)
+                .expect("search result")
+                .matches
+                .iter()
+                .map(|m| (m.line, m.column))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(lines_for(vec![SyntaxScope::Code]), vec![(2, Some(4))]);
+        assert_eq!(lines_for(vec![SyntaxScope::Comments]), vec![(1, Some(4))]);
+    }
+
+    #[test]
+    fn run_search_attaches_enclosing_symbols_in_utf16_files() {
+        let dir = tempdir().expect("tempdir");
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "fn outer() {\n    let x = 1;\n}\n\nfn error() {}\n".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        std::fs::write(dir.path().join("lib.rs"), utf16).expect("write");
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.pattern = "x = 1".to_string();
+        config.enclosing = true;
+        let result = run_search(config).expect("search result");
+
+        assert_eq!(result.matches.len(), 1);
+        let enclosing = result.matches[0].enclosing.as_ref().expect("enclosing");
+        assert_eq!(enclosing.name, "outer");
+    }
+
+    #[test]
+    fn run_search_rejects_unknown_scope() {
+        let mut config = base_config(PathBuf::from("tests/fixtures/text_repo"));
+        config.pattern = "content:foo scope:docs".to_string();
+
+        let err = run_search(config).unwrap_err();
+        assert!(err.to_string().contains("unknown scope `docs`"));
+    }
+
+    #[test]
+    fn run_search_matches_ast_patterns_with_bindings() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("main.rs"),
+            "fn main() {\n    let x = foo().unwrap();\n    call(a, b, c);\n    call();\n    if a == a {}\n    if a == b {}\n}\n",
+        )
+        .expect("write");
+        std::fs::write(dir.path().join("notes.txt"), "foo().unwrap()\n").expect("write");
+
+        let search = |pattern: &str| {
+            let mut config = base_config(dir.path().to_path_buf());
+            config.pattern = pattern.to_string();
+            run_search(config).expect("search result").matches
+        };
+
+        let matches = search("ast:$X.unwrap()");
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].line, matches[0].column), (2, Some(13)));
+        assert_eq!(matches[0].bindings["X"].text, "foo()");
+        let range = matches[0].bindings["X"].range.expect("range");
+        assert_eq!((range.start_line, range.start_column), (2, 13));
+        assert_eq!(
+            (matches[0].spans[0].start, matches[0].spans[0].end),
+            (12, 26)
+        );
+
+        let matches = search(r#"ast:"call($$$ARGS)""#);
+        let args: Vec<_> = matches
+            .iter()
+            .map(|m| m.bindings["ARGS"].text.as_str())
+            .collect();
+        assert_eq!(args, vec!["a, b, c", ""]);
+
+        let matches = search(r#"ast:"$A == $A""#);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 5);
+    }
+
+    #[test]
+    fn run_search_reports_invalid_ast_queries() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").expect("write");
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.pattern = r#"ast:"fn (""#.to_string();
+        let err = run_search(config.clone()).unwrap_err();
+        assert!(err.to_string().contains("not valid syntax"));
+
+        config.pattern = "ast:$X.unwrap() name:foo".to_string();
+        config.query_expr = None;
+        let err = run_search(config).unwrap_err();
+        assert!(err.to_string().contains("file: and language:"));
+    }
+
+    #[test]
+    fn run_search_refs_classifies_usages_across_files() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("config.ts"),
+            "export class Config {}\nexport const DEFAULTS = new Config();\n",
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("main.ts"),
+            "import { Config } from './config';\nfunction load(c: Config): Config {\n  return c;\n}\n",
+        )
+        .expect("write");
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.pattern = "refs:Config".to_string();
+        let result = run_search(config.clone()).expect("search result");
+
+        let found: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| {
+                (
+                    m.path.file_name().unwrap().to_str().unwrap().to_string(),
+                    m.line,
+                    m.reference_kind.expect("reference kind"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("config.ts".to_string(), 2, ReferenceKind::Call),
+                ("main.ts".to_string(), 1, ReferenceKind::Import),
+                ("main.ts".to_string(), 2, ReferenceKind::Type),
+                ("main.ts".to_string(), 2, ReferenceKind::Type),
+            ]
+        );
+        let enclosing = result.matches[2].enclosing.as_ref().expect("enclosing");
+        assert_eq!(enclosing.name, "load");
+
+        config.reference_kinds = vec![ReferenceKind::Import];
+        config.pattern = "refs:Config file:main".to_string();
+        let result = run_search(config.clone()).expect("search result");
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].line, 1);
+
+        config.pattern = "refs:Config kind:class".to_string();
+        let err = run_search(config).unwrap_err();
+        assert!(err.to_string().contains("refs: patterns can only be combined"));
+    }
+
+    #[test]
+    fn run_query_reports_captures_with_enclosing_symbols() {
+        let mut config = base_config(PathBuf::from("tests/fixtures/rust_repo"));
+        config.pattern =
+            r#"(binary_expression left: (_) @lhs right: (identifier) @rhs (#eq? @rhs "delta"))"#
+                .to_string();
+        config.enclosing = true;
+
+        let result = run_query(config).expect("query result");
+        assert_eq!(result.matches.len(), 2);
+
+        let lhs = &result.matches[0];
+        assert_eq!(lhs.path, PathBuf::from("tests/fixtures/rust_repo/lib.rs"));
+        assert_eq!((lhs.line, lhs.column), (14, Some(13)));
+        let capture = lhs.capture.as_ref().expect("capture");
+        assert_eq!(capture.name, "lhs");
+        assert_eq!(capture.text, "self.value");
+        assert_eq!((capture.range.end_line, capture.range.end_column), (14, 23));
+        assert_eq!(capture.pattern, 0);
+        assert_eq!(lhs.enclosing.as_ref().expect("enclosing").name, "increment");
+
+        let rhs = result.matches[1].capture.as_ref().expect("capture");
+        assert_eq!((rhs.name.as_str(), rhs.text.as_str()), ("rhs", "delta"));
+    }
+
+    #[test]
+    fn run_query_reports_invalid_queries() {
+        let mut config = base_config(PathBuf::from("tests/fixtures/rust_repo"));
+        config.pattern = "(no_such_node) @x".to_string();
+        let err = run_query(config.clone()).unwrap_err();
+        assert!(err.to_string().contains("not valid for any supported language"));
+
+        config.pattern = "(call_expression) @call".to_string();
+        config.language = Some("cpp".to_string());
+        assert!(run_query(config.clone()).expect("cpp query").matches.is_empty());
+
+        config.pattern = "(function_item) @f".to_string();
+        let err = run_query(config).unwrap_err();
+        assert!(err.to_string().contains("invalid tree-sitter query for cpp"));
+    }
+
+    #[test]
+    fn run_search_rejects_unknown_encoding() {
+        let dir = tempdir().expect("tempdir");
+        let mut config = base_config(dir.path().to_path_buf());
+        config.encoding = Some("not-an-encoding".to_string());
+
+        let err = run_search(config).unwrap_err();
+        assert!(err.to_string().contains("unsupported text encoding"));
+    }
+
+    #[test]
+    fn run_search_limit_is_deterministic_across_files() {
+        let dir = tempdir().expect("tempdir");
+        for name in ["d.txt", "b.txt", "a.txt", "c.txt"] {
+            std::fs::write(dir.path().join(name), "foo\n").expect("write");
+        }
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.limit = Some(2);
+        let result = run_search(config.clone()).expect("search result");
+
+        let names: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| m.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+        assert!(result.summary.truncated);
+
+        // A limit that is exactly met is not reported as truncated.
+        config.limit = Some(4);
+        let result = run_search(config).expect("search result");
+        assert_eq!(result.matches.len(), 4);
+        assert!(!result.summary.truncated);
+    }
+
+    #[test]
+    fn run_search_limit_pairs_cpp_members_only_when_cpp_symbols_match() {
+        let dir = tempdir().expect("tempdir");
+        let app = dir.path().join("app");
+        std::fs::create_dir(&app).expect("mkdir");
+        // Enough TypeScript files that the C++ ones fall past the
+        // first batch.
+        for idx in 0..=parallel_batch_size() {
+            std::fs::write(app.join(format!("m{idx:04}.ts")), "export function foo() {}\n")
+                .expect("write");
+        }
+        std::fs::write(dir.path().join("shape.h"), "class Shape {\n  int foo();\n};\n")
+            .expect("write");
+        std::fs::write(dir.path().join("shape.cpp"), "int Shape::foo() {\n  return 1;\n}\n")
+            .expect("write");
+        let is_cpp = |path: &PathBuf| {
+            path.starts_with(dir.path())
+                && path.extension().is_some_and(|ext| ext == "h" || ext == "cpp")
+        };
+
+        let mut config = base_config(dir.path().to_path_buf());
+        config.mode = SearchMode::Symbol;
+        config.limit = Some(1);
+        let result = run_search(config.clone()).expect("search result");
+        assert_eq!(result.symbols.len(), 1);
+        assert!(result.summary.truncated);
+        assert!(!crate::language::SCANNED_PATHS
+            .lock()
+            .expect("scanned paths")
+            .iter()
+            .any(is_cpp));
+
+        // Once a C++ symbol matches, its members are still paired with
+        // out-of-line definitions in other files.
+        config.limit = None;
+        config.language = Some("cpp".to_string());
+        let result = run_search(config).expect("search result");
+        let foo: Vec<_> = result.symbols.iter().filter(|s| s.name == "foo").collect();
+        assert_eq!(foo.len(), 1);
+        let definition = foo[0].definition.as_ref().expect("definition");
+        assert!(definition.file.ends_with("shape.cpp"));
+    }
+
     #[test]
     fn run_search_omits_snippet_when_max_lines_is_zero() {
         let dir = tempdir().expect("tempdir");
@@ -1036,8 +2052,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let result = run_search(config).expect("search result");
@@ -1047,15 +2070,6 @@ mod tests {
         assert!(paths.contains(&file_b));
     }
 
-    #[test]
-    fn find_literal_identifier_respects_word_boundaries() {
-        assert_eq!(find_literal_identifier("foo", "foo"), Some(0));
-        assert_eq!(find_literal_identifier("foobar", "foo"), None);
-        assert_eq!(find_literal_identifier("foo_bar", "foo"), None);
-        assert_eq!(find_literal_identifier("bar_foo", "foo"), None);
-        assert_eq!(find_literal_identifier("foo()", "foo"), Some(0));
-    }
-
     #[test]
     fn run_search_errors_on_nonexistent_path() {
         let config = SearchConfig {
@@ -1070,8 +2084,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let err = run_search(config).expect_err("expected error");
@@ -1093,8 +2114,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let result = run_search(config).expect("search result");
@@ -1135,8 +2163,15 @@ mod tests {
             limit: Some(1),
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let result = run_search(config).expect("search result");
@@ -1166,8 +2201,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let result = run_search(config).expect("search result");
@@ -1212,8 +2254,15 @@ mod tests {
             limit: None,
             max_lines: Some(1),
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: None,
+            compile_commands: None,
         };
 
         let result = run_search(config).expect("search result");
@@ -1267,6 +2316,7 @@ mod tests {
             backend: IndexBackendKind::File,
             index_path: index_root,
             language: Some("typescript".to_string()),
+            compile_commands: None,
         };
 
         // Build an initial index so subsequent searches can use it.
@@ -1284,8 +2334,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: Some(index_config.clone()),
+            compile_commands: None,
         };
 
         let initial_result = run_search(initial_search).expect("initial search result");
@@ -1316,8 +2373,15 @@ mod tests {
             limit: None,
             max_lines: None,
             reindex_on_search: true,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             query_expr: None,
             index: Some(index_config),
+            compile_commands: None,
         };
 
         let refreshed_result = run_search(refreshed_search).expect("refreshed search result");
@@ -1329,4 +2393,24 @@ mod tests {
             "expected to find symbol 'add2' after reindex_on_search"
         );
     }
+
+    #[test]
+    fn symbol_search_resolves_calls_across_files() {
+        let mut config = base_config(PathBuf::from("tests/fixtures/cross_file_repo/rust"));
+        config.pattern = "called-by:main".to_string();
+        config.mode = SearchMode::Symbol;
+
+        let result = run_search(config).expect("search result");
+        let mut names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["normalize", "shout"]);
+
+        for symbol in &result.symbols {
+            assert_eq!(symbol.called_by.len(), 1);
+            assert_eq!(
+                symbol.called_by[0].file,
+                PathBuf::from("tests/fixtures/cross_file_repo/rust/src/main.rs")
+            );
+        }
+    }
 }
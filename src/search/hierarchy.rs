@@ -0,0 +1,524 @@
+//! Type hierarchies for `symgrep hierarchy` and the `extends:` /
+//! `implements:` query fields.
+//!
+//! Backends report `extends`/`implements` clauses by type name. This
+//! module resolves both ends of each clause to a class, interface or
+//! trait definition: a definition in the declaring file wins, then one
+//! in a file it imports, then the only definition of that name in the
+//! same language, then the only definition of that name at all. Names
+//! that stay ambiguous or are defined outside the searched files are
+//! kept unresolved, so `Error` or `std::fmt::Debug` still show up as
+//! supertypes.
+//!
+//! The graph is built either by parsing every candidate file or from
+//! the `relations` table of an index.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::index::models::SymbolQuery;
+use crate::index::{build_globset, IndexBackend};
+use crate::language::{
+    backend_for_language, backend_for_path, normalize_path, scan_file, CompileDatabase, FileSymbols,
+};
+use crate::models::{
+    HierarchyDirection, HierarchyEdge, HierarchyResult, HierarchyType, SupertypeRef, Symbol,
+    SymbolKind, TypeRelation, HIERARCHY_RESULT_VERSION,
+};
+use crate::walk::{collect_files, for_each_ordered};
+
+/// Supertypes of every resolved subtype, keyed by its defining file
+/// (normalized) and name.
+pub(crate) type SupertypeMap = HashMap<(PathBuf, String), Vec<SupertypeRef>>;
+
+/// Fill in `Symbol.supertypes` for the classes, interfaces and traits
+/// of `files`.
+pub(crate) fn attach_supertypes(files: &mut [FileSymbols]) {
+    let supertypes = TypeGraph::from_files(files).supertypes();
+    for file in files.iter_mut() {
+        for symbol in &mut file.symbols {
+            attach(&supertypes, symbol);
+        }
+    }
+}
+
+/// Set `symbol.supertypes` from a map built by `TypeGraph::supertypes`.
+pub(crate) fn attach(supertypes: &SupertypeMap, symbol: &mut Symbol) {
+    if !is_type_kind(symbol.kind) {
+        return;
+    }
+    if let Some(refs) = supertypes.get(&(normalize_path(&symbol.file), symbol.name.clone())) {
+        symbol.supertypes = refs.clone();
+    }
+}
+
+fn is_type_kind(kind: SymbolKind) -> bool {
+    matches!(kind, SymbolKind::Class | SymbolKind::Interface)
+}
+
+fn file_language(path: &Path) -> Option<&'static str> {
+    backend_for_path(path).map(|backend| backend.id())
+}
+
+/// A class, interface or trait definition.
+#[derive(Debug)]
+struct TypeDef {
+    name: String,
+    kind: SymbolKind,
+    file: PathBuf,
+    line: u32,
+    language: String,
+}
+
+/// A relation with both ends resolved where possible.
+struct Edge<'a> {
+    relation: &'a TypeRelation,
+    file: &'a Path,
+    subtype: Option<usize>,
+    supertype: Option<usize>,
+}
+
+/// Type definitions and the relations between them.
+#[derive(Debug, Default)]
+pub(crate) struct TypeGraph {
+    types: Vec<TypeDef>,
+    by_name: HashMap<String, Vec<usize>>,
+    /// Resolved import targets of each file.
+    imports: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// Relations with their (normalized) declaring file, in file and
+    /// source order.
+    relations: Vec<(PathBuf, TypeRelation)>,
+}
+
+impl TypeGraph {
+    /// Build the graph from already scanned files.
+    pub(crate) fn from_files(files: &[FileSymbols]) -> Self {
+        let mut graph = Self::default();
+        for file in files {
+            let path = normalize_path(&file.path);
+            for symbol in file.symbols.iter().filter(|s| is_type_kind(s.kind)) {
+                graph.add_type(TypeDef {
+                    name: symbol.name.clone(),
+                    kind: symbol.kind,
+                    file: path.clone(),
+                    line: symbol.range.start_line,
+                    language: symbol.language.clone(),
+                });
+            }
+            graph.imports.entry(path.clone()).or_default().extend(
+                file.imports
+                    .iter()
+                    .flat_map(|import| import.files.iter())
+                    .map(|target| normalize_path(target)),
+            );
+            graph.relations.extend(
+                file.relations
+                    .iter()
+                    .map(|relation| (path.clone(), relation.clone())),
+            );
+        }
+        graph
+    }
+
+    /// Parse every supported file under `paths`, resolving includes
+    /// with the compile database `build`, if any.
+    pub(crate) fn scan(
+        paths: &[PathBuf],
+        globs: &[String],
+        exclude_globs: &[String],
+        language: Option<&str>,
+        build: Option<&CompileDatabase>,
+    ) -> Result<Self> {
+        let include_globs = build_globset(globs)?;
+        let exclude_globs = build_globset(exclude_globs)?;
+
+        let selected_backend = match language {
+            Some(lang) => Some(backend_for_language(lang).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "hierarchy is only supported for known languages (e.g., typescript, javascript, rust, cpp); got {}",
+                    lang
+                )
+            })?),
+            None => None,
+        };
+        let backend_for = |path: &Path| match selected_backend {
+            Some(backend) => {
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                backend
+                    .file_extensions()
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(ext))
+                    .then_some(backend)
+            }
+            None => backend_for_path(path),
+        };
+
+        let files = collect_files(paths, include_globs.as_ref(), exclude_globs.as_ref());
+
+        let mut scanned = Vec::new();
+        for_each_ordered(
+            &files,
+            None,
+            |path| scan_file(path, backend_for(path)?, build),
+            |_, file| {
+                scanned.extend(file);
+                Ok(true)
+            },
+        )?;
+
+        Ok(Self::from_files(&scanned))
+    }
+
+    /// Load the types and relations of indexed files under `roots`
+    /// that pass the glob and language filters.
+    pub(crate) fn from_index(
+        backend: &dyn IndexBackend,
+        roots: &[PathBuf],
+        globs: &[String],
+        exclude_globs: &[String],
+        language: Option<&str>,
+    ) -> Result<Self> {
+        let include_set = build_globset(globs)?;
+        let exclude_set = build_globset(exclude_globs)?;
+
+        let paths: BTreeMap<u64, PathBuf> = backend
+            .list_files()?
+            .into_iter()
+            .filter(|file| {
+                roots.iter().any(|root| file.path.starts_with(root))
+                    && language.is_none_or(|lang| file.language.eq_ignore_ascii_case(lang))
+                    && include_set
+                        .as_ref()
+                        .is_none_or(|set| set.is_match(&file.path))
+                    && !exclude_set
+                        .as_ref()
+                        .is_some_and(|set| set.is_match(&file.path))
+            })
+            .map(|file| (file.id, normalize_path(&file.path)))
+            .collect();
+
+        let mut graph = Self::default();
+
+        let symbols = backend.query_symbols(&SymbolQuery {
+            name_substring: None,
+            language: language.map(str::to_string),
+            paths: roots.to_vec(),
+            globs: globs.to_vec(),
+            exclude_globs: exclude_globs.to_vec(),
+        })?;
+        for record in symbols.into_iter().filter(|r| is_type_kind(r.kind)) {
+            let Some(path) = paths.get(&record.file_id) else {
+                continue;
+            };
+            graph.add_type(TypeDef {
+                name: record.name,
+                kind: record.kind,
+                file: path.clone(),
+                line: record.range.start_line,
+                language: record.language,
+            });
+        }
+
+        for record in backend.query_dependencies(&[])? {
+            if let (Some(path), Some(target)) = (paths.get(&record.file_id), record.target) {
+                graph
+                    .imports
+                    .entry(path.clone())
+                    .or_default()
+                    .insert(normalize_path(&target));
+            }
+        }
+
+        for record in backend.query_relations(&[])? {
+            let Some(path) = paths.get(&record.file_id) else {
+                continue;
+            };
+            graph.relations.push((
+                path.clone(),
+                TypeRelation {
+                    subtype: record.subtype,
+                    supertype: record.supertype,
+                    kind: record.kind,
+                    line: record.line,
+                },
+            ));
+        }
+        graph.relations.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(graph)
+    }
+
+    fn add_type(&mut self, def: TypeDef) {
+        self.by_name
+            .entry(def.name.clone())
+            .or_default()
+            .push(self.types.len());
+        self.types.push(def);
+    }
+
+    /// Definition `name` refers to in `file`, if it can be told apart
+    /// from other definitions of the same name.
+    fn resolve(&self, name: &str, file: &Path) -> Option<usize> {
+        let candidates = self.by_name.get(name)?;
+        let unique = |matching: Vec<usize>| (matching.len() == 1).then(|| matching[0]);
+
+        if let Some(&local) = candidates.iter().find(|&&idx| self.types[idx].file == file) {
+            return Some(local);
+        }
+        if let Some(imported) = self.imports.get(file) {
+            if let Some(&idx) = candidates
+                .iter()
+                .find(|&&idx| imported.contains(&self.types[idx].file))
+            {
+                return Some(idx);
+            }
+        }
+        if let Some(language) = file_language(file) {
+            let same_language = candidates
+                .iter()
+                .copied()
+                .filter(|&idx| self.types[idx].language == language)
+                .collect();
+            if let Some(idx) = unique(same_language) {
+                return Some(idx);
+            }
+        }
+        unique(candidates.clone())
+    }
+
+    fn edges(&self) -> Vec<Edge<'_>> {
+        self.relations
+            .iter()
+            .map(|(file, relation)| Edge {
+                relation,
+                file,
+                subtype: self.resolve(&relation.subtype, file),
+                supertype: self.resolve(&relation.supertype, file),
+            })
+            .collect()
+    }
+
+    /// Supertypes of every subtype that resolved to a definition.
+    pub(crate) fn supertypes(&self) -> SupertypeMap {
+        let mut map = SupertypeMap::new();
+        for edge in self.edges() {
+            let Some(subtype) = edge.subtype else {
+                continue;
+            };
+            let def = &self.types[subtype];
+            let supertype = SupertypeRef {
+                name: edge.relation.supertype.clone(),
+                kind: edge.relation.kind,
+                file: edge.supertype.map(|idx| self.types[idx].file.clone()),
+            };
+            let refs = map.entry((def.file.clone(), def.name.clone())).or_default();
+            if !refs.contains(&supertype) {
+                refs.push(supertype);
+            }
+        }
+        map
+    }
+
+    /// Compute the `HierarchyResult` for the type named `name`.
+    ///
+    /// Relations are walked depth-first from each definition of the
+    /// type, so every edge follows the edge it was reached through.
+    /// Each type is expanded once, which also guards against cyclic
+    /// declarations.
+    pub(crate) fn result_for(
+        &self,
+        name: &str,
+        direction: HierarchyDirection,
+        max_depth: Option<u32>,
+    ) -> HierarchyResult {
+        let mut definitions: Vec<usize> = self.by_name.get(name).cloned().unwrap_or_default();
+        definitions.sort_by(|&a, &b| {
+            (&self.types[a].file, self.types[a].line)
+                .cmp(&(&self.types[b].file, self.types[b].line))
+        });
+
+        let mut walk = Walk {
+            graph: self,
+            edges: self.edges(),
+            direction,
+            max_depth,
+            expanded: HashSet::new(),
+            used: HashSet::new(),
+            out: Vec::new(),
+        };
+        // A type defined outside the searched files can still have
+        // subtypes (or supertypes) referring to it by name.
+        let roots: Vec<Option<usize>> = if definitions.is_empty() {
+            vec![None]
+        } else {
+            definitions.iter().copied().map(Some).collect()
+        };
+        for root in &roots {
+            walk.expanded.insert((name.to_string(), *root));
+        }
+        for root in roots {
+            walk.visit(name, root, 1);
+        }
+
+        HierarchyResult {
+            version: HIERARCHY_RESULT_VERSION.to_string(),
+            symbol: name.to_string(),
+            direction,
+            definitions: definitions
+                .into_iter()
+                .map(|idx| {
+                    let def = &self.types[idx];
+                    HierarchyType {
+                        name: def.name.clone(),
+                        kind: def.kind,
+                        file: def.file.clone(),
+                        line: def.line,
+                    }
+                })
+                .collect(),
+            edges: walk.out,
+        }
+    }
+}
+
+/// State of a depth-first walk in `TypeGraph::result_for`.
+struct Walk<'a> {
+    graph: &'a TypeGraph,
+    edges: Vec<Edge<'a>>,
+    direction: HierarchyDirection,
+    max_depth: Option<u32>,
+    /// Types already expanded, by name and resolved definition.
+    expanded: HashSet<(String, Option<usize>)>,
+    /// Indices of edges already reported.
+    used: HashSet<usize>,
+    out: Vec<HierarchyEdge>,
+}
+
+impl Walk<'_> {
+    fn visit(&mut self, name: &str, def: Option<usize>, depth: u32) {
+        if self.max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+
+        for idx in 0..self.edges.len() {
+            let edge = &self.edges[idx];
+            let (near_name, near_def, far_name, far_def) = match self.direction {
+                HierarchyDirection::Subtypes => (
+                    &edge.relation.supertype,
+                    edge.supertype,
+                    &edge.relation.subtype,
+                    edge.subtype,
+                ),
+                HierarchyDirection::Supertypes => (
+                    &edge.relation.subtype,
+                    edge.subtype,
+                    &edge.relation.supertype,
+                    edge.supertype,
+                ),
+            };
+            // Unresolved ends match any type of the same name.
+            let matches =
+                near_name == name && (near_def == def || near_def.is_none() || def.is_none());
+            if !matches || !self.used.insert(idx) {
+                continue;
+            }
+
+            let file_of = |def: Option<usize>| def.map(|idx| self.graph.types[idx].file.clone());
+            self.out.push(HierarchyEdge {
+                subtype: edge.relation.subtype.clone(),
+                subtype_file: file_of(edge.subtype),
+                supertype: edge.relation.supertype.clone(),
+                supertype_file: file_of(edge.supertype),
+                kind: edge.relation.kind,
+                file: edge.file.to_path_buf(),
+                line: edge.relation.line,
+                depth,
+            });
+
+            let far = (far_name.clone(), far_def);
+            if self.expanded.insert(far.clone()) {
+                self.visit(&far.0, far.1, depth + 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TypeRelationKind;
+
+    fn scan() -> TypeGraph {
+        TypeGraph::scan(
+            &[PathBuf::from("tests/fixtures/hierarchy_repo")],
+            &[],
+            &[],
+            None,
+            None,
+        )
+        .expect("scan")
+    }
+
+    fn edge_names(result: &HierarchyResult) -> Vec<(String, String, u32)> {
+        result
+            .edges
+            .iter()
+            .map(|edge| (edge.subtype.clone(), edge.supertype.clone(), edge.depth))
+            .collect()
+    }
+
+    #[test]
+    fn relations_resolve_through_local_files_and_imports() {
+        let result = scan().result_for("Polygon", HierarchyDirection::Subtypes, None);
+        assert_eq!(
+            edge_names(&result),
+            vec![
+                ("Square".to_string(), "Polygon".to_string(), 1),
+                ("Cube".to_string(), "Square".to_string(), 2),
+            ]
+        );
+        let square = &result.edges[0];
+        assert_eq!(square.kind, TypeRelationKind::Implements);
+        assert_eq!(
+            square.supertype_file,
+            Some(PathBuf::from("tests/fixtures/hierarchy_repo/ts/shapes.ts"))
+        );
+        assert_eq!(
+            square.file,
+            PathBuf::from("tests/fixtures/hierarchy_repo/ts/square.ts")
+        );
+    }
+
+    #[test]
+    fn same_named_types_keep_their_own_subtypes() {
+        let graph = scan();
+        let result = graph.result_for("Shape", HierarchyDirection::Subtypes, Some(1));
+        assert_eq!(result.definitions.len(), 3);
+
+        let rust_shape = PathBuf::from("tests/fixtures/hierarchy_repo/rust/lib.rs");
+        let rust_subtypes: Vec<&str> = result
+            .edges
+            .iter()
+            .filter(|edge| edge.supertype_file.as_ref() == Some(&rust_shape))
+            .map(|edge| edge.subtype.as_str())
+            .collect();
+        assert_eq!(rust_subtypes, vec!["Solid", "Cube"]);
+        assert!(result.edges.iter().all(|edge| edge.depth == 1));
+    }
+
+    #[test]
+    fn supertypes_walk_up_to_unresolved_types() {
+        let result = scan().result_for("Ring", HierarchyDirection::Supertypes, None);
+        assert_eq!(
+            edge_names(&result),
+            vec![
+                ("Ring".to_string(), "Circle".to_string(), 1),
+                ("Circle".to_string(), "Shape".to_string(), 2),
+                ("Ring".to_string(), "Tracked".to_string(), 1),
+            ]
+        );
+        assert_eq!(result.edges[2].supertype_file, None);
+    }
+}
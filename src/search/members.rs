@@ -0,0 +1,208 @@
+//! Pairing of C++ member declarations with out-of-line definitions.
+//!
+//! A method declared in a class body (`double area() const;`) and
+//! defined elsewhere (`double Shape::area() const { ... }`) is
+//! reported as one symbol: the declaration, with the definition's
+//! location in `Symbol.definition` and the definition's call edges
+//! merged in. The definition itself is dropped from symbol results.
+//!
+//! A definition pairs with the declaration of the same name and
+//! parameter count whose enclosing scope ends with the definition's
+//! qualifier, so `geo::Shape::area`, `Shape::area` under `using
+//! namespace geo` and `Shape::area` inside `namespace geo { ... }` all
+//! find `area` declared in `geo::Shape`. Definitions matching several
+//! declarations are left unpaired.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::index::models::MemberRecord;
+use crate::language::{normalize_path, FileSymbols};
+use crate::models::{MemberKey, Symbol, SymbolLocation, TextRange};
+
+/// Declarations paired with their out-of-line definitions.
+#[derive(Debug, Default)]
+pub(crate) struct MemberPairs {
+    /// Definition of each paired declaration, keyed by the
+    /// declaration's (normalized) file and range.
+    definitions: HashMap<(PathBuf, TextRange), SymbolLocation>,
+    /// (Normalized) file and range of each paired definition.
+    paired: HashSet<(PathBuf, TextRange)>,
+}
+
+impl MemberPairs {
+    /// Pair the definitions among `members` with declarations. Each
+    /// entry holds the file the member was found in.
+    pub(crate) fn build(members: &[(PathBuf, MemberKey)]) -> Self {
+        let mut declarations: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, (_, key)) in members.iter().enumerate() {
+            if !key.definition {
+                declarations.entry(key.name.as_str()).or_default().push(idx);
+            }
+        }
+
+        let mut pairs = Self::default();
+        let mut used = HashSet::new();
+        for (file, key) in members.iter().filter(|(_, key)| key.definition) {
+            let candidates: Vec<usize> = declarations
+                .get(key.name.as_str())
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|idx| {
+                    let declaration = &members[*idx].1;
+                    !used.contains(idx)
+                        && declaration.arity == key.arity
+                        && declaration.scope.ends_with(&key.scope)
+                })
+                .collect();
+            let [idx] = candidates[..] else {
+                continue;
+            };
+
+            used.insert(idx);
+            let (decl_file, declaration) = &members[idx];
+            pairs.definitions.insert(
+                (normalize_path(decl_file), declaration.range),
+                SymbolLocation {
+                    file: file.clone(),
+                    range: key.range,
+                },
+            );
+            pairs.paired.insert((normalize_path(file), key.range));
+        }
+        pairs
+    }
+
+    /// Pair the members stored in an index, given the paths of their
+    /// files.
+    pub(crate) fn from_records(
+        records: Vec<MemberRecord>,
+        file_paths: &HashMap<u64, PathBuf>,
+    ) -> Self {
+        let members: Vec<(PathBuf, MemberKey)> = records
+            .into_iter()
+            .filter_map(|record| {
+                let path = file_paths.get(&record.file_id)?;
+                let scope = if record.scope.is_empty() {
+                    Vec::new()
+                } else {
+                    record.scope.split("::").map(str::to_string).collect()
+                };
+                Some((
+                    path.clone(),
+                    MemberKey {
+                        scope,
+                        name: record.name,
+                        arity: record.arity,
+                        range: record.range,
+                        definition: record.definition,
+                    },
+                ))
+            })
+            .collect();
+        Self::build(&members)
+    }
+
+    /// Out-of-line definition of the member declared at `range` in
+    /// `file`, if it was paired.
+    pub(crate) fn definition_of(&self, file: &Path, range: TextRange) -> Option<&SymbolLocation> {
+        self.definitions.get(&(normalize_path(file), range))
+    }
+
+    /// Whether the symbol at `range` in `file` is a paired definition,
+    /// reported through its declaration instead.
+    pub(crate) fn is_paired_definition(&self, file: &Path, range: TextRange) -> bool {
+        self.paired.contains(&(normalize_path(file), range))
+    }
+}
+
+/// Merge the out-of-line definitions of `files` into their
+/// declarations: set `Symbol.definition`, move the definition's call
+/// edges over and drop the definition symbol.
+pub(crate) fn pair_member_definitions(files: &mut [FileSymbols]) {
+    let members: Vec<(PathBuf, MemberKey)> = files
+        .iter()
+        .flat_map(|file| {
+            file.members
+                .iter()
+                .map(|member| (file.path.clone(), member.clone()))
+        })
+        .collect();
+    let pairs = MemberPairs::build(&members);
+    if pairs.paired.is_empty() {
+        return;
+    }
+
+    let mut removed: HashMap<(PathBuf, TextRange), Symbol> = HashMap::new();
+    for file in files.iter_mut() {
+        let path = file.path.clone();
+        file.symbols.retain(|symbol| {
+            if !pairs.is_paired_definition(&path, symbol.range) {
+                return true;
+            }
+            removed.insert((normalize_path(&path), symbol.range), symbol.clone());
+            false
+        });
+    }
+
+    for file in files.iter_mut() {
+        for symbol in &mut file.symbols {
+            let Some(location) = pairs.definition_of(&file.path, symbol.range) else {
+                continue;
+            };
+            if let Some(definition) =
+                removed.remove(&(normalize_path(&location.file), location.range))
+            {
+                symbol.calls.extend(definition.calls);
+                symbol.called_by.extend(definition.called_by);
+            }
+            symbol.definition = Some(location.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(scope: &[&str], name: &str, arity: u32, line: u32, definition: bool) -> MemberKey {
+        MemberKey {
+            scope: scope.iter().map(|s| s.to_string()).collect(),
+            name: name.to_string(),
+            arity,
+            range: TextRange {
+                start_line: line,
+                start_column: 1,
+                end_line: line,
+                end_column: 10,
+            },
+            definition,
+        }
+    }
+
+    #[test]
+    fn definitions_pair_by_scope_suffix_and_arity() {
+        let header = PathBuf::from("shape.h");
+        let source = PathBuf::from("shape.cpp");
+        let members = vec![
+            (header.clone(), key(&["geo", "Shape"], "scale", 1, 3, false)),
+            (header.clone(), key(&["geo", "Shape"], "scale", 2, 4, false)),
+            (header.clone(), key(&["geo", "Other"], "scale", 1, 8, false)),
+            (source.clone(), key(&["geo", "Shape"], "scale", 2, 10, true)),
+            // Ambiguous between `geo::Shape` and `geo::Other`.
+            (source.clone(), key(&[], "scale", 1, 20, true)),
+        ];
+
+        let pairs = MemberPairs::build(&members);
+
+        let definition = pairs
+            .definition_of(&header, members[1].1.range)
+            .expect("paired declaration");
+        assert_eq!(definition.file, source);
+        assert_eq!(definition.range.start_line, 10);
+        assert!(pairs.definition_of(&header, members[0].1.range).is_none());
+        assert!(pairs.is_paired_definition(&source, members[3].1.range));
+        assert!(!pairs.is_paired_definition(&source, members[4].1.range));
+    }
+}
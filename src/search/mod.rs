@@ -5,3 +5,11 @@
 
 pub mod engine;
 pub mod query;
+pub(crate) mod ast;
+pub(crate) mod calls;
+pub(crate) mod capture;
+pub(crate) mod deps;
+pub(crate) mod hierarchy;
+pub(crate) mod members;
+pub(crate) mod refs;
+pub(crate) mod text;
@@ -9,8 +9,37 @@
 //! - When the first alternative in a group has a known field,
 //!   subsequent bare alternatives inherit that field (e.g.
 //!   `kind:function|method`).
+//! - `file:` values containing glob metacharacters are matched as
+//!   globs (`file:src/**/*.test.ts`) against the path relative to the
+//!   search root, and a leading `!` negates the path constraint
+//!   (`file:!vendor/**`).
+//! - `scope:code|comments|strings` restricts text matches to a
+//!   syntactic region; it is neutral when evaluated against symbols.
+//! - `crate:` matches files by the Rust crate (or, with `::`, the
+//!   module) they belong to; like `file:`, it also narrows text
+//!   searches.
+//! - `attr:` matches a symbol's attributes and decorators by path,
+//!   optionally with arguments (`attr:derive(Serialize)`).
+//! - `is:test` keeps test code and `-is:test` (or `is:!test`) drops
+//!   it; in text searches only test files count.
+//! - `deprecated:` matches deprecated symbols, optionally by message,
+//!   and `doc-tag:` symbols whose doc comment has a tag or section
+//!   (`doc-tag:throws`, `doc-tag:safety`).
+//! - `ast:"<snippet>"` selects structural search. Its value is never
+//!   split on `|`, and a quoted value may contain `\"` escapes so code
+//!   with string literals can be written.
 
-use crate::models::{QueryExpr, QueryField, QueryTerm, Symbol, SymbolKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::language::{is_test_file, rust_module_path};
+use crate::models::{
+    QueryExpr, QueryField, QueryTerm, Symbol, SymbolKind, SyntaxScope, TypeRelationKind,
+};
 
 /// Parse a raw query string into a `QueryExpr`.
 ///
@@ -69,6 +98,14 @@ pub fn parse_query_expr(input: &str) -> Option<QueryExpr> {
     let mut groups: Vec<Vec<QueryExpr>> = Vec::new();
 
     for token in tokens {
+        if let Some(snippet) = strip_ast_prefix(&token) {
+            groups.push(vec![QueryExpr::Term(QueryTerm {
+                field: QueryField::Ast,
+                value: snippet.to_string(),
+            })]);
+            continue;
+        }
+
         let mut clauses = Vec::new();
         let mut default_field: Option<QueryField> = None;
         for raw_alt in token.split('|') {
@@ -76,17 +113,17 @@ pub fn parse_query_expr(input: &str) -> Option<QueryExpr> {
             if alt.is_empty() {
                 continue;
             }
-            let term = if alt.contains(':') || default_field.is_none() {
-                let t = parse_term(alt);
-                if default_field.is_none() {
-                    default_field = Some(t.field);
-                }
-                t
-            } else {
-                let field = default_field.expect("default_field must be set");
-                QueryTerm {
+            let term = match default_field {
+                Some(field) if !alt.contains(':') => QueryTerm {
                     field,
                     value: alt.to_string(),
+                },
+                _ => {
+                    let t = parse_term(alt);
+                    if default_field.is_none() {
+                        default_field = Some(t.field);
+                    }
+                    t
                 }
             };
             clauses.push(QueryExpr::Term(term));
@@ -119,15 +156,35 @@ pub fn parse_query_expr(input: &str) -> Option<QueryExpr> {
     Some(expr)
 }
 
+/// Return the snippet of an `ast:` token.
+fn strip_ast_prefix(token: &str) -> Option<&str> {
+    let head = token.get(..4)?;
+    head.eq_ignore_ascii_case("ast:").then(|| &token[4..])
+}
+
 /// Tokenize a query string, treating whitespace as separators and
 /// allowing double-quoted segments to contain spaces.
+///
+/// Inside a quoted `ast:` value, `\"` and `\\` are unescaped so the
+/// snippet can contain string literals.
 fn tokenize(input: &str) -> Vec<String> {
     let mut tokens = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
+    let mut chars = input.chars();
 
-    for ch in input.chars() {
+    while let Some(ch) = chars.next() {
         match ch {
+            '\\' if in_quotes && strip_ast_prefix(&current).is_some() => {
+                match chars.next() {
+                    Some(next @ ('"' | '\\')) => current.push(next),
+                    Some(next) => {
+                        current.push('\\');
+                        current.push(next);
+                    }
+                    None => current.push('\\'),
+                }
+            }
             '"' => {
                 in_quotes = !in_quotes;
             }
@@ -195,6 +252,54 @@ fn parse_term(atom: &str) -> QueryTerm {
                 field: QueryField::CalledBy,
                 value,
             },
+            "extends" => QueryTerm {
+                field: QueryField::Extends,
+                value,
+            },
+            "implements" => QueryTerm {
+                field: QueryField::Implements,
+                value,
+            },
+            "module" => QueryTerm {
+                field: QueryField::Module,
+                value,
+            },
+            "crate" => QueryTerm {
+                field: QueryField::Crate,
+                value,
+            },
+            "attr" | "attribute" | "decorator" => QueryTerm {
+                field: QueryField::Attr,
+                value,
+            },
+            "is" => QueryTerm {
+                field: QueryField::Is,
+                value,
+            },
+            "-is" => QueryTerm {
+                field: QueryField::Is,
+                value: format!("!{value}"),
+            },
+            "deprecated" => QueryTerm {
+                field: QueryField::Deprecated,
+                value,
+            },
+            "doc-tag" | "doc_tag" | "doctag" => QueryTerm {
+                field: QueryField::DocTag,
+                value,
+            },
+            "scope" => QueryTerm {
+                field: QueryField::Scope,
+                value,
+            },
+            "ast" => QueryTerm {
+                field: QueryField::Ast,
+                value,
+            },
+            "refs" | "references" => QueryTerm {
+                field: QueryField::References,
+                value,
+            },
             // Unknown field – treat the whole atom as a name filter
             // to stay backward compatible and conservative.
             _ => QueryTerm {
@@ -242,6 +347,58 @@ pub fn expr_has_call_terms(expr: &QueryExpr) -> bool {
     }
 }
 
+/// Whether the expression contains any type hierarchy terms
+/// (`extends:` / `implements:`).
+pub fn expr_has_hierarchy_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => {
+            matches!(term.field, QueryField::Extends | QueryField::Implements)
+        }
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().any(expr_has_hierarchy_terms)
+        }
+    }
+}
+
+/// Whether the expression contains any `attr:` terms.
+pub fn expr_has_attr_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => term.field == QueryField::Attr,
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().any(expr_has_attr_terms)
+        }
+    }
+}
+
+/// Whether the expression contains any `is:` terms.
+pub fn expr_has_is_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => term.field == QueryField::Is,
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => clauses.iter().any(expr_has_is_terms),
+    }
+}
+
+/// Whether the expression contains any `deprecated:` or `doc-tag:`
+/// terms.
+pub fn expr_has_doc_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => {
+            matches!(term.field, QueryField::Deprecated | QueryField::DocTag)
+        }
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => clauses.iter().any(expr_has_doc_terms),
+    }
+}
+
+/// Whether the expression contains any `module:` terms.
+pub fn expr_has_module_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => term.field == QueryField::Module,
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().any(expr_has_module_terms)
+        }
+    }
+}
+
 /// Evaluate only the metadata portion of a query (name, kind, file,
 /// language) against a symbol.
 ///
@@ -251,24 +408,37 @@ pub fn expr_has_call_terms(expr: &QueryExpr) -> bool {
 /// The `literal` flag controls how `name:` terms are interpreted:
 /// - When `literal == false`, name filters use substring matching.
 /// - When `literal == true`, name filters require an exact match.
-pub fn symbol_matches_metadata(expr: &QueryExpr, symbol: &Symbol, literal: bool) -> bool {
+pub fn symbol_matches_metadata(
+    expr: &QueryExpr,
+    symbol: &Symbol,
+    roots: &[PathBuf],
+    literal: bool,
+) -> bool {
     match expr {
-        QueryExpr::Term(term) => matches_term_metadata(term, symbol, literal),
+        QueryExpr::Term(term) => matches_term_metadata(term, symbol, roots, literal),
         QueryExpr::And(clauses) => clauses
             .iter()
-            .all(|c| symbol_matches_metadata(c, symbol, literal)),
+            .all(|c| symbol_matches_metadata(c, symbol, roots, literal)),
         QueryExpr::Or(clauses) => clauses
             .iter()
-            .any(|c| symbol_matches_metadata(c, symbol, literal)),
+            .any(|c| symbol_matches_metadata(c, symbol, roots, literal)),
     }
 }
 
-fn matches_term_metadata(term: &QueryTerm, symbol: &Symbol, literal: bool) -> bool {
+fn matches_term_metadata(
+    term: &QueryTerm,
+    symbol: &Symbol,
+    roots: &[PathBuf],
+    literal: bool,
+) -> bool {
     match term.field {
         QueryField::Content
         | QueryField::Comment
         | QueryField::Keyword
-        | QueryField::Description => true,
+        | QueryField::Description
+        | QueryField::Scope
+        | QueryField::Ast
+        | QueryField::References => true,
         QueryField::Name => {
             let value = term.value.as_str();
             if let Some(exact) = value.strip_prefix('=') {
@@ -283,11 +453,29 @@ fn matches_term_metadata(term: &QueryTerm, symbol: &Symbol, literal: bool) -> bo
             Some(kind) => symbol.kind == kind,
             None => false,
         },
-        QueryField::File => symbol
-            .file
-            .to_string_lossy()
-            .to_string()
-            .contains(&term.value),
+        QueryField::File => file_term_matches(&term.value, &symbol.file, roots),
+        QueryField::Crate => crate_term_matches(&term.value, &symbol.file),
+        QueryField::Attr => symbol
+            .attributes
+            .as_ref()
+            .is_some_and(|attrs| attr_term_matches(&term.value, &attrs.annotations)),
+        QueryField::Is => is_term_matches(
+            &term.value,
+            symbol.attributes.as_ref().is_some_and(|attrs| attrs.is_test),
+        ),
+        QueryField::Deprecated => deprecated_term_matches(
+            &term.value,
+            symbol
+                .attributes
+                .as_ref()
+                .and_then(|attrs| attrs.doc.as_ref())
+                .and_then(|doc| doc.deprecated.as_deref()),
+        ),
+        QueryField::DocTag => symbol
+            .attributes
+            .as_ref()
+            .and_then(|attrs| attrs.doc.as_ref())
+            .is_some_and(|doc| doc.has_tag(&term.value)),
         QueryField::Language => symbol.language.eq_ignore_ascii_case(term.value.as_str()),
         QueryField::Calls => {
             let value = term.value.as_str();
@@ -317,9 +505,381 @@ fn matches_term_metadata(term: &QueryTerm, symbol: &Symbol, literal: bool) -> bo
                 }
             })
         }
+        QueryField::Extends | QueryField::Implements => {
+            let kind = if term.field == QueryField::Extends {
+                TypeRelationKind::Extends
+            } else {
+                TypeRelationKind::Implements
+            };
+            let value = term.value.as_str();
+            symbol.supertypes.iter().any(|supertype| {
+                let target = supertype.name.as_str();
+                supertype.kind == kind
+                    && if let Some(exact) = value.strip_prefix('=') {
+                        target == exact
+                    } else {
+                        target.contains(value)
+                    }
+            })
+        }
+        QueryField::Module => {
+            let value = term.value.as_str();
+            symbol.modules.iter().any(|module| {
+                if let Some(exact) = value.strip_prefix('=') {
+                    module == exact
+                } else {
+                    module.contains(value)
+                }
+            })
+        }
+    }
+}
+
+/// Evaluate only the `file:`, `crate:` and `is:` terms of a query
+/// against a path found under the search `roots`.
+///
+/// Other terms are treated as neutral, mirroring how
+/// `symbol_matches_metadata` treats content-like terms. This lets text
+/// search skip whole files before reading them. `is:test` holds for
+/// test files by naming convention (see `is_test_file`).
+pub fn path_matches_file_terms(expr: &QueryExpr, path: &Path, roots: &[PathBuf]) -> bool {
+    match expr {
+        QueryExpr::Term(term) => match term.field {
+            QueryField::File => file_term_matches(&term.value, path, roots),
+            QueryField::Crate => crate_term_matches(&term.value, path),
+            QueryField::Is => is_term_matches(&term.value, is_test_file(path, roots)),
+            _ => true,
+        },
+        QueryExpr::And(clauses) => clauses
+            .iter()
+            .all(|c| path_matches_file_terms(c, path, roots)),
+        QueryExpr::Or(clauses) => clauses
+            .iter()
+            .any(|c| path_matches_file_terms(c, path, roots)),
+    }
+}
+
+/// Whether the expression contains any `scope:` terms.
+pub fn expr_has_scope_terms(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => term.field == QueryField::Scope,
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().any(expr_has_scope_terms)
+        }
+    }
+}
+
+/// Evaluate only the `scope:` terms of a query against the syntactic
+/// region of a match. Other terms are treated as neutral.
+pub fn scope_matches_terms(expr: &QueryExpr, scope: SyntaxScope) -> bool {
+    match expr {
+        QueryExpr::Term(term) => match term.field {
+            QueryField::Scope => parse_syntax_scope(&term.value) == Some(scope),
+            _ => true,
+        },
+        QueryExpr::And(clauses) => clauses.iter().all(|c| scope_matches_terms(c, scope)),
+        QueryExpr::Or(clauses) => clauses.iter().any(|c| scope_matches_terms(c, scope)),
+    }
+}
+
+/// Return the value of the first `scope:` term that does not name a
+/// known scope, so it can be reported instead of matching nothing.
+pub fn find_invalid_scope_term(expr: &QueryExpr) -> Option<&str> {
+    match expr {
+        QueryExpr::Term(term) if term.field == QueryField::Scope => {
+            match parse_syntax_scope(&term.value) {
+                Some(_) => None,
+                None => Some(term.value.as_str()),
+            }
+        }
+        QueryExpr::Term(_) => None,
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().find_map(find_invalid_scope_term)
+        }
     }
 }
 
+/// Parse a `scope:` value. Singular and plural forms are accepted.
+pub fn parse_syntax_scope(value: &str) -> Option<SyntaxScope> {
+    match value.to_ascii_lowercase().as_str() {
+        "code" => Some(SyntaxScope::Code),
+        "comment" | "comments" => Some(SyntaxScope::Comments),
+        "string" | "strings" => Some(SyntaxScope::Strings),
+        _ => None,
+    }
+}
+
+/// Value of the lone `field` term (`ast:`, `refs:`) in a query that otherwise only filters files.
+pub(crate) fn exclusive_term<'a>(
+    expr: &'a QueryExpr,
+    field: QueryField,
+    prefix: &str,
+) -> Result<Option<&'a str>> {
+    fn is_filter(expr: &QueryExpr) -> bool {
+        match expr {
+            QueryExpr::Term(term) => matches!(term.field, QueryField::File | QueryField::Language),
+            QueryExpr::Or(clauses) | QueryExpr::And(clauses) => clauses.iter().all(is_filter),
+        }
+    }
+
+    fn has_field(expr: &QueryExpr, field: QueryField) -> bool {
+        match expr {
+            QueryExpr::Term(term) => term.field == field,
+            QueryExpr::Or(clauses) | QueryExpr::And(clauses) => {
+                clauses.iter().any(|c| has_field(c, field))
+            }
+        }
+    }
+
+    if !has_field(expr, field) {
+        return Ok(None);
+    }
+
+    let clauses = match expr {
+        QueryExpr::And(clauses) => clauses.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+
+    let mut value = None;
+    for clause in clauses {
+        match clause {
+            QueryExpr::Term(term) if term.field == field => {
+                if value.is_some() {
+                    bail!("only one {prefix}: pattern is allowed per query");
+                }
+                value = Some(term.value.trim());
+            }
+            other if is_filter(other) => {}
+            _ => bail!("{prefix}: patterns can only be combined with file: and language: terms"),
+        }
+    }
+
+    match value {
+        Some("") => bail!("{prefix}: pattern must not be empty"),
+        Some(value) => Ok(Some(value)),
+        None => bail!("{prefix}: patterns cannot be used inside an `|` alternative"),
+    }
+}
+
+/// Validate the glob syntax of every `file:` term in the expression so
+/// malformed patterns are reported instead of silently matching
+/// nothing.
+pub fn check_file_globs(expr: &QueryExpr) -> Result<(), globset::Error> {
+    match expr {
+        QueryExpr::Term(term) if term.field == QueryField::File => {
+            let value = term.value.strip_prefix('!').unwrap_or(&term.value);
+            if is_glob_value(value) {
+                compile_file_glob(value)?;
+            }
+            Ok(())
+        }
+        QueryExpr::Term(_) => Ok(()),
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            clauses.iter().try_for_each(check_file_globs)
+        }
+    }
+}
+
+/// Match a `crate:` term value against the Rust crate and module path
+/// of a file.
+///
+/// - Values containing `::` name a module (`symgrep::index`) and match
+///   it and its submodules; `=` restricts them to that module.
+/// - Other values match the crate name, as a substring or exactly
+///   with `=`.
+/// - `-` and `_` are interchangeable, as in Cargo package names.
+/// - Files outside a Cargo package target never match.
+pub fn crate_term_matches(value: &str, path: &Path) -> bool {
+    let Some(module) = rust_module_path(path) else {
+        return false;
+    };
+    let (exact, value) = match value.strip_prefix('=') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let value = value.replace('-', "_");
+
+    if value.contains("::") {
+        let full = module.to_path_string();
+        full == value || (!exact && full.starts_with(&format!("{value}::")))
+    } else if exact {
+        module.crate_name == value
+    } else {
+        module.crate_name.contains(&value)
+    }
+}
+
+/// Match an `is:` term value against a symbol's (or file's) test
+/// classification.
+///
+/// `test` matches test code and a leading `!` negates it, so
+/// `is:!test` (written `-is:test`) keeps production code. Unknown
+/// values never match.
+pub fn is_term_matches(value: &str, is_test: bool) -> bool {
+    let (negated, value) = match value.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    if !value.eq_ignore_ascii_case("test") {
+        return false;
+    }
+    is_test != negated
+}
+
+/// Match a `deprecated:` term value against a symbol's deprecation
+/// message (`None` when it is not deprecated).
+///
+/// - An empty value or `true` matches any deprecated symbol and
+///   `false` any other symbol.
+/// - `=message` requires the whole message to equal `message`.
+/// - Any other value is a substring of the message.
+pub fn deprecated_term_matches(value: &str, message: Option<&str>) -> bool {
+    match value {
+        "" | "true" => message.is_some(),
+        "false" => message.is_none(),
+        _ => message.is_some_and(|message| match value.strip_prefix('=') {
+            Some(exact) => message == exact,
+            None => message.contains(value),
+        }),
+    }
+}
+
+/// Match an `attr:` term value against a symbol's annotations (see
+/// `SymbolAttributes::annotations`).
+///
+/// - `=value` requires a whole annotation to equal `value`.
+/// - `path(args)` matches annotations whose path contains `path` and
+///   whose arguments contain each comma-separated `args` entry, so
+///   `derive(Serialize)` matches `derive(Debug, Serialize)`.
+/// - Any other value is a substring of an annotation's path:
+///   `test` matches `test` and `tokio::test` but not `cfg(test)`.
+///
+/// Whitespace and double quotes are ignored when comparing, since the
+/// query tokenizer drops quotes.
+pub fn attr_term_matches(value: &str, annotations: &[String]) -> bool {
+    fn compact(text: &str) -> String {
+        text.chars()
+            .filter(|c| !c.is_whitespace() && *c != '"')
+            .collect()
+    }
+
+    fn split(annotation: &str) -> (&str, Option<&str>) {
+        match annotation.split_once('(') {
+            Some((path, rest)) => (path, Some(rest.strip_suffix(')').unwrap_or(rest))),
+            None => (annotation, None),
+        }
+    }
+
+    if let Some(exact) = value.strip_prefix('=') {
+        let exact = compact(exact);
+        return annotations.iter().any(|a| compact(a) == exact);
+    }
+
+    let value = compact(value);
+    let (path, args) = split(&value);
+    annotations.iter().any(|annotation| {
+        let annotation = compact(annotation);
+        let (annotation_path, annotation_args) = split(&annotation);
+        if !annotation_path.contains(path) {
+            return false;
+        }
+        match args {
+            None => true,
+            Some(args) => {
+                let annotation_args = annotation_args.unwrap_or_default();
+                args.split(',')
+                    .filter(|arg| !arg.is_empty())
+                    .all(|arg| annotation_args.contains(arg))
+            }
+        }
+    })
+}
+
+/// Match a `file:` term value against a path found under the search
+/// `roots`.
+///
+/// Substrings and relative globs both see the path relative to
+/// whichever of `roots` contains it, so with root `repo` neither
+/// `repo/src` nor `repo/**` matches `repo/src/a.ts`.
+///
+/// - A leading `!` negates the rest of the value.
+/// - `=path` requires the whole path to equal `path`.
+/// - Values containing glob metacharacters (`*`, `?`, `[`, `{`) are
+///   matched as globs. Relative globs may match any trailing run of
+///   the path's components, so `src/**/*.ts` matches
+///   `repo/lib/src/a/b.ts` under root `repo`. Absolute globs (`/...`)
+///   see the full path. `*` does not cross `/`; use `**` for that.
+/// - Any other value is a plain substring of the path.
+pub fn file_term_matches(value: &str, path: &Path, roots: &[PathBuf]) -> bool {
+    if let Some(negated) = value.strip_prefix('!') {
+        return !file_term_matches(negated, path, roots);
+    }
+
+    let path_text = path.to_string_lossy();
+
+    if let Some(exact) = value.strip_prefix('=') {
+        let trimmed = path_text.strip_prefix("./").unwrap_or(&path_text);
+        return trimmed == exact.strip_prefix("./").unwrap_or(exact);
+    }
+
+    let is_glob = is_glob_value(value);
+    let target = if is_glob && value.starts_with('/') {
+        path
+    } else {
+        path_below_roots(path, roots)
+    };
+
+    if !is_glob {
+        return target.to_string_lossy().contains(value);
+    }
+
+    FILE_GLOB_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let matcher = cache
+            .entry(value.to_string())
+            .or_insert_with(|| compile_file_glob(value).ok());
+        matcher.as_ref().is_some_and(|matcher| matcher.is_match(target))
+    })
+}
+
+/// The part of `path` below whichever of `roots` contains it, or the
+/// file name when a root is the file itself. Paths outside every root
+/// are returned unchanged.
+fn path_below_roots<'a>(path: &'a Path, roots: &[PathBuf]) -> &'a Path {
+    match roots.iter().find_map(|root| path.strip_prefix(root).ok()) {
+        Some(relative) if relative.as_os_str().is_empty() => {
+            path.file_name().map(Path::new).unwrap_or(path)
+        }
+        Some(relative) => relative,
+        None => path,
+    }
+}
+
+thread_local! {
+    /// Compiled `file:` globs keyed by their source text, so symbol
+    /// filtering does not recompile the same glob per symbol.
+    static FILE_GLOB_CACHE: RefCell<HashMap<String, Option<GlobMatcher>>> =
+        RefCell::new(HashMap::new());
+}
+
+fn is_glob_value(value: &str) -> bool {
+    value.contains(['*', '?', '[', '{'])
+}
+
+fn compile_file_glob(value: &str) -> Result<GlobMatcher, globset::Error> {
+    let anchored = value.starts_with('/') || value.starts_with("**");
+    let pattern = if anchored {
+        value.to_string()
+    } else {
+        format!("**/{}", value.strip_prefix("./").unwrap_or(value))
+    };
+
+    Ok(GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher())
+}
+
 fn parse_symbol_kind(value: &str) -> Option<SymbolKind> {
     match value.to_ascii_lowercase().as_str() {
         "function" | "func" => Some(SymbolKind::Function),
@@ -348,16 +908,17 @@ pub fn symbol_matches_with_text(
     expr: &QueryExpr,
     symbol: &Symbol,
     snippet: Option<&str>,
+    roots: &[PathBuf],
     literal: bool,
 ) -> bool {
     match expr {
-        QueryExpr::Term(term) => matches_term_full(term, symbol, snippet, literal),
+        QueryExpr::Term(term) => matches_term_full(term, symbol, snippet, roots, literal),
         QueryExpr::And(clauses) => clauses
             .iter()
-            .all(|c| symbol_matches_with_text(c, symbol, snippet, literal)),
+            .all(|c| symbol_matches_with_text(c, symbol, snippet, roots, literal)),
         QueryExpr::Or(clauses) => clauses
             .iter()
-            .any(|c| symbol_matches_with_text(c, symbol, snippet, literal)),
+            .any(|c| symbol_matches_with_text(c, symbol, snippet, roots, literal)),
     }
 }
 
@@ -365,9 +926,11 @@ fn matches_term_full(
     term: &QueryTerm,
     symbol: &Symbol,
     snippet: Option<&str>,
+    roots: &[PathBuf],
     literal: bool,
 ) -> bool {
     match term.field {
+        QueryField::Scope | QueryField::Ast | QueryField::References => true,
         QueryField::Content => {
             let value = term.value.as_str();
             let mut parts: Vec<String> = Vec::new();
@@ -467,9 +1030,15 @@ fn matches_term_full(
         | QueryField::File
         | QueryField::Language
         | QueryField::Calls
-        | QueryField::CalledBy => {
-            matches_term_metadata(term, symbol, literal)
-        }
+        | QueryField::CalledBy
+        | QueryField::Extends
+        | QueryField::Implements
+        | QueryField::Module
+        | QueryField::Crate
+        | QueryField::Attr
+        | QueryField::Is
+        | QueryField::Deprecated
+        | QueryField::DocTag => matches_term_metadata(term, symbol, roots, literal),
     }
 }
 
@@ -575,6 +1144,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_ast_terms_without_splitting_alternatives() {
+        let expr = parse_query_expr(r#"ast:"a || log(\"x\")" file:src"#).unwrap();
+        assert_eq!(
+            expr,
+            QueryExpr::And(vec![
+                term(QueryField::Ast, r#"a || log("x")"#),
+                term(QueryField::File, "src"),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_refs_terms() {
+        assert_eq!(
+            parse_query_expr("refs:Widget"),
+            Some(term(QueryField::References, "Widget"))
+        );
+        assert_eq!(
+            parse_query_expr("references:Widget"),
+            Some(term(QueryField::References, "Widget"))
+        );
+    }
+
     #[test]
     fn tokenize_respects_quotes() {
         let tokens = tokenize(r#"content:"rate limit" name:foo"#);
@@ -600,6 +1193,9 @@ mod tests {
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         let expr = QueryExpr::And(vec![
@@ -608,7 +1204,7 @@ mod tests {
             term(QueryField::Name, "add"),
         ]);
 
-        assert!(symbol_matches_metadata(&expr, &symbol, false));
+        assert!(symbol_matches_metadata(&expr, &symbol, &[], false));
     }
 
     #[test]
@@ -630,13 +1226,16 @@ mod tests {
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         let expr = term(QueryField::Name, "add");
         let expr_other = term(QueryField::Name, "adder");
 
-        assert!(symbol_matches_metadata(&expr, &symbol, true));
-        assert!(!symbol_matches_metadata(&expr_other, &symbol, true));
+        assert!(symbol_matches_metadata(&expr, &symbol, &[], true));
+        assert!(!symbol_matches_metadata(&expr_other, &symbol, &[], true));
     }
 
     #[test]
@@ -691,15 +1290,21 @@ mod tests {
                 comment_range: None,
                 keywords: Vec::new(),
                 description: None,
+                annotations: Vec::new(),
+                is_test: false,
+                doc: None,
             }),
             def_line_count: None,
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         let expr = term(QueryField::Comment, "authentication");
-        assert!(symbol_matches_with_text(&expr, &symbol, None, false));
+        assert!(symbol_matches_with_text(&expr, &symbol, None, &[], false));
     }
 
     #[test]
@@ -721,18 +1326,24 @@ mod tests {
                 comment_range: None,
                 keywords: vec!["auth".to_string(), "jwt-token".to_string()],
                 description: None,
+                annotations: Vec::new(),
+                is_test: false,
+                doc: None,
             }),
             def_line_count: None,
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         let expr_exact = term(QueryField::Keyword, "auth");
         let expr_sub = term(QueryField::Keyword, "~jwt");
 
-        assert!(symbol_matches_with_text(&expr_exact, &symbol, None, false));
-        assert!(symbol_matches_with_text(&expr_sub, &symbol, None, false));
+        assert!(symbol_matches_with_text(&expr_exact, &symbol, None, &[], false));
+        assert!(symbol_matches_with_text(&expr_sub, &symbol, None, &[], false));
     }
 
     #[test]
@@ -756,15 +1367,21 @@ mod tests {
                 description: Some(
                     "Performs user authentication and issues JWTs".to_string(),
                 ),
+                annotations: Vec::new(),
+                is_test: false,
+                doc: None,
             }),
             def_line_count: None,
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         let expr = term(QueryField::Description, "issues JWTs");
-        assert!(symbol_matches_with_text(&expr, &symbol, None, false));
+        assert!(symbol_matches_with_text(&expr, &symbol, None, &[], false));
     }
 
     #[test]
@@ -802,7 +1419,7 @@ mod tests {
 
     #[test]
     fn calls_and_called_by_terms_match_call_metadata() {
-        use crate::models::CallRef;
+        use crate::models::{CallEdgeKind, CallRef};
 
         let mut symbol = Symbol {
             name: "foo".to_string(),
@@ -821,36 +1438,238 @@ mod tests {
             matches: Vec::new(),
             calls: Vec::new(),
             called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
         };
 
         symbol.calls.push(CallRef {
             name: "bar".to_string(),
             file: "src/lib.ts".into(),
             line: Some(3),
+            column: None,
             kind: Some(SymbolKind::Function),
+            target_file: None,
+            edge_kind: CallEdgeKind::Call,
         });
 
         symbol.called_by.push(CallRef {
             name: "qux".to_string(),
             file: "src/lib.ts".into(),
             line: Some(7),
+            column: None,
             kind: Some(SymbolKind::Function),
+            target_file: None,
+            edge_kind: CallEdgeKind::Call,
         });
 
         let expr_calls = term(QueryField::Calls, "bar");
         let expr_calls_exact = term(QueryField::Calls, "=bar");
         let expr_calls_other = term(QueryField::Calls, "baz");
 
-        assert!(symbol_matches_metadata(&expr_calls, &symbol, false));
-        assert!(symbol_matches_metadata(&expr_calls_exact, &symbol, false));
-        assert!(!symbol_matches_metadata(&expr_calls_other, &symbol, false));
+        assert!(symbol_matches_metadata(&expr_calls, &symbol, &[], false));
+        assert!(symbol_matches_metadata(&expr_calls_exact, &symbol, &[], false));
+        assert!(!symbol_matches_metadata(&expr_calls_other, &symbol, &[], false));
 
         let expr_called_by = term(QueryField::CalledBy, "qux");
         let expr_called_by_exact = term(QueryField::CalledBy, "=qux");
         let expr_called_by_other = term(QueryField::CalledBy, "other");
 
-        assert!(symbol_matches_metadata(&expr_called_by, &symbol, false));
-        assert!(symbol_matches_metadata(&expr_called_by_exact, &symbol, false));
-        assert!(!symbol_matches_metadata(&expr_called_by_other, &symbol, false));
+        assert!(symbol_matches_metadata(&expr_called_by, &symbol, &[], false));
+        assert!(symbol_matches_metadata(&expr_called_by_exact, &symbol, &[], false));
+        assert!(!symbol_matches_metadata(&expr_called_by_other, &symbol, &[], false));
+    }
+
+    #[test]
+    fn module_terms_match_import_specifiers() {
+        let expr = parse_query_expr("module:@app kind:function").expect("expr");
+        assert!(expr_has_module_terms(&expr));
+
+        let mut symbol = Symbol {
+            name: "formatPrice".to_string(),
+            kind: SymbolKind::Function,
+            language: "typescript".to_string(),
+            file: "src/utils/format.ts".into(),
+            range: crate::models::TextRange {
+                start_line: 1,
+                start_column: 1,
+                end_line: 3,
+                end_column: 1,
+            },
+            signature: None,
+            attributes: None,
+            def_line_count: None,
+            matches: Vec::new(),
+            calls: Vec::new(),
+            called_by: Vec::new(),
+            supertypes: Vec::new(),
+            definition: None,
+            modules: Vec::new(),
+        };
+        assert!(!symbol_matches_metadata(&expr, &symbol, &[], false));
+
+        symbol.modules = vec!["./format".to_string(), "@app/utils/format".to_string()];
+        assert!(symbol_matches_metadata(&expr, &symbol, &[], false));
+        assert!(symbol_matches_metadata(
+            &term(QueryField::Module, "=./format"),
+            &symbol,
+            &[],
+            false
+        ));
+        assert!(!symbol_matches_metadata(
+            &term(QueryField::Module, "=@app/utils"),
+            &symbol,
+            &[],
+            false
+        ));
+    }
+
+    #[test]
+    fn file_term_supports_substring_glob_and_negation() {
+        let path = Path::new("./repo/src/routes/user.test.ts");
+        let matches = |value: &str| file_term_matches(value, path, &[]);
+
+        assert!(matches("routes"));
+        assert!(matches("src/**/*.test.ts"));
+        assert!(matches("*.ts"));
+        assert!(file_term_matches(
+            "/**/user.*.ts",
+            Path::new("/abs/user.test.ts"),
+            &[]
+        ));
+        assert!(!matches("src/*.test.ts"));
+        assert!(!matches("vendor/**"));
+
+        assert!(matches("!vendor/**"));
+        assert!(!matches("!src/**"));
+        assert!(!matches("!routes"));
+
+        assert!(matches("=repo/src/routes/user.test.ts"));
+        assert!(!matches("=src/routes/user.test.ts"));
+    }
+
+    #[test]
+    fn file_globs_match_below_the_search_root() {
+        let roots = [PathBuf::from("src/cli")];
+        let path = Path::new("src/cli/args.rs");
+
+        assert!(file_term_matches("!cli/**", path, &roots));
+        assert!(!file_term_matches("src/**", path, &roots));
+        assert!(file_term_matches("*.rs", path, &roots));
+        assert!(file_term_matches("/**/cli/*.rs", Path::new("/repo/src/cli/args.rs"), &roots));
+
+        let roots = [PathBuf::from("src")];
+        assert!(!file_term_matches("!cli/**", path, &roots));
+        assert!(file_term_matches("cli/args", path, &roots));
+        assert!(file_term_matches(
+            "!src/**",
+            Path::new("src/cli/args.rs"),
+            &[PathBuf::from("src/cli/args.rs")]
+        ));
+    }
+
+    #[test]
+    fn file_substrings_and_globs_see_the_same_path() {
+        let roots = [PathBuf::from("rv")];
+        let path = Path::new("rv/src/main.rs");
+
+        assert!(file_term_matches("src/*.rs", path, &roots));
+        assert!(file_term_matches("src/main", path, &roots));
+        assert!(!file_term_matches("rv/src/*.rs", path, &roots));
+        assert!(!file_term_matches("rv/src", path, &roots));
+    }
+
+    #[test]
+    fn parse_negated_file_glob_term() {
+        let expr = parse_query_expr("name:add file:!vendor/**").expect("expr");
+        let path_ok = Path::new("src/math.ts");
+        let path_vendor = Path::new("vendor/lib/math.ts");
+
+        assert!(path_matches_file_terms(&expr, path_ok, &[]));
+        assert!(!path_matches_file_terms(&expr, path_vendor, &[]));
+    }
+
+    #[test]
+    fn check_file_globs_reports_invalid_patterns() {
+        let valid = parse_query_expr("file:src/**/*.{ts,tsx}").expect("expr");
+        assert!(check_file_globs(&valid).is_ok());
+
+        let invalid = parse_query_expr("file:src/[ab").expect("expr");
+        assert!(check_file_globs(&invalid).is_err());
+    }
+
+    #[test]
+    fn parse_negated_is_term() {
+        let expr = parse_query_expr("-is:test kind:function").expect("expr");
+        let QueryExpr::And(clauses) = expr else {
+            panic!("expected AND expression");
+        };
+        assert_eq!(
+            clauses[0],
+            QueryExpr::Term(QueryTerm {
+                field: QueryField::Is,
+                value: "!test".to_string(),
+            })
+        );
+
+        assert!(is_term_matches("test", true));
+        assert!(!is_term_matches("test", false));
+        assert!(is_term_matches("!test", false));
+        assert!(!is_term_matches("!test", true));
+        assert!(!is_term_matches("generated", true));
+        assert!(!is_term_matches("!generated", false));
+    }
+
+    #[test]
+    fn attr_term_matches_paths_and_arguments() {
+        let annotations = vec![
+            "derive(Debug, Clone, Serialize)".to_string(),
+            "serde(rename_all = \"camelCase\")".to_string(),
+            "cfg(test)".to_string(),
+        ];
+
+        assert!(attr_term_matches("derive", &annotations));
+        assert!(attr_term_matches("derive(Serialize)", &annotations));
+        assert!(attr_term_matches("derive(Clone,Debug)", &annotations));
+        assert!(!attr_term_matches("derive(Deserialize)", &annotations));
+        assert!(attr_term_matches("serde(rename_all=camelCase)", &annotations));
+        assert!(!attr_term_matches("test", &annotations));
+        assert!(!attr_term_matches("inline", &annotations));
+
+        assert!(attr_term_matches("=cfg(test)", &annotations));
+        assert!(attr_term_matches("=derive(Debug,Clone,Serialize)", &annotations));
+        assert!(!attr_term_matches("=derive", &annotations));
+        assert!(!attr_term_matches("derive", &[]));
+    }
+
+    #[test]
+    fn deprecated_and_doc_tag_terms() {
+        let expr = parse_query_expr("deprecated: doc-tag:@throws").expect("expr");
+        let QueryExpr::And(clauses) = expr else {
+            panic!("expected AND expression");
+        };
+        assert_eq!(
+            clauses,
+            vec![
+                QueryExpr::Term(QueryTerm {
+                    field: QueryField::Deprecated,
+                    value: String::new(),
+                }),
+                QueryExpr::Term(QueryTerm {
+                    field: QueryField::DocTag,
+                    value: "@throws".to_string(),
+                }),
+            ]
+        );
+
+        assert!(deprecated_term_matches("", Some("")));
+        assert!(deprecated_term_matches("true", Some("use open")));
+        assert!(!deprecated_term_matches("", None));
+        assert!(deprecated_term_matches("false", None));
+        assert!(!deprecated_term_matches("false", Some("")));
+        assert!(deprecated_term_matches("open", Some("use open")));
+        assert!(deprecated_term_matches("=use open", Some("use open")));
+        assert!(!deprecated_term_matches("=open", Some("use open")));
+        assert!(!deprecated_term_matches("open", None));
     }
 }
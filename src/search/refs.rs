@@ -0,0 +1,427 @@
+//! Identifier usages for `refs:` query terms.
+//!
+//! References are name-based: every identifier node whose text equals
+//! the requested name is a usage, except the name being declared by a
+//! function, type, module, or field definition. Each usage is
+//! classified from its position in the syntax tree:
+//!
+//! - `import` – inside an import, `use`, or `using` declaration.
+//! - `call` – the callee of a call, `new`, or macro invocation,
+//!   including the trailing name of `a.b()` / `a::b()`.
+//! - `write` – an assignment target, increment/decrement operand,
+//!   struct literal field, or a variable or parameter binding.
+//! - `type` – a type identifier, a base class, or a path qualifier such
+//!   as `Widget` in `Widget::new()`.
+//! - `read` – everything else (reads, field accesses, passing a
+//!   function as a value).
+//!
+//! Node kinds are shared closely enough between the TypeScript,
+//! JavaScript, C++, and Rust grammars that one rule set covers all of
+//! them.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use tree_sitter::Node;
+
+use crate::language::backend_for_path;
+use crate::models::{MatchSpan, QueryExpr, QueryField, ReferenceKind, SearchConfig, SearchMatch};
+use crate::search::ast::{language_allowed, FileOutcome};
+use crate::search::query::exclusive_term;
+
+/// Extract the name from the `refs:` term of a query expression.
+///
+/// Returns `Ok(None)` when the query has no `refs:` term; like `ast:`,
+/// it may only be combined with `file:` and `language:` filters.
+pub(crate) fn refs_target(expr: &QueryExpr) -> Result<Option<&str>> {
+    exclusive_term(expr, QueryField::References, "refs")
+}
+
+/// Definition nodes whose `name` field declares (rather than uses) the
+/// identifier.
+const DEFINITION_KINDS: &[&str] = &[
+    // Rust
+    "function_item",
+    "function_signature_item",
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "type_item",
+    "mod_item",
+    "const_item",
+    "static_item",
+    "enum_variant",
+    "field_declaration",
+    "macro_definition",
+    // TypeScript / JavaScript
+    "function_declaration",
+    "generator_function_declaration",
+    "function_expression",
+    "function_signature",
+    "class_declaration",
+    "abstract_class_declaration",
+    "class",
+    "interface_declaration",
+    "type_alias_declaration",
+    "enum_declaration",
+    "method_definition",
+    "method_signature",
+    "abstract_method_signature",
+    "public_field_definition",
+    "internal_module",
+    "module",
+    // C++
+    "namespace_definition",
+    "alias_declaration",
+];
+
+/// C++ type specifiers, which only define a type when they have a body
+/// (`struct Foo { ... }` as opposed to `struct Foo x;`).
+const SPECIFIER_KINDS: &[&str] = &[
+    "class_specifier",
+    "struct_specifier",
+    "union_specifier",
+    "enum_specifier",
+];
+
+/// Declarations whose contents are imports.
+const IMPORT_KINDS: &[&str] = &[
+    "import_statement",
+    "use_declaration",
+    "extern_crate_declaration",
+    "using_declaration",
+];
+
+/// Access expressions and the field holding their trailing name, so
+/// that `b` in `a.b` / `a::b` is classified by the position of the
+/// whole expression.
+const ACCESS_FIELDS: &[(&str, &str)] = &[
+    ("member_expression", "property"),
+    ("field_expression", "field"),
+    ("scoped_identifier", "name"),
+    ("scoped_type_identifier", "name"),
+    ("qualified_identifier", "name"),
+    ("generic_function", "function"),
+    ("template_function", "name"),
+];
+
+/// Find up to `cap` usages of `name` in `path`.
+pub(crate) fn search_file(
+    path: &Path,
+    name: &str,
+    expr: &QueryExpr,
+    config: &SearchConfig,
+    cap: usize,
+) -> FileOutcome {
+    let Some(backend) = backend_for_path(path) else {
+        return FileOutcome::Skipped;
+    };
+    if !language_allowed(expr, config, backend) {
+        return FileOutcome::Skipped;
+    }
+    let Ok(source) = fs::read_to_string(path) else {
+        return FileOutcome::Skipped;
+    };
+    if !source.contains(name) {
+        return FileOutcome::Matched(Vec::new());
+    }
+    let Ok(tree) = backend.parse_syntax_tree(path, &source) else {
+        return FileOutcome::Skipped;
+    };
+
+    let mut matches = Vec::new();
+    let mut cursor = tree.root_node().walk();
+
+    'walk: loop {
+        let node = cursor.node();
+        if node.child_count() == 0
+            && node.kind().ends_with("identifier")
+            && &source[node.byte_range()] == name
+        {
+            if let Some(kind) = classify(node) {
+                if config.reference_kinds.is_empty() || config.reference_kinds.contains(&kind) {
+                    matches.push(build_match(path, &source, node, kind, config));
+                    if matches.len() >= cap {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+
+    FileOutcome::Matched(matches)
+}
+
+/// Classify an identifier usage, or return `None` when the identifier
+/// is the name of a definition.
+fn classify(node: Node) -> Option<ReferenceKind> {
+    let Some(parent) = node.parent() else {
+        return Some(ReferenceKind::Read);
+    };
+
+    if is_definition_name(node, parent) {
+        return None;
+    }
+
+    if has_ancestor(node, IMPORT_KINDS) || is_reexport(node) {
+        return Some(ReferenceKind::Import);
+    }
+
+    let outer = access_root(node);
+    if let Some(holder) = outer.parent() {
+        if is_field(holder, "call_expression", "function", outer)
+            || is_field(holder, "new_expression", "constructor", outer)
+            || is_field(holder, "macro_invocation", "macro", outer)
+        {
+            return Some(ReferenceKind::Call);
+        }
+        if is_write_target(holder, outer) {
+            return Some(ReferenceKind::Write);
+        }
+    }
+
+    if node.kind() == "type_identifier"
+        || matches!(parent.kind(), "extends_clause" | "base_class_clause")
+        || is_field(parent, "scoped_identifier", "path", node)
+        || is_field(parent, "scoped_type_identifier", "path", node)
+        || is_field(parent, "qualified_identifier", "scope", node)
+    {
+        return Some(ReferenceKind::Type);
+    }
+
+    Some(ReferenceKind::Read)
+}
+
+fn is_definition_name(node: Node, parent: Node) -> bool {
+    let is_name = same_node(parent.child_by_field_name("name"), node);
+    if DEFINITION_KINDS.contains(&parent.kind()) && is_name {
+        return true;
+    }
+    if SPECIFIER_KINDS.contains(&parent.kind()) {
+        return is_name && parent.child_by_field_name("body").is_some();
+    }
+
+    // C++ declarators name the function/field/typedef being declared.
+    matches!(
+        parent.kind(),
+        "function_declarator" | "field_declaration" | "type_definition"
+    ) && same_node(parent.child_by_field_name("declarator"), node)
+}
+
+fn is_write_target(holder: Node, outer: Node) -> bool {
+    match holder.kind() {
+        "assignment_expression"
+        | "augmented_assignment_expression"
+        | "compound_assignment_expr" => same_node(holder.child_by_field_name("left"), outer),
+        "update_expression" => true,
+        "variable_declarator" => same_node(holder.child_by_field_name("name"), outer),
+        "let_declaration" | "parameter" | "required_parameter" | "optional_parameter" => {
+            same_node(holder.child_by_field_name("pattern"), outer)
+        }
+        "init_declarator" => same_node(holder.child_by_field_name("declarator"), outer),
+        "field_initializer" => same_node(holder.child_by_field_name("field"), outer),
+        _ => false,
+    }
+}
+
+/// `export { x } from "./mod"` re-exports count as imports.
+fn is_reexport(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if ancestor.kind() == "export_statement" {
+            return ancestor.child_by_field_name("source").is_some();
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
+/// Climb access expressions while `node` is their trailing name.
+fn access_root(node: Node) -> Node {
+    let mut current = node;
+    while let Some(parent) = current.parent() {
+        let trailing = ACCESS_FIELDS
+            .iter()
+            .any(|(kind, field)| is_field(parent, kind, field, current));
+        if !trailing {
+            break;
+        }
+        current = parent;
+    }
+    current
+}
+
+fn has_ancestor(node: Node, kinds: &[&str]) -> bool {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if kinds.contains(&ancestor.kind()) {
+            return true;
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
+fn is_field(parent: Node, kind: &str, field: &str, child: Node) -> bool {
+    parent.kind() == kind && same_node(parent.child_by_field_name(field), child)
+}
+
+fn same_node(candidate: Option<Node>, node: Node) -> bool {
+    candidate.is_some_and(|candidate| candidate.id() == node.id())
+}
+
+fn build_match(
+    path: &Path,
+    source: &str,
+    node: Node,
+    kind: ReferenceKind,
+    config: &SearchConfig,
+) -> SearchMatch {
+    let start = node.start_byte();
+    let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |idx| start + idx);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+
+    SearchMatch {
+        path: path.to_path_buf(),
+        line: node.start_position().row as u32 + 1,
+        column: Some(node.start_position().column as u32 + 1),
+        snippet: (config.max_lines != Some(0)).then(|| line.to_string()),
+        spans: vec![MatchSpan::in_text(
+            line,
+            start - line_start,
+            node.end_byte() - line_start,
+        )],
+        enclosing: None,
+        bindings: Default::default(),
+        capture: None,
+        reference_kind: Some(kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::backend_for_language;
+
+    fn kinds(language: &str, file: &str, source: &str, name: &str) -> Vec<(u32, ReferenceKind)> {
+        let backend = backend_for_language(language).expect("backend");
+        let tree = backend
+            .parse_syntax_tree(Path::new(file), source)
+            .expect("tree");
+        let mut out = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.child_count() == 0 && &source[node.byte_range()] == name {
+                if let Some(kind) = classify(node) {
+                    out.push((node.start_position().row as u32 + 1, kind));
+                }
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        out.sort_by_key(|(line, _)| *line);
+        out
+    }
+
+    #[test]
+    fn classifies_rust_usages() {
+        let source = "use crate::shapes::Widget;\n\
+                      struct Widget { value: i32 }\n\
+                      fn make() -> Widget { Widget { value: 1 } }\n\
+                      fn run(w: &mut Widget) {\n    \
+                      let f = Widget::new;\n    \
+                      Widget::new();\n    \
+                      w.value = 2;\n    \
+                      let v = w.value;\n}\n";
+        assert_eq!(
+            kinds("rust", "lib.rs", source, "Widget"),
+            vec![
+                (1, ReferenceKind::Import),
+                (3, ReferenceKind::Type),
+                (3, ReferenceKind::Type),
+                (4, ReferenceKind::Type),
+                (5, ReferenceKind::Type),
+                (6, ReferenceKind::Type),
+            ]
+        );
+        assert_eq!(
+            kinds("rust", "lib.rs", source, "value"),
+            vec![
+                (3, ReferenceKind::Write),
+                (7, ReferenceKind::Write),
+                (8, ReferenceKind::Read),
+            ]
+        );
+        assert_eq!(
+            kinds("rust", "lib.rs", source, "new"),
+            vec![(5, ReferenceKind::Read), (6, ReferenceKind::Call)]
+        );
+    }
+
+    #[test]
+    fn classifies_typescript_usages() {
+        let source = "import { helper } from './util';\n\
+                      export function helper2(x: Config): void {\n  \
+                      helper(x);\n  \
+                      const cb = helper;\n  \
+                      counter++;\n  \
+                      counter = helper.length;\n  \
+                      new Config();\n}\n\
+                      class Local extends Config {}\n";
+        assert_eq!(
+            kinds("typescript", "a.ts", source, "helper"),
+            vec![
+                (1, ReferenceKind::Import),
+                (3, ReferenceKind::Call),
+                (4, ReferenceKind::Read),
+                (6, ReferenceKind::Read),
+            ]
+        );
+        assert_eq!(
+            kinds("typescript", "a.ts", source, "counter"),
+            vec![(5, ReferenceKind::Write), (6, ReferenceKind::Write)]
+        );
+        assert_eq!(
+            kinds("typescript", "a.ts", source, "Config"),
+            vec![
+                (2, ReferenceKind::Type),
+                (7, ReferenceKind::Call),
+                (9, ReferenceKind::Type),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_cpp_definitions() {
+        let source = "struct Point;\n\
+                      struct Point { int x; };\n\
+                      int area(Point p);\n\
+                      int area(Point p) { return p.x * area(p); }\n";
+        assert_eq!(
+            kinds("cpp", "a.cpp", source, "area"),
+            vec![(4, ReferenceKind::Call)]
+        );
+        assert_eq!(
+            kinds("cpp", "a.cpp", source, "Point"),
+            vec![
+                (1, ReferenceKind::Type),
+                (3, ReferenceKind::Type),
+                (4, ReferenceKind::Type),
+            ]
+        );
+    }
+}
@@ -0,0 +1,599 @@
+//! Text-mode file scanning.
+//!
+//! Files are read through `grep-searcher`, which gives text mode the
+//! same I/O behavior as ripgrep: binary files are skipped, UTF-16
+//! files with a byte-order mark (and any explicitly configured
+//! encoding such as Latin-1) are transcoded to UTF-8, large files are
+//! memory-mapped when that is faster, and invalid UTF-8 no longer
+//! ends the scan of a file.
+//!
+//! In line mode a regex built from the pattern's literals is only a
+//! prefilter; each candidate line is still checked with the DSL-aware
+//! `line_match_ranges` logic so `--literal`, `=exact`, and AND/OR
+//! terms behave exactly as before. Every occurrence on a matching line
+//! is reported as a span. In `--multiline` mode the pattern is a
+//! regular expression that may span lines.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use grep_matcher::Matcher;
+use grep_regex::{RegexMatcher, RegexMatcherBuilder};
+use grep_searcher::{
+    BinaryDetection, Encoding, MmapChoice, Searcher, SearcherBuilder, Sink, SinkContext,
+    SinkMatch,
+};
+
+use crate::language::{backend_for_path, syntax_scope_at};
+use crate::models::{
+    ContextKind, EnclosingSymbol, MatchSpan, QueryExpr, QueryField, SearchConfig, SearchMatch,
+    Symbol, SyntaxScope,
+};
+
+/// A compiled text search shared by all worker threads.
+pub(crate) struct TextSearcher {
+    matcher: RegexMatcher,
+    builder: SearcherBuilder,
+    multiline: bool,
+}
+
+impl TextSearcher {
+    /// Build the matcher and searcher configuration for a text search.
+    ///
+    /// `text_only_expr` is the parsed DSL expression when the pattern
+    /// consists only of content (and `file:`) terms.
+    pub(crate) fn new(config: &SearchConfig, text_only_expr: Option<&QueryExpr>) -> Result<Self> {
+        let matcher = if config.multiline {
+            RegexMatcherBuilder::new()
+                .multi_line(true)
+                .build(&config.pattern)
+                .with_context(|| format!("invalid multiline pattern: {}", config.pattern))?
+        } else {
+            let mut literals = Vec::new();
+            match text_only_expr {
+                Some(expr) => collect_content_literals(expr, &mut literals),
+                None => literals.push(config.pattern.as_str()),
+            }
+            RegexMatcherBuilder::new()
+                .line_terminator(Some(b'\n'))
+                .build_literals(&literals)
+                .context("failed to build text matcher")?
+        };
+
+        let mut builder = SearcherBuilder::new();
+        builder
+            .line_number(true)
+            .multi_line(config.multiline)
+            .binary_detection(BinaryDetection::quit(b'\x00'));
+
+        // SAFETY: memory maps are only read for the duration of a single
+        // search. As with ripgrep, a file truncated concurrently by
+        // another process may cause a SIGBUS; `auto` limits mmap use to
+        // cases where it is expected to be faster than buffered reads.
+        builder.memory_map(unsafe { MmapChoice::auto() });
+
+        if let Some(label) = &config.encoding {
+            let encoding = Encoding::new(label)
+                .with_context(|| format!("unsupported text encoding: {label}"))?;
+            builder.encoding(Some(encoding));
+        }
+
+        Ok(Self {
+            matcher,
+            builder,
+            multiline: config.multiline,
+        })
+    }
+
+    /// Scan a single file, returning up to `cap` matches in file order.
+    ///
+    /// Unreadable files yield no matches, mirroring how the walker
+    /// skips entries it cannot access.
+    pub(crate) fn search_file(
+        &self,
+        path: &Path,
+        config: &SearchConfig,
+        text_only_expr: Option<&QueryExpr>,
+        cap: usize,
+    ) -> Vec<SearchMatch> {
+        let mut searcher = self.builder.build();
+        let mut sink = MatchSink {
+            path,
+            config,
+            text_only_expr,
+            matcher: &self.matcher,
+            multiline: self.multiline,
+            cap,
+            matches: Vec::new(),
+        };
+
+        let _ = searcher.search_path(&self.matcher, path, &mut sink);
+        sink.matches
+    }
+
+    /// The full text of `path` as this searcher decodes it (UTF-16 with
+    /// a byte-order mark or the configured encoding, transcoded to
+    /// UTF-8), so match positions line up with it. `None` when the
+    /// file cannot be read.
+    pub(crate) fn decoded_source(&self, path: &Path) -> Option<String> {
+        let mut builder = self.builder.clone();
+        builder.passthru(true);
+        let mut sink = SourceSink(Vec::new());
+        builder
+            .build()
+            .search_path(&self.matcher, path, &mut sink)
+            .ok()?;
+        Some(String::from_utf8_lossy(&sink.0).into_owned())
+    }
+}
+
+/// Collects every line reported by a passthru search.
+struct SourceSink(Vec<u8>);
+
+impl Sink for SourceSink {
+    type Error = io::Error;
+
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> io::Result<bool> {
+        self.0.extend_from_slice(mat.bytes());
+        Ok(true)
+    }
+
+    fn context(&mut self, _searcher: &Searcher, context: &SinkContext<'_>) -> io::Result<bool> {
+        self.0.extend_from_slice(context.bytes());
+        Ok(true)
+    }
+}
+
+struct MatchSink<'a> {
+    path: &'a Path,
+    config: &'a SearchConfig,
+    text_only_expr: Option<&'a QueryExpr>,
+    matcher: &'a RegexMatcher,
+    multiline: bool,
+    cap: usize,
+    matches: Vec<SearchMatch>,
+}
+
+impl MatchSink<'_> {
+    fn push(&mut self, line: u32, column: u32, snippet: String, spans: Vec<MatchSpan>) {
+        let snippet = match self.config.max_lines {
+            Some(0) => None,
+            _ => Some(snippet),
+        };
+
+        self.matches.push(SearchMatch {
+            path: self.path.to_path_buf(),
+            line,
+            column: Some(column),
+            snippet,
+            spans,
+            enclosing: None,
+            bindings: Default::default(),
+            capture: None,
+            reference_kind: None,
+        });
+    }
+
+    fn matched_line(&mut self, line_number: u32, bytes: &[u8]) {
+        let text = String::from_utf8_lossy(bytes);
+        let line = trim_line_terminator(&text);
+
+        let ranges = match self.text_only_expr {
+            Some(expr) => line_match_ranges(expr, line, self.config.literal),
+            None => value_ranges(line, &self.config.pattern, self.config.literal),
+        };
+
+        let Some(&(first, _)) = ranges.first() else {
+            return;
+        };
+
+        let spans = ranges
+            .iter()
+            .map(|&(start, end)| MatchSpan::in_text(line, start, end))
+            .collect();
+        self.push(line_number, first as u32 + 1, line.to_string(), spans);
+    }
+
+    fn matched_multiline(&mut self, first_line: u32, bytes: &[u8]) -> io::Result<()> {
+        let mut found = Vec::new();
+        self.matcher
+            .find_iter(bytes, |m| {
+                found.push((m.start(), m.end()));
+                true
+            })
+            .map_err(io::Error::other)?;
+
+        for (start, end) in found {
+            if self.matches.len() >= self.cap {
+                break;
+            }
+
+            let line_start = bytes[..start]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |idx| idx + 1);
+            let last_byte = end.max(start + 1).min(bytes.len());
+            let line_end = bytes[last_byte.saturating_sub(1)..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |idx| last_byte.saturating_sub(1) + idx);
+
+            let line = first_line + bytes[..start].iter().filter(|&&b| b == b'\n').count() as u32;
+            let column = (start - line_start) as u32 + 1;
+
+            let text = String::from_utf8_lossy(&bytes[line_start..line_end]);
+            let mut snippet: Vec<&str> = text.lines().collect();
+            if let Some(max) = self.config.max_lines {
+                snippet.truncate(max);
+            }
+
+            let span = span_in_bytes(&bytes[line_start..], start - line_start, end - line_start);
+            self.push(line, column, snippet.join("\n"), vec![span]);
+        }
+
+        Ok(())
+    }
+}
+
+impl Sink for MatchSink<'_> {
+    type Error = io::Error;
+
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> io::Result<bool> {
+        let line_number = mat.line_number().unwrap_or(0) as u32;
+
+        if self.multiline {
+            self.matched_multiline(line_number, mat.bytes())?;
+        } else {
+            self.matched_line(line_number, mat.bytes());
+        }
+
+        Ok(self.matches.len() < self.cap)
+    }
+}
+
+/// Keep only the occurrences in `matches` whose syntactic region
+/// (code, comment, or string literal) satisfies `allowed`.
+///
+/// Spans are filtered individually, so a line with a hit in code and
+/// another in a trailing comment keeps only the relevant one; matches
+/// left without spans are dropped. `source` is the file's text as
+/// decoded by the text searcher (see `TextSearcher::decoded_source`);
+/// files without a language backend, or whose source is unavailable,
+/// yield no matches.
+pub(crate) fn retain_in_scopes<F>(
+    path: &Path,
+    source: Option<&str>,
+    matches: &mut Vec<SearchMatch>,
+    allowed: F,
+) where
+    F: Fn(SyntaxScope) -> bool,
+{
+    let tree = backend_for_path(path)
+        .zip(source)
+        .and_then(|(backend, source)| backend.parse_syntax_tree(path, source).ok());
+    let Some(tree) = tree else {
+        matches.clear();
+        return;
+    };
+
+    matches.retain_mut(|search_match| {
+        let row = search_match.line.saturating_sub(1) as usize;
+        if search_match.spans.is_empty() {
+            let column = search_match.column.unwrap_or(1).saturating_sub(1) as usize;
+            return allowed(syntax_scope_at(&tree, row, column));
+        }
+
+        search_match
+            .spans
+            .retain(|span| allowed(syntax_scope_at(&tree, row, span.start as usize)));
+        match search_match.spans.first() {
+            Some(first) => {
+                search_match.column = Some(first.start + 1);
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+/// Attach the innermost enclosing symbol to each match in `path`.
+///
+/// Symbols come from the same backend and parent-chain logic used by
+/// symbol mode. `source` is the file's text as the matches were found
+/// in (see `TextSearcher::decoded_source` for text mode). Files
+/// without a language backend, or whose source is unavailable or
+/// cannot be parsed, are left unannotated.
+pub(crate) fn attach_enclosing_symbols(
+    path: &Path,
+    source: Option<&str>,
+    matches: &mut [SearchMatch],
+) {
+    let Some((backend, source)) = backend_for_path(path).zip(source) else {
+        return;
+    };
+    let Ok(parsed) = backend.parse_file(path, source) else {
+        return;
+    };
+    let Ok(symbols) = backend.index_symbols(&parsed) else {
+        return;
+    };
+
+    // Many matches usually share a function; build each annotation once.
+    let mut cache: HashMap<usize, EnclosingSymbol> = HashMap::new();
+
+    for search_match in matches {
+        let column = search_match.column.unwrap_or(1);
+        let Some(idx) = innermost_symbol_at(&symbols, search_match.line, column) else {
+            continue;
+        };
+
+        let enclosing = cache.entry(idx).or_insert_with(|| {
+            let symbol = &symbols[idx];
+            let parent_chain = backend
+                .get_context_snippet(&parsed, symbol, ContextKind::Decl)
+                .map(|context| context.parent_chain)
+                .unwrap_or_default();
+
+            EnclosingSymbol {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                range: symbol.range,
+                parent_chain,
+            }
+        });
+        search_match.enclosing = Some(enclosing.clone());
+    }
+}
+
+/// Index of the smallest symbol whose range contains the 1-based
+/// `line`/`column` position.
+fn innermost_symbol_at(symbols: &[Symbol], line: u32, column: u32) -> Option<usize> {
+    let position = (line, column);
+
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, symbol)| {
+            let range = &symbol.range;
+            (range.start_line, range.start_column) <= position
+                && position < (range.end_line, range.end_column)
+        })
+        .max_by(|(_, a), (_, b)| {
+            (a.range.start_line, a.range.start_column)
+                .cmp(&(b.range.start_line, b.range.start_column))
+                .then_with(|| {
+                    (b.range.end_line, b.range.end_column)
+                        .cmp(&(a.range.end_line, a.range.end_column))
+                })
+        })
+        .map(|(idx, _)| idx)
+}
+
+/// Build a span from byte offsets into possibly invalid UTF-8. The
+/// character and UTF-16 offsets are omitted when the text up to `end`
+/// is not valid UTF-8.
+fn span_in_bytes(text: &[u8], start: usize, end: usize) -> MatchSpan {
+    match std::str::from_utf8(&text[..end]) {
+        Ok(prefix) if prefix.is_char_boundary(start) => MatchSpan::in_text(prefix, start, end),
+        _ => MatchSpan {
+            start: start as u32,
+            end: end as u32,
+            start_char: None,
+            end_char: None,
+            start_utf16: None,
+            end_utf16: None,
+        },
+    }
+}
+
+fn trim_line_terminator(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Collect the literal values of content terms, used to build the
+/// line prefilter. `=exact` terms contribute their text so the
+/// prefilter still finds candidate lines for them.
+fn collect_content_literals<'a>(expr: &'a QueryExpr, out: &mut Vec<&'a str>) {
+    match expr {
+        QueryExpr::Term(term) if term.field == QueryField::Content => {
+            let value = term.value.as_str();
+            out.push(value.strip_prefix('=').unwrap_or(value));
+        }
+        QueryExpr::Term(_) => {}
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+            for clause in clauses {
+                collect_content_literals(clause, out);
+            }
+        }
+    }
+}
+
+/// Whether a term is applied outside line matching: `file:`, `crate:`
+/// and `is:` narrow which files are read and `scope:` filters
+/// occurrences afterwards.
+fn is_file_filter(field: QueryField) -> bool {
+    matches!(
+        field,
+        QueryField::File | QueryField::Crate | QueryField::Is | QueryField::Scope
+    )
+}
+
+/// Whether an expression consists only of `file:`/`crate:`/`is:`/`scope:` terms,
+/// e.g. `scope:comments|strings`.
+fn is_filter_only(expr: &QueryExpr) -> bool {
+    match expr {
+        QueryExpr::Term(term) => is_file_filter(term.field),
+        QueryExpr::And(clauses) | QueryExpr::Or(clauses) => clauses.iter().all(is_filter_only),
+    }
+}
+
+/// Whether the expression is composed only of content-like terms that
+/// can be evaluated directly against a single line of text, plus any
+/// `file:` terms that narrow which files are searched and `scope:`
+/// terms that restrict occurrences to a syntactic region.
+pub(crate) fn expr_is_text_only(expr: &QueryExpr) -> bool {
+    fn only_content_or_file(expr: &QueryExpr) -> bool {
+        match expr {
+            QueryExpr::Term(term) => {
+                term.field == QueryField::Content || is_file_filter(term.field)
+            }
+            QueryExpr::And(clauses) | QueryExpr::Or(clauses) => {
+                clauses.iter().all(only_content_or_file)
+            }
+        }
+    }
+
+    fn has_content(expr: &QueryExpr) -> bool {
+        match expr {
+            QueryExpr::Term(term) => term.field == QueryField::Content,
+            QueryExpr::And(clauses) | QueryExpr::Or(clauses) => clauses.iter().any(has_content),
+        }
+    }
+
+    only_content_or_file(expr) && has_content(expr)
+}
+
+/// Byte ranges of every occurrence of a content-only query expression
+/// within a single line, sorted and with overlaps merged. Empty when
+/// the line does not satisfy the expression. `file:` and `scope:` terms
+/// are applied separately and are ignored here.
+pub(crate) fn line_match_ranges(
+    expr: &QueryExpr,
+    line: &str,
+    literal: bool,
+) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    if !collect_line_ranges(expr, line, literal, &mut ranges) {
+        return Vec::new();
+    }
+
+    ranges.sort_unstable();
+    ranges.dedup();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start < last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Push the ranges matched by `expr` onto `out`, returning whether the
+/// line satisfies the expression.
+fn collect_line_ranges(
+    expr: &QueryExpr,
+    line: &str,
+    literal: bool,
+    out: &mut Vec<(usize, usize)>,
+) -> bool {
+    match expr {
+        QueryExpr::Term(term) if is_file_filter(term.field) => false,
+        QueryExpr::Term(term) => {
+            let value = term.value.as_str();
+            let ranges = match value.strip_prefix('=') {
+                Some(exact) if line == exact => vec![(0, line.len())],
+                Some(_) => Vec::new(),
+                None => value_ranges(line, value, literal),
+            };
+
+            let matched = !ranges.is_empty();
+            out.extend(ranges);
+            matched
+        }
+        QueryExpr::And(clauses) => {
+            let mut ranges = Vec::new();
+            for clause in clauses {
+                if is_filter_only(clause) {
+                    continue;
+                }
+                if !collect_line_ranges(clause, line, literal, &mut ranges) {
+                    return false;
+                }
+            }
+            out.extend(ranges);
+            true
+        }
+        QueryExpr::Or(clauses) => {
+            let mut matched = false;
+            for clause in clauses {
+                matched |= collect_line_ranges(clause, line, literal, out);
+            }
+            matched
+        }
+    }
+}
+
+/// Byte ranges of every non-overlapping occurrence of `value` in
+/// `line`, honoring `--literal` identifier boundaries.
+fn value_ranges(line: &str, value: &str, literal: bool) -> Vec<(usize, usize)> {
+    if literal {
+        literal_identifier_matches(line, value)
+            .map(|start| (start, start + value.len()))
+            .collect()
+    } else if value.is_empty() {
+        vec![(0, 0)]
+    } else {
+        line.match_indices(value)
+            .map(|(start, matched)| (start, start + matched.len()))
+            .collect()
+    }
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+/// Byte offsets of every occurrence of `needle` in `haystack` that is
+/// not part of a longer identifier.
+fn literal_identifier_matches<'a>(
+    haystack: &'a str,
+    needle: &'a str,
+) -> impl Iterator<Item = usize> + 'a {
+    let mut search_start = 0;
+
+    std::iter::from_fn(move || {
+        if needle.is_empty() {
+            return None;
+        }
+
+        while let Some(rel_idx) = haystack[search_start..].find(needle) {
+            let start = search_start + rel_idx;
+            let end = start + needle.len();
+            search_start = end;
+
+            let prev_char = haystack[..start].chars().next_back();
+            let next_char = haystack[end..].chars().next();
+
+            let left_ok = prev_char.is_none_or(|ch| !is_identifier_char(ch));
+            let right_ok = next_char.is_none_or(|ch| !is_identifier_char(ch));
+
+            if left_ok && right_ok {
+                return Some(start);
+            }
+        }
+
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_identifier_matches_respect_word_boundaries() {
+        let first = |haystack, needle| literal_identifier_matches(haystack, needle).next();
+        assert_eq!(first("foo", "foo"), Some(0));
+        assert_eq!(first("foobar", "foo"), None);
+        assert_eq!(first("foo_bar", "foo"), None);
+        assert_eq!(first("bar_foo", "foo"), None);
+        assert_eq!(first("foo()", "foo"), Some(0));
+
+        let all: Vec<usize> = literal_identifier_matches("foo(foo, foobar, foo)", "foo").collect();
+        assert_eq!(all, vec![0, 4, 17]);
+    }
+}
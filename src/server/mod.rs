@@ -5,8 +5,16 @@
 //!
 //! - `POST /v1/search` – accepts a JSON-encoded `SearchConfig` and
 //!   returns a `SearchResult`.
+//! - `POST /v1/query` – accepts a JSON-encoded `SearchConfig` whose
+//!   pattern is a tree-sitter query and returns a `SearchResult`.
 //! - `POST /v1/index` – accepts a JSON-encoded `IndexConfig` and
 //!   returns an `IndexSummary`.
+//! - `POST /v1/deps` – accepts a JSON-encoded `DepsConfig` and
+//!   returns a `DepsResult`.
+//! - `POST /v1/deps/check` – accepts a JSON-encoded `CheckDepsConfig`
+//!   and returns a `CheckDepsResult`.
+//! - `POST /v1/hierarchy` – accepts a JSON-encoded `HierarchyConfig`
+//!   and returns a `HierarchyResult`.
 //! - `GET /v1/health` – simple health check endpoint.
 //!
 //! The server is intentionally thin: it performs JSON
@@ -26,8 +34,8 @@ use serde::Serialize;
 use tokio::net::TcpListener;
 
 use crate::models::{
-    IndexConfig, IndexSummary, SearchConfig, SearchResult, SymbolAttributesRequest,
-    SymbolAttributesResponse,
+    CheckDepsConfig, CheckDepsResult, DepsConfig, DepsResult, HierarchyConfig, HierarchyResult,
+    IndexConfig, IndexSummary, SearchConfig, SearchResult, SymbolAttributesRequest, SymbolAttributesResponse,
 };
 use crate::search::engine;
 
@@ -59,12 +67,6 @@ impl ApiError {
         }
     }
 
-    fn internal(message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::INTERNAL_SERVER_ERROR,
-            message: message.into(),
-        }
-    }
 }
 
 impl From<anyhow::Error> for ApiError {
@@ -98,8 +100,12 @@ pub fn router() -> Router {
     Router::new()
         .route("/v1/health", get(health))
         .route("/v1/search", post(search))
+        .route("/v1/query", post(query))
         .route("/v1/index", post(index))
         .route("/v1/index/info", post(index_info))
+        .route("/v1/deps", post(deps))
+        .route("/v1/deps/check", post(check_deps))
+        .route("/v1/hierarchy", post(hierarchy))
         .route("/v1/symbol/attributes", post(symbol_attributes))
 }
 
@@ -129,6 +135,11 @@ async fn search(Json(config): Json<SearchConfig>) -> Result<Json<SearchResult>,
     Ok(Json(result))
 }
 
+async fn query(Json(config): Json<SearchConfig>) -> Result<Json<SearchResult>, ApiError> {
+    let result = engine::run_query(config).map_err(ApiError::from)?;
+    Ok(Json(result))
+}
+
 async fn index(Json(config): Json<IndexConfig>) -> Result<Json<IndexSummary>, ApiError> {
     let summary = engine::run_index(config).map_err(ApiError::from)?;
     Ok(Json(summary))
@@ -139,6 +150,25 @@ async fn index_info(Json(config): Json<IndexConfig>) -> Result<Json<IndexSummary
     Ok(Json(summary))
 }
 
+async fn deps(Json(config): Json<DepsConfig>) -> Result<Json<DepsResult>, ApiError> {
+    let result = engine::run_deps(config).map_err(ApiError::from)?;
+    Ok(Json(result))
+}
+
+async fn check_deps(
+    Json(config): Json<CheckDepsConfig>,
+) -> Result<Json<CheckDepsResult>, ApiError> {
+    let result = engine::run_check_deps(config).map_err(ApiError::from)?;
+    Ok(Json(result))
+}
+
+async fn hierarchy(
+    Json(config): Json<HierarchyConfig>,
+) -> Result<Json<HierarchyResult>, ApiError> {
+    let result = engine::run_hierarchy(config).map_err(ApiError::from)?;
+    Ok(Json(result))
+}
+
 async fn symbol_attributes(
     Json(request): Json<SymbolAttributesRequest>,
 ) -> Result<Json<SymbolAttributesResponse>, ApiError> {
@@ -169,11 +199,18 @@ mod tests {
             mode: crate::models::SearchMode::Text,
             literal: false,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             limit: None,
             max_lines: None,
             query_expr: None,
             index: None,
             symbol_views: Vec::new(),
+            compile_commands: None,
         };
 
         let Json(result) = search(Json(config)).await.expect("search result");
@@ -206,6 +243,7 @@ mod tests {
             backend: crate::models::IndexBackendKind::File,
             index_path: index_root.clone(),
             language: Some("typescript".to_string()),
+            compile_commands: None,
         };
 
         let Json(summary) = index(Json(config)).await.expect("index summary");
@@ -239,6 +277,7 @@ mod tests {
             backend: crate::models::IndexBackendKind::File,
             index_path: index_root.clone(),
             language: Some("typescript".to_string()),
+            compile_commands: None,
         };
 
         // Build the index once using the core engine.
@@ -279,6 +318,7 @@ mod tests {
             backend: IndexBackendKind::File,
             index_path: index_root,
             language: Some("typescript".to_string()),
+            compile_commands: None,
         };
 
         // Build the index once using the core engine.
@@ -328,11 +368,18 @@ mod tests {
             mode: crate::models::SearchMode::Text,
             literal: false,
             reindex_on_search: false,
+            multiline: false,
+            encoding: None,
+            enclosing: false,
+            call_graph: false,
+            scopes: Vec::new(),
+            reference_kinds: Vec::new(),
             limit: None,
             max_lines: None,
             query_expr: None,
             index: None,
             symbol_views: Vec::new(),
+            compile_commands: None,
         };
 
         let err = search(Json(config)).await.expect_err("expected error");
@@ -0,0 +1,166 @@
+//! Parallel file discovery and ordered per-file processing.
+//!
+//! Search and indexing share the same shape of work: enumerate the
+//! files under a set of roots, then read/parse each file
+//! independently. This module walks roots with `ignore`'s parallel
+//! walker and runs per-file work on the rayon pool, while keeping
+//! results in a deterministic order so `--limit` cut-offs and symbol
+//! indices are stable across runs.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use globset::GlobSet;
+use ignore::{WalkBuilder, WalkState};
+use rayon::prelude::*;
+
+/// Collect the regular files under `paths`, honoring `.gitignore`
+/// rules and the optional include/exclude glob sets.
+///
+/// Files are returned grouped by root in the order the roots were
+/// given, and sorted by path within each root. This matches a serial
+/// depth-first walk with entries sorted by file name.
+pub(crate) fn collect_files(
+    paths: &[PathBuf],
+    include_globs: Option<&GlobSet>,
+    exclude_globs: Option<&GlobSet>,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for root in paths {
+        let found = Mutex::new(Vec::new());
+
+        WalkBuilder::new(root).build_parallel().run(|| {
+            let found = &found;
+            Box::new(move |entry_result| {
+                let entry = match entry_result {
+                    Ok(e) => e,
+                    Err(_) => return WalkState::Continue,
+                };
+
+                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                    return WalkState::Continue;
+                }
+
+                let path = entry.path();
+                if let Some(set) = include_globs {
+                    if !set.is_match(path) {
+                        return WalkState::Continue;
+                    }
+                }
+                if let Some(set) = exclude_globs {
+                    if set.is_match(path) {
+                        return WalkState::Continue;
+                    }
+                }
+
+                found
+                    .lock()
+                    .expect("file list mutex poisoned")
+                    .push(entry.into_path());
+                WalkState::Continue
+            })
+        });
+
+        let mut found = found.into_inner().expect("file list mutex poisoned");
+        found.sort();
+        files.extend(found);
+    }
+
+    files
+}
+
+/// Run `work` over `items` (typically files) on the rayon pool and
+/// hand each item and its result to `consume` in input order.
+///
+/// Items are processed in batches of `batch_size` (or all at once
+/// when `None`). `consume` returns `Ok(false)` to stop early, in which
+/// case no further batches are started; this keeps `--limit` searches
+/// from parsing the whole tree.
+pub(crate) fn for_each_ordered<I, T, W, C>(
+    items: &[I],
+    batch_size: Option<usize>,
+    work: W,
+    mut consume: C,
+) -> Result<()>
+where
+    I: Sync,
+    T: Send,
+    W: Fn(&I) -> T + Sync,
+    C: FnMut(&I, T) -> Result<bool>,
+{
+    let batch_size = batch_size.unwrap_or(items.len()).max(1);
+
+    for batch in items.chunks(batch_size) {
+        let results: Vec<T> = batch.par_iter().map(&work).collect();
+        for (item, result) in batch.iter().zip(results) {
+            if !consume(item, result)? {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Batch size used when work can stop early (a result limit) or when
+/// per-file results should not all be held in memory at once.
+///
+/// Large enough to keep every worker busy, small enough that a
+/// `--limit 10` search does not parse thousands of files.
+pub(crate) fn parallel_batch_size() -> usize {
+    rayon::current_num_threads().max(1) * 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn collect_files_returns_sorted_paths_per_root() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("b")).unwrap();
+        fs::write(root.join("c.txt"), "c").unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("b").join("z.txt"), "z").unwrap();
+
+        let files = collect_files(&[root.to_path_buf()], None, None);
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b/z.txt"),
+                PathBuf::from("c.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn for_each_ordered_preserves_order_and_stops_early() {
+        let files: Vec<PathBuf> = (0..50).map(|i| PathBuf::from(format!("{i:02}"))).collect();
+        let mut seen = Vec::new();
+
+        for_each_ordered(
+            &files,
+            Some(4),
+            |path| path.to_string_lossy().to_string(),
+            |_, name| {
+                seen.push(name);
+                Ok(seen.len() < 10)
+            },
+        )
+        .unwrap();
+
+        let expected: Vec<String> = (0..10).map(|i| format!("{i:02}")).collect();
+        assert_eq!(seen, expected);
+    }
+}
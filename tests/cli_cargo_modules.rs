@@ -0,0 +1,90 @@
+mod common;
+
+use common::{run_json, search_symbols};
+use serde_json::Value;
+
+const REPO: &str = "tests/fixtures/cargo_workspace_repo";
+const CORE: &str = "tests/fixtures/cargo_workspace_repo/crates/core/src";
+const CLI_MAIN: &str = "tests/fixtures/cargo_workspace_repo/crates/cli/src/main.rs";
+
+fn parent_chain(value: &Value) -> Vec<&str> {
+    value["contexts"][0]["parent_chain"]
+        .as_array()
+        .expect("parent_chain")
+        .iter()
+        .map(|node| node["name"].as_str().expect("name"))
+        .collect()
+}
+
+#[test]
+fn cli_search_parent_chain_starts_at_crate_and_module_path() {
+    let value = run_json(&[
+        "search",
+        "name:=open",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+        "--view",
+        "parent",
+    ]);
+    assert_eq!(parent_chain(&value), vec!["acme_core", "storage", "sqlite"]);
+    assert_eq!(value["contexts"][0]["parent_chain"][0]["kind"], "namespace");
+
+    // Inline modules follow the file's module path.
+    let value = run_json(&[
+        "search",
+        "name:=helper",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+        "--view",
+        "parent",
+    ]);
+    assert_eq!(parent_chain(&value), vec!["acme_core", "inline"]);
+
+    // Files no `mod` declaration reaches keep the file name.
+    let value = run_json(&[
+        "search",
+        "name:=unused",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+        "--view",
+        "parent",
+    ]);
+    assert_eq!(parent_chain(&value), vec!["orphan.rs"]);
+}
+
+#[test]
+fn cli_deps_resolves_uses_of_workspace_crates() {
+    let value = run_json(&["deps", CLI_MAIN, "--path", REPO]);
+
+    let imports: Vec<(&str, Option<&str>)> = value["imports"]
+        .as_array()
+        .expect("imports")
+        .iter()
+        .map(|import| {
+            (
+                import["module"].as_str().expect("module"),
+                import["target"].as_str(),
+            )
+        })
+        .collect();
+    let sqlite = format!("{CORE}/storage/sqlite.rs");
+    assert_eq!(
+        imports,
+        vec![
+            ("acme_core::storage::sqlite", Some(sqlite.as_str())),
+            (
+                "commands",
+                Some("tests/fixtures/cargo_workspace_repo/crates/cli/src/commands.rs")
+            ),
+        ]
+    );
+
+    let value = search_symbols("name:=open called-by:=main", REPO);
+    assert_eq!(value["symbols"][0]["called_by"][0]["file"], CLI_MAIN);
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::run_json_status;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const REPO: &str = "tests/fixtures/layers_repo";
+const LAYER_RULE: &str = "src/language/** must not import src/cli/**";
+
+#[test]
+fn cli_check_deps_text_reports_cycles_and_violations_and_fails() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(["check-deps", "--path", REPO, "--rule", LAYER_RULE]);
+    let assert = cmd.assert().failure().code(1);
+    let output = assert.get_output();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    assert!(stdout.starts_with("import cycle (2 files):\n"), "{stdout}");
+    assert!(stdout.contains(
+        "  tests/fixtures/layers_repo/src/core/math.ts:1: import ./format -> tests/fixtures/layers_repo/src/core/format.ts\n"
+    ));
+    assert!(stdout.contains(&format!(
+        "layering violation: {LAYER_RULE}\n  tests/fixtures/layers_repo/src/language/parser.ts:1: import ../cli/flags -> tests/fixtures/layers_repo/src/cli/flags.ts\n"
+    )));
+    assert!(stdout.ends_with("checked 4 files: 1 import cycle, 1 layering violation\n"));
+    assert!(
+        stderr.contains("dependency check failed: 1 import cycle, 1 layering violation"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn cli_check_deps_json_lists_findings() {
+    let (success, value) = run_json_status(&["check-deps", "--path", REPO, "--rule", LAYER_RULE]);
+    assert!(!success);
+
+    assert_eq!(value["version"], "1.0.0");
+    assert_eq!(value["files_checked"], 4);
+
+    let cycles = value["cycles"].as_array().expect("cycles");
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(
+        cycles[0]["files"],
+        serde_json::json!([
+            "tests/fixtures/layers_repo/src/core/format.ts",
+            "tests/fixtures/layers_repo/src/core/math.ts",
+        ])
+    );
+    assert_eq!(cycles[0]["dependencies"].as_array().expect("deps").len(), 2);
+
+    let violations = value["violations"].as_array().expect("violations");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["rule"], LAYER_RULE);
+    assert_eq!(
+        violations[0]["dependency"]["target"],
+        "tests/fixtures/layers_repo/src/cli/flags.ts"
+    );
+}
+
+#[test]
+fn cli_check_deps_passes_without_findings() {
+    let (success, value) = run_json_status(&[
+        "check-deps",
+        "--path",
+        REPO,
+        "--rule",
+        "src/cli/** must not import src/language/**",
+        "--no-cycles",
+    ]);
+
+    assert!(success);
+    assert_eq!(value["cycles"].as_array().expect("cycles").len(), 0);
+    assert_eq!(value["violations"].as_array().expect("violations").len(), 0);
+}
+
+#[test]
+fn cli_check_deps_sarif_output() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "check-deps",
+        "--path",
+        REPO,
+        "--rule",
+        LAYER_RULE,
+        "--format",
+        "sarif",
+    ]);
+    let assert = cmd.assert().failure();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid sarif output");
+
+    assert_eq!(value["version"], "2.1.0");
+    let run = &value["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "symgrep");
+
+    let results = run["results"].as_array().expect("results");
+    let rule_ids: Vec<&str> = results
+        .iter()
+        .map(|result| result["ruleId"].as_str().unwrap())
+        .collect();
+    assert_eq!(rule_ids, vec!["import-cycle", "layering-violation"]);
+
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "tests/fixtures/layers_repo/src/language/parser.ts"
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+}
+
+#[test]
+fn cli_check_deps_reads_rules_from_config() {
+    let tmp = tempdir().expect("tempdir");
+    let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join(REPO);
+    let config = format!(
+        "[check_deps]\npaths = [{:?}]\nrules = [{:?}]\nno_cycles = true\n",
+        repo.display().to_string(),
+        LAYER_RULE
+    );
+    let symgrep_dir = tmp.path().join(".symgrep");
+    fs::create_dir_all(&symgrep_dir).expect("create .symgrep directory");
+    fs::write(symgrep_dir.join("config.toml"), config).expect("write config.toml");
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.current_dir(tmp.path())
+        .args(["check-deps", "--format", "json"]);
+    let output = cmd.output().expect("run symgrep");
+    assert!(!output.status.success());
+    let value: Value = serde_json::from_slice(&output.stdout).expect("valid json output");
+
+    assert_eq!(value["cycles"].as_array().expect("cycles").len(), 0);
+    let violations = value["violations"].as_array().expect("violations");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["rule"], LAYER_RULE);
+}
+
+#[test]
+fn cli_check_deps_rejects_malformed_rules() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "check-deps",
+        "--path",
+        REPO,
+        "--rule",
+        "src/language/** -> src/cli/**",
+    ]);
+    let assert = cmd.assert().failure();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(stderr.contains("invalid dependency rule"), "{stderr}");
+}
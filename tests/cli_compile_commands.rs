@@ -0,0 +1,222 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::run_json;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const REPO: &str = "tests/fixtures/compile_commands_repo";
+const DATABASE: &str = "tests/fixtures/compile_commands_repo/compile_commands.json";
+const MAIN: &str = "tests/fixtures/compile_commands_repo/src/main.cpp";
+
+/// `(module, target)` of each import in a `DepsResult`.
+fn imports(value: &Value) -> Vec<(String, Option<String>)> {
+    value["imports"]
+        .as_array()
+        .expect("imports")
+        .iter()
+        .map(|import| {
+            (
+                import["module"].as_str().expect("module").to_string(),
+                import["target"].as_str().map(str::to_string),
+            )
+        })
+        .collect()
+}
+
+fn import(module: &str, target: Option<&str>) -> (String, Option<String>) {
+    (
+        module.to_string(),
+        target.map(|target| format!("{REPO}/{target}")),
+    )
+}
+
+#[test]
+fn cli_deps_resolves_includes_through_compile_commands() {
+    let value = run_json(&["deps", MAIN, "--path", REPO, "--compile-commands", DATABASE]);
+
+    // `-Iinclude` and `-isystem third_party` resolve both include
+    // styles; `-DUSE_LOGGING` drops the `#else` branch and `-UNO_VECTORS`
+    // keeps the `#ifndef NO_VECTORS` one.
+    assert_eq!(
+        imports(&value),
+        vec![
+            import("geo/shape.h", Some("include/geo/shape.h")),
+            import("mathx/vec.h", Some("third_party/mathx/vec.h")),
+            import("cstdio", None),
+            import("geo/log.h", Some("include/geo/log.h")),
+            import("mathx/vec_ops.h", Some("third_party/mathx/vec_ops.h")),
+        ]
+    );
+}
+
+#[test]
+fn cli_deps_without_compile_commands_keeps_includes_unresolved() {
+    let value = run_json(&["deps", MAIN, "--path", REPO]);
+
+    assert_eq!(
+        imports(&value),
+        vec![
+            import("geo/shape.h", None),
+            import("mathx/vec.h", None),
+            import("cstdio", None),
+            import("geo/log.h", None),
+            import("geo/quiet.h", None),
+            import("mathx/vec_ops.h", None),
+        ]
+    );
+}
+
+#[test]
+fn cli_deps_headers_use_flags_of_nearest_translation_unit() {
+    let value = run_json(&[
+        "deps",
+        "tests/fixtures/compile_commands_repo/include/geo/shape.h",
+        "--path",
+        REPO,
+        "--compile-commands",
+        DATABASE,
+    ]);
+
+    assert_eq!(
+        imports(&value),
+        vec![import("mathx/vec.h", Some("third_party/mathx/vec.h"))]
+    );
+    let importers: Vec<&str> = value["importers"]
+        .as_array()
+        .expect("importers")
+        .iter()
+        .map(|importer| importer["file"].as_str().expect("file"))
+        .collect();
+    assert_eq!(
+        importers,
+        vec!["tests/fixtures/compile_commands_repo/src/legacy.cpp", MAIN]
+    );
+}
+
+#[test]
+fn cli_search_resolves_calls_into_included_headers() {
+    let value = run_json(&[
+        "search",
+        "name:=main",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+        "--compile-commands",
+        DATABASE,
+    ]);
+
+    let targets: Vec<(&str, &str)> = value["symbols"][0]["calls"]
+        .as_array()
+        .expect("calls")
+        .iter()
+        .map(|call| {
+            (
+                call["name"].as_str().expect("name"),
+                call["target_file"].as_str().expect("target_file"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        targets,
+        vec![
+            (
+                "area",
+                "tests/fixtures/compile_commands_repo/include/geo/shape.h"
+            ),
+            (
+                "log_value",
+                "tests/fixtures/compile_commands_repo/include/geo/log.h"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn cli_index_skips_sources_missing_from_compile_commands() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        REPO,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+        "--compile-commands",
+        DATABASE,
+        "--no-server",
+    ]);
+    index_cmd.assert().success();
+
+    let value = run_json(&[
+        "search",
+        "kind:function",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+        "--use-index",
+        "--index-path",
+        db_path,
+    ]);
+    let files: Vec<&str> = value["symbols"]
+        .as_array()
+        .expect("symbols")
+        .iter()
+        .map(|symbol| symbol["file"].as_str().expect("file"))
+        .collect();
+
+    assert!(files.contains(&MAIN), "main.cpp is in the build: {files:?}");
+    assert!(
+        !files.iter().any(|file| file.ends_with("legacy.cpp")),
+        "legacy.cpp is not in the build: {files:?}"
+    );
+    assert!(
+        files
+            .iter()
+            .any(|file| file.ends_with("include/geo/shape.h")),
+        "headers are kept: {files:?}"
+    );
+}
+
+#[test]
+fn cli_deps_reads_compile_commands_from_config() {
+    let tmp = tempdir().expect("tempdir");
+    let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join(REPO);
+    let config = format!(
+        "[cpp]\ncompile_commands = {:?}\n",
+        repo.join("compile_commands.json").display().to_string()
+    );
+    let symgrep_dir = tmp.path().join(".symgrep");
+    fs::create_dir_all(&symgrep_dir).expect("create .symgrep directory");
+    fs::write(symgrep_dir.join("config.toml"), config).expect("write config.toml");
+
+    let main = repo.join("src/main.cpp");
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.current_dir(tmp.path()).args([
+        "deps",
+        main.to_str().unwrap(),
+        "--path",
+        repo.to_str().unwrap(),
+        "--format",
+        "json",
+        "--no-server",
+    ]);
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    let target = value["imports"][0]["target"].as_str().expect("resolved");
+    assert!(
+        Path::new(target).ends_with("include/geo/shape.h"),
+        "unexpected target {target}"
+    );
+}
@@ -187,3 +187,62 @@ no_server = true
         "expected local search output when config sets no_server = true"
     );
 }
+
+#[test]
+fn cli_search_expands_saved_queries_with_parameters() {
+    let (_tmp, repo_root) = copy_fixture_repo("cpp_repo");
+    let symgrep_dir = repo_root.join(".symgrep");
+    fs::create_dir_all(&symgrep_dir).expect("create .symgrep directory");
+
+    let config_toml = r#"
+[search]
+paths = ["."]
+language = "cpp"
+mode = "symbol"
+format = "json"
+
+[queries]
+methods = "kind:method"
+named = "name:=$1"
+"#;
+    fs::write(symgrep_dir.join("config.toml"), config_toml).expect("write config.toml");
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.current_dir(&repo_root);
+    cmd.args(["search", "@methods @named(increment)"]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    assert_eq!(value["query"], "kind:method name:=increment");
+
+    let symbols = value["symbols"].as_array().expect("symbols array");
+    assert_eq!(symbols.len(), 1, "expected exactly one saved-query match");
+    assert_eq!(symbols[0]["name"], "increment");
+    assert_eq!(symbols[0]["kind"], "method");
+}
+
+#[test]
+fn cli_search_reports_saved_query_argument_errors() {
+    let (_tmp, repo_root) = copy_fixture_repo("cpp_repo");
+    let symgrep_dir = repo_root.join(".symgrep");
+    fs::create_dir_all(&symgrep_dir).expect("create .symgrep directory");
+
+    let config_toml = r#"
+[search]
+paths = ["."]
+
+[queries]
+named = "name:=$1"
+"#;
+    fs::write(symgrep_dir.join("config.toml"), config_toml).expect("write config.toml");
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.current_dir(&repo_root);
+    cmd.args(["search", "@named"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("expects 1 argument(s)"));
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::{run_json, run_text};
+use serde_json::Value;
+use tempfile::tempdir;
+
+const TS_DIR: &str = "tests/fixtures/deps_repo/ts";
+
+fn files(list: &Value) -> Vec<(String, u64)> {
+    list.as_array()
+        .expect("array")
+        .iter()
+        .map(|entry| {
+            let file = entry["file"].as_str().expect("file").to_string();
+            (file, entry["depth"].as_u64().unwrap_or(0))
+        })
+        .collect()
+}
+
+#[test]
+fn cli_deps_text_lists_imports_and_importers() {
+    let output = run_text(&[
+        "deps",
+        "tests/fixtures/deps_repo/ts/app.ts",
+        "--path",
+        TS_DIR,
+    ]);
+
+    assert!(output.starts_with("imports (4):\n"), "{output}");
+    assert!(output.contains(
+        "  tests/fixtures/deps_repo/ts/app.ts:2: import ./polyfill -> tests/fixtures/deps_repo/ts/polyfill.ts\n"
+    ));
+    assert!(output.contains("  tests/fixtures/deps_repo/ts/app.ts:3: import express (external)\n"));
+    assert!(
+        output.contains("app.ts:4: require ./logger -> tests/fixtures/deps_repo/ts/logger.ts\n")
+    );
+    assert!(output.contains(
+        "importers (1):\n  tests/fixtures/deps_repo/ts/index.ts:1: export ./app -> tests/fixtures/deps_repo/ts/app.ts\n"
+    ));
+    assert!(!output.contains("transitive"));
+}
+
+#[test]
+fn cli_deps_json_reports_transitive_closure() {
+    let value = run_json(&[
+        "deps",
+        "tests/fixtures/deps_repo/ts/logger.ts",
+        "--path",
+        TS_DIR,
+        "--transitive",
+    ]);
+
+    assert_eq!(value["version"], "1.0.0");
+    assert_eq!(value["file"], "tests/fixtures/deps_repo/ts/logger.ts");
+    assert_eq!(value["imports"].as_array().expect("imports").len(), 0);
+
+    let importers: Vec<(&str, &str)> = value["importers"]
+        .as_array()
+        .expect("importers")
+        .iter()
+        .map(|dep| (dep["file"].as_str().unwrap(), dep["kind"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        importers,
+        vec![
+            ("tests/fixtures/deps_repo/ts/app.ts", "require"),
+            ("tests/fixtures/deps_repo/ts/parser.ts", "import"),
+        ]
+    );
+
+    assert_eq!(files(&value["transitive_imports"]), Vec::new());
+    assert_eq!(
+        files(&value["transitive_importers"]),
+        vec![
+            ("tests/fixtures/deps_repo/ts/app.ts".to_string(), 1),
+            ("tests/fixtures/deps_repo/ts/parser.ts".to_string(), 1),
+            ("tests/fixtures/deps_repo/ts/index.ts".to_string(), 2),
+        ]
+    );
+}
+
+#[test]
+fn cli_deps_reports_rust_mod_and_use_dependencies() {
+    let value = run_json(&[
+        "deps",
+        "tests/fixtures/deps_repo/rust/src/util.rs",
+        "--path",
+        "tests/fixtures/deps_repo/rust",
+    ]);
+
+    // `use super::*` in the inline test module refers to util.rs itself
+    // and `use super::super::net` only compiles into tests; neither is a
+    // dependency.
+    assert_eq!(value["imports"].as_array().expect("imports").len(), 0);
+
+    let importers: Vec<(&str, u64)> = value["importers"]
+        .as_array()
+        .expect("importers")
+        .iter()
+        .map(|dep| (dep["kind"].as_str().unwrap(), dep["line"].as_u64().unwrap()))
+        .collect();
+    // `mod util;` and the `use crate::util::{clamp, wrap}` list, which
+    // collapses into one dependency.
+    assert_eq!(importers, vec![("mod", 4), ("use", 7)]);
+}
+
+#[test]
+fn cli_deps_with_sqlite_index_match_without_index() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        TS_DIR,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+    ]);
+    index_cmd.assert().success();
+
+    let args = [
+        "deps",
+        "tests/fixtures/deps_repo/ts/app.ts",
+        "--path",
+        TS_DIR,
+        "--transitive",
+    ];
+    let without_index = run_json(&args);
+
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let with_index = run_json(&indexed_args);
+
+    assert_eq!(with_index, without_index);
+}
+
+#[test]
+fn cli_deps_errors_on_missing_file() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "deps",
+        "tests/fixtures/deps_repo/ts/missing.ts",
+        "--path",
+        TS_DIR,
+    ]);
+    let assert = cmd.assert().failure();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(stderr.contains("deps file does not exist"), "{stderr}");
+}
@@ -374,3 +374,239 @@ fn cli_follow_literal_requires_exact_name_match() {
         names_literal
     );
 }
+
+#[test]
+fn cli_follow_json_resolves_callers_across_files() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "follow",
+        "name:scale",
+        "--path",
+        "tests/fixtures/cross_file_repo/ts",
+        "--direction",
+        "both",
+        "--format",
+        "json",
+    ]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    assert_eq!(value["version"], "1.4.0");
+    let targets = value["targets"].as_array().expect("targets array");
+    assert_eq!(targets.len(), 1);
+
+    // `app.ts` imports `scale` under the alias `resize`.
+    let callers = targets[0]["callers"].as_array().expect("callers array");
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0]["symbol"]["name"], "run");
+    assert_eq!(
+        callers[0]["symbol"]["file"],
+        "tests/fixtures/cross_file_repo/ts/app.ts"
+    );
+    assert_eq!(callers[0]["call_sites"][0]["line"], 6);
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "follow",
+        "name:run",
+        "--path",
+        "tests/fixtures/cross_file_repo/ts",
+        "--direction",
+        "callees",
+        "--format",
+        "json",
+    ]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    let callees = value["targets"][0]["callees"]
+        .as_array()
+        .expect("callees array");
+    let format = callees
+        .iter()
+        .find(|edge| edge["symbol"]["name"] == "format")
+        .expect("format callee");
+    // Resolved through the `export { format } from` in lib/index.ts.
+    assert_eq!(
+        format["symbol"]["file"],
+        "tests/fixtures/cross_file_repo/ts/lib/format.ts"
+    );
+    assert_eq!(
+        format["call_sites"][0]["file"],
+        "tests/fixtures/cross_file_repo/ts/app.ts"
+    );
+}
+
+fn follow_json(args: &[&str]) -> Value {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.arg("follow")
+        .args(args)
+        .args(["--path", "tests/fixtures/call_chain_repo", "--format", "json"]);
+    let assert = cmd.assert().success();
+    serde_json::from_slice(&assert.get_output().stdout).expect("valid json output")
+}
+
+fn edge_names(edges: &Value) -> Vec<&str> {
+    edges
+        .as_array()
+        .expect("edge array")
+        .iter()
+        .map(|edge| edge["symbol"]["name"].as_str().expect("edge name"))
+        .collect()
+}
+
+#[test]
+fn cli_follow_depth_builds_caller_tree_with_cycle_markers() {
+    let value = follow_json(&["name:=log", "--depth", "4"]);
+    assert_eq!(value["version"], "1.4.0");
+
+    let callers = &value["targets"][0]["callers"];
+    assert_eq!(edge_names(callers), ["handle", "main", "validate"]);
+    assert_eq!(edge_names(&callers[0]["children"]), ["main"]);
+    assert!(callers[1].get("children").is_none());
+
+    // log <- validate <- parse <- {handle <- main, validate (cycle)}
+    let parse = &callers[2]["children"][0];
+    assert_eq!(parse["symbol"]["name"], "parse");
+    assert_eq!(parse["symbol"]["kind"], "function");
+    assert_eq!(edge_names(&parse["children"]), ["handle", "validate"]);
+    assert_eq!(parse["children"][1]["cycle"], true);
+    assert!(parse["children"][1].get("children").is_none());
+    assert_eq!(edge_names(&parse["children"][0]["children"]), ["main"]);
+    assert!(value.get("truncated").is_none());
+}
+
+#[test]
+fn cli_follow_depth_one_keeps_direct_edges_only() {
+    let value = follow_json(&["name:=log"]);
+    let callers = value["targets"][0]["callers"].as_array().expect("callers");
+    assert_eq!(callers.len(), 3);
+    assert!(callers.iter().all(|edge| edge.get("children").is_none()));
+}
+
+#[test]
+fn cli_follow_max_nodes_truncates_tree() {
+    let value = follow_json(&["name:=log", "--depth", "4", "--max-nodes", "2"]);
+    assert_eq!(value["truncated"], true);
+
+    let callers = &value["targets"][0]["callers"];
+    assert_eq!(edge_names(callers), ["handle"]);
+    assert_eq!(edge_names(&callers[0]["children"]), ["main"]);
+}
+
+#[test]
+fn cli_follow_from_to_returns_call_paths() {
+    let value = follow_json(&["--from", "name:=main", "--to", "name:=log"]);
+    assert_eq!(value["query"], "name:=main");
+    assert_eq!(value["to"], "name:=log");
+    assert!(value.get("targets").is_some_and(|t| t.as_array().unwrap().is_empty()));
+
+    let paths: Vec<Vec<&str>> = value["paths"]
+        .as_array()
+        .expect("paths array")
+        .iter()
+        .map(|path| {
+            let mut names = vec![path["from"]["name"].as_str().unwrap()];
+            names.extend(edge_names(&path["steps"]));
+            names
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            vec!["main", "log"],
+            vec!["main", "handle", "log"],
+            vec!["main", "handle", "parse", "validate", "log"],
+        ]
+    );
+
+    let last_step = &value["paths"][2]["steps"][3];
+    assert_eq!(last_step["call_sites"][0]["line"], 17);
+
+    let short = follow_json(&["--from", "name:=main", "--to", "name:=log", "--depth", "2"]);
+    assert_eq!(short["paths"].as_array().expect("paths array").len(), 2);
+}
+
+#[test]
+fn cli_follow_paths_finds_shortest_paths_first_within_max_nodes() {
+    // `main -> handle -> log` is reached first depth-first; two
+    // expanded edges only leave room for the direct call.
+    let value = follow_json(&[
+        "--from",
+        "name:=main",
+        "--to",
+        "name:=log",
+        "--max-nodes",
+        "2",
+    ]);
+    assert_eq!(value["truncated"], true);
+
+    let paths = value["paths"].as_array().expect("paths array");
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0]["from"]["name"], "main");
+    assert_eq!(edge_names(&paths[0]["steps"]), ["log"]);
+}
+
+#[test]
+fn cli_follow_rejects_pattern_with_from() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(["follow", "name:main", "--from", "name:main", "--to", "name:log"]);
+    cmd.assert().failure();
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(["follow", "--from", "name:main"]);
+    cmd.assert().failure();
+}
+
+#[test]
+fn cli_follow_use_index_reads_call_edges_from_the_index() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let source = tmp.path().join("chain.ts");
+    std::fs::write(
+        &source,
+        "export function main(): void {\n  log();\n}\n\nexport function log(): void {}\n",
+    )
+    .expect("write source");
+    let repo = tmp.path().to_str().unwrap();
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        repo,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+        "--no-server",
+    ]);
+    index_cmd.assert().success();
+
+    // Drop the call on disk; only the index still knows about it.
+    std::fs::write(
+        &source,
+        "export function main(): void {}\n\nexport function log(): void {}\n",
+    )
+    .expect("rewrite source");
+
+    let callers = |extra: &[&str]| -> usize {
+        let mut cmd = cargo_bin_cmd!("symgrep");
+        cmd.args(["follow", "name:=log", "--path", repo, "--format", "json"])
+            .args(extra);
+        let assert = cmd.assert().success();
+        let value: Value =
+            serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+        value["targets"][0]["callers"]
+            .as_array()
+            .map_or(0, Vec::len)
+    };
+
+    assert_eq!(callers(&[]), 0);
+    assert_eq!(callers(&["--use-index", "--index-path", db_path]), 1);
+}
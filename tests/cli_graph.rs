@@ -0,0 +1,171 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::{run_json, run_text};
+use serde_json::Value;
+
+fn node_id<'a>(value: &'a Value, name: &str) -> &'a str {
+    value["nodes"]
+        .as_array()
+        .expect("nodes array")
+        .iter()
+        .find(|node| node["name"] == name)
+        .and_then(|node| node["id"].as_str())
+        .unwrap_or_else(|| panic!("node {name} not found"))
+}
+
+#[test]
+fn cli_graph_dot_lists_nodes_and_call_edges() {
+    let output = run_text(&["graph", "name:", "--path", "tests/fixtures/call_chain_repo"]);
+
+    assert!(output.starts_with("digraph calls {\n"));
+    assert!(output.contains("  n0 [label=\"main\"];\n"));
+    assert!(output.contains("  n4 [label=\"log\"];\n"));
+    // main -> handle, main -> log, and the parse <-> validate cycle.
+    for edge in ["n0 -> n1;", "n0 -> n4;", "n2 -> n3;", "n3 -> n2;"] {
+        assert!(output.contains(edge), "missing edge {edge} in:\n{output}");
+    }
+    assert!(output.ends_with("}\n"));
+}
+
+#[test]
+fn cli_graph_json_has_nodes_edges_and_call_sites() {
+    let value = run_json(&["graph", "name:", "--path", "tests/fixtures/call_chain_repo"]);
+
+    assert_eq!(value["version"], "1.1.0");
+    assert_eq!(value["query"], "name:");
+    assert_eq!(value["nodes"].as_array().expect("nodes").len(), 5);
+    assert_eq!(value["nodes"][0]["kind"], "function");
+    assert_eq!(value["nodes"][0]["line"], 1);
+
+    let edges = value["edges"].as_array().expect("edges");
+    assert_eq!(edges.len(), 7);
+    let validate_to_log = edges
+        .iter()
+        .find(|e| e["from"] == node_id(&value, "validate") && e["to"] == node_id(&value, "log"))
+        .expect("validate -> log edge");
+    assert_eq!(validate_to_log["call_sites"][0]["line"], 17);
+    assert_eq!(validate_to_log["call_sites"][0]["column"], 3);
+}
+
+#[test]
+fn cli_graph_keeps_edges_within_selection_unless_external() {
+    let value = run_json(&[
+        "graph",
+        "name:=main",
+        "--path",
+        "tests/fixtures/call_chain_repo",
+    ]);
+    assert_eq!(value["nodes"].as_array().expect("nodes").len(), 1);
+    assert!(value["edges"].as_array().expect("edges").is_empty());
+
+    let value = run_json(&[
+        "graph",
+        "name:=main",
+        "--path",
+        "tests/fixtures/call_chain_repo",
+        "--external",
+    ]);
+    let names: Vec<&str> = value["nodes"]
+        .as_array()
+        .expect("nodes")
+        .iter()
+        .map(|node| node["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["main", "handle", "log"]);
+    assert_eq!(value["nodes"][1]["external"], true);
+    assert!(value["nodes"][1].get("line").is_none());
+    assert_eq!(value["edges"].as_array().expect("edges").len(), 2);
+}
+
+#[test]
+fn cli_graph_mermaid_clusters_by_file_across_imports() {
+    let output = run_text(&[
+        "graph",
+        "name:",
+        "--path",
+        "tests/fixtures/cross_file_repo/ts",
+        "--cluster",
+        "file",
+        "--format",
+        "mermaid",
+    ]);
+
+    assert!(output.starts_with("flowchart LR\n"));
+    assert!(output.contains("  subgraph c2[\"tests/fixtures/cross_file_repo/ts/math.ts\"]\n    n2[\"add\"]\n    n3[\"scale\"]\n  end\n"));
+    // run (app.ts) calls add, format and scale in other files.
+    for edge in ["n0 --> n2", "n0 --> n1", "n0 --> n3"] {
+        assert!(output.contains(edge), "missing edge {edge} in:\n{output}");
+    }
+}
+
+#[test]
+fn cli_graph_clusters_by_namespace_from_parent_chain() {
+    let value = run_json(&[
+        "graph",
+        "name:",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--cluster",
+        "namespace",
+    ]);
+    assert_eq!(value["cluster_by"], "namespace");
+
+    let cluster_of = |name: &str| {
+        value["nodes"]
+            .as_array()
+            .expect("nodes")
+            .iter()
+            .find(|node| node["name"] == name)
+            .map(|node| node["cluster"].clone())
+            .expect("node")
+    };
+    assert_eq!(cluster_of("increment"), "my_mod");
+    assert_eq!(cluster_of("depth"), "deep::level1::level2");
+    assert_eq!(cluster_of("add_with_doc"), Value::Null);
+
+    let output = run_text(&[
+        "graph",
+        "name:",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--cluster",
+        "namespace",
+    ]);
+    assert!(output.contains("  subgraph cluster_0 {\n    label=\"my_mod\";\n"));
+}
+
+#[test]
+fn cli_graph_use_index_matches_scan() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        "tests/fixtures/cross_file_repo/ts",
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+        "--no-server",
+    ]);
+    index_cmd.assert().success();
+
+    let args = [
+        "graph",
+        "name:",
+        "--path",
+        "tests/fixtures/cross_file_repo/ts",
+    ];
+    let scanned = run_json(&args);
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let indexed = run_json(&indexed_args);
+
+    assert!(!scanned["edges"].as_array().expect("edges").is_empty());
+    assert_eq!(indexed["nodes"], scanned["nodes"]);
+    assert_eq!(indexed["edges"], scanned["edges"]);
+}
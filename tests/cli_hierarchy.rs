@@ -0,0 +1,192 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::{run_json, run_text};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const REPO: &str = "tests/fixtures/hierarchy_repo";
+const TS_DIR: &str = "tests/fixtures/hierarchy_repo/ts";
+
+fn edges(value: &Value) -> Vec<(String, String, String, u64)> {
+    value["edges"]
+        .as_array()
+        .expect("edges")
+        .iter()
+        .map(|edge| {
+            (
+                edge["subtype"].as_str().expect("subtype").to_string(),
+                edge["kind"].as_str().expect("kind").to_string(),
+                edge["supertype"].as_str().expect("supertype").to_string(),
+                edge["depth"].as_u64().expect("depth"),
+            )
+        })
+        .collect()
+}
+
+fn edge(sub: &str, kind: &str, sup: &str, depth: u64) -> (String, String, String, u64) {
+    (sub.to_string(), kind.to_string(), sup.to_string(), depth)
+}
+
+#[test]
+fn cli_hierarchy_text_lists_subtypes_by_depth() {
+    let output = run_text(&["hierarchy", "Shape", "--path", TS_DIR]);
+
+    assert_eq!(
+        output,
+        "Shape (interface) tests/fixtures/hierarchy_repo/ts/shapes.ts:1\n\
+         subtypes (3):\n\
+         \x20 Polygon extends Shape  tests/fixtures/hierarchy_repo/ts/shapes.ts:9\n\
+         \x20   Square implements Polygon  tests/fixtures/hierarchy_repo/ts/square.ts:3\n\
+         \x20     Cube extends Square  tests/fixtures/hierarchy_repo/ts/square.ts:13\n"
+    );
+}
+
+#[test]
+fn cli_hierarchy_json_walks_supertypes_across_files() {
+    let value = run_json(&[
+        "hierarchy",
+        "Cube",
+        "--path",
+        TS_DIR,
+        "--direction",
+        "supertypes",
+    ]);
+
+    assert_eq!(value["version"], "1.0.0");
+    assert_eq!(value["symbol"], "Cube");
+    assert_eq!(value["direction"], "supertypes");
+    assert_eq!(value["definitions"][0]["kind"], "class");
+    assert_eq!(
+        value["definitions"][0]["file"],
+        "tests/fixtures/hierarchy_repo/ts/square.ts"
+    );
+    assert_eq!(
+        edges(&value),
+        vec![
+            edge("Cube", "extends", "Square", 1),
+            edge("Square", "extends", "Base", 2),
+            edge("Base", "implements", "Named", 3),
+            edge("Square", "implements", "Polygon", 2),
+            edge("Polygon", "extends", "Shape", 3),
+        ]
+    );
+    assert_eq!(
+        value["edges"][1]["supertype_file"],
+        "tests/fixtures/hierarchy_repo/ts/shapes.ts"
+    );
+}
+
+#[test]
+fn cli_hierarchy_depth_limits_walk() {
+    let value = run_json(&[
+        "hierarchy",
+        "Cube",
+        "--path",
+        TS_DIR,
+        "--direction",
+        "supertypes",
+        "--depth",
+        "1",
+    ]);
+
+    assert_eq!(edges(&value), vec![edge("Cube", "extends", "Square", 1)]);
+}
+
+#[test]
+fn cli_hierarchy_covers_cpp_and_rust() {
+    let cpp = run_json(&[
+        "hierarchy",
+        "Ring",
+        "--path",
+        REPO,
+        "--language",
+        "cpp",
+        "--direction",
+        "supertypes",
+    ]);
+    assert_eq!(
+        edges(&cpp),
+        vec![
+            edge("Ring", "extends", "Circle", 1),
+            edge("Circle", "extends", "Shape", 2),
+            edge("Ring", "extends", "Tracked", 1),
+        ]
+    );
+    assert!(cpp["edges"][2].get("supertype_file").is_none());
+
+    let rust = run_json(&["hierarchy", "Shape", "--path", REPO, "--language", "rust"]);
+    assert_eq!(
+        edges(&rust),
+        vec![
+            edge("Solid", "extends", "Shape", 1),
+            edge("Cube", "implements", "Solid", 2),
+            edge("Cube", "implements", "Shape", 1),
+        ]
+    );
+}
+
+#[test]
+fn cli_hierarchy_with_index_matches_without_index() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        REPO,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+    ]);
+    index_cmd.assert().success();
+
+    let args = ["hierarchy", "Shape", "--path", REPO];
+    let without_index = run_json(&args);
+
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let with_index = run_json(&indexed_args);
+
+    assert_eq!(with_index, without_index);
+    assert_eq!(with_index["edges"].as_array().expect("edges").len(), 8);
+}
+
+#[test]
+fn cli_hierarchy_reads_defaults_from_config() {
+    let tmp = tempdir().expect("tempdir");
+    let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join(TS_DIR);
+    let config = format!(
+        "[hierarchy]\npaths = [{:?}]\ndirection = \"supertypes\"\ndepth = 1\nformat = \"json\"\n",
+        repo.display().to_string()
+    );
+    let symgrep_dir = tmp.path().join(".symgrep");
+    fs::create_dir_all(&symgrep_dir).expect("create .symgrep directory");
+    fs::write(symgrep_dir.join("config.toml"), config).expect("write config.toml");
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.current_dir(tmp.path()).args(["hierarchy", "Square"]);
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    assert_eq!(value["direction"], "supertypes");
+    assert_eq!(
+        edges(&value),
+        vec![
+            edge("Square", "extends", "Base", 1),
+            edge("Square", "implements", "Polygon", 1),
+        ]
+    );
+}
+
+#[test]
+fn cli_hierarchy_reports_unknown_type_without_definitions() {
+    let output = run_text(&["hierarchy", "Missing", "--path", TS_DIR]);
+    assert_eq!(output, "Missing (no definition found)\nsubtypes (0):\n");
+}
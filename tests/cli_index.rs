@@ -23,6 +23,19 @@ fn copy_fixture_repo(name: &str) -> (tempfile::TempDir, PathBuf) {
     (tmp, dst_root)
 }
 
+fn copy_fixture_tree(src: &Path, dst: &Path) {
+    fs::create_dir_all(dst).expect("create dst");
+    for entry in fs::read_dir(src).expect("read src") {
+        let entry = entry.expect("entry");
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type().expect("file_type").is_dir() {
+            copy_fixture_tree(&entry.path(), &dst_path);
+        } else {
+            fs::copy(entry.path(), &dst_path).expect("copy file");
+        }
+    }
+}
+
 fn sort_matches(value: &mut Value) {
     if let Some(array) = value.get_mut("matches").and_then(|v| v.as_array_mut()) {
         array.sort_by(|a, b| {
@@ -146,7 +159,7 @@ fn cli_index_builds_file_backend_layout() {
     let meta_file = fs::File::open(&meta_path).expect("open meta.json");
     let meta: Value = serde_json::from_reader(meta_file).expect("parse meta.json");
 
-    assert_eq!(meta["schema_version"], "2");
+    assert_eq!(meta["schema_version"], "10");
 }
 
 #[test]
@@ -584,6 +597,144 @@ fn cli_search_symbol_cpp_with_sqlite_index_matches_without_index() {
     );
 }
 
+#[test]
+fn cli_search_call_terms_with_sqlite_index_match_without_index() {
+    let tmp = tempdir().expect("tempdir");
+    let repo_root = tmp.path().join("ts");
+    copy_fixture_tree(Path::new("tests/fixtures/cross_file_repo/ts"), &repo_root);
+    let db_path = repo_root.join(".symgrep").join("index.sqlite");
+
+    run_index_sqlite(&repo_root, &db_path);
+
+    let search = |use_index: bool| -> Value {
+        let mut cmd = cargo_bin_cmd!("symgrep");
+        cmd.current_dir(&repo_root);
+        cmd.args([
+            "search",
+            "called-by:run",
+            "--path",
+            ".",
+            "--mode",
+            "symbol",
+            "--view",
+            "meta",
+            "--format",
+            "json",
+        ]);
+        if use_index {
+            cmd.args([
+                "--use-index",
+                "--index-backend",
+                "sqlite",
+                "--index-path",
+                ".symgrep/index.sqlite",
+            ]);
+        }
+        let assert = cmd.assert().success();
+        let mut value: Value =
+            serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+        normalize_search_result(&mut value);
+        value
+    };
+
+    let base_value = search(false);
+    let names: Vec<&str> = base_value["symbols"]
+        .as_array()
+        .expect("symbols array")
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["format", "add", "scale"]);
+
+    assert_eq!(
+        base_value,
+        search(true),
+        "indexed call search should match non-indexed search"
+    );
+
+    // Changing only the callee's file reassigns its symbol ids; the
+    // unchanged caller's edges must still resolve after reindexing.
+    let math = repo_root.join("math.ts");
+    let source = fs::read_to_string(&math).expect("read math.ts");
+    fs::write(&math, format!("// updated\n{source}")).expect("write math.ts");
+    run_index_sqlite(&repo_root, &db_path);
+
+    assert_eq!(
+        search(false),
+        search(true),
+        "indexed call search should match non-indexed search after reindex"
+    );
+}
+
+#[test]
+fn cli_search_call_terms_with_sqlite_index_follow_changed_reexports() {
+    let tmp = tempdir().expect("tempdir");
+    let repo_root = tmp.path().join("ts");
+    fs::create_dir_all(repo_root.join("lib")).expect("create lib");
+    fs::write(
+        repo_root.join("a.ts"),
+        "import { helper } from \"./lib\";\n\nexport function main() {\n  return helper();\n}\n",
+    )
+    .expect("write a.ts");
+    let index_ts = repo_root.join("lib").join("index.ts");
+    fs::write(&index_ts, "export { helper } from \"./impl\";\n").expect("write index.ts");
+    for name in ["impl.ts", "impl2.ts"] {
+        fs::write(
+            repo_root.join("lib").join(name),
+            "export function helper() {\n  return 1;\n}\n",
+        )
+        .expect("write impl");
+    }
+    let db_path = repo_root.join(".symgrep").join("index.sqlite");
+
+    run_index_sqlite(&repo_root, &db_path);
+
+    let called_by_main = |use_index: bool| -> Vec<String> {
+        let mut cmd = cargo_bin_cmd!("symgrep");
+        cmd.current_dir(&repo_root);
+        cmd.args([
+            "search",
+            "called-by:main",
+            "--path",
+            ".",
+            "--mode",
+            "symbol",
+            "--format",
+            "json",
+        ]);
+        if use_index {
+            cmd.args([
+                "--use-index",
+                "--index-backend",
+                "sqlite",
+                "--index-path",
+                ".symgrep/index.sqlite",
+            ]);
+        }
+        let assert = cmd.assert().success();
+        let value: Value =
+            serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+        value["symbols"]
+            .as_array()
+            .expect("symbols array")
+            .iter()
+            .map(|s| format!("{} {}", s["file"].as_str().unwrap(), s["name"]))
+            .collect()
+    };
+
+    assert_eq!(called_by_main(true), called_by_main(false));
+
+    // Only the re-exporting file changes; the unchanged importer's
+    // call must now resolve to the new definition.
+    fs::write(&index_ts, "export { helper } from \"./impl2\";\n").expect("write index.ts");
+    run_index_sqlite(&repo_root, &db_path);
+
+    let expected = called_by_main(false);
+    assert_eq!(expected.len(), 1);
+    assert!(expected[0].ends_with("lib/impl2.ts \"helper\""), "{expected:?}");
+    assert_eq!(called_by_main(true), expected);
+}
+
 #[test]
 fn cli_search_symbol_ts_auto_prefers_existing_sqlite_index() {
     let (_tmp, repo_root) = copy_fixture_repo("ts_js_repo");
@@ -0,0 +1,153 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use common::run_json;
+use serde_json::Value;
+use tempfile::tempdir;
+
+const REPO: &str = "tests/fixtures/jsx_repo";
+const APP: &str = "tests/fixtures/jsx_repo/src/App.tsx";
+
+/// `(name, line, edge_kind)` of each call edge.
+fn edges(edges: &Value) -> Vec<(&str, u64, &str)> {
+    edges
+        .as_array()
+        .expect("edges")
+        .iter()
+        .map(|edge| {
+            (
+                edge["name"].as_str().expect("name"),
+                edge["line"].as_u64().expect("line"),
+                edge["edge_kind"].as_str().unwrap_or("call"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn cli_search_records_jsx_elements_as_render_edges() {
+    let value = run_json(&[
+        "search",
+        "name:=App calls:Button",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+    ]);
+    let app = &value["symbols"][0];
+
+    // Intrinsic elements (`<div>`, `<span>`) and fragments are not
+    // component usages; `<Menu.Item>` is a usage of `Menu`.
+    assert_eq!(
+        edges(&app["calls"]),
+        vec![
+            ("Layout", 14, "render"),
+            ("Button", 15, "render"),
+            ("save", 15, "call"),
+            ("Menu", 16, "render"),
+            ("Menu", 17, "render"),
+        ]
+    );
+    assert_eq!(
+        app["calls"][1]["target_file"],
+        "tests/fixtures/jsx_repo/src/Button.tsx"
+    );
+    // Ordinary calls omit the field.
+    assert!(app["calls"][2].get("edge_kind").is_none());
+
+    let value = run_json(&[
+        "search",
+        "name:=Layout called-by:App",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+    ]);
+    assert_eq!(
+        edges(&value["symbols"][0]["called_by"]),
+        vec![("App", 14, "render")]
+    );
+}
+
+#[test]
+fn cli_follow_callers_of_component_lists_render_sites() {
+    let value = run_json(&[
+        "follow",
+        "name:=Button kind:function",
+        "--path",
+        REPO,
+        "--direction",
+        "callers",
+    ]);
+
+    let callers: Vec<(&str, &str, &str)> = value["targets"][0]["callers"]
+        .as_array()
+        .expect("callers")
+        .iter()
+        .map(|caller| {
+            let site = &caller["call_sites"][0];
+            (
+                caller["symbol"]["name"].as_str().expect("name"),
+                site["file"].as_str().expect("file"),
+                site["edge_kind"].as_str().expect("edge_kind"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        callers,
+        vec![
+            ("App", APP, "render"),
+            (
+                "Toolbar",
+                "tests/fixtures/jsx_repo/src/toolbar.jsx",
+                "render"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn cli_search_render_edges_with_index_match_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    let mut index_cmd = cargo_bin_cmd!("symgrep");
+    index_cmd.args([
+        "index",
+        "--path",
+        REPO,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+        "--no-server",
+    ]);
+    index_cmd.assert().success();
+
+    let args = [
+        "search",
+        "kind:function called-by:App|Toolbar",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+    ];
+    let without_index = run_json(&args);
+
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let with_index = run_json(&indexed_args);
+    assert_eq!(with_index["symbols"], without_index["symbols"]);
+
+    let button = with_index["symbols"]
+        .as_array()
+        .expect("symbols")
+        .iter()
+        .find(|symbol| symbol["name"] == "Button")
+        .expect("Button");
+    assert_eq!(
+        edges(&button["called_by"]),
+        vec![("App", 15, "render"), ("Toolbar", 4, "render")]
+    );
+}
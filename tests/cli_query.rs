@@ -0,0 +1,72 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+
+const ADD_QUERY: &str = r#"(function_item name: (identifier) @name (#match? @name "^add"))"#;
+
+#[test]
+fn cli_query_json_reports_captures() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "query",
+        "--language",
+        "rust",
+        ADD_QUERY,
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--format",
+        "json",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    let value: Value = serde_json::from_str(&output).expect("json");
+
+    assert_eq!(value["version"], "1.16.0");
+    let matches = value["matches"].as_array().expect("matches");
+    let names: Vec<_> = matches
+        .iter()
+        .map(|m| m["capture"]["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["add", "add_with_doc"]);
+
+    let first = &matches[0];
+    assert_eq!(first["capture"]["name"], "name");
+    assert_eq!(first["capture"]["range"]["start_line"], 22);
+    assert_eq!(first["enclosing"]["name"], "add");
+    assert_eq!(first["enclosing"]["kind"], "function");
+}
+
+#[test]
+fn cli_query_text_prints_capture_lines() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "query",
+        ADD_QUERY,
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--limit",
+        "1",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+
+    assert_eq!(
+        output,
+        "tests/fixtures/rust_repo/lib.rs:22:12 (in function add): @name add\n"
+    );
+}
+
+#[test]
+fn cli_query_rejects_invalid_query() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "query",
+        "--language",
+        "rust",
+        "(function_item",
+        "--path",
+        "tests/fixtures/rust_repo",
+    ]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "invalid tree-sitter query for rust",
+    ));
+}
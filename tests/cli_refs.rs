@@ -0,0 +1,64 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+
+#[test]
+fn cli_refs_json_classifies_usages() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "refs",
+        "value",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--format",
+        "json",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    let value: Value = serde_json::from_str(&output).expect("json");
+
+    assert_eq!(value["version"], "1.16.0");
+    let matches = value["matches"].as_array().expect("matches");
+    let found: Vec<_> = matches
+        .iter()
+        .map(|m| {
+            (
+                m["line"].as_u64().unwrap(),
+                m["reference_kind"].as_str().unwrap(),
+                m["enclosing"]["name"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    // Field definitions (lines 5 and 37) are not usages; the `value`
+    // parameter is a binding and therefore a write.
+    assert_eq!(
+        found,
+        vec![
+            (9, "write", "new"),
+            (10, "read", "new"),
+            (14, "read", "increment"),
+            (42, "read", "depth"),
+        ]
+    );
+}
+
+#[test]
+fn cli_refs_kind_filter_and_text_output() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "refs",
+        "Widget",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--kind",
+        "type",
+        "--limit",
+        "1",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+
+    assert_eq!(
+        output,
+        "tests/fixtures/rust_repo/lib.rs:8:10 (in namespace my_mod): [type]     impl Widget {\n"
+    );
+}
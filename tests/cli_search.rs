@@ -1,6 +1,22 @@
+mod common;
+
 use assert_cmd::cargo::cargo_bin_cmd;
+use common::{names, run_json, run_text, search_symbols};
 use serde_json::Value;
 use std::{fs, path::PathBuf};
+use tempfile::tempdir;
+
+const TS_ALIAS_REPO: &str = "tests/fixtures/ts_alias_repo";
+const CARGO_WORKSPACE_REPO: &str = "tests/fixtures/cargo_workspace_repo";
+const DOC_REPO: &str = "tests/fixtures/doc_repo";
+const TEST_CODE_REPO: &str = "tests/fixtures/test_code_repo";
+const TEST_CODE_WEB: &str = "tests/fixtures/test_code_repo/web";
+const ATTRS_REPO: &str = "tests/fixtures/attrs_repo";
+const HIERARCHY_REPO: &str = "tests/fixtures/hierarchy_repo";
+const HIERARCHY_TS: &str = "tests/fixtures/hierarchy_repo/ts";
+const CPP_HEADER_REPO: &str = "tests/fixtures/cpp_header_repo";
+const CPP_HEADER: &str = "tests/fixtures/cpp_header_repo/src/shape.h";
+const CPP_SOURCE: &str = "tests/fixtures/cpp_header_repo/src/shape.cpp";
 
 fn fixture_dir() -> PathBuf {
     PathBuf::from("tests/fixtures/text_repo")
@@ -14,6 +30,22 @@ fn symbol_literal_fixture_dir() -> PathBuf {
     PathBuf::from("tests/fixtures/symbol_literal_repo")
 }
 
+/// Builds a SQLite index of `repo` at `db_path` for `--use-index` comparisons.
+fn build_sqlite_index(repo: &str, db_path: &str) {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "index",
+        "--path",
+        repo,
+        "--index-backend",
+        "sqlite",
+        "--index-path",
+        db_path,
+        "--no-server",
+    ]);
+    cmd.assert().success();
+}
+
 fn sort_matches(value: &mut Value) {
     if let Some(array) = value.get_mut("matches").and_then(|v| v.as_array_mut()) {
         array.sort_by(|a, b| {
@@ -78,7 +110,7 @@ fn normalize_search_result(value: &mut Value) {
     sort_contexts(value);
 }
 
-fn find_symbol_index_by_name<'a>(value: &'a Value, name: &str, language: &str) -> Option<usize> {
+fn find_symbol_index_by_name(value: &Value, name: &str, language: &str) -> Option<usize> {
     let symbols = value.get("symbols")?.as_array()?;
     for (idx, symbol) in symbols.iter().enumerate() {
         if symbol.get("name") == Some(&Value::String(name.to_string()))
@@ -90,6 +122,26 @@ fn find_symbol_index_by_name<'a>(value: &'a Value, name: &str, language: &str) -
     None
 }
 
+/// `(name, file, start line)` of each symbol in a JSON search result.
+fn symbol_locations(value: &Value) -> Vec<(String, String, u64)> {
+    value["symbols"]
+        .as_array()
+        .expect("symbols")
+        .iter()
+        .map(|symbol| {
+            (
+                symbol["name"].as_str().expect("name").to_string(),
+                symbol["file"].as_str().expect("file").to_string(),
+                symbol["range"]["start_line"].as_u64().expect("line"),
+            )
+        })
+        .collect()
+}
+
+fn location(name: &str, file: &str, line: u64) -> (String, String, u64) {
+    (name.to_string(), file.to_string(), line)
+}
+
 fn tokenize_table_output(s: &str) -> Vec<Vec<String>> {
     s.lines()
         .filter(|line| !line.trim().is_empty())
@@ -249,7 +301,7 @@ fn cli_search_json_outputs_structured_result_with_version() {
     let value: Value =
         serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
 
-    assert_eq!(value["version"], "1.2.0");
+    assert_eq!(value["version"], "1.16.0");
     assert_eq!(value["query"], "foo");
 
     let matches = value["matches"].as_array().expect("matches array");
@@ -497,6 +549,131 @@ fn cli_search_text_matches_snapshot_for_text_repo() {
     assert_eq!(actual_lines, expected_lines);
 }
 
+#[test]
+fn cli_search_color_always_highlights_match_spans() {
+    let fixture_dir = fixture_dir();
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "foo",
+        "--path",
+        fixture_dir.to_str().unwrap(),
+        "--color",
+        "always",
+    ]);
+
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    assert!(
+        output.contains("b.txt:2:1: \u{1b}[1;31mfoo\u{1b}[0m bar"),
+        "expected highlighted span, got: {output}"
+    );
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "foo",
+        "--path",
+        fixture_dir.to_str().unwrap(),
+        "--color",
+        "never",
+    ]);
+
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    assert!(!output.contains('\u{1b}'));
+}
+
+#[test]
+fn cli_search_enclosing_reports_containing_symbol() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "self.value",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--enclosing",
+    ]);
+
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    assert!(
+        output.contains("lib.rs:14:13 (in method increment):"),
+        "expected enclosing annotation, got: {output}"
+    );
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "self.value",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--enclosing",
+        "--format",
+        "json",
+    ]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+    assert_eq!(value["matches"][1]["enclosing"]["name"], "depth");
+    assert_eq!(value["matches"][1]["enclosing"]["kind"], "method");
+}
+
+#[test]
+fn cli_search_in_comments_skips_code_occurrences() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "comment",
+        "--path",
+        "tests/fixtures/ts_js_repo/doc_comments.ts",
+        "--in",
+        "code",
+    ]);
+    let assert = cmd.assert().success();
+    let code_output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "comment",
+        "--path",
+        "tests/fixtures/ts_js_repo/doc_comments.ts",
+        "--in",
+        "comments",
+    ]);
+    let assert = cmd.assert().success();
+    let comment_output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+
+    assert!(code_output.trim().is_empty(), "unexpected: {code_output}");
+    assert!(comment_output.contains("doc comment"), "got: {comment_output}");
+}
+
+#[test]
+fn cli_search_ast_pattern_reports_bindings() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "ast:\"$A + $B\" language:rust",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--format",
+        "json",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
+    let value: Value = serde_json::from_str(&output).expect("json");
+
+    let matches = value["matches"].as_array().expect("matches");
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches[0]["line"], 14);
+    assert_eq!(matches[0]["bindings"]["A"]["text"], "self.value");
+    assert_eq!(matches[0]["bindings"]["B"]["text"], "delta");
+    assert_eq!(matches[0]["bindings"]["B"]["range"]["start_column"], 26);
+}
+
 #[test]
 fn cli_search_text_with_context_prints_context_blocks() {
     let fixture_dir = fixture_dir();
@@ -622,7 +799,7 @@ fn cli_search_table_output_for_symbol_includes_context_name() {
 
     let row = &tokens[1];
     assert!(
-        row.get(0)
+        row.first()
             .map(|v| v.ends_with("sample.cpp"))
             .unwrap_or(false),
         "expected FILE column to end with sample.cpp"
@@ -922,11 +1099,11 @@ fn cli_search_symbol_rust_parent_context_includes_module_and_type() {
 
     assert_eq!(names[0], "lib.rs");
     assert!(
-        names.iter().any(|n| *n == "my_mod"),
+        names.contains(&"my_mod"),
         "expected module 'my_mod' in parent_chain"
     );
     assert!(
-        names.iter().any(|n| *n == "Widget"),
+        names.contains(&"Widget"),
         "expected type 'Widget' in parent_chain"
     );
 
@@ -1021,19 +1198,19 @@ fn cli_search_symbol_rust_deeply_nested_modules_build_full_parent_chain() {
 
     assert_eq!(names[0], "lib.rs");
     assert!(
-        names.iter().any(|n| *n == "deep"),
+        names.contains(&"deep"),
         "expected module 'deep' in parent_chain"
     );
     assert!(
-        names.iter().any(|n| *n == "level1"),
+        names.contains(&"level1"),
         "expected module 'level1' in parent_chain"
     );
     assert!(
-        names.iter().any(|n| *n == "level2"),
+        names.contains(&"level2"),
         "expected module 'level2' in parent_chain"
     );
     assert!(
-        names.iter().any(|n| *n == "DeepWidget"),
+        names.contains(&"DeepWidget"),
         "expected type 'DeepWidget' in parent_chain"
     );
 
@@ -1627,11 +1804,11 @@ fn cli_search_symbol_cpp_parent_context_includes_namespace_and_class() {
 
     assert_eq!(names[0], "sample.cpp");
     assert!(
-        names.iter().any(|n| *n == "util"),
+        names.contains(&"util"),
         "expected namespace 'util' in parent_chain"
     );
     assert!(
-        names.iter().any(|n| *n == "Widget"),
+        names.contains(&"Widget"),
         "expected class 'Widget' in parent_chain"
     );
 
@@ -1681,8 +1858,8 @@ fn cli_schema_version_flag_prints_current_version() {
     let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8");
 
     assert!(
-        output.contains("1.2.0"),
-        "schema version output should include 1.2.0"
+        output.contains("1.16.0"),
+        "schema version output should include 1.16.0"
     );
 }
 
@@ -2050,3 +2227,612 @@ fn cli_search_symbol_ts_calls_and_called_by_filters() {
         "callers:foo should return the same symbols as called-by:foo"
     );
 }
+
+#[test]
+fn cli_search_text_file_glob_filters_paths() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "content:foo file:b.*",
+        "--path",
+        fixture_dir().to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    let matches = value["matches"].as_array().expect("matches array");
+    assert_eq!(matches.len(), 1, "expected only the match in b.txt");
+    assert!(matches[0]["path"].as_str().unwrap().ends_with("b.txt"));
+}
+
+#[test]
+fn cli_search_symbol_negated_file_glob_excludes_paths() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "name:=add file:!**/*.cpp",
+        "--path",
+        "tests/fixtures/mixed_repo",
+        "--mode",
+        "symbol",
+        "--format",
+        "json",
+    ]);
+
+    let assert = cmd.assert().success();
+    let value: Value =
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output");
+
+    let symbols = value["symbols"].as_array().expect("symbols array");
+    assert_eq!(symbols.len(), 2, "expected the TS and JS `add` functions");
+    for symbol in symbols {
+        let file = symbol["file"].as_str().unwrap();
+        assert!(!file.ends_with(".cpp"), "unexpected C++ symbol in {file}");
+    }
+}
+
+#[test]
+fn cli_search_file_globs_ignore_the_search_root() {
+    let search = |query: &str, path: &str| -> Value {
+        let mut cmd = cargo_bin_cmd!("symgrep");
+        cmd.args([
+            "search",
+            query,
+            "--mode",
+            "symbol",
+            "--path",
+            path,
+            "--format",
+            "json",
+            "--no-server",
+        ]);
+        let assert = cmd.assert().success();
+        serde_json::from_slice(&assert.get_output().stdout).expect("valid json output")
+    };
+
+    // The root's own `fixtures/` component must not match the glob.
+    let value = search("name:=add file:!fixtures/**", "tests/fixtures/mixed_repo");
+    assert_eq!(value["symbols"].as_array().expect("symbols array").len(), 3);
+
+    let value = search("name:=add file:!mixed_repo/**", "tests/fixtures");
+    for symbol in value["symbols"].as_array().expect("symbols array") {
+        let file = symbol["file"].as_str().unwrap();
+        assert!(!file.contains("mixed_repo"), "unexpected symbol in {file}");
+    }
+}
+
+#[test]
+fn cli_search_filters_symbols_by_extends_and_implements() {
+    assert_eq!(
+        names(&search_symbols("extends:Shape", HIERARCHY_REPO)),
+        vec!["Circle", "Solid", "Polygon"]
+    );
+    assert_eq!(
+        names(&search_symbols("kind:class implements:", HIERARCHY_TS)),
+        vec!["Base", "Square"]
+    );
+    assert_eq!(
+        names(&search_symbols("implements:=Named", HIERARCHY_TS)),
+        vec!["Base"]
+    );
+}
+
+#[test]
+fn cli_search_symbols_report_supertypes_in_json() {
+    let value = search_symbols("name:=Square extends:Base", HIERARCHY_TS);
+
+    let supertypes = &value["symbols"][0]["supertypes"];
+    assert_eq!(supertypes[0]["name"], "Base");
+    assert_eq!(supertypes[0]["kind"], "extends");
+    assert_eq!(
+        supertypes[0]["file"],
+        "tests/fixtures/hierarchy_repo/ts/shapes.ts"
+    );
+    assert_eq!(supertypes[1]["name"], "Polygon");
+    assert_eq!(supertypes[1]["kind"], "implements");
+}
+
+#[test]
+fn cli_search_reports_header_methods_with_out_of_line_definitions() {
+    let value = search_symbols("kind:method|kind:function", CPP_HEADER_REPO);
+
+    // Paired definitions in shape.cpp are folded into the header methods.
+    assert_eq!(
+        symbol_locations(&value),
+        vec![
+            location("total_area", CPP_SOURCE, 20),
+            location("area", CPP_HEADER, 7),
+            location("scale", CPP_HEADER, 8),
+            location("scale", CPP_HEADER, 9),
+            location("draw", CPP_HEADER, 10),
+        ]
+    );
+
+    let symbols = value["symbols"].as_array().expect("symbols");
+    let definition_lines: Vec<Option<u64>> = symbols
+        .iter()
+        .map(|symbol| {
+            let definition = symbol.get("definition")?;
+            assert_eq!(definition["file"], CPP_SOURCE);
+            definition["range"]["start_line"].as_u64()
+        })
+        .collect();
+    assert_eq!(
+        definition_lines,
+        vec![None, Some(5), Some(9), Some(15), None]
+    );
+}
+
+#[test]
+fn cli_search_def_view_returns_out_of_line_body() {
+    let args = [
+        "search",
+        "name:=area",
+        "--mode",
+        "symbol",
+        "--path",
+        CPP_HEADER_REPO,
+        "--view",
+        "def",
+    ];
+    let value = run_json(&args);
+
+    assert_eq!(value["symbols"][0]["file"], CPP_HEADER);
+    assert_eq!(value["symbols"][0]["def_line_count"], 3);
+    let context = &value["contexts"][0];
+    assert_eq!(context["kind"], "def");
+    assert_eq!(context["file"], CPP_SOURCE);
+    assert_eq!(
+        context["snippet"],
+        "double Shape::area() const {\n    return width * height;\n}"
+    );
+
+    let text = run_text(&args);
+    assert!(
+        text.starts_with(&format!(
+            "{CPP_HEADER}:7:5: method area (def: {CPP_SOURCE}:5, 3 lines)\n"
+        )),
+        "unexpected text output: {text}"
+    );
+}
+
+#[test]
+fn cli_search_merges_definition_call_edges_into_declaration() {
+    let value = search_symbols("calls:scale", CPP_HEADER_REPO);
+
+    assert_eq!(
+        symbol_locations(&value),
+        vec![location("scale", CPP_HEADER, 8)]
+    );
+    assert_eq!(value["symbols"][0]["calls"][0]["file"], CPP_SOURCE);
+    assert_eq!(value["symbols"][0]["calls"][0]["line"], 10);
+}
+
+#[test]
+fn cli_search_with_index_pairs_members_like_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    build_sqlite_index(CPP_HEADER_REPO, db_path);
+
+    let args = [
+        "search",
+        "kind:method|kind:function",
+        "--mode",
+        "symbol",
+        "--path",
+        CPP_HEADER_REPO,
+        "--view",
+        "def",
+    ];
+    let without_index = run_json(&args);
+
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let with_index = run_json(&indexed_args);
+
+    assert_eq!(with_index["symbols"], without_index["symbols"]);
+    assert_eq!(with_index["contexts"], without_index["contexts"]);
+}
+
+#[test]
+fn cli_search_module_terms_match_import_specifiers() {
+    let value = run_json(&[
+        "search",
+        "module:@acme/ui",
+        "--mode",
+        "symbol",
+        "--path",
+        TS_ALIAS_REPO,
+    ]);
+    assert_eq!(names(&value), vec!["Button", "toCents"]);
+    assert_eq!(value["symbols"][0]["modules"][0], "@acme/ui");
+    assert_eq!(value["symbols"][1]["modules"][0], "@acme/ui/money");
+
+    let exact = run_json(&[
+        "search",
+        "module:=@acme/ui kind:function",
+        "--mode",
+        "symbol",
+        "--path",
+        TS_ALIAS_REPO,
+    ]);
+    assert_eq!(names(&exact), vec!["Button"]);
+
+    // Symbols are only annotated with modules for `module:` queries.
+    let plain = run_json(&[
+        "search",
+        "name:=Button",
+        "--mode",
+        "symbol",
+        "--path",
+        TS_ALIAS_REPO,
+    ]);
+    assert!(plain["symbols"][0].get("modules").is_none());
+}
+
+#[test]
+fn cli_search_module_terms_with_index_match_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    build_sqlite_index(TS_ALIAS_REPO, db_path);
+
+    let args = [
+        "search",
+        "module:@app|=@config|@acme/logger",
+        "--mode",
+        "symbol",
+        "--path",
+        TS_ALIAS_REPO,
+    ];
+    let without_index = run_json(&args);
+    assert_eq!(
+        names(&without_index),
+        vec!["currency", "formatPrice", "log"]
+    );
+
+    let mut indexed_args = args.to_vec();
+    indexed_args.extend(["--use-index", "--index-path", db_path]);
+    let with_index = run_json(&indexed_args);
+    assert_eq!(with_index["symbols"], without_index["symbols"]);
+}
+
+#[test]
+fn cli_search_crate_terms_filter_by_crate_and_module() {
+    assert_eq!(
+        names(&search_symbols(
+            "crate:acme_core kind:function",
+            CARGO_WORKSPACE_REPO
+        )),
+        vec!["version", "helper", "open"]
+    );
+    // Package names with `-` work too; `=` requires the whole name.
+    assert_eq!(
+        names(&search_symbols(
+            "crate:acme-core kind:function",
+            CARGO_WORKSPACE_REPO
+        )),
+        vec!["version", "helper", "open"]
+    );
+    assert_eq!(
+        names(&search_symbols(
+            "crate:=acme kind:function",
+            CARGO_WORKSPACE_REPO
+        )),
+        vec!["run", "main"]
+    );
+    assert!(names(&search_symbols("crate:=acme_cli", CARGO_WORKSPACE_REPO)).is_empty());
+
+    assert_eq!(
+        names(&search_symbols(
+            "crate:acme_core::storage",
+            CARGO_WORKSPACE_REPO
+        )),
+        vec!["sqlite", "Store", "open"]
+    );
+    assert_eq!(
+        names(&search_symbols(
+            "crate:=acme_core::storage",
+            CARGO_WORKSPACE_REPO
+        )),
+        vec!["sqlite", "Store"]
+    );
+}
+
+#[test]
+fn cli_search_text_mode_honours_crate_terms() {
+    let value = run_json(&[
+        "search",
+        "content:path crate:=acme",
+        "--path",
+        CARGO_WORKSPACE_REPO,
+    ]);
+    let paths: Vec<&str> = value["matches"]
+        .as_array()
+        .expect("matches")
+        .iter()
+        .map(|m| m["path"].as_str().expect("path"))
+        .collect();
+    assert!(!paths.is_empty());
+    assert!(
+        paths.iter().all(|path| path.contains("crates/cli/")),
+        "unexpected paths: {paths:?}"
+    );
+}
+
+#[test]
+fn cli_search_attr_terms_match_rust_attributes() {
+    let search = |query: &str| search_symbols(query, ATTRS_REPO);
+    let value = search("name:=Settings kind:struct");
+    assert_eq!(
+        value["symbols"][0]["attributes"]["annotations"],
+        serde_json::json!([
+            "derive(Debug, Clone, Serialize, Deserialize)",
+            "serde(rename_all = \"camelCase\")"
+        ])
+    );
+    // Attributes inside `impl` blocks attach to the method.
+    let value = search("name:=name kind:method");
+    assert_eq!(
+        value["symbols"][0]["attributes"]["annotations"][0],
+        "inline"
+    );
+
+    assert_eq!(names(&search("attr:derive")), vec!["Settings", "Plain"]);
+    assert_eq!(names(&search("attr:derive(Serialize)")), vec!["Settings"]);
+    assert_eq!(names(&search("attr:test")), vec!["loads"]);
+    assert_eq!(names(&search("attr:cfg(test)")), vec!["tests"]);
+    assert_eq!(names(&search("attr:tokio::main")), vec!["main"]);
+    assert_eq!(names(&search("attr:=derive(Debug)")), vec!["Plain"]);
+    assert_eq!(names(&search("attribute:serde")), vec!["Settings"]);
+}
+
+#[test]
+fn cli_search_attr_terms_match_decorators_and_cpp_attributes() {
+    let search = |query: &str| search_symbols(query, ATTRS_REPO);
+    assert_eq!(names(&search("attr:Component")), vec!["WidgetComponent"]);
+    assert_eq!(names(&search("attr:HostListener(click)")), vec!["onClick"]);
+    assert_eq!(names(&search("attr:Injectable")), vec!["WidgetService"]);
+    assert!(search("name:=render")["symbols"][0]["attributes"]
+        .get("annotations")
+        .is_none());
+
+    assert_eq!(names(&search("attr:nodiscard")), vec!["start"]);
+    assert_eq!(names(&search("attr:=deprecated")), vec!["Engine"]);
+    assert_eq!(names(&search("attr:maybe_unused")), vec!["helper"]);
+    assert_eq!(names(&search("attr:inline")), vec!["name", "helper"]);
+}
+
+#[test]
+fn cli_search_attr_terms_with_index_match_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    build_sqlite_index(ATTRS_REPO, db_path);
+
+    for query in [
+        "attr:derive",
+        "attr:HostListener",
+        "attr:nodiscard",
+        "kind:class",
+    ] {
+        let args = ["search", query, "--mode", "symbol", "--path", ATTRS_REPO];
+        let without_index = run_json(&args);
+
+        let mut indexed_args = args.to_vec();
+        indexed_args.extend(["--use-index", "--index-path", db_path]);
+        let with_index = run_json(&indexed_args);
+        assert_eq!(with_index["symbols"], without_index["symbols"], "{query}");
+    }
+}
+
+#[test]
+fn cli_search_is_test_selects_test_code() {
+    let search = |query: &str| search_symbols(query, TEST_CODE_REPO);
+    let value = search("is:test kind:function|method");
+    assert_eq!(
+        names(&value),
+        vec![
+            "MathTest.Adds",
+            "MathFixture.AddsNegatives",
+            "parse_config_fixture",
+            "parses_pairs",
+            "fetches_remote_config",
+            "run_cli",
+            "runs",
+            "addCase",
+            "seedDatabase",
+        ]
+    );
+    assert_eq!(value["symbols"][0]["attributes"]["is_test"], true);
+
+    // `#[cfg(test)]` modules are test code themselves.
+    assert_eq!(names(&search("is:test kind:namespace")), vec!["tests"]);
+
+    let value = search("-is:test kind:function");
+    assert_eq!(
+        names(&value),
+        vec!["add", "parse_config", "add", "addAll", "configure"]
+    );
+    assert!(value["symbols"][1]["attributes"].get("is_test").is_none());
+    assert_eq!(names(&search("is:!test kind:function")), names(&value));
+}
+
+#[test]
+fn cli_search_lists_tests_after_production_symbols() {
+    // `__tests__/math.test.ts` sorts before `math.ts`.
+    let args = [
+        "search",
+        "name:add",
+        "--mode",
+        "symbol",
+        "--path",
+        TEST_CODE_WEB,
+    ];
+    assert_eq!(names(&run_json(&args)), vec!["add", "addAll", "addCase"]);
+
+    let mut limited = args.to_vec();
+    limited.extend(["--limit", "2"]);
+    let value = run_json(&limited);
+    assert_eq!(names(&value), vec!["add", "addAll"]);
+    assert_eq!(value["summary"]["truncated"], true);
+
+    // Asking for tests keeps file order.
+    assert_eq!(
+        names(&run_json(&[
+            "search",
+            "name:add is:test",
+            "--mode",
+            "symbol",
+            "--path",
+            TEST_CODE_WEB
+        ])),
+        vec!["addCase"]
+    );
+}
+
+#[test]
+fn cli_search_text_mode_filters_test_files() {
+    let paths = |query: &str| -> Vec<String> {
+        let value = run_json(&["search", query, "--path", TEST_CODE_REPO]);
+        let mut paths: Vec<String> = value["matches"]
+            .as_array()
+            .expect("matches")
+            .iter()
+            .map(|m| m["path"].as_str().expect("path").to_string())
+            .collect();
+        paths.dedup();
+        paths
+    };
+
+    assert_eq!(
+        paths("content:add is:test"),
+        vec![
+            format!("{TEST_CODE_REPO}/cpp/math_test.cc"),
+            format!("{TEST_CODE_REPO}/web/__tests__/math.test.ts"),
+        ]
+    );
+    assert_eq!(
+        paths("content:add -is:test"),
+        vec![
+            format!("{TEST_CODE_REPO}/cpp/math.cc"),
+            format!("{TEST_CODE_REPO}/web/math.ts")
+        ]
+    );
+}
+
+#[test]
+fn cli_search_test_classification_with_index_matches_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    build_sqlite_index(TEST_CODE_REPO, db_path);
+
+    // Without an index, calls are resolved across files only for call
+    // graphs, so call edges are left out of the comparison.
+    let symbols = |mut value: Value| {
+        for symbol in value["symbols"].as_array_mut().expect("symbols") {
+            let symbol = symbol.as_object_mut().expect("symbol");
+            symbol.remove("calls");
+            symbol.remove("called_by");
+        }
+        value["symbols"].take()
+    };
+
+    for query in ["is:test", "-is:test", "name:add", "kind:function"] {
+        let args = [
+            "search",
+            query,
+            "--mode",
+            "symbol",
+            "--path",
+            TEST_CODE_REPO,
+        ];
+        let without_index = run_json(&args);
+
+        let mut indexed_args = args.to_vec();
+        indexed_args.extend(["--use-index", "--index-path", db_path]);
+        let with_index = run_json(&indexed_args);
+        assert_eq!(symbols(with_index), symbols(without_index), "{query}");
+    }
+}
+
+#[test]
+fn cli_search_deprecated_and_doc_tag_terms() {
+    let search = |query: &str| search_symbols(query, DOC_REPO);
+    assert_eq!(
+        names(&search("deprecated:")),
+        vec!["close_all", "multiply", "open_legacy"]
+    );
+    assert_eq!(names(&search("deprecated:close")), vec!["close_all"]);
+    assert_eq!(
+        names(&search("deprecated:=\"use open instead\"")),
+        vec!["open_legacy"]
+    );
+    assert_eq!(
+        names(&search("deprecated:false kind:function")),
+        vec!["open_device", "divide", "product", "open", "read_raw"]
+    );
+
+    assert_eq!(names(&search("doc-tag:throws")), vec!["divide"]);
+    assert_eq!(names(&search("doc-tag:safety")), vec!["read_raw"]);
+    assert_eq!(
+        names(&search("doc-tag:@param")),
+        vec!["open_device", "divide"]
+    );
+    assert_eq!(names(&search("doc-tag:errors|panics")), vec!["open"]);
+    assert_eq!(names(&search("doc-tag:link")), vec!["multiply", "open"]);
+}
+
+#[test]
+fn cli_search_doc_view_prints_summary_lines() {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args([
+        "search",
+        "name:divide|read_raw",
+        "--mode",
+        "symbol",
+        "--path",
+        DOC_REPO,
+        "--view",
+        "doc",
+        "--no-server",
+    ]);
+    let assert = cmd.assert().success();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).expect("utf8");
+    assert_eq!(
+        output,
+        "tests/fixtures/doc_repo/src/math.ts:9:8: function divide\n\
+         Divides two numbers.\n\
+         tests/fixtures/doc_repo/src/store.rs:23:1: function read_raw\n\
+         Reads a raw record without bounds checks.\n"
+    );
+}
+
+#[test]
+fn cli_search_doc_terms_with_index_match_scan() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = tmp.path().join("index.sqlite");
+    let db_path = db_path.to_str().unwrap();
+
+    build_sqlite_index(DOC_REPO, db_path);
+
+    for query in ["kind:function", "deprecated:", "doc-tag:param"] {
+        let args = ["search", query, "--mode", "symbol", "--path", DOC_REPO];
+        let without_index = run_json(&args);
+
+        let mut indexed_args = args.to_vec();
+        indexed_args.extend(["--use-index", "--index-path", db_path]);
+        let with_index = run_json(&indexed_args);
+        assert_eq!(with_index["symbols"], without_index["symbols"], "{query}");
+    }
+}
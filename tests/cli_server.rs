@@ -1,4 +1,4 @@
-use assert_cmd::cargo::{cargo_bin_cmd, CommandCargoExt};
+use assert_cmd::cargo::cargo_bin_cmd;
 use reqwest::blocking::Client;
 use serde_json::Value;
 use std::net::TcpListener;
@@ -39,7 +39,7 @@ impl TestDaemon {
         let stderr_file =
             std::fs::File::create(&stderr_path).expect("create daemon stderr log file");
 
-        let mut cmd = Command::cargo_bin("symgrep").expect("locate symgrep binary");
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("symgrep"));
         cmd.args(["serve", "--addr", &addr_arg])
             .stdout(stdout_file)
             .stderr(stderr_file);
@@ -374,3 +374,125 @@ fn cli_search_via_server_surfaces_http_errors() {
         "expected CLI error output to mention server-side HTTP error, got: {stderr}"
     );
 }
+
+#[test]
+fn cli_deps_via_server_matches_local_deps() {
+    let daemon = TestDaemon::spawn();
+
+    let args = [
+        "deps",
+        "tests/fixtures/deps_repo/ts/logger.ts",
+        "--path",
+        "tests/fixtures/deps_repo/ts",
+        "--transitive",
+        "--format",
+        "json",
+    ];
+
+    let mut local_cmd = cargo_bin_cmd!("symgrep");
+    local_cmd.args(args);
+    let local_assert = local_cmd.assert().success();
+    let local_value: Value =
+        serde_json::from_slice(&local_assert.get_output().stdout).expect("valid local json");
+
+    let mut server_cmd = cargo_bin_cmd!("symgrep");
+    server_cmd.args(args).args(["--server", &daemon.base_url]);
+    let server_assert = server_cmd.assert().success();
+    let server_value: Value =
+        serde_json::from_slice(&server_assert.get_output().stdout).expect("valid server json");
+
+    assert_eq!(server_value, local_value);
+    assert_eq!(server_value["importers"].as_array().expect("importers").len(), 2);
+}
+
+#[test]
+fn cli_check_deps_via_server_matches_local_check() {
+    let daemon = TestDaemon::spawn();
+
+    let args = [
+        "check-deps",
+        "--path",
+        "tests/fixtures/layers_repo",
+        "--rule",
+        "src/language/** must not import src/cli/**",
+        "--format",
+        "json",
+    ];
+
+    let mut local_cmd = cargo_bin_cmd!("symgrep");
+    local_cmd.args(args);
+    let local_assert = local_cmd.assert().failure();
+    let local_value: Value =
+        serde_json::from_slice(&local_assert.get_output().stdout).expect("valid local json");
+
+    let mut server_cmd = cargo_bin_cmd!("symgrep");
+    server_cmd.args(args).args(["--server", &daemon.base_url]);
+    let server_assert = server_cmd.assert().failure();
+    let server_value: Value =
+        serde_json::from_slice(&server_assert.get_output().stdout).expect("valid server json");
+
+    assert_eq!(server_value, local_value);
+    assert_eq!(server_value["violations"].as_array().expect("violations").len(), 1);
+}
+
+#[test]
+fn cli_hierarchy_via_server_matches_local_hierarchy() {
+    let daemon = TestDaemon::spawn();
+
+    let args = [
+        "hierarchy",
+        "Cube",
+        "--path",
+        "tests/fixtures/hierarchy_repo/ts",
+        "--direction",
+        "supertypes",
+        "--format",
+        "json",
+    ];
+
+    let mut local_cmd = cargo_bin_cmd!("symgrep");
+    local_cmd.args(args);
+    let local_assert = local_cmd.assert().success();
+    let local_value: Value =
+        serde_json::from_slice(&local_assert.get_output().stdout).expect("valid local json");
+
+    let mut server_cmd = cargo_bin_cmd!("symgrep");
+    server_cmd.args(args).args(["--server", &daemon.base_url]);
+    let server_assert = server_cmd.assert().success();
+    let server_value: Value =
+        serde_json::from_slice(&server_assert.get_output().stdout).expect("valid server json");
+
+    assert_eq!(server_value, local_value);
+    assert_eq!(server_value["edges"].as_array().expect("edges").len(), 5);
+}
+
+#[test]
+fn cli_query_via_server_matches_local_query() {
+    let daemon = TestDaemon::spawn();
+
+    let args = [
+        "query",
+        r#"(function_item name: (identifier) @name (#match? @name "^add"))"#,
+        "--language",
+        "rust",
+        "--path",
+        "tests/fixtures/rust_repo",
+        "--format",
+        "json",
+    ];
+
+    let mut local_cmd = cargo_bin_cmd!("symgrep");
+    local_cmd.args(args);
+    let local_assert = local_cmd.assert().success();
+    let local_value: Value =
+        serde_json::from_slice(&local_assert.get_output().stdout).expect("valid local json");
+
+    let mut server_cmd = cargo_bin_cmd!("symgrep");
+    server_cmd.args(args).args(["--server", &daemon.base_url]);
+    let server_assert = server_cmd.assert().success();
+    let server_value: Value =
+        serde_json::from_slice(&server_assert.get_output().stdout).expect("valid server json");
+
+    assert_eq!(server_value, local_value);
+    assert_eq!(server_value["matches"].as_array().expect("matches").len(), 2);
+}
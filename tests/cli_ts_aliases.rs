@@ -0,0 +1,96 @@
+mod common;
+
+use common::run_json;
+
+const REPO: &str = "tests/fixtures/ts_alias_repo";
+const MAIN: &str = "tests/fixtures/ts_alias_repo/apps/web/src/main.ts";
+
+#[test]
+fn cli_deps_resolves_path_aliases_and_workspace_packages() {
+    let value = run_json(&["deps", MAIN, "--path", REPO]);
+
+    let imports: Vec<(&str, Option<&str>)> = value["imports"]
+        .as_array()
+        .expect("imports")
+        .iter()
+        .map(|import| {
+            (
+                import["module"].as_str().expect("module"),
+                import["target"].as_str(),
+            )
+        })
+        .collect();
+    // `paths` and `baseUrl` come from the extended tsconfig.base.json;
+    // `@acme/*` packages resolve through `exports` or `main`.
+    assert_eq!(
+        imports,
+        vec![
+            (
+                "@app/utils/format",
+                Some("tests/fixtures/ts_alias_repo/apps/web/src/utils/format.ts")
+            ),
+            (
+                "@config",
+                Some("tests/fixtures/ts_alias_repo/apps/web/src/config.ts")
+            ),
+            (
+                "@acme/ui",
+                Some("tests/fixtures/ts_alias_repo/packages/ui/src/index.ts")
+            ),
+            (
+                "@acme/ui/money",
+                Some("tests/fixtures/ts_alias_repo/packages/ui/src/money.ts")
+            ),
+            (
+                "@acme/logger",
+                Some("tests/fixtures/ts_alias_repo/packages/logger/lib/index.js")
+            ),
+            (
+                "libs/clock",
+                Some("tests/fixtures/ts_alias_repo/libs/clock.ts")
+            ),
+            ("react", None),
+        ]
+    );
+}
+
+#[test]
+fn cli_search_resolves_calls_through_aliased_imports() {
+    let value = run_json(&[
+        "search",
+        "name:=main calls:toCents",
+        "--mode",
+        "symbol",
+        "--path",
+        REPO,
+    ]);
+
+    let targets: Vec<(&str, &str)> = value["symbols"][0]["calls"]
+        .as_array()
+        .expect("calls")
+        .iter()
+        .filter_map(|call| Some((call["name"].as_str()?, call["target_file"].as_str()?)))
+        .collect();
+    assert_eq!(
+        targets,
+        vec![
+            (
+                "toCents",
+                "tests/fixtures/ts_alias_repo/packages/ui/src/money.ts"
+            ),
+            (
+                "log",
+                "tests/fixtures/ts_alias_repo/packages/logger/lib/index.js"
+            ),
+            (
+                "formatPrice",
+                "tests/fixtures/ts_alias_repo/apps/web/src/utils/format.ts"
+            ),
+            ("now", "tests/fixtures/ts_alias_repo/libs/clock.ts"),
+            (
+                "Button",
+                "tests/fixtures/ts_alias_repo/packages/ui/src/index.ts"
+            ),
+        ]
+    );
+}
@@ -0,0 +1,46 @@
+//! Helpers shared by the CLI integration tests.
+#![allow(dead_code)]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+
+/// Runs `symgrep` with `args` plus `--format json --no-server` and parses stdout.
+pub fn run_json(args: &[&str]) -> Value {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(args).args(["--format", "json", "--no-server"]);
+    let assert = cmd.assert().success();
+    serde_json::from_slice(&assert.get_output().stdout).expect("valid json output")
+}
+
+/// Like `run_json`, but for commands that may fail: returns whether
+/// `symgrep` exited successfully along with its parsed stdout.
+pub fn run_json_status(args: &[&str]) -> (bool, Value) {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(args).args(["--format", "json", "--no-server"]);
+    let output = cmd.output().expect("run symgrep");
+    let value = serde_json::from_slice(&output.stdout).expect("valid json output");
+    (output.status.success(), value)
+}
+
+/// Runs `symgrep` with `args` plus `--no-server` and returns stdout.
+pub fn run_text(args: &[&str]) -> String {
+    let mut cmd = cargo_bin_cmd!("symgrep");
+    cmd.args(args).arg("--no-server");
+    let assert = cmd.assert().success();
+    String::from_utf8(assert.get_output().stdout.clone()).expect("utf-8 output")
+}
+
+/// Runs a symbol-mode search for `query` over `path`.
+pub fn search_symbols(query: &str, path: &str) -> Value {
+    run_json(&["search", query, "--mode", "symbol", "--path", path])
+}
+
+/// Names of the symbols in a JSON search result, in output order.
+pub fn names(value: &Value) -> Vec<&str> {
+    value["symbols"]
+        .as_array()
+        .expect("symbols")
+        .iter()
+        .map(|symbol| symbol["name"].as_str().expect("name"))
+        .collect()
+}
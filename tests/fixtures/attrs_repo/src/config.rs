@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings loaded from disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct Plain;
+
+impl Settings {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[tokio::main]
+async fn main() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn loads() {}
+}
@@ -0,0 +1,6 @@
+class [[deprecated]] Engine {
+public:
+  [[nodiscard]] int start();
+};
+
+[[gnu::always_inline, maybe_unused]] inline int helper() { return 0; }
@@ -0,0 +1,12 @@
+@Injectable()
+export class WidgetService {
+  load(): void {}
+}
+
+@Component({ selector: "app-widget" })
+export class WidgetComponent {
+  @HostListener("click")
+  onClick(): void {}
+
+  render(): void {}
+}
@@ -0,0 +1,20 @@
+export function main(): void {
+  handle();
+  log();
+}
+
+export function handle(): void {
+  parse();
+  log();
+}
+
+export function parse(): void {
+  validate();
+}
+
+export function validate(): void {
+  parse();
+  log();
+}
+
+export function log(): void {}
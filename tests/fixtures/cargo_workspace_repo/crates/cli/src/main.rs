@@ -0,0 +1,8 @@
+use acme_core::storage::sqlite::open;
+
+mod commands;
+
+fn main() {
+    let store = open("acme.db");
+    commands::run(&store.path);
+}
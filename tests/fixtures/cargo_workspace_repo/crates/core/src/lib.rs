@@ -0,0 +1,11 @@
+pub mod storage;
+
+pub fn version() -> u32 {
+    1
+}
+
+mod inline {
+    pub fn helper() -> u32 {
+        2
+    }
+}
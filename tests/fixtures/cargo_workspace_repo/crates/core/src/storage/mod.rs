@@ -0,0 +1,5 @@
+pub mod sqlite;
+
+pub struct Store {
+    pub path: String,
+}
@@ -0,0 +1,7 @@
+use super::Store;
+
+pub fn open(path: &str) -> Store {
+    Store {
+        path: path.to_string(),
+    }
+}
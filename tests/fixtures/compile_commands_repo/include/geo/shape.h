@@ -0,0 +1,7 @@
+#pragma once
+
+#include <mathx/vec.h>
+
+inline double area(double width, double height) {
+    return width * height;
+}
@@ -0,0 +1,5 @@
+#include "geo/shape.h"
+
+double legacy_area() {
+    return area(1.0, 1.0);
+}
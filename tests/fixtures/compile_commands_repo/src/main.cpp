@@ -0,0 +1,19 @@
+#include "geo/shape.h"
+#include <mathx/vec.h>
+#include <cstdio>
+
+#ifdef USE_LOGGING
+#include "geo/log.h"
+#else
+#include "geo/quiet.h"
+#endif
+
+#ifndef NO_VECTORS
+#include <mathx/vec_ops.h>
+#endif
+
+int main() {
+    double a = area(2.0, 3.0);
+    log_value(a);
+    return 0;
+}
@@ -0,0 +1,6 @@
+#pragma once
+
+struct Vec {
+    double x;
+    double y;
+};
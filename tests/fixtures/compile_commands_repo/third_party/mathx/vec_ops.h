@@ -0,0 +1,7 @@
+#pragma once
+
+#include <mathx/vec.h>
+
+inline double dot(Vec a, Vec b) {
+    return a.x * b.x + a.y * b.y;
+}
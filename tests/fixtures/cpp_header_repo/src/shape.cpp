@@ -0,0 +1,22 @@
+#include "shape.h"
+
+namespace geo {
+
+double Shape::area() const {
+    return width * height;
+}
+
+void Shape::scale(double factor) {
+    scale(factor, factor);
+}
+
+}  // namespace geo
+
+void geo::Shape::scale(double fx, double fy) {
+    width *= fx;
+    height *= fy;
+}
+
+double total_area(const geo::Shape& shape) {
+    return shape.area();
+}
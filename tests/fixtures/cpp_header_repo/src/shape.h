@@ -0,0 +1,17 @@
+#pragma once
+
+namespace geo {
+
+class Shape {
+public:
+    double area() const;
+    void scale(double factor);
+    void scale(double fx, double fy);
+    virtual void draw() = 0;
+
+private:
+    double width;
+    double height;
+};
+
+}  // namespace geo
@@ -0,0 +1,7 @@
+#include <cstdio>
+#include "math.h"
+
+int main() {
+    std::printf("%d\n", square(4));
+    return 0;
+}
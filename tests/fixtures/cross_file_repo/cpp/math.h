@@ -0,0 +1,5 @@
+#pragma once
+
+inline int square(int x) {
+    return x * x;
+}
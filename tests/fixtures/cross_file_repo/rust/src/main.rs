@@ -0,0 +1,10 @@
+mod util;
+
+use crate::util::normalize;
+use crate::util::text;
+
+fn main() {
+    let value = normalize(" Hello ");
+    let loud = text::shout(&value);
+    println!("{loud}");
+}
@@ -0,0 +1,5 @@
+pub mod text;
+
+pub fn normalize(input: &str) -> String {
+    input.trim().to_lowercase()
+}
@@ -0,0 +1,7 @@
+import { add, scale as resize } from "./math";
+import { format } from "./lib";
+
+export function run(): string {
+  const total = add(1, 2);
+  return format(resize(total, 3));
+}
@@ -0,0 +1,7 @@
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+export function scale(value: number, factor: number): number {
+  return value * factor;
+}
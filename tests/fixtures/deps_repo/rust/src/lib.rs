@@ -0,0 +1,19 @@
+extern crate alloc;
+
+mod net;
+mod util;
+
+use std::fmt;
+use crate::util::{clamp, wrap};
+
+pub fn run() -> String {
+    format!("{}", wrap(clamp(1)))
+}
+
+pub struct Report;
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", net::fetch())
+    }
+}
@@ -0,0 +1,3 @@
+pub fn fetch() -> &'static str {
+    "ok"
+}
@@ -0,0 +1,19 @@
+pub fn clamp(value: i32) -> i32 {
+    value.max(0)
+}
+
+pub fn wrap(value: i32) -> String {
+    format!("[{value}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::net;
+
+    #[test]
+    fn clamp_is_non_negative() {
+        assert_eq!(clamp(-1), 0);
+        assert_eq!(net::fetch(), "ok");
+    }
+}
@@ -0,0 +1,9 @@
+import { parse } from "./parser";
+import "./polyfill";
+import express from "express";
+const { log } = require("./logger");
+
+export function main(): void {
+  log(parse("hello"));
+  express();
+}
@@ -0,0 +1 @@
+export { main } from "./app";
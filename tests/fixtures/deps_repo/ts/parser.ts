@@ -0,0 +1,6 @@
+import { log } from "./logger";
+
+export function parse(input: string): string {
+  log(input);
+  return input.trim();
+}
@@ -0,0 +1 @@
+(globalThis as any).ready = true;
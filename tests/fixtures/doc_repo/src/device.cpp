@@ -0,0 +1,11 @@
+/**
+ * \brief Opens the device.
+ * \param[in] path device path
+ * \return zero on success
+ */
+int open_device(const char* path) {
+    return path ? 0 : -1;
+}
+
+/// Closes every device.
+[[deprecated("use close_device")]] void close_all() {}
@@ -0,0 +1,27 @@
+/**
+ * Divides two numbers. Rounds toward zero.
+ * @param {number} a - the dividend
+ * @param {number} b - the divisor
+ * @returns {number} the quotient
+ * @throws {RangeError} when b is zero
+ * @see multiply
+ */
+export function divide(a: number, b: number): number {
+  if (b === 0) {
+    throw new RangeError("division by zero");
+  }
+  return Math.trunc(a / b);
+}
+
+/**
+ * Multiplies two numbers.
+ * @deprecated use {@link product} instead
+ */
+export function multiply(a: number, b: number): number {
+  return a * b;
+}
+
+/** Multiplies all numbers. */
+export function product(...values: number[]): number {
+  return values.reduce((acc, value) => acc * value, 1);
+}
@@ -0,0 +1,36 @@
+/// Opens a [`Store`] rooted at `path`.
+///
+/// Creates the directory when it is missing.
+///
+/// # Errors
+///
+/// Returns an error when the directory cannot be created.
+///
+/// # Panics
+///
+/// Panics if `path` is empty.
+pub fn open(path: &str) -> Result<Store, std::io::Error> {
+    assert!(!path.is_empty());
+    std::fs::create_dir_all(path)?;
+    Ok(Store { root: path.to_string() })
+}
+
+/// Reads a raw record without bounds checks.
+///
+/// # Safety
+///
+/// `offset` must be within the store.
+pub unsafe fn read_raw(store: &Store, offset: usize) -> u8 {
+    *store.root.as_ptr().add(offset)
+}
+
+/// Opens the legacy store format.
+#[deprecated(since = "0.3.0", note = "use open instead")]
+pub fn open_legacy(path: &str) -> Result<Store, std::io::Error> {
+    open(path)
+}
+
+/// On-disk key-value store.
+pub struct Store {
+    root: String,
+}
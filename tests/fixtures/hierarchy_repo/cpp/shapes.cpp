@@ -0,0 +1,14 @@
+namespace geo {
+
+struct Shape {
+    virtual double area() const = 0;
+};
+
+class Circle : public Shape {
+public:
+    double area() const override { return 3.14; }
+};
+
+class Ring : public Circle, private Tracked {};
+
+}
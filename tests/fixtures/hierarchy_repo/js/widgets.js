@@ -0,0 +1,5 @@
+class Widget {}
+
+class Button extends Widget {}
+
+module.exports = { Button };
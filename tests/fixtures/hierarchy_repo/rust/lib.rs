@@ -0,0 +1,22 @@
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub trait Solid: Shape + std::fmt::Debug {}
+
+#[derive(Debug)]
+pub struct Cube;
+
+impl Shape for Cube {
+    fn area(&self) -> f64 {
+        6.0
+    }
+}
+
+impl Solid for Cube {}
+
+impl Cube {
+    pub fn new() -> Self {
+        Cube
+    }
+}
@@ -0,0 +1,17 @@
+export interface Shape {
+  area(): number;
+}
+
+export interface Named {
+  name(): string;
+}
+
+export interface Polygon extends Shape {
+  sides(): number;
+}
+
+export class Base implements Named {
+  name(): string {
+    return "base";
+  }
+}
@@ -0,0 +1,13 @@
+import { Base, Polygon } from "./shapes";
+
+export class Square extends Base implements Polygon {
+  area(): number {
+    return 1;
+  }
+
+  sides(): number {
+    return 4;
+  }
+}
+
+export class Cube extends Square {}
@@ -0,0 +1,24 @@
+import { Button } from "./Button";
+import { Menu } from "./Menu";
+
+function Layout(props: { children?: unknown }) {
+  return <div className="layout">{props.children}</div>;
+}
+
+function save() {
+  return true;
+}
+
+export function App() {
+  return (
+    <Layout>
+      <Button label="Save" onClick={() => save()} />
+      <Menu>
+        <Menu.Item label="Open" />
+      </Menu>
+      <>
+        <span>footer</span>
+      </>
+    </Layout>
+  );
+}
@@ -0,0 +1,9 @@
+export function MenuItem(props: { label: string }) {
+  return <li>{props.label}</li>;
+}
+
+export function Menu(props: { children?: unknown }) {
+  return <ul>{props.children}</ul>;
+}
+
+Menu.Item = MenuItem;
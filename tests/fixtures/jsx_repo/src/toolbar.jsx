@@ -0,0 +1,5 @@
+import { Button } from "./Button";
+
+export function Toolbar() {
+  return <Button label="Print" />;
+}
@@ -0,0 +1,5 @@
+import { clamp } from "../core/math";
+
+export function flag(name: string, value: number): string {
+  return `--${name}=${clamp(value)}`;
+}
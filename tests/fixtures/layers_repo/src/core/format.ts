@@ -0,0 +1,5 @@
+import { clamp } from "./math";
+
+export function format(value: number): string {
+  return `${clamp(value)}%`;
+}
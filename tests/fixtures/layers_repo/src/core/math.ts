@@ -0,0 +1,9 @@
+import { format } from "./format";
+
+export function clamp(value: number): number {
+  return Math.max(0, Math.min(100, value));
+}
+
+export function describe(value: number): string {
+  return format(clamp(value));
+}
@@ -0,0 +1,6 @@
+import { flag } from "../cli/flags";
+import { clamp } from "../core/math";
+
+export function parse(input: string): string {
+  return flag(input, clamp(input.length));
+}
@@ -0,0 +1,11 @@
+#include <gtest/gtest.h>
+
+int add(int a, int b);
+
+TEST(MathTest, Adds) {
+  EXPECT_EQ(add(1, 2), 3);
+}
+
+TEST_F(MathFixture, AddsNegatives) {
+  EXPECT_EQ(add(-1, -2), -3);
+}
@@ -0,0 +1,21 @@
+/// Parse a `key=value` configuration line.
+pub fn parse_config(line: &str) -> Option<(&str, &str)> {
+    line.split_once('=')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_config_fixture() -> &'static str {
+        "name=demo"
+    }
+
+    #[test]
+    fn parses_pairs() {
+        assert!(parse_config(parse_config_fixture()).is_some());
+    }
+}
+
+#[tokio::test]
+async fn fetches_remote_config() {}
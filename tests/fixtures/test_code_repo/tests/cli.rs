@@ -0,0 +1,6 @@
+fn run_cli() {}
+
+#[test]
+fn runs() {
+    run_cli();
+}
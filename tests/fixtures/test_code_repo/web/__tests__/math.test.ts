@@ -0,0 +1,11 @@
+import { add } from "../math";
+
+function addCase(a: number, b: number) {
+  return { a, b, sum: add(a, b) };
+}
+
+describe("add", () => {
+  it("adds", () => {
+    expect(addCase(1, 2).sum).toBe(3);
+  });
+});
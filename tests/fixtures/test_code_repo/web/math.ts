@@ -0,0 +1,7 @@
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+export function addAll(values: number[]): number {
+  return values.reduce(add, 0);
+}
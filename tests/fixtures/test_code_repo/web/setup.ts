@@ -0,0 +1,7 @@
+export function configure(): void {}
+
+describe.each([1, 2])("setup %i", () => {
+  function seedDatabase(): void {}
+
+  beforeEach(() => seedDatabase());
+});
@@ -0,0 +1,13 @@
+import { formatPrice } from "@app/utils/format";
+import { currency } from "@config";
+import { Button } from "@acme/ui";
+import { toCents } from "@acme/ui/money";
+import { log } from "@acme/logger";
+import { now } from "libs/clock";
+import { render } from "react";
+
+export function main(amount: number) {
+  const cents = toCents(amount);
+  log(formatPrice(cents, currency), now());
+  return render(Button("Pay"));
+}
@@ -0,0 +1,3 @@
+export function formatPrice(cents: number, currency: string): string {
+  return `${(cents / 100).toFixed(2)} ${currency}`;
+}
@@ -0,0 +1,3 @@
+export function log(...values) {
+  console.log(...values);
+}
@@ -0,0 +1,3 @@
+export function Button(label: string) {
+  return { type: "button", label };
+}